@@ -6,7 +6,7 @@ use anchor_lang::idl::{IdlAccount, IdlInstruction};
 use anchor_lang::{AccountDeserialize, AnchorDeserialize, AnchorSerialize};
 use anchor_syn::idl::Idl;
 use anyhow::{anyhow, Context, Result};
-use clap::Clap;
+use clap::{Args, Parser, Subcommand};
 use flate2::read::ZlibDecoder;
 use flate2::write::ZlibEncoder;
 use flate2::Compression;
@@ -37,7 +37,7 @@ mod template;
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 const DOCKER_BUILDER_VERSION: &str = VERSION;
 
-#[derive(Debug, Clap)]
+#[derive(Debug, Parser)]
 #[clap(version = VERSION)]
 pub struct Opts {
     #[clap(flatten)]
@@ -46,7 +46,7 @@ pub struct Opts {
     pub command: Command,
 }
 
-#[derive(Debug, Clap)]
+#[derive(Debug, Args)]
 pub struct ConfigOverride {
     /// Cluster override.
     #[clap(global = true, long = "provider.cluster")]
@@ -56,7 +56,7 @@ pub struct ConfigOverride {
     wallet: Option<WalletPath>,
 }
 
-#[derive(Debug, Clap)]
+#[derive(Debug, Subcommand)]
 pub enum Command {
     /// Initializes a workspace.
     Init {
@@ -151,7 +151,7 @@ pub enum Command {
     },
 }
 
-#[derive(Debug, Clap)]
+#[derive(Debug, Subcommand)]
 pub enum IdlCommand {
     /// Initializes a program's IDL account. Can only be run once.
     Init {
@@ -223,7 +223,7 @@ pub enum IdlCommand {
     },
 }
 
-#[derive(Debug, Clap)]
+#[derive(Debug, Subcommand)]
 pub enum ClusterCommand {
     /// Prints common cluster urls.
     List,
@@ -27,6 +27,7 @@ name = "{1}"
 no-entrypoint = []
 no-idl = []
 cpi = ["no-entrypoint"]
+client = ["no-entrypoint"]
 default = []
 
 [dependencies]
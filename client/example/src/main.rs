@@ -17,14 +17,14 @@ use basic_4::accounts as basic_4_accounts;
 use basic_4::basic_4::Counter as CounterState;
 use basic_4::instruction as basic_4_instruction;
 // The `accounts` and `instructions` modules are generated by the framework.
-use clap::Clap;
+use clap::Parser;
 use composite::accounts::{Bar, CompositeUpdate, Foo, Initialize};
 use composite::instruction as composite_instruction;
 use composite::{DummyA, DummyB};
 use rand::rngs::OsRng;
 use std::time::Duration;
 
-#[derive(Clap)]
+#[derive(Parser)]
 pub struct Opts {
     #[clap(long)]
     composite_pid: Pubkey,
@@ -0,0 +1,128 @@
+//! The blocking API, enabled by the `blocking` feature.
+
+use crate::{
+    ClientError, DynamicProgram, EventContext, Filter, Program, RequestBuilder, Simulation,
+};
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::{AccountDeserialize, Discriminator};
+use serde_json::Value;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_filter::RpcFilterType;
+use solana_sdk::signature::Signature;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+// The runtime the blocking API runs requests to completion on, built once per
+// `Client` and shared by its programs and their requests.
+pub(crate) type Runtime = Arc<tokio::runtime::Runtime>;
+
+pub(crate) fn runtime() -> Runtime {
+    Arc::new(
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("Unable to build runtime"),
+    )
+}
+
+impl Program {
+    /// Returns the account at the given address.
+    pub fn account<T: AccountDeserialize>(&self, address: Pubkey) -> Result<T, ClientError> {
+        self.cfg.runtime.block_on(self.account_internal(address))
+    }
+
+    /// Returns all program accounts of type `T` matching the given filters,
+    /// in addition to the account discriminator. Offsets of memcmp filters
+    /// include the discriminator. `DynamicProgram::filter` resolves a filter
+    /// on a field by name.
+    pub fn accounts<T: AccountDeserialize + Discriminator>(
+        &self,
+        filters: Vec<RpcFilterType>,
+    ) -> Result<Vec<(Pubkey, T)>, ClientError> {
+        self.cfg.runtime.block_on(self.accounts_internal(filters))
+    }
+
+    /// Returns the accounts at the given addresses, in order, or `None` for
+    /// those that don't exist.
+    pub fn accounts_multiple<T: AccountDeserialize>(
+        &self,
+        addresses: &[Pubkey],
+    ) -> Result<Vec<Option<T>>, ClientError> {
+        self.cfg
+            .runtime
+            .block_on(self.accounts_multiple_internal(addresses))
+    }
+
+    /// Returns the events of type `T` emitted by the program in the given
+    /// confirmed transaction, first those logged and then those emitted via
+    /// `emit_cpi!`.
+    pub fn events<T: anchor_lang::Event + anchor_lang::AnchorDeserialize>(
+        &self,
+        signature: &Signature,
+    ) -> Result<Vec<T>, ClientError> {
+        self.cfg.runtime.block_on(self.events_internal(signature))
+    }
+
+    /// Returns the events of type `T` emitted by the program in the
+    /// successful transactions of the given slots, oldest first.
+    pub fn events_in_slots<T: anchor_lang::Event + anchor_lang::AnchorDeserialize>(
+        &self,
+        slots: RangeInclusive<u64>,
+    ) -> Result<Vec<(EventContext, T)>, ClientError> {
+        self.cfg
+            .runtime
+            .block_on(self.events_in_slots_internal(slots))
+    }
+
+    pub fn state<T: AccountDeserialize>(&self) -> Result<T, ClientError> {
+        self.cfg.runtime.block_on(self.state_internal())
+    }
+
+    pub fn rpc(&self) -> RpcClient {
+        RpcClient::new_with_commitment(
+            self.cfg.cluster.url().to_string(),
+            self.cfg.options.unwrap_or_default(),
+        )
+    }
+}
+
+impl<'a> RequestBuilder<'a> {
+    pub fn send(self) -> Result<Signature, ClientError> {
+        self.runtime().block_on(self.send_internal())
+    }
+
+    /// Simulates the request's transaction, without sending it.
+    pub fn simulate(&self) -> Result<Simulation, ClientError> {
+        self.runtime().block_on(self.simulate_internal())
+    }
+
+    // Requests built by a `Program` run on its client's runtime, those built
+    // via `RequestBuilder::from` on their own.
+    fn runtime(&self) -> Runtime {
+        self.runtime.clone().unwrap_or_else(runtime)
+    }
+}
+
+impl DynamicProgram {
+    /// Returns the account of the type with the given name at the given
+    /// address.
+    pub fn account(&self, name: &str, address: Pubkey) -> Result<Value, ClientError> {
+        self.program()
+            .cfg
+            .runtime
+            .block_on(self.account_internal(name, address))
+    }
+
+    /// Returns all program accounts of the type with the given name matching
+    /// the given filters, in addition to the account discriminator.
+    pub fn accounts(
+        &self,
+        name: &str,
+        filters: Vec<Filter>,
+    ) -> Result<Vec<(Pubkey, Value)>, ClientError> {
+        self.program()
+            .cfg
+            .runtime
+            .block_on(self.accounts_internal(name, filters))
+    }
+}
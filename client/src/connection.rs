@@ -0,0 +1,159 @@
+//! The connection over which accounts are fetched and transactions are sent:
+//! a cluster's RPC node or, with the `program-test` feature, a bank.
+
+use crate::{ClientError, Simulation, MAX_MULTIPLE_ACCOUNTS};
+use anchor_lang::solana_program::pubkey::Pubkey;
+use solana_client::nonblocking::rpc_client::RpcClient as AsyncRpcClient;
+use solana_sdk::account::Account;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::hash::Hash;
+use solana_sdk::transaction::VersionedTransaction;
+
+#[cfg(feature = "program-test")]
+use solana_banks_client::BanksClient;
+
+// Where a client's requests go, cloned into each `Program` and request.
+#[derive(Clone)]
+pub(crate) enum Backend {
+    // The RPC node at the configured cluster's URL.
+    Rpc,
+    #[cfg(feature = "program-test")]
+    Banks(BanksClient),
+}
+
+impl Backend {
+    pub(crate) fn connect(&self, url: &str, commitment: CommitmentConfig) -> Connection {
+        match self {
+            Backend::Rpc => Connection::Rpc(AsyncRpcClient::new_with_commitment(
+                url.to_string(),
+                commitment,
+            )),
+            #[cfg(feature = "program-test")]
+            Backend::Banks(banks_client) => Connection::Banks(banks_client.clone()),
+        }
+    }
+}
+
+pub(crate) enum Connection {
+    Rpc(AsyncRpcClient),
+    #[cfg(feature = "program-test")]
+    Banks(BanksClient),
+}
+
+impl Connection {
+    // Returns the RPC client, for features a bank doesn't support, e.g.,
+    // `getProgramAccounts` or transaction history.
+    #[cfg_attr(not(feature = "program-test"), allow(unused_variables))]
+    pub(crate) fn rpc(&self, operation: &'static str) -> Result<&AsyncRpcClient, ClientError> {
+        match self {
+            Connection::Rpc(rpc_client) => Ok(rpc_client),
+            #[cfg(feature = "program-test")]
+            Connection::Banks(_) => Err(ClientError::UnsupportedByBanksClient(operation)),
+        }
+    }
+
+    pub(crate) async fn get_account(
+        &self,
+        address: &Pubkey,
+        commitment: CommitmentConfig,
+    ) -> Result<Option<Account>, ClientError> {
+        match self {
+            Connection::Rpc(rpc_client) => Ok(rpc_client
+                .get_account_with_commitment(address, commitment)
+                .await?
+                .value),
+            #[cfg(feature = "program-test")]
+            Connection::Banks(banks_client) => Ok(banks_client
+                .clone()
+                .get_account_with_commitment(*address, commitment.commitment)
+                .await?),
+        }
+    }
+
+    pub(crate) async fn get_multiple_accounts(
+        &self,
+        addresses: &[Pubkey],
+        commitment: CommitmentConfig,
+    ) -> Result<Vec<Option<Account>>, ClientError> {
+        let mut accounts = Vec::with_capacity(addresses.len());
+        match self {
+            Connection::Rpc(rpc_client) => {
+                for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+                    accounts.extend(
+                        rpc_client
+                            .get_multiple_accounts_with_commitment(chunk, commitment)
+                            .await?
+                            .value,
+                    );
+                }
+            }
+            #[cfg(feature = "program-test")]
+            Connection::Banks(_) => {
+                for address in addresses {
+                    accounts.push(self.get_account(address, commitment).await?);
+                }
+            }
+        }
+        Ok(accounts)
+    }
+
+    pub(crate) async fn get_latest_blockhash(&self) -> Result<Hash, ClientError> {
+        match self {
+            Connection::Rpc(rpc_client) => Ok(rpc_client.get_latest_blockhash().await?),
+            #[cfg(feature = "program-test")]
+            Connection::Banks(banks_client) => {
+                Ok(banks_client.clone().get_latest_blockhash().await?)
+            }
+        }
+    }
+
+    pub(crate) async fn simulate_transaction(
+        &self,
+        program_id: Pubkey,
+        tx: &VersionedTransaction,
+    ) -> Result<Simulation, ClientError> {
+        match self {
+            Connection::Rpc(rpc_client) => {
+                let result = rpc_client.simulate_transaction(tx).await?.value;
+                let return_data = match result.return_data {
+                    None => None,
+                    Some(return_data) => {
+                        let program_id = return_data.program_id.parse().map_err(|_| {
+                            ClientError::LogParseError(return_data.program_id.clone())
+                        })?;
+                        let data = anchor_lang::__private::base64::decode(&return_data.data.0)
+                            .map_err(|_| ClientError::LogParseError(return_data.data.0.clone()))?;
+                        Some((program_id, data))
+                    }
+                };
+                Ok(Simulation {
+                    program_id,
+                    err: result.err,
+                    logs: result.logs.unwrap_or_default(),
+                    units_consumed: result.units_consumed,
+                    return_data,
+                })
+            }
+            #[cfg(feature = "program-test")]
+            Connection::Banks(banks_client) => {
+                let result = banks_client
+                    .clone()
+                    .simulate_transaction(tx.clone())
+                    .await?;
+                let details = result.simulation_details;
+                Ok(Simulation {
+                    program_id,
+                    err: result.result.and_then(Result::err),
+                    logs: details
+                        .as_ref()
+                        .map(|details| details.logs.clone())
+                        .unwrap_or_default(),
+                    units_consumed: details.as_ref().map(|details| details.units_consumed),
+                    return_data: details
+                        .and_then(|details| details.return_data)
+                        .map(|return_data| (return_data.program_id, return_data.data)),
+                })
+            }
+        }
+    }
+}
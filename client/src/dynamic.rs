@@ -0,0 +1,733 @@
+//! A client for programs known only at runtime, via their IDL.
+//!
+//! Instruction arguments, accounts, and events are represented as JSON
+//! values, like in the TypeScript client. Integers wider than 64 bits are
+//! given and returned as strings, public keys as base58 strings, and enum
+//! variants as objects with a single key, the variant name, e.g.,
+//! `{ "Active": { "since": 42 } }`.
+
+use crate::{parse_logs_with, ClientError, Program, RequestBuilder};
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_syn::idl::{
+    EnumFields, Idl, IdlAccountItem, IdlEvent, IdlField, IdlType, IdlTypeDefinition,
+    IdlTypeDefinitionTy,
+};
+use heck::SnakeCase;
+use serde_json::{Map, Value};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::commitment_config::CommitmentConfig;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+/// A filter on the accounts fetched by `DynamicProgram::accounts`.
+pub enum Filter {
+    /// Matches accounts whose field with the given name has the given value.
+    Field { name: String, value: Value },
+    /// Passed to the RPC node as is, e.g., a data size filter.
+    Rpc(RpcFilterType),
+}
+
+impl Filter {
+    /// Matches accounts whose field with the given name has the given value,
+    /// e.g., `Filter::field("authority", authority.to_string())`. The field
+    /// must be preceded only by fields of fixed size.
+    pub fn field(name: &str, value: impl Into<Value>) -> Self {
+        Filter::Field {
+            name: name.to_string(),
+            value: value.into(),
+        }
+    }
+}
+
+/// `DynamicProgram` builds instructions and decodes accounts and events of a
+/// program given its IDL, rather than the types generated for it.
+pub struct DynamicProgram {
+    program: Program,
+    idl: Idl,
+}
+
+impl DynamicProgram {
+    pub(crate) fn new(program: Program, idl: Idl) -> Self {
+        Self { program, idl }
+    }
+
+    /// Returns the client for the program, e.g., for sending instructions.
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    pub fn idl(&self) -> &Idl {
+        &self.idl
+    }
+
+    /// Returns a request builder.
+    pub fn request(&self) -> RequestBuilder {
+        self.program.request()
+    }
+
+    /// Returns the instruction with the given name, e.g., `"initialize"`.
+    ///
+    /// `accounts` is an object mapping each account name to its address,
+    /// with nested objects for composite accounts. Optional accounts that
+    /// are omitted are replaced by the program id. `args` is an object
+    /// mapping each argument name to its value.
+    pub fn instruction(
+        &self,
+        name: &str,
+        accounts: &Value,
+        args: &Value,
+    ) -> Result<Instruction, ClientError> {
+        let ix = self
+            .idl
+            .instructions
+            .iter()
+            .find(|ix| ix.name == name)
+            .ok_or_else(|| idl_error(format!("Instruction {} not found", name)))?;
+
+        let mut metas = Vec::new();
+        self.account_metas(&ix.accounts, accounts, &mut metas)?;
+
+        let namespace = ix.interface.as_deref().unwrap_or("global");
+        let mut data = sighash(namespace, &ix.name.to_snake_case()).to_vec();
+        self.encode_fields(&ix.args, args, &mut data)?;
+
+        Ok(Instruction {
+            program_id: self.program.id(),
+            accounts: metas,
+            data,
+        })
+    }
+
+    /// Decodes the data of an account of the type with the given name,
+    /// checking its discriminator.
+    pub fn decode_account(&self, name: &str, data: &[u8]) -> Result<Value, ClientError> {
+        let account = self.idl_account(name)?;
+        let mut data = data
+            .strip_prefix(&account_discriminator(account)[..])
+            .ok_or_else(|| idl_error(format!("Account isn't a {}", name)))?;
+        if let Some(version) = account.version {
+            // Accounts written before versioning have a version of 0.
+            let given = read::<1>(&mut data)?[0];
+            if given != 0 && given != version {
+                return Err(idl_error(format!(
+                    "Unsupported version {} for account {}",
+                    given, name
+                )));
+            }
+        }
+        self.decode_type_definition(account, &mut data)
+    }
+
+    /// Decodes an event from its data, i.e., the discriminator followed by
+    /// the event, returning its name and fields. Returns `None` if the data
+    /// isn't one of the program's events.
+    pub fn decode_event(&self, data: &[u8]) -> Result<Option<(String, Value)>, ClientError> {
+        let data = data
+            .strip_prefix(&anchor_lang::event::EVENT_IX_TAG_LE[..])
+            .unwrap_or(data);
+        for event in self.idl.events.iter().flatten() {
+            if let Some(mut data) = data.strip_prefix(&event_discriminator(event)[..]) {
+                let fields: Vec<IdlField> = event
+                    .fields
+                    .iter()
+                    .map(|f| IdlField {
+                        name: f.name.clone(),
+                        ty: f.ty.clone(),
+                    })
+                    .collect();
+                let value = self.decode_fields(&fields, &mut data)?;
+                return Ok(Some((event.name.clone(), value)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the events emitted by the program in a transaction's logs, in
+    /// order, including those emitted within CPIs.
+    pub fn parse_logs(&self, logs: &[String]) -> Result<Vec<(String, Value)>, ClientError> {
+        parse_logs_with(&self.program.id(), logs, |data| self.decode_event(data))
+    }
+
+    /// Returns the memcmp filter matching accounts of the type with the
+    /// given name on the field and value of `filter`, offset by the
+    /// account's discriminator, version, and preceding fields. Can be passed
+    /// to `Program::accounts`.
+    pub fn filter(&self, name: &str, filter: Filter) -> Result<RpcFilterType, ClientError> {
+        let (field_name, value) = match filter {
+            Filter::Field { name, value } => (name, value),
+            Filter::Rpc(filter) => return Ok(filter),
+        };
+        let account = self.idl_account(name)?;
+        let fields = match &account.ty {
+            IdlTypeDefinitionTy::Struct { fields } => fields,
+            IdlTypeDefinitionTy::Enum { .. } => {
+                return Err(idl_error(format!("Account {} has no fields", name)))
+            }
+        };
+        let mut offset = account_discriminator(account).len() + account.version.map_or(0, |_| 1);
+        for field in fields {
+            if field.name == field_name {
+                let mut bytes = Vec::new();
+                self.encode(&field.ty, &value, &mut bytes)?;
+                return Ok(RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                    offset, &bytes,
+                )));
+            }
+            offset += self.fixed_size(&field.ty)?.ok_or_else(|| {
+                idl_error(format!(
+                    "Field {} follows {}, which isn't of fixed size",
+                    field_name, field.name
+                ))
+            })?;
+        }
+        Err(idl_error(format!(
+            "Field {} not found in account {}",
+            field_name, name
+        )))
+    }
+
+    pub(crate) async fn account_internal(
+        &self,
+        name: &str,
+        address: Pubkey,
+    ) -> Result<Value, ClientError> {
+        let account = self
+            .program
+            .connection()
+            .get_account(&address, CommitmentConfig::processed())
+            .await?
+            .ok_or(ClientError::AccountNotFound)?;
+        self.decode_account(name, &account.data)
+    }
+
+    pub(crate) async fn accounts_internal(
+        &self,
+        name: &str,
+        filters: Vec<Filter>,
+    ) -> Result<Vec<(Pubkey, Value)>, ClientError> {
+        let discriminator = account_discriminator(self.idl_account(name)?);
+        let mut rpc_filters = vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            0,
+            &discriminator,
+        ))];
+        for filter in filters {
+            rpc_filters.push(self.filter(name, filter)?);
+        }
+        self.program
+            .program_accounts(rpc_filters)
+            .await?
+            .into_iter()
+            .map(|(key, account)| Ok((key, self.decode_account(name, &account.data)?)))
+            .collect()
+    }
+
+    fn account_metas(
+        &self,
+        items: &[IdlAccountItem],
+        accounts: &Value,
+        metas: &mut Vec<AccountMeta>,
+    ) -> Result<(), ClientError> {
+        for item in items {
+            match item {
+                IdlAccountItem::IdlAccount(account) => {
+                    let pubkey = match accounts.get(&account.name) {
+                        Some(address) => pubkey(address)?,
+                        None if account.is_optional => self.program.id(),
+                        None => {
+                            return Err(idl_error(format!("Account {} not provided", account.name)))
+                        }
+                    };
+                    // Omitted optional accounts are never mutable or signers.
+                    let (is_mut, is_signer) = match pubkey == self.program.id() {
+                        true if account.is_optional => (false, false),
+                        _ => (account.is_mut, account.is_signer),
+                    };
+                    metas.push(match is_mut {
+                        false => AccountMeta::new_readonly(pubkey, is_signer),
+                        true => AccountMeta::new(pubkey, is_signer),
+                    });
+                }
+                IdlAccountItem::IdlAccounts(composite) => {
+                    let accounts = accounts.get(&composite.name).ok_or_else(|| {
+                        idl_error(format!("Accounts {} not provided", composite.name))
+                    })?;
+                    self.account_metas(&composite.accounts, accounts, metas)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn idl_account(&self, name: &str) -> Result<&IdlTypeDefinition, ClientError> {
+        self.idl
+            .accounts
+            .iter()
+            .find(|account| account.name == name)
+            .ok_or_else(|| idl_error(format!("Account {} not found", name)))
+    }
+
+    fn type_definition(&self, name: &str) -> Result<&IdlTypeDefinition, ClientError> {
+        self.idl
+            .types
+            .iter()
+            .chain(self.idl.accounts.iter())
+            .find(|ty| ty.name == name)
+            .ok_or_else(|| idl_error(format!("Type {} not found", name)))
+    }
+
+    fn encode_fields(
+        &self,
+        fields: &[IdlField],
+        value: &Value,
+        out: &mut Vec<u8>,
+    ) -> Result<(), ClientError> {
+        for field in fields {
+            let value = value
+                .get(&field.name)
+                .ok_or_else(|| idl_error(format!("Field {} not provided", field.name)))?;
+            self.encode(&field.ty, value, out)?;
+        }
+        Ok(())
+    }
+
+    fn encode(&self, ty: &IdlType, value: &Value, out: &mut Vec<u8>) -> Result<(), ClientError> {
+        match ty {
+            IdlType::Bool => {
+                out.push(value.as_bool().ok_or_else(|| invalid_value(ty, value))? as u8)
+            }
+            IdlType::U8 => out.extend_from_slice(&unsigned::<u8>(ty, value)?.to_le_bytes()),
+            IdlType::I8 => out.extend_from_slice(&signed::<i8>(ty, value)?.to_le_bytes()),
+            IdlType::U16 => out.extend_from_slice(&unsigned::<u16>(ty, value)?.to_le_bytes()),
+            IdlType::I16 => out.extend_from_slice(&signed::<i16>(ty, value)?.to_le_bytes()),
+            IdlType::U32 => out.extend_from_slice(&unsigned::<u32>(ty, value)?.to_le_bytes()),
+            IdlType::I32 => out.extend_from_slice(&signed::<i32>(ty, value)?.to_le_bytes()),
+            IdlType::U64 => out.extend_from_slice(&unsigned::<u64>(ty, value)?.to_le_bytes()),
+            IdlType::I64 => out.extend_from_slice(&signed::<i64>(ty, value)?.to_le_bytes()),
+            IdlType::U128 => out.extend_from_slice(&unsigned::<u128>(ty, value)?.to_le_bytes()),
+            IdlType::I128 => out.extend_from_slice(&signed::<i128>(ty, value)?.to_le_bytes()),
+            IdlType::Bytes => {
+                let bytes = value.as_array().ok_or_else(|| invalid_value(ty, value))?;
+                out.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+                for byte in bytes {
+                    self.encode(&IdlType::U8, byte, out)?;
+                }
+            }
+            IdlType::String => {
+                let s = value.as_str().ok_or_else(|| invalid_value(ty, value))?;
+                out.extend_from_slice(&(s.len() as u32).to_le_bytes());
+                out.extend_from_slice(s.as_bytes());
+            }
+            IdlType::PublicKey => out.extend_from_slice(pubkey(value)?.as_ref()),
+            IdlType::Option(inner) => match value {
+                Value::Null => out.push(0),
+                value => {
+                    out.push(1);
+                    self.encode(inner, value, out)?;
+                }
+            },
+            IdlType::Vec(inner) => {
+                let items = value.as_array().ok_or_else(|| invalid_value(ty, value))?;
+                out.extend_from_slice(&(items.len() as u32).to_le_bytes());
+                for item in items {
+                    self.encode(inner, item, out)?;
+                }
+            }
+            IdlType::Array(inner, len) => {
+                let items = value
+                    .as_array()
+                    .filter(|items| items.len() == *len)
+                    .ok_or_else(|| invalid_value(ty, value))?;
+                for item in items {
+                    self.encode(inner, item, out)?;
+                }
+            }
+            IdlType::Defined(name) => {
+                let definition = self.type_definition(name)?;
+                match &definition.ty {
+                    IdlTypeDefinitionTy::Struct { fields } => {
+                        self.encode_fields(fields, value, out)?
+                    }
+                    IdlTypeDefinitionTy::Enum { variants } => {
+                        let (variant_name, fields_value) = value
+                            .as_object()
+                            .filter(|variant| variant.len() == 1)
+                            .and_then(|variant| variant.iter().next())
+                            .ok_or_else(|| invalid_value(ty, value))?;
+                        let index = variants
+                            .iter()
+                            .position(|variant| &variant.name == variant_name)
+                            .ok_or_else(|| invalid_value(ty, value))?;
+                        out.push(index as u8);
+                        match &variants[index].fields {
+                            None => {}
+                            Some(EnumFields::Named(fields)) => {
+                                self.encode_fields(fields, fields_value, out)?
+                            }
+                            Some(EnumFields::Tuple(tys)) => {
+                                let items = fields_value
+                                    .as_array()
+                                    .filter(|items| items.len() == tys.len())
+                                    .ok_or_else(|| invalid_value(ty, value))?;
+                                for (ty, item) in tys.iter().zip(items) {
+                                    self.encode(ty, item, out)?;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Returns the encoded size of the type, or `None` if it depends on the
+    // value.
+    fn fixed_size(&self, ty: &IdlType) -> Result<Option<usize>, ClientError> {
+        Ok(match ty {
+            IdlType::Bool | IdlType::U8 | IdlType::I8 => Some(1),
+            IdlType::U16 | IdlType::I16 => Some(2),
+            IdlType::U32 | IdlType::I32 => Some(4),
+            IdlType::U64 | IdlType::I64 => Some(8),
+            IdlType::U128 | IdlType::I128 => Some(16),
+            IdlType::PublicKey => Some(32),
+            IdlType::Bytes | IdlType::String | IdlType::Option(_) | IdlType::Vec(_) => None,
+            IdlType::Array(inner, len) => self.fixed_size(inner)?.map(|size| size * len),
+            IdlType::Defined(name) => match &self.type_definition(name)?.ty {
+                IdlTypeDefinitionTy::Struct { fields } => {
+                    let mut size = 0;
+                    for field in fields {
+                        match self.fixed_size(&field.ty)? {
+                            Some(field_size) => size += field_size,
+                            None => return Ok(None),
+                        }
+                    }
+                    Some(size)
+                }
+                // Only the variant index if no variant has fields.
+                IdlTypeDefinitionTy::Enum { variants } => variants
+                    .iter()
+                    .all(|variant| variant.fields.is_none())
+                    .then_some(1),
+            },
+        })
+    }
+
+    fn decode_type_definition(
+        &self,
+        definition: &IdlTypeDefinition,
+        data: &mut &[u8],
+    ) -> Result<Value, ClientError> {
+        match &definition.ty {
+            IdlTypeDefinitionTy::Struct { fields } => self.decode_fields(fields, data),
+            IdlTypeDefinitionTy::Enum { variants } => {
+                let index = read::<1>(data)?[0] as usize;
+                let variant = variants
+                    .get(index)
+                    .ok_or_else(|| idl_error(format!("Invalid {} variant", definition.name)))?;
+                let fields = match &variant.fields {
+                    None => Value::Object(Map::new()),
+                    Some(EnumFields::Named(fields)) => self.decode_fields(fields, data)?,
+                    Some(EnumFields::Tuple(tys)) => Value::Array(
+                        tys.iter()
+                            .map(|ty| self.decode(ty, data))
+                            .collect::<Result<_, _>>()?,
+                    ),
+                };
+                let mut value = Map::new();
+                value.insert(variant.name.clone(), fields);
+                Ok(Value::Object(value))
+            }
+        }
+    }
+
+    fn decode_fields(&self, fields: &[IdlField], data: &mut &[u8]) -> Result<Value, ClientError> {
+        let mut value = Map::new();
+        for field in fields {
+            value.insert(field.name.clone(), self.decode(&field.ty, data)?);
+        }
+        Ok(Value::Object(value))
+    }
+
+    fn decode(&self, ty: &IdlType, data: &mut &[u8]) -> Result<Value, ClientError> {
+        Ok(match ty {
+            IdlType::Bool => Value::Bool(read::<1>(data)?[0] != 0),
+            IdlType::U8 => u8::from_le_bytes(read(data)?).into(),
+            IdlType::I8 => i8::from_le_bytes(read(data)?).into(),
+            IdlType::U16 => u16::from_le_bytes(read(data)?).into(),
+            IdlType::I16 => i16::from_le_bytes(read(data)?).into(),
+            IdlType::U32 => u32::from_le_bytes(read(data)?).into(),
+            IdlType::I32 => i32::from_le_bytes(read(data)?).into(),
+            IdlType::U64 => u64::from_le_bytes(read(data)?).into(),
+            IdlType::I64 => i64::from_le_bytes(read(data)?).into(),
+            IdlType::U128 => u128::from_le_bytes(read(data)?).to_string().into(),
+            IdlType::I128 => i128::from_le_bytes(read(data)?).to_string().into(),
+            IdlType::Bytes => {
+                let len = u32::from_le_bytes(read(data)?) as usize;
+                read_slice(data, len)?.to_vec().into()
+            }
+            IdlType::String => {
+                let len = u32::from_le_bytes(read(data)?) as usize;
+                String::from_utf8(read_slice(data, len)?.to_vec())
+                    .map_err(|e| idl_error(e.to_string()))?
+                    .into()
+            }
+            IdlType::PublicKey => Pubkey::new_from_array(read(data)?).to_string().into(),
+            IdlType::Option(inner) => match read::<1>(data)?[0] {
+                0 => Value::Null,
+                _ => self.decode(inner, data)?,
+            },
+            IdlType::Vec(inner) => {
+                let len = u32::from_le_bytes(read(data)?) as usize;
+                Value::Array(
+                    (0..len)
+                        .map(|_| self.decode(inner, data))
+                        .collect::<Result<_, _>>()?,
+                )
+            }
+            IdlType::Array(inner, len) => Value::Array(
+                (0..*len)
+                    .map(|_| self.decode(inner, data))
+                    .collect::<Result<_, _>>()?,
+            ),
+            IdlType::Defined(name) => {
+                let definition = self.type_definition(name)?;
+                self.decode_type_definition(definition, data)?
+            }
+        })
+    }
+}
+
+// Not technically sighash, since we don't include the arguments, as Rust
+// doesn't allow function overloading.
+fn sighash(namespace: &str, name: &str) -> [u8; 8] {
+    let preimage = format!("{}:{}", namespace, name);
+    let mut sighash = [0u8; 8];
+    sighash.copy_from_slice(&hash(preimage.as_bytes()).to_bytes()[..8]);
+    sighash
+}
+
+fn account_discriminator(account: &IdlTypeDefinition) -> Vec<u8> {
+    match &account.discriminator {
+        Some(discriminator) => discriminator.clone(),
+        None => sighash("account", &account.name).to_vec(),
+    }
+}
+
+fn event_discriminator(event: &IdlEvent) -> [u8; 8] {
+    match event.discriminator {
+        Some(discriminator) => discriminator,
+        None => sighash("event", &event.name),
+    }
+}
+
+fn idl_error(msg: String) -> ClientError {
+    ClientError::IdlError(msg)
+}
+
+fn invalid_value(ty: &IdlType, value: &Value) -> ClientError {
+    idl_error(format!("Invalid value {} for type {:?}", value, ty))
+}
+
+fn pubkey(value: &Value) -> Result<Pubkey, ClientError> {
+    value
+        .as_str()
+        .and_then(|s| Pubkey::from_str(s).ok())
+        .ok_or_else(|| invalid_value(&IdlType::PublicKey, value))
+}
+
+// Integers may be given as JSON numbers or, e.g., if wider than 64 bits, as
+// strings.
+fn unsigned<T: TryFrom<u128>>(ty: &IdlType, value: &Value) -> Result<T, ClientError> {
+    let n = match value {
+        Value::Number(n) => n.as_u64().map(u128::from),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    };
+    n.and_then(|n| T::try_from(n).ok())
+        .ok_or_else(|| invalid_value(ty, value))
+}
+
+fn signed<T: TryFrom<i128>>(ty: &IdlType, value: &Value) -> Result<T, ClientError> {
+    let n = match value {
+        Value::Number(n) => n.as_i64().map(i128::from),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    };
+    n.and_then(|n| T::try_from(n).ok())
+        .ok_or_else(|| invalid_value(ty, value))
+}
+
+fn read<const N: usize>(data: &mut &[u8]) -> Result<[u8; N], ClientError> {
+    let mut bytes = [0u8; N];
+    bytes.copy_from_slice(read_slice(data, N)?);
+    Ok(bytes)
+}
+
+fn read_slice<'a>(data: &mut &'a [u8], len: usize) -> Result<&'a [u8], ClientError> {
+    if data.len() < len {
+        return Err(idl_error("Unexpected end of data".to_string()));
+    }
+    let (bytes, rest) = data.split_at(len);
+    *data = rest;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Client, Cluster};
+    use anchor_lang::solana_program::instruction::AccountMeta;
+    use serde_json::json;
+    use solana_sdk::signature::Keypair;
+
+    fn program() -> DynamicProgram {
+        let idl: Idl = serde_json::from_value(json!({
+            "version": "0.0.0",
+            "name": "counter",
+            "instructions": [{
+                "name": "setCount",
+                "accounts": [
+                    { "name": "counter", "isMut": true, "isSigner": false },
+                    {
+                        "name": "auth",
+                        "accounts": [
+                            { "name": "authority", "isMut": false, "isSigner": true },
+                            { "name": "delegate", "isMut": false, "isSigner": true, "isOptional": true }
+                        ]
+                    }
+                ],
+                "args": [
+                    { "name": "count", "type": "u128" },
+                    { "name": "mode", "type": { "defined": "Mode" } }
+                ]
+            }],
+            "accounts": [{
+                "name": "Counter",
+                "type": {
+                    "kind": "struct",
+                    "fields": [
+                        { "name": "authority", "type": "publicKey" },
+                        { "name": "count", "type": "u64" },
+                        { "name": "label", "type": { "option": "string" } }
+                    ]
+                }
+            }],
+            "types": [{
+                "name": "Mode",
+                "type": {
+                    "kind": "enum",
+                    "variants": [
+                        { "name": "Off" },
+                        { "name": "Step", "fields": [{ "name": "by", "type": "i16" }] }
+                    ]
+                }
+            }],
+            "events": [{
+                "name": "CountSet",
+                "fields": [{ "name": "count", "type": "u8", "index": false }]
+            }]
+        }))
+        .unwrap();
+        Client::new(Cluster::Localnet, Keypair::new()).dynamic_program(Pubkey::new_unique(), idl)
+    }
+
+    #[test]
+    fn encode_instruction() {
+        let program = program();
+        let counter = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let ix = program
+            .instruction(
+                "setCount",
+                &json!({
+                    "counter": counter.to_string(),
+                    "auth": { "authority": authority.to_string() }
+                }),
+                &json!({ "count": "340282366920938463463374607431768211455", "mode": { "Step": { "by": -2 } } }),
+            )
+            .unwrap();
+        assert_eq!(
+            ix.accounts,
+            vec![
+                AccountMeta::new(counter, false),
+                AccountMeta::new_readonly(authority, true),
+                AccountMeta::new_readonly(program.program().id(), false),
+            ]
+        );
+        let mut data = sighash("global", "set_count").to_vec();
+        data.extend_from_slice(&u128::MAX.to_le_bytes());
+        data.push(1);
+        data.extend_from_slice(&(-2i16).to_le_bytes());
+        assert_eq!(ix.data, data);
+
+        assert!(program
+            .instruction(
+                "setCount",
+                &json!({ "counter": counter.to_string() }),
+                &json!({})
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn decode_account_and_event() {
+        let program = program();
+        let authority = Pubkey::new_unique();
+        let mut data = sighash("account", "Counter").to_vec();
+        data.extend_from_slice(authority.as_ref());
+        data.extend_from_slice(&7u64.to_le_bytes());
+        data.extend_from_slice(&[1, 2, 0, 0, 0]);
+        data.extend_from_slice(b"hi");
+        assert_eq!(
+            program.decode_account("Counter", &data).unwrap(),
+            json!({ "authority": authority.to_string(), "count": 7, "label": "hi" })
+        );
+        assert!(program.decode_account("Counter", &data[1..]).is_err());
+
+        let mut data = sighash("event", "CountSet").to_vec();
+        data.push(3);
+        assert_eq!(
+            program.decode_event(&data).unwrap(),
+            Some(("CountSet".to_string(), json!({ "count": 3 })))
+        );
+        assert_eq!(program.decode_event(&[0; 9]).unwrap(), None);
+    }
+
+    #[test]
+    fn resolve_field_filters() {
+        let program = program();
+        let authority = Pubkey::new_unique();
+        assert_eq!(
+            program
+                .filter("Counter", Filter::field("authority", authority.to_string()))
+                .unwrap(),
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(8, authority.as_ref()))
+        );
+        assert_eq!(
+            program
+                .filter("Counter", Filter::field("count", 7))
+                .unwrap(),
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(40, &7u64.to_le_bytes()))
+        );
+        assert_eq!(
+            program
+                .filter("Counter", Filter::field("label", "hi"))
+                .unwrap(),
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(48, &[1, 2, 0, 0, 0, b'h', b'i']))
+        );
+        assert_eq!(
+            program
+                .filter("Counter", Filter::Rpc(RpcFilterType::DataSize(60)))
+                .unwrap(),
+            RpcFilterType::DataSize(60)
+        );
+        assert!(program
+            .filter("Counter", Filter::field("owner", 7))
+            .is_err());
+        assert!(program
+            .filter("Counter", Filter::field("count", "x"))
+            .is_err());
+    }
+}
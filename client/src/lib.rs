@@ -1,30 +1,73 @@
 //! `anchor_client` provides an RPC client to send transactions and fetch
 //! deserialized accounts from Solana programs written in `anchor_lang`.
+//!
+//! Its API is async, for use within an async runtime, e.g., tokio. With the
+//! `blocking` feature, the same API is instead blocking, each call running to
+//! completion on a runtime owned by the `Client`, so it mustn't be used within
+//! one. Since Cargo unifies features across the dependency graph, enabling
+//! `blocking` makes the API blocking for every crate in the build that
+//! depends on `anchor-client`, so libraries should leave it to binaries.
+//!
+//! With the `program-test` feature, a `Client` can send requests to a
+//! `BanksClient`, e.g., of a `solana_program_test::ProgramTest`, rather than
+//! to a cluster, so that the same requests run in tests without a validator.
+//! Since the bank runs on the test's runtime, use it with the async API.
 
+use anchor_lang::event::{EVENT_AUTHORITY_SEED, EVENT_IX_TAG_LE};
 use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
 use anchor_lang::solana_program::program_error::ProgramError;
 use anchor_lang::solana_program::pubkey::Pubkey;
 use anchor_lang::solana_program::system_program;
 use anchor_lang::solana_program::sysvar::rent;
-use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_lang::{AccountDeserialize, Discriminator, InstructionData, ToAccountMetas};
+use connection::{Backend, Connection};
 use regex::Regex;
+use solana_account_decoder::UiAccountEncoding;
 use solana_client::client_error::ClientError as SolanaClientError;
 use solana_client::pubsub_client::{PubsubClient, PubsubClientError, PubsubClientSubscription};
-use solana_client::rpc_client::RpcClient;
-use solana_client::rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter};
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_client::rpc_config::{
+    RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcSendTransactionConfig, RpcTransactionConfig,
+    RpcTransactionLogsConfig, RpcTransactionLogsFilter,
+};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
 use solana_client::rpc_response::{Response as RpcResponse, RpcLogsResponse};
+use solana_sdk::account::Account;
+use solana_sdk::address_lookup_table_account::AddressLookupTableAccount;
+use solana_sdk::bs58;
 use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::message::{v0, CompileError, VersionedMessage};
 use solana_sdk::signature::{Keypair, Signature, Signer};
-use solana_sdk::transaction::Transaction;
-use std::convert::Into;
+use solana_sdk::transaction::{Transaction, TransactionError, VersionedTransaction};
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, UiInnerInstructions, UiInstruction,
+    UiLoadedAddresses, UiTransactionEncoding,
+};
+use std::convert::{Into, TryFrom};
+use std::ops::RangeInclusive;
+use std::time::Duration;
 use thiserror::Error;
 
+#[cfg(feature = "program-test")]
+use solana_banks_client::{BanksClient, BanksClientError};
+
 pub use anchor_lang;
+pub use anchor_syn::idl;
 pub use cluster::Cluster;
+pub use dynamic::{DynamicProgram, Filter};
 pub use solana_client;
 pub use solana_sdk;
 
 mod cluster;
+mod connection;
+mod dynamic;
+
+#[cfg(feature = "blocking")]
+mod blocking;
+#[cfg(not(feature = "blocking"))]
+mod nonblocking;
 
 /// EventHandle unsubscribes from a program event stream on drop.
 pub type EventHandle = PubsubClientSubscription<RpcResponse<RpcLogsResponse>>;
@@ -43,6 +86,9 @@ impl Client {
                 cluster,
                 payer,
                 options: None,
+                backend: Backend::Rpc,
+                #[cfg(feature = "blocking")]
+                runtime: blocking::runtime(),
             },
         }
     }
@@ -53,6 +99,26 @@ impl Client {
                 cluster,
                 payer,
                 options: Some(options),
+                backend: Backend::Rpc,
+                #[cfg(feature = "blocking")]
+                runtime: blocking::runtime(),
+            },
+        }
+    }
+
+    /// Returns a client sending requests to the given bank, e.g., that of a
+    /// `ProgramTest`, rather than to a cluster. Fetching program accounts and
+    /// transaction history requires a cluster, as do `rpc` and `on`.
+    #[cfg(feature = "program-test")]
+    pub fn new_with_banks_client(banks_client: BanksClient, payer: Keypair) -> Self {
+        Self {
+            cfg: Config {
+                cluster: Cluster::Localnet,
+                payer,
+                options: None,
+                backend: Backend::Banks(banks_client),
+                #[cfg(feature = "blocking")]
+                runtime: blocking::runtime(),
             },
         }
     }
@@ -64,16 +130,32 @@ impl Client {
                 cluster: self.cfg.cluster.clone(),
                 options: self.cfg.options,
                 payer: Keypair::from_bytes(&self.cfg.payer.to_bytes()).unwrap(),
+                backend: self.cfg.backend.clone(),
+                #[cfg(feature = "blocking")]
+                runtime: self.cfg.runtime.clone(),
             },
         }
     }
+
+    /// Returns a client for the program with the given IDL, e.g., loaded at
+    /// runtime.
+    pub fn dynamic_program(&self, program_id: Pubkey, idl: idl::Idl) -> DynamicProgram {
+        DynamicProgram::new(self.program(program_id), idl)
+    }
 }
 
+// The maximum number of accounts fetched by a single `getMultipleAccounts`
+// request.
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
 // Internal configuration for a client.
 struct Config {
     cluster: Cluster,
     payer: Keypair,
     options: Option<CommitmentConfig>,
+    backend: Backend,
+    #[cfg(feature = "blocking")]
+    runtime: blocking::Runtime,
 }
 
 /// Program is the primary client handle to be used to build and send requests.
@@ -89,55 +171,184 @@ impl Program {
 
     /// Returns a request builder.
     pub fn request(&self) -> RequestBuilder {
-        RequestBuilder::from(
-            self.program_id,
-            self.cfg.cluster.url(),
-            Keypair::from_bytes(&self.cfg.payer.to_bytes()).unwrap(),
-            self.cfg.options,
-            RequestNamespace::Global,
-        )
+        self.request_builder(RequestNamespace::Global)
     }
 
     /// Returns a request builder for program state.
     pub fn state_request(&self) -> RequestBuilder {
-        RequestBuilder::from(
-            self.program_id,
-            self.cfg.cluster.url(),
-            Keypair::from_bytes(&self.cfg.payer.to_bytes()).unwrap(),
-            self.cfg.options,
-            RequestNamespace::State { new: false },
-        )
+        self.request_builder(RequestNamespace::State { new: false })
     }
 
-    /// Returns the account at the given address.
-    pub fn account<T: AccountDeserialize>(&self, address: Pubkey) -> Result<T, ClientError> {
-        let rpc_client = RpcClient::new_with_commitment(
-            self.cfg.cluster.url().to_string(),
-            self.cfg.options.unwrap_or_default(),
-        );
-        let account = rpc_client
-            .get_account_with_commitment(&address, CommitmentConfig::processed())?
-            .value
+    fn request_builder(&self, namespace: RequestNamespace) -> RequestBuilder {
+        RequestBuilder {
+            backend: self.cfg.backend.clone(),
+            #[cfg(feature = "blocking")]
+            runtime: Some(self.cfg.runtime.clone()),
+            ..RequestBuilder::from(
+                self.program_id,
+                self.cfg.cluster.url(),
+                Keypair::from_bytes(&self.cfg.payer.to_bytes()).unwrap(),
+                self.cfg.options,
+                namespace,
+            )
+        }
+    }
+
+    // The blocking and async APIs both run these, the former to completion.
+
+    async fn account_internal<T: AccountDeserialize>(
+        &self,
+        address: Pubkey,
+    ) -> Result<T, ClientError> {
+        let account = self
+            .connection()
+            .get_account(&address, CommitmentConfig::processed())
+            .await?
             .ok_or(ClientError::AccountNotFound)?;
         let mut data: &[u8] = &account.data;
         T::try_deserialize(&mut data).map_err(Into::into)
     }
 
-    pub fn state<T: AccountDeserialize>(&self) -> Result<T, ClientError> {
-        self.account(anchor_lang::__private::state::address(&self.program_id))
+    async fn accounts_internal<T: AccountDeserialize + Discriminator>(
+        &self,
+        filters: Vec<RpcFilterType>,
+    ) -> Result<Vec<(Pubkey, T)>, ClientError> {
+        let mut filters = filters;
+        filters.insert(
+            0,
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, T::discriminator())),
+        );
+        self.program_accounts(filters)
+            .await?
+            .into_iter()
+            .map(|(key, account)| {
+                let mut data: &[u8] = &account.data;
+                Ok((key, T::try_deserialize(&mut data)?))
+            })
+            .collect()
     }
 
-    pub fn rpc(&self) -> RpcClient {
-        RpcClient::new_with_commitment(
-            self.cfg.cluster.url().to_string(),
-            self.cfg.options.unwrap_or_default(),
-        )
+    pub(crate) async fn program_accounts(
+        &self,
+        filters: Vec<RpcFilterType>,
+    ) -> Result<Vec<(Pubkey, Account)>, ClientError> {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(filters),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                commitment: Some(self.cfg.options.unwrap_or_default()),
+                ..RpcAccountInfoConfig::default()
+            },
+            with_context: None,
+        };
+        Ok(self
+            .connection()
+            .rpc("Fetching program accounts")?
+            .get_program_accounts_with_config(&self.program_id, config)
+            .await?)
+    }
+
+    async fn accounts_multiple_internal<T: AccountDeserialize>(
+        &self,
+        addresses: &[Pubkey],
+    ) -> Result<Vec<Option<T>>, ClientError> {
+        self.connection()
+            .get_multiple_accounts(addresses, self.cfg.options.unwrap_or_default())
+            .await?
+            .into_iter()
+            .map(|account| match account {
+                None => Ok(None),
+                Some(account) => {
+                    let mut data: &[u8] = &account.data;
+                    Ok(Some(T::try_deserialize(&mut data)?))
+                }
+            })
+            .collect()
+    }
+
+    async fn events_internal<T: anchor_lang::Event + anchor_lang::AnchorDeserialize>(
+        &self,
+        signature: &Signature,
+    ) -> Result<Vec<T>, ClientError> {
+        let config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(history_commitment(self.cfg.options)),
+            max_supported_transaction_version: Some(0),
+        };
+        let tx = self
+            .connection()
+            .rpc("Fetching transactions")?
+            .get_transaction_with_config(signature, config)
+            .await?;
+        transaction_events_with(&self.program_id, tx, decode_event::<T>)
+    }
+
+    async fn events_in_slots_internal<T: anchor_lang::Event + anchor_lang::AnchorDeserialize>(
+        &self,
+        slots: RangeInclusive<u64>,
+    ) -> Result<Vec<(EventContext, T)>, ClientError> {
+        let connection = self.connection();
+        let rpc_client = connection.rpc("Fetching transactions")?;
+
+        // Signatures are returned newest first, a page at a time.
+        let mut statuses = Vec::new();
+        let mut before = None;
+        loop {
+            let config = GetConfirmedSignaturesForAddress2Config {
+                before,
+                until: None,
+                limit: None,
+                commitment: Some(history_commitment(self.cfg.options)),
+            };
+            let page = rpc_client
+                .get_signatures_for_address_with_config(&self.program_id, config)
+                .await?;
+            let last = match page.last() {
+                None => break,
+                Some(last) => last,
+            };
+            before = Some(last.signature.parse().unwrap());
+            let done = last.slot < *slots.start();
+            statuses.extend(
+                page.into_iter()
+                    .filter(|status| status.err.is_none() && slots.contains(&status.slot)),
+            );
+            if done {
+                break;
+            }
+        }
+
+        let mut events = Vec::new();
+        for status in statuses.into_iter().rev() {
+            let ctx = EventContext {
+                signature: status.signature.parse().unwrap(),
+                slot: status.slot,
+            };
+            for e in self.events_internal(&ctx.signature).await? {
+                events.push((ctx.clone(), e));
+            }
+        }
+        Ok(events)
+    }
+
+    async fn state_internal<T: AccountDeserialize>(&self) -> Result<T, ClientError> {
+        self.account_internal(anchor_lang::__private::state::address(&self.program_id))
+            .await
+    }
+
+    pub(crate) fn connection(&self) -> Connection {
+        self.cfg
+            .backend
+            .connect(self.cfg.cluster.url(), self.cfg.options.unwrap_or_default())
     }
 
     pub fn id(&self) -> Pubkey {
         self.program_id
     }
 
+    /// Subscribes to the program's events of type `T`, invoking `f` with each
+    /// one emitted by a successful transaction, until the returned handle is
+    /// dropped.
     pub fn on<T: anchor_lang::Event + anchor_lang::AnchorDeserialize>(
         &self,
         f: impl Fn(&EventContext, T) + Send + 'static,
@@ -148,56 +359,22 @@ impl Program {
         let cfg = RpcTransactionLogsConfig {
             commitment: self.cfg.options,
         };
-        let self_program_str = self.program_id.to_string();
+        let program_id = self.program_id;
         let (client, receiver) = PubsubClient::logs_subscribe(&ws_url, filter, cfg)?;
         std::thread::spawn(move || {
-            loop {
-                match receiver.recv() {
-                    Ok(logs) => {
-                        let ctx = EventContext {
-                            signature: logs.value.signature.parse().unwrap(),
-                            slot: logs.context.slot,
-                        };
-                        let mut logs = &logs.value.logs[..];
-                        if !logs.is_empty() {
-                            if let Ok(mut execution) = Execution::new(&mut logs) {
-                                for l in logs {
-                                    // Parse the log.
-                                    let (event, new_program, did_pop) = {
-                                        if self_program_str == execution.program() {
-                                            handle_program_log(&self_program_str, l).unwrap_or_else(
-                                                |e| {
-                                                    println!(
-                                                        "Unable to parse log: {}",
-                                                        e.to_string()
-                                                    );
-                                                    std::process::exit(1);
-                                                },
-                                            )
-                                        } else {
-                                            let (program, did_pop) =
-                                                handle_system_log(&self_program_str, l);
-                                            (None, program, did_pop)
-                                        }
-                                    };
-                                    // Emit the event.
-                                    if let Some(e) = event {
-                                        f(&ctx, e);
-                                    }
-                                    // Switch program context on CPI.
-                                    if let Some(new_program) = new_program {
-                                        execution.push(new_program);
-                                    }
-                                    // Program returned.
-                                    if did_pop {
-                                        execution.pop();
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    Err(_err) => {
-                        return;
+            while let Ok(logs) = receiver.recv() {
+                if logs.value.err.is_some() {
+                    continue;
+                }
+                let ctx = EventContext {
+                    signature: logs.value.signature.parse().unwrap(),
+                    slot: logs.context.slot,
+                };
+                // Skip transactions whose logs can't be parsed, rather than
+                // ending the subscription.
+                if let Ok(events) = parse_logs::<T>(&program_id, &logs.value.logs) {
+                    for e in events {
+                        f(&ctx, e);
                     }
                 }
             }
@@ -206,51 +383,171 @@ impl Program {
     }
 }
 
-fn handle_program_log<T: anchor_lang::Event + anchor_lang::AnchorDeserialize>(
-    self_program_str: &str,
+/// Returns the events of type `T` emitted by the given program in a
+/// transaction's logs, in order, including those emitted within CPIs.
+pub fn parse_logs<T: anchor_lang::Event + anchor_lang::AnchorDeserialize>(
+    program_id: &Pubkey,
+    logs: &[String],
+) -> Result<Vec<T>, ClientError> {
+    parse_logs_with(program_id, logs, decode_event::<T>)
+}
+
+// Decodes an event of type `T` from its discriminator and data, if it's one.
+fn decode_event<T: anchor_lang::Event + anchor_lang::AnchorDeserialize>(
+    data: &[u8],
+) -> Result<Option<T>, ClientError> {
+    match data.strip_prefix(T::discriminator()) {
+        None => Ok(None),
+        Some(mut slice) => anchor_lang::AnchorDeserialize::deserialize(&mut slice)
+            .map(Some)
+            .map_err(|e| ClientError::LogParseError(e.to_string())),
+    }
+}
+
+// Transaction history isn't available at the processed commitment.
+fn history_commitment(options: Option<CommitmentConfig>) -> CommitmentConfig {
+    let commitment = options.unwrap_or_default();
+    match commitment.is_at_least_confirmed() {
+        true => commitment,
+        false => CommitmentConfig::confirmed(),
+    }
+}
+
+// Returns the events decoded by `decode` from a confirmed transaction, first
+// those logged by the given program and then those it emitted via self-CPI,
+// or none if the transaction failed.
+fn transaction_events_with<E>(
+    program_id: &Pubkey,
+    tx: EncodedConfirmedTransactionWithStatusMeta,
+    mut decode: impl FnMut(&[u8]) -> Result<Option<E>, ClientError>,
+) -> Result<Vec<E>, ClientError> {
+    let meta = match tx.transaction.meta {
+        Some(meta) if meta.err.is_none() => meta,
+        _ => return Ok(Vec::new()),
+    };
+    let logs: Option<Vec<String>> = meta.log_messages.into();
+    let mut events = parse_logs_with(program_id, &logs.unwrap_or_default(), &mut decode)?;
+
+    let inner_instructions: Option<Vec<UiInnerInstructions>> = meta.inner_instructions.into();
+    let inner_instructions = inner_instructions.unwrap_or_default();
+    if inner_instructions.is_empty() {
+        return Ok(events);
+    }
+    let decoded_tx =
+        tx.transaction.transaction.decode().ok_or_else(|| {
+            ClientError::LogParseError("Unable to decode transaction".to_string())
+        })?;
+    let mut account_keys = decoded_tx.message.static_account_keys().to_vec();
+    let loaded_addresses: Option<UiLoadedAddresses> = meta.loaded_addresses.into();
+    if let Some(loaded_addresses) = loaded_addresses {
+        for address in loaded_addresses
+            .writable
+            .iter()
+            .chain(loaded_addresses.readonly.iter())
+        {
+            account_keys.push(
+                address
+                    .parse()
+                    .map_err(|_| ClientError::LogParseError(address.clone()))?,
+            );
+        }
+    }
+
+    // Only the program can sign its self-CPIs as the event authority.
+    let event_authority = Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], program_id).0;
+    let key = |index: &u8| account_keys.get(*index as usize);
+    for ix in inner_instructions.iter().flat_map(|ixs| &ixs.instructions) {
+        let ix = match ix {
+            UiInstruction::Compiled(ix) => ix,
+            UiInstruction::Parsed(_) => continue,
+        };
+        if key(&ix.program_id_index) != Some(program_id)
+            || ix.accounts.first().and_then(key) != Some(&event_authority)
+        {
+            continue;
+        }
+        let data = bs58::decode(&ix.data)
+            .into_vec()
+            .map_err(|_| ClientError::LogParseError(ix.data.clone()))?;
+        if let Some(data) = data.strip_prefix(&EVENT_IX_TAG_LE[..]) {
+            if let Some(e) = decode(data)? {
+                events.push(e);
+            }
+        }
+    }
+    Ok(events)
+}
+
+// Returns the events decoded by `decode` from the base64 data logged by the
+// given program, skipping data it doesn't recognize as an event.
+pub(crate) fn parse_logs_with<E>(
+    program_id: &Pubkey,
+    logs: &[String],
+    mut decode: impl FnMut(&[u8]) -> Result<Option<E>, ClientError>,
+) -> Result<Vec<E>, ClientError> {
+    let mut events = Vec::new();
+    if logs.is_empty() {
+        return Ok(events);
+    }
+    let self_program_str = program_id.to_string();
+    let mut logs = logs;
+    let mut execution = Execution::new(&mut logs)?;
+    for l in logs {
+        // Parse the log.
+        let (event, new_program, did_pop) = {
+            if execution.program() == Some(self_program_str.as_str()) {
+                handle_program_log(l, &mut decode)?
+            } else {
+                let (program, did_pop) = handle_system_log(l);
+                (None, program, did_pop)
+            }
+        };
+        // Emit the event.
+        if let Some(e) = event {
+            events.push(e);
+        }
+        // Switch program context on CPI.
+        if let Some(new_program) = new_program {
+            execution.push(new_program);
+        }
+        // Program returned.
+        if did_pop {
+            execution.pop();
+        }
+    }
+    Ok(events)
+}
+
+fn handle_program_log<E>(
     l: &str,
-) -> Result<(Option<T>, Option<String>, bool), ClientError> {
+    decode: impl FnOnce(&[u8]) -> Result<Option<E>, ClientError>,
+) -> Result<(Option<E>, Option<String>, bool), ClientError> {
     // Log emitted from the current program.
-    if l.starts_with("Program log:") {
-        let log = l.to_string().split_off("Program log: ".len());
-        let borsh_bytes = anchor_lang::__private::base64::decode(log)
-            .map_err(|_| ClientError::LogParseError(l.to_string()))?;
-
-        let mut slice: &[u8] = &borsh_bytes[..];
-        let disc: [u8; 8] = {
-            let mut disc = [0; 8];
-            disc.copy_from_slice(&borsh_bytes[..8]);
-            slice = &slice[8..];
-            disc
+    if let Some(log) = l.strip_prefix("Program log: ") {
+        // Logs that aren't base64, e.g., from `msg!`, aren't events.
+        let borsh_bytes = match anchor_lang::__private::base64::decode(log) {
+            Ok(borsh_bytes) => borsh_bytes,
+            Err(_) => return Ok((None, None, false)),
         };
-        let mut event = None;
-        if disc == T::discriminator() {
-            let e: T = anchor_lang::AnchorDeserialize::deserialize(&mut slice)
-                .map_err(|e| ClientError::LogParseError(e.to_string()))?;
-            event = Some(e);
-        }
+        let event = decode(&borsh_bytes)?;
         Ok((event, None, false))
     }
     // System log.
     else {
-        let (program, did_pop) = handle_system_log(self_program_str, l);
+        let (program, did_pop) = handle_system_log(l);
         Ok((None, program, did_pop))
     }
 }
 
-fn handle_system_log(this_program_str: &str, log: &str) -> (Option<String>, bool) {
-    if log.starts_with(&format!("Program {} log:", this_program_str)) {
-        (Some(this_program_str.to_string()), false)
-    } else if log.contains("invoke") {
-        (Some("cpi".to_string()), false) // Any string will do.
-    } else {
-        let re = Regex::new(r"^Program (.*) success*$").unwrap();
-        if re.is_match(log) {
-            (None, true)
-        } else {
-            (None, false)
-        }
+// Returns the program invoked by the log, if any, and whether the log ends
+// the current program's execution.
+fn handle_system_log(log: &str) -> (Option<String>, bool) {
+    let invoke_re = Regex::new(r"^Program (\S+) invoke \[\d+\]$").unwrap();
+    if let Some(c) = invoke_re.captures(log) {
+        return (Some(c[1].to_string()), false);
     }
+    let return_re = Regex::new(r"^Program \S+ (success|failed.*)$").unwrap();
+    (None, return_re.is_match(log))
 }
 
 struct Execution {
@@ -276,9 +573,9 @@ impl Execution {
         })
     }
 
-    pub fn program(&self) -> String {
-        assert!(!self.stack.is_empty());
-        self.stack[self.stack.len() - 1].clone()
+    // None between a transaction's top level instructions.
+    pub fn program(&self) -> Option<&str> {
+        self.stack.last().map(String::as_str)
     }
 
     pub fn push(&mut self, new_program: String) {
@@ -286,12 +583,11 @@ impl Execution {
     }
 
     pub fn pop(&mut self) {
-        assert!(!self.stack.is_empty());
-        self.stack.pop().unwrap();
+        self.stack.pop();
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct EventContext {
     pub signature: Signature,
     pub slot: u64,
@@ -306,6 +602,18 @@ pub enum ClientError {
     #[error("{0}")]
     SolanaClientError(#[from] SolanaClientError),
     #[error("{0}")]
+    CompileError(#[from] CompileError),
+    #[error("{0}")]
+    IdlError(String),
+    #[error("Transaction {0} was not confirmed")]
+    TransactionUnconfirmed(Signature),
+    #[cfg(feature = "program-test")]
+    #[error("{0}")]
+    BanksClientError(#[from] BanksClientError),
+    #[cfg(feature = "program-test")]
+    #[error("{0} isn't supported by a BanksClient")]
+    UnsupportedByBanksClient(&'static str),
+    #[error("{0}")]
     SolanaClientPubsubError(#[from] PubsubClientError),
     #[error("Unable to parse log: {0}")]
     LogParseError(String),
@@ -325,6 +633,52 @@ pub struct RequestBuilder<'a> {
     signers: Vec<&'a dyn Signer>,
     // True if the user is sending a state instruction.
     namespace: RequestNamespace,
+    compute_unit_limit: Option<ComputeUnitLimit>,
+    compute_unit_price: Option<u64>,
+    lookup_tables: Vec<AddressLookupTableAccount>,
+    // Lookup tables fetched when the transaction is built.
+    lookup_table_addresses: Vec<Pubkey>,
+    send_policy: SendPolicy,
+    backend: Backend,
+    #[cfg(feature = "blocking")]
+    runtime: Option<blocking::Runtime>,
+}
+
+/// `SendPolicy` configures how `RequestBuilder::send` lands a transaction.
+#[derive(Clone, Debug)]
+pub struct SendPolicy {
+    /// The number of times the transaction is resent while unconfirmed.
+    pub max_retries: usize,
+    /// True if the transaction is signed again with a new blockhash when its
+    /// blockhash expires, rather than failing.
+    pub refresh_blockhash: bool,
+    /// True if the transaction isn't simulated by the RPC node before being
+    /// sent.
+    pub skip_preflight: bool,
+    /// How long to wait for the transaction to be confirmed before checking
+    /// its status and resending it.
+    pub poll_interval: Duration,
+}
+
+impl Default for SendPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 30,
+            refresh_blockhash: false,
+            skip_preflight: false,
+            poll_interval: Duration::from_secs(2),
+        }
+    }
+}
+
+// The maximum compute units a transaction may consume.
+const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+#[derive(Clone, Copy)]
+enum ComputeUnitLimit {
+    Units(u32),
+    // Sized from the units consumed by a simulation of the transaction.
+    Simulated { margin_percent: u32 },
 }
 
 #[derive(PartialEq)]
@@ -355,6 +709,14 @@ impl<'a> RequestBuilder<'a> {
             instruction_data: None,
             signers: Vec::new(),
             namespace,
+            compute_unit_limit: None,
+            compute_unit_price: None,
+            lookup_tables: Vec::new(),
+            lookup_table_addresses: Vec::new(),
+            send_policy: SendPolicy::default(),
+            backend: Backend::Rpc,
+            #[cfg(feature = "blocking")]
+            runtime: None,
         }
     }
 
@@ -363,8 +725,11 @@ impl<'a> RequestBuilder<'a> {
         self
     }
 
+    /// Sends the request to the cluster at the given URL, even if the client
+    /// sends requests to a bank.
     pub fn cluster(mut self, url: &str) -> Self {
         self.cluster = url.to_string();
+        self.backend = Backend::Rpc;
         self
     }
 
@@ -408,7 +773,50 @@ impl<'a> RequestBuilder<'a> {
         self
     }
 
-    pub fn send(self) -> Result<Signature, ClientError> {
+    /// Limits the compute units the transaction may consume, by prepending a
+    /// compute budget instruction.
+    pub fn compute_unit_limit(mut self, units: u32) -> Self {
+        self.compute_unit_limit = Some(ComputeUnitLimit::Units(units));
+        self
+    }
+
+    /// Limits the compute units the transaction may consume to those it
+    /// consumes when simulated right before sending, plus the given margin.
+    pub fn compute_unit_limit_from_simulation(mut self, margin_percent: u32) -> Self {
+        self.compute_unit_limit = Some(ComputeUnitLimit::Simulated { margin_percent });
+        self
+    }
+
+    /// Configures how the transaction is sent and confirmed.
+    pub fn send_policy(mut self, send_policy: SendPolicy) -> Self {
+        self.send_policy = send_policy;
+        self
+    }
+
+    /// Sends the request as a v0 transaction, referencing accounts via the
+    /// given lookup table where possible.
+    pub fn lookup_table(mut self, lookup_table: AddressLookupTableAccount) -> Self {
+        self.lookup_tables.push(lookup_table);
+        self
+    }
+
+    /// Like `lookup_table`, but fetches the lookup table at the given address
+    /// when the transaction is built.
+    pub fn lookup_table_address(mut self, address: Pubkey) -> Self {
+        self.lookup_table_addresses.push(address);
+        self
+    }
+
+    /// Sets the priority fee paid per compute unit, in micro-lamports, by
+    /// prepending a compute budget instruction.
+    pub fn compute_unit_price(mut self, micro_lamports: u64) -> Self {
+        self.compute_unit_price = Some(micro_lamports);
+        self
+    }
+
+    // Returns the request's instructions, including the compute budget
+    // instructions and the one built via `args`, if any.
+    fn instructions(&self) -> Vec<Instruction> {
         let accounts = match self.namespace {
             RequestNamespace::State { new } => {
                 let mut accounts = match new {
@@ -434,35 +842,221 @@ impl<'a> RequestBuilder<'a> {
                 accounts.extend_from_slice(&self.accounts);
                 accounts
             }
-            _ => self.accounts,
+            _ => self.accounts.clone(),
         };
-        let mut instructions = self.instructions;
-        if let Some(ix_data) = self.instruction_data {
+        let mut instructions = Vec::new();
+        match self.compute_unit_limit {
+            None => {}
+            Some(ComputeUnitLimit::Units(units)) => {
+                instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(units))
+            }
+            // Simulate without constraining the transaction.
+            Some(ComputeUnitLimit::Simulated { .. }) => instructions.push(
+                ComputeBudgetInstruction::set_compute_unit_limit(MAX_COMPUTE_UNIT_LIMIT),
+            ),
+        }
+        if let Some(micro_lamports) = self.compute_unit_price {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_price(
+                micro_lamports,
+            ));
+        }
+        instructions.extend_from_slice(&self.instructions);
+        if let Some(ix_data) = &self.instruction_data {
             instructions.push(Instruction {
                 program_id: self.program_id,
-                data: ix_data,
+                data: ix_data.clone(),
                 accounts,
             });
         }
+        instructions
+    }
 
-        let mut signers = self.signers;
+    // Returns a legacy transaction, unless lookup tables were given, in which
+    // case a v0 transaction using them.
+    async fn signed_transaction(
+        &self,
+        connection: &Connection,
+    ) -> Result<VersionedTransaction, ClientError> {
+        let mut signers = self.signers.clone();
         signers.push(&self.payer);
 
-        let rpc_client = RpcClient::new_with_commitment(self.cluster, self.options);
-
-        let tx = {
-            let (recent_hash, _fee_calc) = rpc_client.get_recent_blockhash()?;
-            Transaction::new_signed_with_payer(
-                &instructions,
+        let recent_hash = connection.get_latest_blockhash().await?;
+        if self.lookup_tables.is_empty() && self.lookup_table_addresses.is_empty() {
+            return Ok(Transaction::new_signed_with_payer(
+                &self.instructions(),
                 Some(&self.payer.pubkey()),
                 &signers,
                 recent_hash,
             )
-        };
+            .into());
+        }
+
+        let mut lookup_tables = self.lookup_tables.clone();
+        if !self.lookup_table_addresses.is_empty() {
+            let accounts = connection
+                .get_multiple_accounts(&self.lookup_table_addresses, self.options)
+                .await?;
+            for (key, account) in self.lookup_table_addresses.iter().zip(accounts) {
+                let account = account.ok_or(ClientError::AccountNotFound)?;
+                lookup_tables.push(AddressLookupTableAccount {
+                    key: *key,
+                    addresses: lookup_table_addresses(&account.data)?,
+                });
+            }
+        }
+        let message = v0::Message::try_compile(
+            &self.payer.pubkey(),
+            &self.instructions(),
+            &lookup_tables,
+            recent_hash,
+        )?;
+        VersionedTransaction::try_new(VersionedMessage::V0(message), &signers)
+            .map_err(|e| SolanaClientError::from(e).into())
+    }
+
+    async fn send_internal(mut self) -> Result<Signature, ClientError> {
+        if let Some(ComputeUnitLimit::Simulated { margin_percent }) = self.compute_unit_limit {
+            // If the simulation fails, sending does too, reporting the error.
+            let simulation = self.simulate_internal().await?;
+            if let (None, Some(units)) = (&simulation.err, simulation.units_consumed) {
+                let units = units.saturating_mul(100 + margin_percent as u64) / 100;
+                self.compute_unit_limit = Some(ComputeUnitLimit::Units(
+                    units.min(MAX_COMPUTE_UNIT_LIMIT as u64) as u32,
+                ));
+            }
+        }
+
+        let connection = self.connection();
+        let mut tx = self.signed_transaction(&connection).await?;
+        // A bank processes the transaction before returning, so there's
+        // nothing to confirm or retry.
+        #[cfg(feature = "program-test")]
+        if let Connection::Banks(banks_client) = &connection {
+            let signature = tx.signatures[0];
+            banks_client.clone().process_transaction(tx).await?;
+            return Ok(signature);
+        }
+        let rpc_client = connection.rpc("Sending transactions")?;
+        let mut skip_preflight = self.send_policy.skip_preflight;
+        let mut retries = 0;
+        loop {
+            let config = RpcSendTransactionConfig {
+                skip_preflight,
+                preflight_commitment: Some(self.options.commitment),
+                ..RpcSendTransactionConfig::default()
+            };
+            let signature = rpc_client.send_transaction_with_config(&tx, config).await?;
+            tokio::time::sleep(self.send_policy.poll_interval).await;
+
+            // Checked before the status, since a transaction can't be
+            // confirmed once its blockhash has expired.
+            let expired = !rpc_client
+                .is_blockhash_valid(tx.message.recent_blockhash(), CommitmentConfig::processed())
+                .await?;
+            if let Some(result) = rpc_client
+                .get_signature_status_with_commitment(&signature, self.options)
+                .await?
+            {
+                return result
+                    .map(|()| signature)
+                    .map_err(|e| SolanaClientError::from(e).into());
+            }
+            if retries == self.send_policy.max_retries
+                || (expired && !self.send_policy.refresh_blockhash)
+            {
+                return Err(ClientError::TransactionUnconfirmed(signature));
+            }
+            retries += 1;
+
+            // Resending an unexpired transaction may fail preflight, if it was
+            // processed in the meantime.
+            skip_preflight = true;
+            if expired {
+                tx = self.signed_transaction(&connection).await?;
+                skip_preflight = self.send_policy.skip_preflight;
+            }
+        }
+    }
+
+    async fn simulate_internal(&self) -> Result<Simulation, ClientError> {
+        let connection = self.connection();
+        let tx = self.signed_transaction(&connection).await?;
+        connection.simulate_transaction(self.program_id, &tx).await
+    }
+
+    fn connection(&self) -> Connection {
+        self.backend.connect(&self.cluster, self.options)
+    }
+}
 
-        rpc_client
-            .send_and_confirm_transaction(&tx)
-            .map_err(Into::into)
+// The length of a lookup table account's metadata, preceding its addresses.
+const LOOKUP_TABLE_META_SIZE: usize = 56;
+
+// Returns the addresses stored in the given lookup table account data.
+fn lookup_table_addresses(data: &[u8]) -> Result<Vec<Pubkey>, ClientError> {
+    // The first 4 bytes are the account type, 1 for initialized tables.
+    if data.len() < LOOKUP_TABLE_META_SIZE || data[..4] != 1u32.to_le_bytes() {
+        return Err(ProgramError::InvalidAccountData.into());
+    }
+    let addresses = data[LOOKUP_TABLE_META_SIZE..].chunks_exact(32);
+    if !addresses.remainder().is_empty() {
+        return Err(ProgramError::InvalidAccountData.into());
+    }
+    Ok(addresses
+        .map(|address| Pubkey::try_from(address).unwrap())
+        .collect())
+}
+
+/// The outcome of simulating a request's transaction, without sending it.
+#[derive(Debug)]
+pub struct Simulation {
+    program_id: Pubkey,
+    /// The error the transaction failed with, if any.
+    pub err: Option<TransactionError>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+    /// The program that set the return data, along with the data.
+    pub return_data: Option<(Pubkey, Vec<u8>)>,
+}
+
+impl Simulation {
+    /// Returns the events of type `T` emitted by the program.
+    pub fn events<T: anchor_lang::Event + anchor_lang::AnchorDeserialize>(
+        &self,
+    ) -> Result<Vec<T>, ClientError> {
+        parse_logs(&self.program_id, &self.logs)
+    }
+
+    /// Returns the custom error code the transaction failed with, if any.
+    pub fn error_code(&self) -> Option<u32> {
+        match &self.err {
+            Some(TransactionError::InstructionError(_, InstructionError::Custom(code))) => {
+                Some(*code)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the error the transaction failed with, if it's one of `E`,
+    /// e.g., a program's `#[error]` enum or `anchor_lang::error::ErrorCode`.
+    pub fn error<E: TryFrom<u32>>(&self) -> Option<E> {
+        self.error_code().and_then(|code| E::try_from(code).ok())
+    }
+
+    /// Returns the value returned by the program, if it set any.
+    pub fn return_value<T: anchor_lang::AnchorDeserialize>(
+        &self,
+    ) -> Result<Option<T>, ClientError> {
+        match &self.return_data {
+            Some((program_id, data)) if program_id == &self.program_id => {
+                T::try_from_slice(data).map(Some).map_err(|_| {
+                    ClientError::ProgramError(
+                        anchor_lang::error::ErrorCode::ReturnDataDidNotDeserialize.into(),
+                    )
+                })
+            }
+            _ => Ok(None),
+        }
     }
 }
 
@@ -483,7 +1077,7 @@ mod tests {
     #[test]
     fn handle_system_log_pop() {
         let log = "Program 7Y8VDzehoewALqJfyxZYMgYCnMTCDhWuGfJKUvjYWATw success";
-        let (program, did_pop) = handle_system_log("asdf", log);
+        let (program, did_pop) = handle_system_log(log);
         assert_eq!(program, None);
         assert!(did_pop);
     }
@@ -491,8 +1085,241 @@ mod tests {
     #[test]
     fn handle_system_log_no_pop() {
         let log = "Program 7swsTUiQ6KUK4uFYquQKg4epFRsBnvbrTf2fZQCa2sTJ qwer";
-        let (program, did_pop) = handle_system_log("asdf", log);
+        let (program, did_pop) = handle_system_log(log);
         assert_eq!(program, None);
         assert!(!did_pop);
     }
+
+    #[test]
+    fn handle_system_log_invoke() {
+        let log = "Program 7swsTUiQ6KUK4uFYquQKg4epFRsBnvbrTf2fZQCa2sTJ invoke [2]";
+        let (program, did_pop) = handle_system_log(log);
+        assert_eq!(
+            program,
+            Some("7swsTUiQ6KUK4uFYquQKg4epFRsBnvbrTf2fZQCa2sTJ".to_string())
+        );
+        assert!(!did_pop);
+    }
+
+    #[test]
+    fn handle_system_log_failed_pop() {
+        let log = "Program 7swsTUiQ6KUK4uFYquQKg4epFRsBnvbrTf2fZQCa2sTJ failed: custom program error: 0x1";
+        let (program, did_pop) = handle_system_log(log);
+        assert_eq!(program, None);
+        assert!(did_pop);
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct TestEvent {
+        data: u64,
+    }
+
+    impl anchor_lang::AnchorSerialize for TestEvent {
+        fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+            anchor_lang::AnchorSerialize::serialize(&self.data, writer)
+        }
+    }
+
+    impl anchor_lang::AnchorDeserialize for TestEvent {
+        fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+            Ok(Self {
+                data: anchor_lang::AnchorDeserialize::deserialize(buf)?,
+            })
+        }
+    }
+
+    impl anchor_lang::Discriminator for TestEvent {
+        const DISCRIMINATOR: &'static [u8] = &[1, 2, 3, 4, 5, 6, 7, 8];
+    }
+
+    impl anchor_lang::Event for TestEvent {
+        fn data(&self) -> Vec<u8> {
+            let mut d = <Self as anchor_lang::Discriminator>::DISCRIMINATOR.to_vec();
+            d.append(&mut anchor_lang::AnchorSerialize::try_to_vec(self).unwrap());
+            d
+        }
+    }
+
+    fn event_log(data: u64) -> String {
+        format!(
+            "Program log: {}",
+            anchor_lang::__private::base64::encode(anchor_lang::Event::data(&TestEvent { data }))
+        )
+    }
+
+    #[test]
+    fn parse_logs_events() {
+        let program = "7Y8VDzehoewALqJfyxZYMgYCnMTCDhWuGfJKUvjYWATw";
+        let other = "7swsTUiQ6KUK4uFYquQKg4epFRsBnvbrTf2fZQCa2sTJ";
+        let logs = vec![
+            format!("Program {} invoke [1]", program),
+            "Program log: Instruction: Initialize".to_string(),
+            event_log(1),
+            format!("Program {} invoke [2]", other),
+            // Not emitted by the program.
+            event_log(2),
+            format!("Program {} success", other),
+            event_log(3),
+            format!("Program {} success", program),
+            format!("Program {} invoke [1]", program),
+            event_log(4),
+            format!("Program {} success", program),
+        ];
+        let events: Vec<TestEvent> = parse_logs(&program.parse().unwrap(), &logs).unwrap();
+        assert_eq!(
+            events,
+            vec![
+                TestEvent { data: 1 },
+                TestEvent { data: 3 },
+                TestEvent { data: 4 }
+            ]
+        );
+    }
+
+    #[test]
+    fn simulation_error_and_return_value() {
+        let program_id = Pubkey::new_unique();
+        let simulation = Simulation {
+            program_id,
+            err: Some(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(anchor_lang::error::ErrorCode::ConstraintMut as u32),
+            )),
+            logs: Vec::new(),
+            units_consumed: Some(1_000),
+            return_data: Some((program_id, vec![7, 0, 0, 0, 0, 0, 0, 0])),
+        };
+        assert!(matches!(
+            simulation.error::<anchor_lang::error::ErrorCode>(),
+            Some(anchor_lang::error::ErrorCode::ConstraintMut)
+        ));
+        assert_eq!(simulation.return_value::<u64>().unwrap(), Some(7));
+
+        let simulation = Simulation {
+            return_data: Some((Pubkey::new_unique(), vec![7, 0, 0, 0, 0, 0, 0, 0])),
+            err: None,
+            ..simulation
+        };
+        assert_eq!(simulation.error_code(), None);
+        assert_eq!(simulation.return_value::<u64>().unwrap(), None);
+    }
+
+    #[test]
+    fn compute_budget_instructions() {
+        let ix = Instruction {
+            program_id: Pubkey::new_unique(),
+            accounts: Vec::new(),
+            data: Vec::new(),
+        };
+        let request = RequestBuilder::from(
+            Pubkey::new_unique(),
+            "http://localhost:8899",
+            Keypair::new(),
+            None,
+            RequestNamespace::Global,
+        )
+        .instruction(ix.clone())
+        .compute_unit_price(1_000)
+        .compute_unit_limit(50_000);
+        assert_eq!(
+            request.instructions(),
+            vec![
+                ComputeBudgetInstruction::set_compute_unit_limit(50_000),
+                ComputeBudgetInstruction::set_compute_unit_price(1_000),
+                ix.clone(),
+            ]
+        );
+
+        let request = request.compute_unit_limit_from_simulation(10);
+        assert_eq!(
+            request.instructions()[0],
+            ComputeBudgetInstruction::set_compute_unit_limit(MAX_COMPUTE_UNIT_LIMIT)
+        );
+    }
+
+    #[test]
+    fn parse_lookup_table_addresses() {
+        let addresses = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut data = vec![0; LOOKUP_TABLE_META_SIZE];
+        data[0] = 1;
+        for address in &addresses {
+            data.extend_from_slice(address.as_ref());
+        }
+        assert_eq!(lookup_table_addresses(&data).unwrap(), addresses);
+
+        // Uninitialized.
+        data[0] = 0;
+        assert!(lookup_table_addresses(&data).is_err());
+    }
+
+    #[test]
+    fn transaction_events_from_logs_and_cpis() {
+        use solana_sdk::message::Message;
+        use solana_transaction_status::{
+            Encodable, EncodedTransactionWithStatusMeta, UiTransactionStatusMeta,
+        };
+
+        let program_id = Pubkey::new_unique();
+        let event_authority = Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &program_id).0;
+        let payer = Pubkey::new_unique();
+        let message = Message::new(
+            &[Instruction {
+                program_id,
+                accounts: vec![AccountMeta::new_readonly(event_authority, false)],
+                data: Vec::new(),
+            }],
+            Some(&payer),
+        );
+        let index = |key: &Pubkey| message.account_keys.iter().position(|k| k == key).unwrap();
+        let cpi_data = |data: u64| {
+            let mut ix_data = EVENT_IX_TAG_LE.to_vec();
+            ix_data.extend_from_slice(&anchor_lang::Event::data(&TestEvent { data }));
+            bs58::encode(ix_data).into_string()
+        };
+
+        let meta: UiTransactionStatusMeta = serde_json::from_value(serde_json::json!({
+            "err": null,
+            "status": { "Ok": null },
+            "fee": 5000,
+            "preBalances": [],
+            "postBalances": [],
+            "logMessages": [
+                format!("Program {} invoke [1]", program_id),
+                event_log(1),
+                format!("Program {} invoke [2]", program_id),
+                format!("Program {} success", program_id),
+                format!("Program {} success", program_id),
+            ],
+            "innerInstructions": [{
+                "index": 0,
+                "instructions": [
+                    {
+                        "programIdIndex": index(&program_id),
+                        "accounts": [index(&event_authority)],
+                        "data": cpi_data(2),
+                    },
+                    // Not signed by the event authority.
+                    {
+                        "programIdIndex": index(&program_id),
+                        "accounts": [index(&payer)],
+                        "data": cpi_data(3),
+                    },
+                ],
+            }],
+        }))
+        .unwrap();
+        let tx = EncodedConfirmedTransactionWithStatusMeta {
+            slot: 1,
+            transaction: EncodedTransactionWithStatusMeta {
+                transaction: Transaction::new_unsigned(message)
+                    .encode(UiTransactionEncoding::Base64),
+                meta: Some(meta),
+                version: None,
+            },
+            block_time: None,
+        };
+        let events: Vec<TestEvent> =
+            transaction_events_with(&program_id, tx, decode_event::<TestEvent>).unwrap();
+        assert_eq!(events, vec![TestEvent { data: 1 }, TestEvent { data: 2 }]);
+    }
 }
@@ -0,0 +1,98 @@
+//! The async API.
+
+use crate::{
+    ClientError, DynamicProgram, EventContext, Filter, Program, RequestBuilder, Simulation,
+};
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::{AccountDeserialize, Discriminator};
+use serde_json::Value;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_filter::RpcFilterType;
+use solana_sdk::signature::Signature;
+use std::ops::RangeInclusive;
+
+impl Program {
+    /// Returns the account at the given address.
+    pub async fn account<T: AccountDeserialize>(&self, address: Pubkey) -> Result<T, ClientError> {
+        self.account_internal(address).await
+    }
+
+    /// Returns all program accounts of type `T` matching the given filters,
+    /// in addition to the account discriminator. Offsets of memcmp filters
+    /// include the discriminator. `DynamicProgram::filter` resolves a filter
+    /// on a field by name.
+    pub async fn accounts<T: AccountDeserialize + Discriminator>(
+        &self,
+        filters: Vec<RpcFilterType>,
+    ) -> Result<Vec<(Pubkey, T)>, ClientError> {
+        self.accounts_internal(filters).await
+    }
+
+    /// Returns the accounts at the given addresses, in order, or `None` for
+    /// those that don't exist.
+    pub async fn accounts_multiple<T: AccountDeserialize>(
+        &self,
+        addresses: &[Pubkey],
+    ) -> Result<Vec<Option<T>>, ClientError> {
+        self.accounts_multiple_internal(addresses).await
+    }
+
+    /// Returns the events of type `T` emitted by the program in the given
+    /// confirmed transaction, first those logged and then those emitted via
+    /// `emit_cpi!`.
+    pub async fn events<T: anchor_lang::Event + anchor_lang::AnchorDeserialize>(
+        &self,
+        signature: &Signature,
+    ) -> Result<Vec<T>, ClientError> {
+        self.events_internal(signature).await
+    }
+
+    /// Returns the events of type `T` emitted by the program in the
+    /// successful transactions of the given slots, oldest first.
+    pub async fn events_in_slots<T: anchor_lang::Event + anchor_lang::AnchorDeserialize>(
+        &self,
+        slots: RangeInclusive<u64>,
+    ) -> Result<Vec<(EventContext, T)>, ClientError> {
+        self.events_in_slots_internal(slots).await
+    }
+
+    pub async fn state<T: AccountDeserialize>(&self) -> Result<T, ClientError> {
+        self.state_internal().await
+    }
+
+    pub fn rpc(&self) -> RpcClient {
+        RpcClient::new_with_commitment(
+            self.cfg.cluster.url().to_string(),
+            self.cfg.options.unwrap_or_default(),
+        )
+    }
+}
+
+impl<'a> RequestBuilder<'a> {
+    pub async fn send(self) -> Result<Signature, ClientError> {
+        self.send_internal().await
+    }
+
+    /// Simulates the request's transaction, without sending it.
+    pub async fn simulate(&self) -> Result<Simulation, ClientError> {
+        self.simulate_internal().await
+    }
+}
+
+impl DynamicProgram {
+    /// Returns the account of the type with the given name at the given
+    /// address.
+    pub async fn account(&self, name: &str, address: Pubkey) -> Result<Value, ClientError> {
+        self.account_internal(name, address).await
+    }
+
+    /// Returns all program accounts of the type with the given name matching
+    /// the given filters, in addition to the account discriminator.
+    pub async fn accounts(
+        &self,
+        name: &str,
+        filters: Vec<Filter>,
+    ) -> Result<Vec<(Pubkey, Value)>, ClientError> {
+        self.accounts_internal(name, filters).await
+    }
+}
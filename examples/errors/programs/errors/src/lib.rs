@@ -19,6 +19,14 @@ mod errors {
         Err(MyError::HelloNext.into())
     }
 
+    pub fn hello_with_location(_ctx: Context<Hello>) -> Result<()> {
+        err!(MyError::Hello)
+    }
+
+    pub fn hello_with_context(_ctx: Context<Hello>) -> anchor_lang::error::Result<()> {
+        Err(anchor_lang::error::Error::from(MyError::Hello).with_message("Hello with context"))
+    }
+
     pub fn mut_error(_ctx: Context<MutError>) -> Result<()> {
         Ok(())
     }
@@ -30,6 +38,28 @@ mod errors {
     pub fn signer_error(_ctx: Context<SignerError>) -> Result<()> {
         Ok(())
     }
+
+    pub fn account_not_signer_error(_ctx: Context<AccountNotSignerError>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn mut_custom_error(_ctx: Context<MutCustomError>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn raw_custom_error(_ctx: Context<RawCustomError>, _amount: u64) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn require_eq_error(_ctx: Context<Hello>, amount: u64) -> Result<()> {
+        require_eq!(amount, 1);
+        Ok(())
+    }
+
+    pub fn require_gt_error(_ctx: Context<Hello>, amount: u64) -> Result<()> {
+        require_gt!(amount, 0);
+        Ok(())
+    }
 }
 
 #[derive(Accounts)]
@@ -55,6 +85,24 @@ pub struct SignerError<'info> {
     my_account: AccountInfo<'info>,
 }
 
+#[derive(Accounts)]
+pub struct AccountNotSignerError<'info> {
+    my_account: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MutCustomError<'info> {
+    #[account(mut @ MyError::AccountNotMutable)]
+    my_account: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct RawCustomError<'info> {
+    #[account(constraint = amount > 0 @ MyError::InvalidAmount)]
+    my_account: AccountInfo<'info>,
+}
+
 #[account]
 pub struct HasOneAccount {
     owner: Pubkey,
@@ -66,4 +114,8 @@ pub enum MyError {
     Hello,
     HelloNoMsg = 123,
     HelloNext,
+    #[msg("The account must be mutable")]
+    AccountNotMutable,
+    #[msg("The amount must be greater than zero")]
+    InvalidAmount,
 }
@@ -45,6 +45,32 @@ describe("errors", () => {
     }
   });
 
+  it("Emits a Hello error via err!", async () => {
+    try {
+      const tx = await program.rpc.helloWithLocation();
+      assert.ok(false);
+    } catch (err) {
+      const errMsg =
+        "This is an error message clients will automatically display";
+      assert.equal(err.toString(), errMsg);
+      assert.equal(err.msg, errMsg);
+      assert.equal(err.code, 300);
+    }
+  });
+
+  it("Emits a Hello error with context", async () => {
+    try {
+      const tx = await program.rpc.helloWithContext();
+      assert.ok(false);
+    } catch (err) {
+      const errMsg =
+        "This is an error message clients will automatically display";
+      assert.equal(err.toString(), errMsg);
+      assert.equal(err.msg, errMsg);
+      assert.equal(err.code, 300);
+    }
+  });
+
   it("Emits a mut error", async () => {
     try {
       const tx = await program.rpc.mutError({
@@ -112,4 +138,87 @@ describe("errors", () => {
       assert.equal(err.toString(), errMsg);
     }
   });
+
+  // As above, a raw transaction is used since the client won't allow one to
+  // send a transaction with an invalid signer account.
+  it("Emits an account not signer error", async () => {
+    try {
+      const tx = new Transaction();
+      tx.add(
+        new TransactionInstruction({
+          keys: [
+            {
+              pubkey: anchor.web3.SYSVAR_RENT_PUBKEY,
+              isWritable: false,
+              isSigner: false,
+            },
+          ],
+          programId: program.programId,
+          data: program.coder.instruction.encode("account_not_signer_error", {}),
+        })
+      );
+      await program.provider.send(tx);
+      assert.ok(false);
+    } catch (err) {
+      const errMsg =
+        "Error: failed to send transaction: Transaction simulation failed: Error processing Instruction 0: custom program error: 0xa8";
+      assert.equal(err.toString(), errMsg);
+    }
+  });
+
+  it("Emits a custom error for a mut constraint", async () => {
+    try {
+      const tx = await program.rpc.mutCustomError({
+        accounts: {
+          myAccount: anchor.web3.SYSVAR_RENT_PUBKEY,
+        },
+      });
+      assert.ok(false);
+    } catch (err) {
+      const errMsg = "The account must be mutable";
+      assert.equal(err.toString(), errMsg);
+      assert.equal(err.msg, errMsg);
+      assert.equal(err.code, 300 + 125);
+    }
+  });
+
+  it("Emits a custom error for a raw constraint", async () => {
+    try {
+      const tx = await program.rpc.rawCustomError(new anchor.BN(0), {
+        accounts: {
+          myAccount: anchor.web3.SYSVAR_RENT_PUBKEY,
+        },
+      });
+      assert.ok(false);
+    } catch (err) {
+      const errMsg = "The amount must be greater than zero";
+      assert.equal(err.toString(), errMsg);
+      assert.equal(err.msg, errMsg);
+      assert.equal(err.code, 300 + 126);
+    }
+  });
+
+  it("Emits a require_eq error", async () => {
+    try {
+      const tx = await program.rpc.requireEqError(new anchor.BN(2));
+      assert.ok(false);
+    } catch (err) {
+      const errMsg = "A require_eq expression was violated";
+      assert.equal(err.toString(), errMsg);
+      assert.equal(err.msg, errMsg);
+      assert.equal(err.code, 190);
+    }
+  });
+
+  it("Emits a require_gt error", async () => {
+    try {
+      const tx = await program.rpc.requireGtError(new anchor.BN(0));
+      assert.ok(false);
+    } catch (err) {
+      const errMsg = "A require_gt expression was violated";
+      assert.equal(err.toString(), errMsg);
+      assert.equal(err.msg, errMsg);
+      assert.equal(err.code, 194);
+    }
+  });
 });
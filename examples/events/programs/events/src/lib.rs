@@ -13,14 +13,43 @@ pub mod events {
         });
         Ok(())
     }
+
+    pub fn initialize_cpi(ctx: Context<InitializeCpi>) -> ProgramResult {
+        emit_cpi!(MyEvent {
+            data: 6,
+            label: "cpi".to_string(),
+        });
+        Ok(())
+    }
+
+    pub fn initialize_versioned(_ctx: Context<Initialize>) -> ProgramResult {
+        emit!(MyVersionedEvent {
+            data: 7,
+            label: "versioned".to_string(),
+            extra: 8,
+        });
+        Ok(())
+    }
 }
 
 #[derive(Accounts)]
 pub struct Initialize {}
 
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeCpi<'info> {}
+
 #[event]
 pub struct MyEvent {
     pub data: u64,
     #[index]
     pub label: String,
 }
+
+#[event(namespace = "events", version = 2)]
+pub struct MyVersionedEvent {
+    pub data: u64,
+    #[index]
+    pub label: String,
+    pub extra: u64,
+}
@@ -22,4 +22,54 @@ describe("events", () => {
     assert.ok(event.data.toNumber() === 5);
     assert.ok(event.label === "hello");
   });
+
+  it("Emits events via self-CPI", async () => {
+    const program = anchor.workspace.Events;
+
+    const [eventAuthority] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("__event_authority")],
+      program.programId
+    );
+    const tx = await program.rpc.initializeCpi({
+      accounts: {
+        eventAuthority,
+        program: program.programId,
+      },
+    });
+    const txResult = await program.provider.connection.getConfirmedTransaction(
+      tx,
+      "confirmed"
+    );
+
+    const ixData = anchor.utils.bytes.bs58.decode(
+      txResult.meta.innerInstructions[0].instructions[0].data
+    );
+    const event = program.coder.events.decodeCpi(ixData);
+    assert.ok(event.name === "MyEvent");
+    assert.ok(event.data.data.toNumber() === 6);
+    assert.ok(event.data.label === "cpi");
+  });
+
+  it("Emits versioned events", async () => {
+    const program = anchor.workspace.Events;
+
+    const tx = await program.rpc.initializeVersioned();
+    const txResult = await program.provider.connection.getConfirmedTransaction(
+      tx,
+      "confirmed"
+    );
+
+    const events = txResult.meta.logMessages
+      .filter((log) => log.startsWith("Program log: "))
+      .map((log) =>
+        program.coder.events.decode(log.slice("Program log: ".length))
+      )
+      .filter((event) => event !== null);
+    assert.ok(events.length === 1);
+    assert.ok(events[0].name === "MyVersionedEvent");
+    assert.ok(events[0].version === 2);
+    assert.ok(events[0].data.data.toNumber() === 7);
+    assert.ok(events[0].data.label === "versioned");
+    assert.ok(events[0].data.extra.toNumber() === 8);
+  });
 });
@@ -5,6 +5,7 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::program_option::COption;
 use anchor_spl::token::{self, Mint, TokenAccount, Transfer};
 use lockup::{CreateVesting, RealizeLock, Realizor, Vesting};
+use std::collections::BTreeMap;
 use std::convert::Into;
 
 #[program]
@@ -503,6 +504,7 @@ mod registry {
             ctx.accounts.lockup_program.key,
             &mut remaining_accounts,
             &[],
+            &mut BTreeMap::new(),
         )?;
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
         lockup::cpi::create_vesting(
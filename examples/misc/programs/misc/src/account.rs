@@ -34,3 +34,20 @@ pub struct DataZeroCopy {
     pub data: u16,
     pub bump: u8,
 }
+
+#[account]
+#[derive(Default)]
+pub struct DataVec {
+    pub data: Vec<u8>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct DataWithMaxLen {
+    pub authority: Pubkey,
+    #[max_len(32)]
+    pub name: String,
+    #[max_len(4, 8)]
+    pub tags: Vec<String>,
+    pub count: Option<u64>,
+}
@@ -1,7 +1,9 @@
 use crate::account::*;
 use crate::misc::MyState;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, TokenAccount};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface;
 use misc2::misc2::MyState as Misc2State;
 
 #[derive(Accounts)]
@@ -23,6 +25,97 @@ pub struct TestTokenSeedsInit<'info> {
     pub token_program: AccountInfo<'info>,
 }
 
+#[derive(Accounts)]
+pub struct TestTokenAccountInit<'info> {
+    #[account(init, token::mint = mint, token::authority = authority)]
+    pub token: CpiAccount<'info, TokenAccount>,
+    pub mint: CpiAccount<'info, Mint>,
+    pub authority: AccountInfo<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    pub token_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TestTokenAccountConstraint<'info> {
+    #[account(token::mint = mint, token::authority = authority)]
+    pub token: CpiAccount<'info, TokenAccount>,
+    pub mint: CpiAccount<'info, Mint>,
+    pub authority: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TestTokenAccountHardening<'info> {
+    #[account(token::delegate = none, token::close_authority = none)]
+    pub token: CpiAccount<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct TestMintInit<'info> {
+    #[account(
+        init,
+        mint::decimals = 6,
+        mint::authority = authority,
+        mint::freeze_authority = authority,
+    )]
+    pub mint: CpiAccount<'info, Mint>,
+    pub authority: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TestMintSeedsInit<'info> {
+    #[account(
+        init,
+        seeds = [b"my-mint-seed".as_ref()],
+        bump,
+        payer = payer,
+        mint::decimals = 6,
+        mint::authority = payer,
+    )]
+    pub my_pda: CpiAccount<'info, Mint>,
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct TestMintConstraint<'info> {
+    #[account(
+        mint::decimals = 6,
+        mint::authority = authority,
+        mint::freeze_authority = authority,
+        mint::token_program = token_program,
+    )]
+    pub mint: CpiAccount<'info, Mint>,
+    pub authority: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TestAssociatedTokenInit<'info> {
+    #[account(
+        init,
+        associated_token::mint = mint,
+        associated_token::authority = payer,
+        payer = payer,
+    )]
+    pub token: CpiAccount<'info, TokenAccount>,
+    pub mint: CpiAccount<'info, Mint>,
+    pub payer: Signer<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct TestAssociatedTokenConstraint<'info> {
+    #[account(associated_token::mint = mint, associated_token::authority = authority)]
+    pub token: CpiAccount<'info, TokenAccount>,
+    pub mint: CpiAccount<'info, Mint>,
+    pub authority: AccountInfo<'info>,
+}
+
 #[derive(Accounts)]
 #[instruction(nonce: u8)]
 pub struct TestInstructionConstraint<'info> {
@@ -31,6 +124,48 @@ pub struct TestInstructionConstraint<'info> {
     pub my_account: AccountInfo<'info>,
 }
 
+#[derive(Accounts)]
+pub struct TestSigner<'info> {
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TestSystemAccount<'info> {
+    pub wallet: SystemAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TestInterfaceAccount<'info> {
+    pub token: InterfaceAccount<'info, token_interface::TokenAccount>,
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    pub token_program: Interface<'info, token_interface::TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct TestUncheckedAccount<'info> {
+    /// CHECK: Any account is accepted, since it's never read or written.
+    pub unchecked: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TestFindBump<'info> {
+    #[account(seeds = [b"my-seed", my_account.key.as_ref()], bump)]
+    pub my_pda: AccountInfo<'info>,
+    pub my_account: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(nonce: u8)]
+pub struct TestSeedsProgram<'info> {
+    #[account(
+        seeds = [b"my-seed", my_account.key.as_ref(), &[nonce]],
+        seeds::program = misc2_program.key,
+    )]
+    pub my_pda: AccountInfo<'info>,
+    pub my_account: AccountInfo<'info>,
+    pub misc2_program: AccountInfo<'info>,
+}
+
 #[derive(Accounts)]
 #[instruction(domain: String, seed: Vec<u8>, bump: u8)]
 pub struct TestPdaInit<'info> {
@@ -46,6 +181,70 @@ pub struct TestPdaInit<'info> {
     pub system_program: AccountInfo<'info>,
 }
 
+#[derive(Accounts)]
+#[instruction(bump: u8)]
+pub struct TestInitIfNeeded<'info> {
+    #[account(
+        init_if_needed,
+        seeds = [b"my-init-if-needed-seed".as_ref()],
+        bump = bump,
+        payer = my_payer,
+    )]
+    pub my_pda: ProgramAccount<'info, DataU16>,
+    pub my_payer: AccountInfo<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(bump: u8)]
+pub struct TestDataVecInit<'info> {
+    #[account(
+        init,
+        seeds = [b"my-realloc-seed".as_ref()],
+        bump = bump,
+        payer = payer,
+    )]
+    pub data: ProgramAccount<'info, DataVec>,
+    #[account(mut, signer)]
+    pub payer: AccountInfo<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(bump: u8)]
+pub struct TestInitSpace<'info> {
+    #[account(
+        init,
+        seeds = [b"my-init-space-seed".as_ref()],
+        bump = bump,
+        payer = payer,
+        space = auto,
+    )]
+    pub data: ProgramAccount<'info, DataWithMaxLen>,
+    #[account(mut, signer)]
+    pub payer: AccountInfo<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(len: u16)]
+pub struct TestRealloc<'info> {
+    #[account(
+        mut,
+        realloc = 8 + 4 + len as usize,
+        realloc::payer = payer,
+        realloc::zero = false,
+    )]
+    pub data: ProgramAccount<'info, DataVec>,
+    #[account(mut, signer)]
+    pub payer: AccountInfo<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: AccountInfo<'info>,
+}
+
 #[derive(Accounts)]
 #[instruction(bump: u8)]
 pub struct TestPdaInitZeroCopy<'info> {
@@ -76,6 +275,20 @@ pub struct Initialize<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
+#[derive(Accounts)]
+pub struct TestBoxed<'info> {
+    #[account(init)]
+    pub first: Box<ProgramAccount<'info, Data>>,
+    #[account(init)]
+    pub second: Box<ProgramAccount<'info, Data>>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct TestLazyAccount<'info> {
+    pub data: LazyAccount<'info, Data>,
+}
+
 #[derive(Accounts)]
 pub struct TestOwner<'info> {
     #[account(owner = misc)]
@@ -83,6 +296,12 @@ pub struct TestOwner<'info> {
     pub misc: AccountInfo<'info>,
 }
 
+#[derive(Accounts)]
+pub struct TestOwnerProgram<'info> {
+    #[account(owner = anchor_spl::token::ID)]
+    pub token: CpiAccount<'info, TokenAccount>,
+}
+
 #[derive(Accounts)]
 pub struct TestExecutable<'info> {
     #[account(executable)]
@@ -99,6 +318,14 @@ pub struct TestStateCpi<'info> {
     pub misc2_program: AccountInfo<'info>,
 }
 
+#[derive(Accounts)]
+pub struct TestReturnDataCpi<'info> {
+    #[account(signer)]
+    pub authority: AccountInfo<'info>,
+    #[account(executable)]
+    pub misc2_program: AccountInfo<'info>,
+}
+
 #[derive(Accounts)]
 pub struct TestClose<'info> {
     #[account(mut, close = sol_dest)]
@@ -144,6 +371,31 @@ pub struct TestU16<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
+#[derive(Accounts)]
+pub struct TestRentSyscall<'info> {
+    #[account(init)]
+    pub my_account: ProgramAccount<'info, DataU16>,
+}
+
+#[derive(Accounts)]
+pub struct TestSyscallSysvars<'info> {
+    #[account(mut)]
+    pub my_account: ProgramAccount<'info, DataU16>,
+    pub clock: Clock,
+    pub rent: Rent,
+}
+
+#[derive(Accounts)]
+pub struct TestOptional<'info> {
+    #[account(mut)]
+    pub my_account: Option<ProgramAccount<'info, DataU16>>,
+    #[account(signer)]
+    pub authority: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TestTypedRemainingAccounts {}
+
 #[derive(Accounts)]
 pub struct TestI16<'info> {
     #[account(init)]
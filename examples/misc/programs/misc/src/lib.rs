@@ -1,6 +1,7 @@
 //! Misc example is a catchall program for testing unrelated features.
 //! It's not too instructive/coherent by itself, so please see other examples.
 
+use account::*;
 use anchor_lang::prelude::*;
 use context::*;
 use event::*;
@@ -10,7 +11,9 @@ mod account;
 mod context;
 mod event;
 
-#[program]
+solana_program::declare_id!("FtMNMKp9DZHKWUyVAsj3Q5QV8ow4P3fUPP7ZrWEQJzKr");
+
+#[program(custom_entry = custom_entry)]
 pub mod misc {
     use super::*;
 
@@ -40,10 +43,31 @@ pub mod misc {
         Ok(())
     }
 
+    pub fn test_boxed(ctx: Context<TestBoxed>, udata: u128) -> ProgramResult {
+        ctx.accounts.first.udata = udata;
+        ctx.accounts.second.udata = udata + 1;
+        Ok(())
+    }
+
+    pub fn test_lazy_account(ctx: Context<TestLazyAccount>, idata: i128) -> ProgramResult {
+        if ctx.accounts.data.load_idata()? != idata {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let data = ctx.accounts.data.load()?;
+        if data.udata != ctx.accounts.data.load_udata()? {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+
     pub fn test_owner(_ctx: Context<TestOwner>) -> ProgramResult {
         Ok(())
     }
 
+    pub fn test_owner_program(_ctx: Context<TestOwnerProgram>) -> ProgramResult {
+        Ok(())
+    }
+
     pub fn test_executable(_ctx: Context<TestExecutable>) -> ProgramResult {
         Ok(())
     }
@@ -57,6 +81,17 @@ pub mod misc {
         misc2::cpi::state::set_data(ctx, data)
     }
 
+    pub fn test_return_data_cpi(ctx: Context<TestReturnDataCpi>, value: u64) -> ProgramResult {
+        let cpi_program = ctx.accounts.misc2_program.clone();
+        let cpi_accounts = Auth {
+            authority: ctx.accounts.authority.clone(),
+        };
+        let ctx = CpiContext::new(cpi_program, cpi_accounts);
+        let doubled = misc2::cpi::double(ctx, value)?.get();
+        require_eq!(doubled, value * 2);
+        Ok(())
+    }
+
     pub fn test_init_associated_account(
         ctx: Context<TestInitAssociatedAccount>,
         data: u64,
@@ -78,6 +113,24 @@ pub mod misc {
         Ok(())
     }
 
+    pub fn test_rent_syscall(ctx: Context<TestRentSyscall>, data: u16) -> ProgramResult {
+        ctx.accounts.my_account.data = data;
+        Ok(())
+    }
+
+    pub fn test_syscall_sysvars(ctx: Context<TestSyscallSysvars>) -> ProgramResult {
+        let info = ctx.accounts.my_account.to_account_info();
+        if !ctx
+            .accounts
+            .rent
+            .is_exempt(info.lamports(), info.data_len())
+        {
+            return Err(ProgramError::AccountNotRentExempt);
+        }
+        ctx.accounts.my_account.data = (ctx.accounts.clock.unix_timestamp > 0) as u16;
+        Ok(())
+    }
+
     pub fn test_simulate(_ctx: Context<TestSimulate>, data: u32) -> ProgramResult {
         emit!(E1 { data });
         emit!(E2 { data: 1234 });
@@ -147,6 +200,123 @@ pub mod misc {
         Ok(())
     }
 
+    pub fn test_init_if_needed(
+        ctx: Context<TestInitIfNeeded>,
+        _bump: u8,
+        data: u16,
+    ) -> ProgramResult {
+        ctx.accounts.my_pda.data = data;
+        Ok(())
+    }
+
+    pub fn test_data_vec_init(_ctx: Context<TestDataVecInit>, _bump: u8) -> ProgramResult {
+        Ok(())
+    }
+
+    pub fn test_realloc(ctx: Context<TestRealloc>, len: u16) -> ProgramResult {
+        ctx.accounts.data.data = vec![1; len as usize];
+        Ok(())
+    }
+
+    pub fn test_optional(ctx: Context<TestOptional>, data: u16) -> ProgramResult {
+        if let Some(my_account) = &mut ctx.accounts.my_account {
+            my_account.data = data;
+        }
+        Ok(())
+    }
+
+    pub fn test_typed_remaining_accounts(
+        ctx: Context<TestTypedRemainingAccounts>,
+        data: u16,
+    ) -> ProgramResult {
+        for my_account in ctx.remaining_accounts_iter::<ProgramAccount<DataU16>>() {
+            let mut my_account = my_account?;
+            my_account.data = data;
+            my_account.exit(ctx.program_id)?;
+        }
+        Ok(())
+    }
+
+    pub fn test_init_space(
+        ctx: Context<TestInitSpace>,
+        _bump: u8,
+        name: String,
+        tags: Vec<String>,
+    ) -> ProgramResult {
+        let data = &mut ctx.accounts.data;
+        data.authority = *ctx.accounts.payer.key;
+        data.name = name;
+        data.tags = tags;
+        data.count = Some(1);
+        Ok(())
+    }
+
+    pub fn test_token_account_init(_ctx: Context<TestTokenAccountInit>) -> ProgramResult {
+        Ok(())
+    }
+
+    pub fn test_token_account_constraint(
+        _ctx: Context<TestTokenAccountConstraint>,
+    ) -> ProgramResult {
+        Ok(())
+    }
+
+    pub fn test_token_account_hardening(_ctx: Context<TestTokenAccountHardening>) -> ProgramResult {
+        Ok(())
+    }
+
+    pub fn test_mint_init(_ctx: Context<TestMintInit>) -> ProgramResult {
+        Ok(())
+    }
+
+    pub fn test_mint_seeds_init(_ctx: Context<TestMintSeedsInit>) -> ProgramResult {
+        Ok(())
+    }
+
+    pub fn test_mint_constraint(_ctx: Context<TestMintConstraint>) -> ProgramResult {
+        Ok(())
+    }
+
+    pub fn test_associated_token_init(_ctx: Context<TestAssociatedTokenInit>) -> ProgramResult {
+        Ok(())
+    }
+
+    pub fn test_associated_token_constraint(
+        _ctx: Context<TestAssociatedTokenConstraint>,
+    ) -> ProgramResult {
+        Ok(())
+    }
+
+    pub fn test_seeds_program(_ctx: Context<TestSeedsProgram>, _nonce: u8) -> ProgramResult {
+        Ok(())
+    }
+
+    pub fn test_find_bump(ctx: Context<TestFindBump>, expected_bump: u8) -> ProgramResult {
+        if ctx.bumps["my_pda"] != expected_bump {
+            return Err(ProgramError::Custom(1)); // Arbitrary error.
+        }
+        Ok(())
+    }
+
+    pub fn test_signer(_ctx: Context<TestSigner>) -> ProgramResult {
+        Ok(())
+    }
+
+    pub fn test_system_account(_ctx: Context<TestSystemAccount>) -> ProgramResult {
+        Ok(())
+    }
+
+    pub fn test_interface_account(ctx: Context<TestInterfaceAccount>) -> ProgramResult {
+        if ctx.accounts.token.mint != *ctx.accounts.mint.to_account_info().key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+
+    pub fn test_unchecked_account(_ctx: Context<TestUncheckedAccount>) -> ProgramResult {
+        Ok(())
+    }
+
     pub fn default<'info>(
         _program_id: &Pubkey,
         _accounts: &[AccountInfo<'info>],
@@ -155,3 +325,19 @@ pub mod misc {
         Err(ProgramError::Custom(1234))
     }
 }
+
+// Handles instructions tagged with `CUSTOM_IX_TAG` before Anchor's dispatch,
+// which would otherwise route them to the fallback function.
+pub const CUSTOM_IX_TAG: [u8; 8] = [255; 8];
+
+fn custom_entry(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    data: &[u8],
+) -> Result<bool, ProgramError> {
+    if !data.starts_with(&CUSTOM_IX_TAG) {
+        return Ok(false);
+    }
+    msg!("Custom entry handled {} bytes", data.len());
+    Ok(true)
+}
@@ -26,6 +26,27 @@ pub mod misc2 {
             Ok(())
         }
     }
+
+    pub fn double(_ctx: Context<Auth>, value: u64) -> Result<u64, ProgramError> {
+        value.checked_mul(2).ok_or(ProgramError::InvalidArgument)
+    }
+
+    // Accepts any instruction that isn't an Anchor instruction, as long as it
+    // has some data.
+    pub fn fallback(
+        _program_id: &Pubkey,
+        _accounts: &[AccountInfo],
+        data: &[u8],
+    ) -> anchor_lang::error::Result<()> {
+        if data.is_empty() {
+            return Err(
+                anchor_lang::error::Error::from(ProgramError::InvalidInstructionData)
+                    .with_message("Fallback requires instruction data"),
+            );
+        }
+        msg!("Fallback received {} bytes", data.len());
+        Ok(())
+    }
 }
 
 #[derive(Accounts)]
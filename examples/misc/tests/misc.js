@@ -1,7 +1,11 @@
 const anchor = require("@project-serum/anchor");
 const PublicKey = anchor.web3.PublicKey;
 const assert = require("assert");
-const { TOKEN_PROGRAM_ID, Token } = require("@solana/spl-token");
+const {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  Token,
+} = require("@solana/spl-token");
 
 describe("misc", () => {
   // Configure the client to use the local cluster.
@@ -46,6 +50,35 @@ describe("misc", () => {
     assert.ok(dataAccount.idata.eq(new anchor.BN(22)));
   });
 
+  it("Can use boxed accounts", async () => {
+    const first = anchor.web3.Keypair.generate();
+    const second = anchor.web3.Keypair.generate();
+    await program.rpc.testBoxed(new anchor.BN(1), {
+      accounts: {
+        first: first.publicKey,
+        second: second.publicKey,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      },
+      signers: [first, second],
+      instructions: [
+        await program.account.data.createInstruction(first),
+        await program.account.data.createInstruction(second),
+      ],
+    });
+    const firstAccount = await program.account.data.fetch(first.publicKey);
+    const secondAccount = await program.account.data.fetch(second.publicKey);
+    assert.ok(firstAccount.udata.eq(new anchor.BN(1)));
+    assert.ok(secondAccount.udata.eq(new anchor.BN(2)));
+  });
+
+  it("Can lazily deserialize account fields", async () => {
+    await program.rpc.testLazyAccount(new anchor.BN(22), {
+      accounts: {
+        data: data.publicKey,
+      },
+    });
+  });
+
   it("Can use u16", async () => {
     const data = anchor.web3.Keypair.generate();
     const tx = await program.rpc.testU16(99, {
@@ -60,6 +93,130 @@ describe("misc", () => {
     assert.ok(dataAccount.data === 99);
   });
 
+  it("Can init an account without passing the rent sysvar", async () => {
+    const data = anchor.web3.Keypair.generate();
+    await program.rpc.testRentSyscall(99, {
+      accounts: {
+        myAccount: data.publicKey,
+      },
+      signers: [data],
+      instructions: [await program.account.dataU16.createInstruction(data)],
+    });
+    const dataAccount = await program.account.dataU16.fetch(data.publicKey);
+    assert.ok(dataAccount.data === 99);
+    // The rent sysvar isn't part of the IDL.
+    const ix = program.idl.instructions.find(
+      (ix) => ix.name === "testRentSyscall"
+    );
+    assert.equal(ix.accounts.length, 1);
+  });
+
+  it("Can load the clock and rent sysvars via syscall", async () => {
+    const data = anchor.web3.Keypair.generate();
+    await program.rpc.testRentSyscall(0, {
+      accounts: {
+        myAccount: data.publicKey,
+      },
+      signers: [data],
+      instructions: [await program.account.dataU16.createInstruction(data)],
+    });
+    await program.rpc.testSyscallSysvars({
+      accounts: {
+        myAccount: data.publicKey,
+      },
+    });
+    const dataAccount = await program.account.dataU16.fetch(data.publicKey);
+    assert.ok(dataAccount.data === 1);
+    // Neither sysvar is part of the IDL.
+    const ix = program.idl.instructions.find(
+      (ix) => ix.name === "testSyscallSysvars"
+    );
+    assert.equal(ix.accounts.length, 1);
+  });
+
+  it("Can use optional accounts", async () => {
+    const data = anchor.web3.Keypair.generate();
+    await program.rpc.testU16(99, {
+      accounts: {
+        myAccount: data.publicKey,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      },
+      signers: [data],
+      instructions: [await program.account.dataU16.createInstruction(data)],
+    });
+
+    // Account given.
+    await program.rpc.testOptional(5, {
+      accounts: {
+        myAccount: data.publicKey,
+        authority: program.provider.wallet.publicKey,
+      },
+    });
+    let dataAccount = await program.account.dataU16.fetch(data.publicKey);
+    assert.ok(dataAccount.data === 5);
+
+    // Account omitted, so the program id is passed in its place.
+    await program.rpc.testOptional(6, {
+      accounts: {
+        authority: program.provider.wallet.publicKey,
+      },
+    });
+    dataAccount = await program.account.dataU16.fetch(data.publicKey);
+    assert.ok(dataAccount.data === 5);
+  });
+
+  it("Can deserialize typed remaining accounts", async () => {
+    const accounts = [
+      anchor.web3.Keypair.generate(),
+      anchor.web3.Keypair.generate(),
+    ];
+    for (const account of accounts) {
+      await program.rpc.testU16(1, {
+        accounts: {
+          myAccount: account.publicKey,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [account],
+        instructions: [
+          await program.account.dataU16.createInstruction(account),
+        ],
+      });
+    }
+
+    await program.rpc.testTypedRemainingAccounts(7, {
+      accounts: {},
+      remainingAccounts: accounts.map((account) => {
+        return {
+          pubkey: account.publicKey,
+          isWritable: true,
+          isSigner: false,
+        };
+      }),
+    });
+    for (const account of accounts) {
+      const dataAccount = await program.account.dataU16.fetch(
+        account.publicKey
+      );
+      assert.ok(dataAccount.data === 7);
+    }
+
+    // Accounts of the wrong type are rejected.
+    await assert.rejects(
+      async () => {
+        await program.rpc.testTypedRemainingAccounts(8, {
+          accounts: {},
+          remainingAccounts: [
+            { pubkey: data.publicKey, isWritable: true, isSigner: false },
+          ],
+        });
+      },
+      (err) => {
+        assert.equal(err.code, 162);
+        return true;
+      }
+    );
+  });
+
   it("Can embed programs into genesis from the Anchor.toml", async () => {
     const pid = new anchor.web3.PublicKey(
       "FtMNMKp9DZHKWUyVAsj3Q5QV8ow4P3fUPP7ZrWEQJzKr"
@@ -393,6 +550,101 @@ describe("misc", () => {
     });
   });
 
+  it("Can use a Signer account", async () => {
+    await program.rpc.testSigner({
+      accounts: {
+        authority: program.provider.wallet.publicKey,
+      },
+    });
+    // The IDL marks the account as a signer.
+    const ix = program.idl.instructions.find((ix) => ix.name === "testSigner");
+    assert.ok(ix.accounts[0].isSigner);
+  });
+
+  it("Can use a SystemAccount", async () => {
+    await program.rpc.testSystemAccount({
+      accounts: {
+        wallet: program.provider.wallet.publicKey,
+      },
+    });
+    // Accounts not owned by the system program are rejected.
+    await assert.rejects(
+      async () => {
+        await program.rpc.testSystemAccount({
+          accounts: {
+            wallet: anchor.web3.SYSVAR_RENT_PUBKEY,
+          },
+        });
+      },
+      (err) => {
+        assert.equal(err.code, 171);
+        return true;
+      }
+    );
+  });
+
+  it("Can use an UncheckedAccount", async () => {
+    await program.rpc.testUncheckedAccount({
+      accounts: {
+        unchecked: anchor.web3.SYSVAR_RENT_PUBKEY,
+      },
+    });
+  });
+
+  it("Can find the canonical bump of a PDA", async () => {
+    // b"my-seed"
+    const seed = Buffer.from([109, 121, 45, 115, 101, 101, 100]);
+    const [myPda, nonce] = await PublicKey.findProgramAddress(
+      [seed, anchor.web3.SYSVAR_RENT_PUBKEY.toBuffer()],
+      program.programId
+    );
+
+    await program.rpc.testFindBump(nonce, {
+      accounts: {
+        myPda,
+        myAccount: anchor.web3.SYSVAR_RENT_PUBKEY,
+      },
+    });
+  });
+
+  it("Can validate a PDA of another program", async () => {
+    // b"my-seed"
+    const seed = Buffer.from([109, 121, 45, 115, 101, 101, 100]);
+    const [myPda, nonce] = await PublicKey.findProgramAddress(
+      [seed, anchor.web3.SYSVAR_RENT_PUBKEY.toBuffer()],
+      misc2Program.programId
+    );
+
+    await program.rpc.testSeedsProgram(nonce, {
+      accounts: {
+        myPda,
+        myAccount: anchor.web3.SYSVAR_RENT_PUBKEY,
+        misc2Program: misc2Program.programId,
+      },
+    });
+
+    // The same seeds derived against this program are rejected.
+    const [otherPda, otherNonce] = await PublicKey.findProgramAddress(
+      [seed, anchor.web3.SYSVAR_RENT_PUBKEY.toBuffer()],
+      program.programId
+    );
+    await assert.rejects(
+      async () => {
+        await program.rpc.testSeedsProgram(otherNonce, {
+          accounts: {
+            myPda: otherPda,
+            myAccount: anchor.web3.SYSVAR_RENT_PUBKEY,
+            misc2Program: misc2Program.programId,
+          },
+        });
+      },
+      (err) => {
+        assert.equal(err.code, 146);
+        return true;
+      }
+    );
+  });
+
   it("Can create a PDA account with instruction data", async () => {
     const seed = Buffer.from([1, 2, 3, 4]);
     const domain = "my-domain";
@@ -489,6 +741,387 @@ describe("misc", () => {
     assert.ok(account.mint.equals(mint.publicKey));
   });
 
+  it("Can init an account only if needed", async () => {
+    const [myPda, bump] = await PublicKey.findProgramAddress(
+      [Buffer.from(anchor.utils.bytes.utf8.encode("my-init-if-needed-seed"))],
+      program.programId
+    );
+    const accounts = {
+      myPda,
+      myPayer: program.provider.wallet.publicKey,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    await program.rpc.testInitIfNeeded(bump, 1, { accounts });
+    let myPdaAccount = await program.account.dataU16.fetch(myPda);
+    assert.ok(myPdaAccount.data === 1);
+
+    // Second invocation should skip the creation and reuse the account.
+    await program.rpc.testInitIfNeeded(bump, 2, { accounts });
+    myPdaAccount = await program.account.dataU16.fetch(myPda);
+    assert.ok(myPdaAccount.data === 2);
+  });
+
+  it("Can realloc an account", async () => {
+    const [data, bump] = await PublicKey.findProgramAddress(
+      [Buffer.from(anchor.utils.bytes.utf8.encode("my-realloc-seed"))],
+      program.programId
+    );
+    const accounts = {
+      data,
+      payer: program.provider.wallet.publicKey,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+    await program.rpc.testDataVecInit(bump, { accounts });
+
+    // Grow the account.
+    await program.rpc.testRealloc(10, { accounts });
+    let accountInfo = await program.provider.connection.getAccountInfo(data);
+    assert.ok(accountInfo.data.length === 8 + 4 + 10);
+    let dataAccount = await program.account.dataVec.fetch(data);
+    assert.ok(dataAccount.data.length === 10);
+
+    // Shrink the account.
+    await program.rpc.testRealloc(2, { accounts });
+    accountInfo = await program.provider.connection.getAccountInfo(data);
+    assert.ok(accountInfo.data.length === 8 + 4 + 2);
+    dataAccount = await program.account.dataVec.fetch(data);
+    assert.ok(dataAccount.data.length === 2);
+  });
+
+  it("Can calculate the space of an account with InitSpace", async () => {
+    const [data, bump] = await PublicKey.findProgramAddress(
+      [Buffer.from(anchor.utils.bytes.utf8.encode("my-init-space-seed"))],
+      program.programId
+    );
+    const name = "a".repeat(32);
+    const tags = ["abcdefgh", "abcdefgh", "abcdefgh", "abcdefgh"];
+    await program.rpc.testInitSpace(bump, name, tags, {
+      accounts: {
+        data,
+        payer: program.provider.wallet.publicKey,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+    });
+
+    const accountInfo = await program.provider.connection.getAccountInfo(data);
+    assert.ok(accountInfo.data.length === 8 + 32 + (4 + 32) + (4 + 4 * 12) + 9);
+    const dataAccount = await program.account.dataWithMaxLen.fetch(data);
+    assert.ok(dataAccount.name === name);
+    assert.deepEqual(dataAccount.tags, tags);
+  });
+
+  it("Can init and validate a token account with token constraints", async () => {
+    const mint = await Token.createMint(
+      program.provider.connection,
+      program.provider.wallet.payer,
+      program.provider.wallet.publicKey,
+      null,
+      0,
+      TOKEN_PROGRAM_ID
+    );
+    const token = anchor.web3.Keypair.generate();
+    await program.rpc.testTokenAccountInit({
+      accounts: {
+        token: token.publicKey,
+        mint: mint.publicKey,
+        authority: program.provider.wallet.publicKey,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      },
+      signers: [token],
+      instructions: [
+        anchor.web3.SystemProgram.createAccount({
+          fromPubkey: program.provider.wallet.publicKey,
+          newAccountPubkey: token.publicKey,
+          space: 165,
+          lamports: await Token.getMinBalanceRentForExemptAccount(
+            program.provider.connection
+          ),
+          programId: TOKEN_PROGRAM_ID,
+        }),
+      ],
+    });
+
+    const account = await mint.getAccountInfo(token.publicKey);
+    assert.ok(account.isInitialized);
+    assert.ok(account.owner.equals(program.provider.wallet.publicKey));
+    assert.ok(account.mint.equals(mint.publicKey));
+
+    await program.rpc.testTokenAccountConstraint({
+      accounts: {
+        token: token.publicKey,
+        mint: mint.publicKey,
+        authority: program.provider.wallet.publicKey,
+      },
+    });
+
+    const otherAuthority = anchor.web3.Keypair.generate();
+    await assert.rejects(
+      async () => {
+        await program.rpc.testTokenAccountConstraint({
+          accounts: {
+            token: token.publicKey,
+            mint: mint.publicKey,
+            authority: otherAuthority.publicKey,
+          },
+        });
+      },
+      (err) => {
+        assert.equal(err.code, 155);
+        return true;
+      }
+    );
+  });
+
+  it("Can require a token account to have no delegate", async () => {
+    const mint = await Token.createMint(
+      program.provider.connection,
+      program.provider.wallet.payer,
+      program.provider.wallet.publicKey,
+      null,
+      0,
+      TOKEN_PROGRAM_ID
+    );
+    const token = await mint.createAccount(program.provider.wallet.publicKey);
+
+    await program.rpc.testTokenAccountHardening({
+      accounts: {
+        token,
+      },
+    });
+
+    const delegate = anchor.web3.Keypair.generate();
+    await mint.approve(
+      token,
+      delegate.publicKey,
+      program.provider.wallet.payer,
+      [],
+      1
+    );
+    await assert.rejects(
+      async () => {
+        await program.rpc.testTokenAccountHardening({
+          accounts: {
+            token,
+          },
+        });
+      },
+      (err) => {
+        assert.equal(err.code, 214);
+        return true;
+      }
+    );
+  });
+
+  it("Can init and validate a mint with mint constraints", async () => {
+    const mint = anchor.web3.Keypair.generate();
+    const authority = program.provider.wallet.publicKey;
+    await program.rpc.testMintInit({
+      accounts: {
+        mint: mint.publicKey,
+        authority,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      },
+      signers: [mint],
+      instructions: [
+        anchor.web3.SystemProgram.createAccount({
+          fromPubkey: authority,
+          newAccountPubkey: mint.publicKey,
+          space: 82,
+          lamports: await Token.getMinBalanceRentForExemptMint(
+            program.provider.connection
+          ),
+          programId: TOKEN_PROGRAM_ID,
+        }),
+      ],
+    });
+
+    const client = new Token(
+      program.provider.connection,
+      mint.publicKey,
+      TOKEN_PROGRAM_ID,
+      program.provider.wallet.payer
+    );
+    const mintInfo = await client.getMintInfo();
+    assert.ok(mintInfo.isInitialized);
+    assert.ok(mintInfo.decimals === 6);
+    assert.ok(mintInfo.mintAuthority.equals(authority));
+    assert.ok(mintInfo.freezeAuthority.equals(authority));
+
+    await program.rpc.testMintConstraint({
+      accounts: {
+        mint: mint.publicKey,
+        authority,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      },
+    });
+
+    const otherAuthority = anchor.web3.Keypair.generate();
+    await assert.rejects(
+      async () => {
+        await program.rpc.testMintConstraint({
+          accounts: {
+            mint: mint.publicKey,
+            authority: otherAuthority.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+        });
+      },
+      (err) => {
+        assert.equal(err.code, 210);
+        return true;
+      }
+    );
+  });
+
+  it("Can init a mint at a program derived address", async () => {
+    const [myPda] = await PublicKey.findProgramAddress(
+      [Buffer.from(anchor.utils.bytes.utf8.encode("my-mint-seed"))],
+      program.programId
+    );
+    await program.rpc.testMintSeedsInit({
+      accounts: {
+        myPda,
+        payer: program.provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      },
+    });
+
+    const client = new Token(
+      program.provider.connection,
+      myPda,
+      TOKEN_PROGRAM_ID,
+      program.provider.wallet.payer
+    );
+    const mintInfo = await client.getMintInfo();
+    assert.ok(mintInfo.decimals === 6);
+    assert.ok(mintInfo.mintAuthority.equals(program.provider.wallet.publicKey));
+    assert.ok(mintInfo.freezeAuthority === null);
+  });
+
+  it("Can check the owner program of a CpiAccount", async () => {
+    const mint = await Token.createMint(
+      program.provider.connection,
+      program.provider.wallet.payer,
+      program.provider.wallet.publicKey,
+      null,
+      0,
+      TOKEN_PROGRAM_ID
+    );
+    const token = await mint.createAccount(program.provider.wallet.publicKey);
+    await program.rpc.testOwnerProgram({
+      accounts: {
+        token,
+      },
+    });
+  });
+
+  it("Can use InterfaceAccount and Interface types", async () => {
+    const mint = await Token.createMint(
+      program.provider.connection,
+      program.provider.wallet.payer,
+      program.provider.wallet.publicKey,
+      null,
+      0,
+      TOKEN_PROGRAM_ID
+    );
+    const token = await mint.createAccount(program.provider.wallet.publicKey);
+    await program.rpc.testInterfaceAccount({
+      accounts: {
+        token,
+        mint: mint.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      },
+    });
+    // Accounts not owned by a token program are rejected.
+    await assert.rejects(
+      async () => {
+        await program.rpc.testInterfaceAccount({
+          accounts: {
+            token: program.provider.wallet.publicKey,
+            mint: mint.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+        });
+      },
+      (err) => {
+        assert.equal(err.code, 172);
+        return true;
+      }
+    );
+  });
+
+  it("Can init and validate an associated token account", async () => {
+    const mint = await Token.createMint(
+      program.provider.connection,
+      program.provider.wallet.payer,
+      program.provider.wallet.publicKey,
+      null,
+      0,
+      TOKEN_PROGRAM_ID
+    );
+    const associatedToken = await Token.getAssociatedTokenAddress(
+      ASSOCIATED_TOKEN_PROGRAM_ID,
+      TOKEN_PROGRAM_ID,
+      mint.publicKey,
+      program.provider.wallet.publicKey
+    );
+    await program.rpc.testAssociatedTokenInit({
+      accounts: {
+        token: associatedToken,
+        mint: mint.publicKey,
+        payer: program.provider.wallet.publicKey,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      },
+    });
+
+    const account = await mint.getAccountInfo(associatedToken);
+    assert.ok(account.isInitialized);
+    assert.ok(account.owner.equals(program.provider.wallet.publicKey));
+    assert.ok(account.mint.equals(mint.publicKey));
+
+    await program.rpc.testAssociatedTokenConstraint({
+      accounts: {
+        token: associatedToken,
+        mint: mint.publicKey,
+        authority: program.provider.wallet.publicKey,
+      },
+    });
+
+    await assert.rejects(
+      async () => {
+        await program.rpc.testAssociatedTokenConstraint({
+          accounts: {
+            token: associatedToken,
+            mint: mint.publicKey,
+            authority: anchor.web3.Keypair.generate().publicKey,
+          },
+        });
+      },
+      (err) => {
+        assert.equal(err.code, 157);
+        return true;
+      }
+    );
+  });
+
+  it("Can CPI to instructions returning data", async () => {
+    await program.rpc.testReturnDataCpi(new anchor.BN(21), {
+      accounts: {
+        authority: program.provider.wallet.publicKey,
+        misc2Program: misc2Program.programId,
+      },
+    });
+  });
+
   it("Can execute a fallback function", async () => {
     await assert.rejects(
       async () => {
@@ -500,4 +1133,18 @@ describe("misc", () => {
       }
     );
   });
+
+  it("Can intercept instructions with a custom entry", async () => {
+    // Without the custom entry, this would be routed to the fallback, which
+    // always fails.
+    await anchor.utils.rpc.invoke(program.programId, [], Buffer.alloc(8, 255));
+  });
+
+  it("Can execute a fallback function returning an anchor Result", async () => {
+    await anchor.utils.rpc.invoke(
+      misc2Program.programId,
+      [],
+      Buffer.from([3, 1, 4, 1, 5])
+    );
+  });
 });
@@ -5,8 +5,6 @@ use anchor_spl::dex;
 use serum_dex::instruction::MarketInstruction;
 use serum_dex::state::OpenOrders;
 use solana_program::instruction::Instruction;
-use solana_program::program;
-use solana_program::system_program;
 use std::mem::size_of;
 
 /// This demonstrates how to create "permissioned markets" on Serum. A
@@ -31,7 +29,9 @@ pub mod permissioned_markets {
     /// Note that although the owner of the open orders account is the dex
     /// program, This instruction must be executed within this program, rather
     /// than a relay, because it initializes a PDA.
-    pub fn init_account(ctx: Context<InitAccount>, bump: u8, bump_init: u8) -> Result<()> {
+    pub fn init_account(ctx: Context<InitAccount>) -> Result<()> {
+        let bump = ctx.bumps["open_orders"];
+        let bump_init = ctx.bumps["open_orders_init_authority"];
         let cpi_ctx = CpiContext::from(&*ctx.accounts);
         let seeds = open_orders_authority! {
             program = ctx.program_id,
@@ -186,26 +186,25 @@ pub mod permissioned_markets {
             accounts: dex_accounts,
             program_id: dex::ID,
         };
-        let seeds = open_orders_authority! {
-            program = program_id,
-            market = market,
-            authority = user
-        };
-        program::invoke_signed(&ix, &acc_infos, &[seeds])
+        pda_invoke!(
+            program_id,
+            &ix,
+            &acc_infos,
+            &[b"open-orders".as_ref(), market.as_ref(), user.as_ref()]
+        )
     }
 }
 
 // Accounts context.
 
 #[derive(Accounts)]
-#[instruction(bump: u8, bump_init: u8)]
 pub struct InitAccount<'info> {
-    #[account(seeds = [b"open-orders-init", market.key.as_ref(), &[bump_init]])]
+    #[account(seeds = [b"open-orders-init", market.key.as_ref()], bump)]
     pub open_orders_init_authority: AccountInfo<'info>,
     #[account(
         init,
         seeds = [b"open-orders", market.key.as_ref(), authority.key.as_ref()],
-        bump = bump,
+        bump,
         payer = authority,
         owner = dex::ID,
         space = size_of::<OpenOrders>() + SERUM_PADDING,
@@ -215,10 +214,8 @@ pub struct InitAccount<'info> {
     pub authority: AccountInfo<'info>,
     pub market: AccountInfo<'info>,
     pub rent: Sysvar<'info, Rent>,
-    #[account(address = system_program::ID)]
-    pub system_program: AccountInfo<'info>,
-    #[account(address = dex::ID)]
-    pub dex_program: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    pub dex_program: Program<'info, dex::Dex>,
 }
 
 // CpiContext transformations.
@@ -235,7 +232,7 @@ impl<'info> From<&InitAccount<'info>>
             market: accs.market.clone(),
             rent: accs.rent.to_account_info(),
         };
-        let program = accs.dex_program.clone();
+        let program = accs.dex_program.to_account_info();
         CpiContext::new(program, accounts)
     }
 }
@@ -22,7 +22,7 @@ describe("permissioned-markets", () => {
 
   // Global DEX accounts and clients shared accross all tests.
   let marketClient, tokenAccount, usdcAccount;
-  let openOrders, openOrdersBump, openOrdersInitAuthority, openOrdersBumpinit;
+  let openOrders, openOrdersInitAuthority;
   let usdcPosted;
   let marketMakerOpenOrders;
 
@@ -49,7 +49,7 @@ describe("permissioned-markets", () => {
   });
 
   it("BOILERPLATE: Calculates open orders addresses", async () => {
-    const [_openOrders, bump] = await PublicKey.findProgramAddress(
+    const [_openOrders] = await PublicKey.findProgramAddress(
       [
         anchor.utils.bytes.utf8.encode("open-orders"),
         marketClient.address.toBuffer(),
@@ -57,10 +57,7 @@ describe("permissioned-markets", () => {
       ],
       program.programId
     );
-    const [
-      _openOrdersInitAuthority,
-      bumpInit,
-    ] = await PublicKey.findProgramAddress(
+    const [_openOrdersInitAuthority] = await PublicKey.findProgramAddress(
       [
         anchor.utils.bytes.utf8.encode("open-orders-init"),
         marketClient.address.toBuffer(),
@@ -70,13 +67,11 @@ describe("permissioned-markets", () => {
 
     // Save global variables re-used across tests.
     openOrders = _openOrders;
-    openOrdersBump = bump;
     openOrdersInitAuthority = _openOrdersInitAuthority;
-    openOrdersBumpInit = bumpInit;
   });
 
   it("Creates an open orders account", async () => {
-    await program.rpc.initAccount(openOrdersBump, openOrdersBumpInit, {
+    await program.rpc.initAccount({
       accounts: {
         openOrdersInitAuthority,
         openOrders,
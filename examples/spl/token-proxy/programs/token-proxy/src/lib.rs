@@ -24,7 +24,7 @@ mod token_proxy {
         authority_type: AuthorityType,
         new_authority: Option<Pubkey>,
     ) -> ProgramResult {
-        token::set_authority(ctx.accounts.into(), authority_type.into(), new_authority)
+        token::set_authority(ctx.accounts.into(), authority_type, new_authority)
     }
 }
 
@@ -2,6 +2,8 @@
 use anchor_lang::prelude::*;
 use puppet::{Puppet, SetData};
 
+solana_program::declare_id!("512tQn5xWTsuCN7LMstgjXXssed2p7J3LX7JkFkEpje3");
+
 #[program]
 mod puppet_master {
     use super::*;
@@ -0,0 +1,53 @@
+use anchor_lang::solana_program::{system_instruction, sysvar};
+use anchor_test::solana_sdk::signature::{Keypair, Signer};
+use puppet::Puppet;
+
+#[tokio::test]
+async fn performs_cpi_from_puppet_master_to_puppet() {
+    let mut ctx = anchor_test::workspace![puppet, puppet_master].start().await;
+    let puppet_program = puppet::ID;
+    let puppet_master_program = puppet_master::ID;
+
+    // Initialize a new puppet account.
+    let puppet = Keypair::new();
+    let space = 8 + 8;
+    let lamports = ctx
+        .banks_client()
+        .get_rent()
+        .await
+        .unwrap()
+        .minimum_balance(space);
+    let create_puppet = system_instruction::create_account(
+        &ctx.payer().pubkey(),
+        &puppet.pubkey(),
+        lamports,
+        space as u64,
+        &puppet_program,
+    );
+    ctx.request(puppet_program)
+        .instruction(create_puppet)
+        .accounts(puppet::accounts::Initialize {
+            puppet: puppet.pubkey(),
+            rent: sysvar::rent::ID,
+        })
+        .args(puppet::instruction::Initialize {})
+        .signer(&puppet)
+        .send()
+        .await
+        .unwrap();
+
+    // Invoke the puppet master to perform a CPI to the puppet.
+    ctx.request(puppet_master_program)
+        .accounts(puppet_master::accounts::PullStrings {
+            puppet: puppet.pubkey(),
+            puppet_program,
+        })
+        .args(puppet_master::instruction::PullStrings { data: 111 })
+        .send()
+        .await
+        .unwrap();
+
+    // Check the state updated.
+    let puppet_account: Puppet = ctx.account(puppet.pubkey()).await.unwrap();
+    assert_eq!(puppet_account.data, 111);
+}
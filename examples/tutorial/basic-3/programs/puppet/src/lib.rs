@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+solana_program::declare_id!("DRWoHZoTrhxroEhrtj7Gv4wXvqpcURe7E5BBuo18CFGB");
+
 #[program]
 pub mod puppet {
     use super::*;
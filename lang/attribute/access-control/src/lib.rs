@@ -1,5 +1,6 @@
 extern crate proc_macro;
 
+use anchor_syn::AccessControlArgs;
 use quote::quote;
 use syn::parse_macro_input;
 
@@ -45,19 +46,44 @@ use syn::parse_macro_input;
 /// on the `Accounts` struct, particularly when instruction arguments are
 /// needed. Here, we use the given `bump_seed` to verify it creates a valid
 /// program-derived address.
+///
+/// # Returning Data
+///
+/// A method can return data computed during validation, e.g., a parsed
+/// oracle price, which is bound to the given name in the handler, so that
+/// it isn't computed twice:
+///
+/// ```ignore
+/// #[access_control(price = valid_price(&ctx, max_age))]
+/// pub fn trade(ctx: Context<Trade>, max_age: u64) -> Result<()> {
+///     // `price` is the `Ok` value returned by `valid_price`.
+///     msg!("Trading at {}", price);
+///     Ok(())
+/// }
+///
+/// fn valid_price(ctx: &Context<Trade>, max_age: u64) -> Result<u64> {
+///     ...
+/// }
+/// ```
+///
+/// Multiple methods are executed in the order given, so later methods can
+/// use the data returned by earlier ones.
 #[proc_macro_attribute]
 pub fn access_control(
     args: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let mut args = args.to_string();
-    args.retain(|c| !c.is_whitespace());
+    let args = parse_macro_input!(args as AccessControlArgs);
     let access_control: Vec<proc_macro2::TokenStream> = args
-        .split(')')
-        .filter(|ac| !ac.is_empty())
-        .map(|ac| format!("{})", ac)) // Put back on the split char.
-        .map(|ac| format!("{}?;", ac)) // Add `?;` syntax.
-        .map(|ac| ac.parse().unwrap())
+        .guards
+        .iter()
+        .map(|guard| {
+            let call = &guard.call;
+            match &guard.binding {
+                None => quote! { #call?; },
+                Some(binding) => quote! { let #binding = #call?; },
+            }
+        })
         .collect();
 
     let item_fn = parse_macro_input!(input as syn::ItemFn);
@@ -77,3 +103,92 @@ pub fn access_control(
         }
     })
 }
+
+/// Rejects the decorated instruction handler if it's invoked via CPI,
+/// returning `ErrorCode::InstructionReentrant`.
+///
+/// Without arguments, which is the recommended form, the handler is rejected
+/// if it's invoked via CPI at all, so it can only be executed directly by a
+/// transaction.
+///
+/// Given the instructions sysvar account, the handler is only rejected if
+/// it's invoked via CPI and the transaction level instruction it descends
+/// from is one of the same program. This allows CPIs from other programs,
+/// but doesn't detect a program reentered through another one, e.g.,
+/// `A -> B -> A` where `B` is invoked by the transaction, since programs
+/// can't see the rest of the call stack.
+///
+/// # Example
+///
+/// ```ignore
+/// #[program]
+/// mod lender {
+///     use super::*;
+///
+///     // Can only be executed directly by a transaction.
+///     #[non_reentrant]
+///     pub fn repay(ctx: Context<Repay>) -> Result<()> {
+///         ...
+///     }
+///
+///     // Can also be invoked via CPI by transactions not starting at
+///     // this program.
+///     #[non_reentrant(ctx.accounts.instructions)]
+///     pub fn flash_loan(ctx: Context<FlashLoan>, amount: u64) -> Result<()> {
+///         ...
+///     }
+/// }
+/// ```
+///
+/// The first argument of the handler must be its `Context`.
+#[proc_macro_attribute]
+pub fn non_reentrant(
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let instructions_sysvar = match args.is_empty() {
+        true => quote! { None },
+        false => {
+            let sysvar = parse_macro_input!(args as syn::Expr);
+            quote! {
+                Some(&anchor_lang::ToAccountInfo::to_account_info(&#sysvar))
+            }
+        }
+    };
+
+    let item_fn = parse_macro_input!(input as syn::ItemFn);
+
+    let ctx = match item_fn.sig.inputs.first() {
+        Some(syn::FnArg::Typed(syn::PatType { pat, .. })) => match &**pat {
+            syn::Pat::Ident(pat_ident) => pat_ident.ident.clone(),
+            _ => {
+                return syn::Error::new_spanned(pat, "expected the context argument")
+                    .to_compile_error()
+                    .into()
+            }
+        },
+        _ => {
+            return syn::Error::new_spanned(&item_fn.sig, "expected a context argument")
+                .to_compile_error()
+                .into()
+        }
+    };
+
+    let fn_vis = item_fn.vis;
+    let fn_sig = item_fn.sig;
+    let fn_block = item_fn.block;
+
+    let fn_stmts = fn_block.stmts;
+
+    proc_macro::TokenStream::from(quote! {
+        #fn_vis #fn_sig {
+
+            anchor_lang::introspection::require_non_reentrant(
+                #ctx.program_id,
+                #instructions_sysvar,
+            )?;
+
+            #(#fn_stmts)*
+        }
+    })
+}
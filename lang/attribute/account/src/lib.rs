@@ -1,6 +1,7 @@
 extern crate proc_macro;
 
-use quote::quote;
+use anchor_syn::AccountArgs;
+use quote::{format_ident, quote};
 use syn::{parse_macro_input, parse_quote};
 
 /// A data structure representing a Solana account, implementing various traits:
@@ -18,6 +19,49 @@ use syn::{parse_macro_input, parse_quote};
 /// check this discriminator. If it doesn't match, an invalid account was given,
 /// and the account deserialization will exit with an error.
 ///
+/// # Custom Discriminators
+///
+/// The discriminator can be given explicitly, e.g., to match the accounts of
+/// a program not written with Anchor, or shortened to save rent:
+///
+/// ```ignore
+/// #[account(discriminator = [1])]
+/// pub struct MyData {
+///     pub data: u64,
+/// }
+/// ```
+///
+/// Discriminators are between 1 and 8 bytes long. Note that the default
+/// account space, i.e., when no `space` constraint is given, reserves
+/// `DISCRIMINATOR.len()` bytes for the discriminator, plus the version tag
+/// of versioned accounts. Unlike the default ones, short discriminators are
+/// likely to collide, so they should be chosen so that no two accounts of a
+/// program share a discriminator prefix.
+///
+/// # Enum Accounts
+///
+/// The attribute can be applied to an enum, so that a single account, e.g.,
+/// one at a program derived address, can hold one of several variants. The
+/// variant is stored as a Borsh enum tag following the discriminator, and
+/// can be checked with the `variant` constraint:
+///
+/// ```ignore
+/// #[account]
+/// pub enum Market {
+///     Pending { authority: Pubkey },
+///     Active { authority: Pubkey, price: u64 },
+///     Closed,
+/// }
+///
+/// #[derive(Accounts)]
+/// pub struct Trade<'info> {
+///     #[account(mut, variant = Market::Active)]
+///     market: ProgramAccount<'info, Market>,
+/// }
+/// ```
+///
+/// Enum accounts can't be zero copy and don't get lazy accessors.
+///
 /// # Zero Copy Deserialization
 ///
 /// **WARNING**: Zero copy deserialization is an experimental feature. It's
@@ -52,71 +96,82 @@ use syn::{parse_macro_input, parse_quote};
 /// [`Pod`](../bytemuck/trait.Pod.html). Please review the
 /// [`safety`](file:///home/armaniferrante/Documents/code/src/github.com/project-serum/anchor/target/doc/bytemuck/trait.Pod.html#safety)
 /// section before using.
+///
+/// # Lazy Deserialization
+///
+/// For Borsh accounts with named fields, a `Lazy<Name>` trait is generated,
+/// with a `load_<field>` method for each field, implemented for
+/// [`LazyAccount`](./struct.LazyAccount.html). Each method deserializes only
+/// the given field, skipping over the fields declared before it, e.g.,
+///
+/// ```ignore
+/// #[account]
+/// pub struct MyData {
+///     pub authority: Pubkey,
+///     pub items: Vec<u64>,
+/// }
+///
+/// // In a handler, with `my_data: LazyAccount<'info, MyData>`.
+/// let authority = ctx.accounts.my_data.load_authority()?;
+/// ```
+///
+/// # Versioning
+///
+/// To evolve an account's layout, pass a `version` argument, e.g.,
+/// `#[account(version = 2)]`. The version is stored in the byte following
+/// the discriminator, so one more byte of space must be allocated. When an
+/// account with an older version is deserialized, it's upgraded via the
+/// [`Migrate`](./trait.Migrate.html) trait, which must be implemented for
+/// versions greater than 1. Accounts with a newer version are rejected.
+///
+/// Since the version byte changes the layout, only accounts created as
+/// versioned can be migrated. Lazy accessors aren't generated for versioned
+/// accounts, and zero copy accounts can't be versioned.
 #[proc_macro_attribute]
 pub fn account(
     args: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let mut namespace = "".to_string();
-    let mut is_zero_copy = false;
-    if args.to_string().split(',').count() > 2 {
-        panic!("Only two args are allowed to the account attribute.")
-    }
-    for arg in args.to_string().split(',') {
-        let ns = arg
-            .to_string()
-            .replace("\"", "")
-            .chars()
-            .filter(|c| !c.is_whitespace())
-            .collect();
-        if ns == "zero_copy" {
-            is_zero_copy = true;
-        } else {
-            namespace = ns;
-        }
-    }
+    let args = match args.is_empty() {
+        true => AccountArgs::default(),
+        false => parse_macro_input!(args as AccountArgs),
+    };
+    let is_zero_copy = args.zero_copy;
+    let version = args.version;
 
-    let account_strct = parse_macro_input!(input as syn::ItemStruct);
-    let account_name = &account_strct.ident;
-    let (impl_gen, type_gen, where_clause) = account_strct.generics.split_for_impl();
-
-    let discriminator: proc_macro2::TokenStream = {
-        // Namespace the discriminator to prevent collisions.
-        let discriminator_preimage = {
-            // For now, zero copy accounts can't be namespaced.
-            if namespace.is_empty() {
-                format!("account:{}", account_name.to_string())
-            } else {
-                format!("{}:{}", namespace, account_name.to_string())
+    let account_item = parse_macro_input!(input as syn::Item);
+    let (account_name, generics, account_strct) = match &account_item {
+        syn::Item::Struct(strct) => (&strct.ident, &strct.generics, Some(strct)),
+        syn::Item::Enum(enm) => {
+            if is_zero_copy {
+                panic!("Zero copy accounts must be structs");
             }
-        };
+            (&enm.ident, &enm.generics, None)
+        }
+        _ => panic!("The account attribute is only allowed on structs and enums"),
+    };
+    let (impl_gen, type_gen, where_clause) = generics.split_for_impl();
 
-        let mut discriminator = [0u8; 8];
-        discriminator.copy_from_slice(
-            &anchor_syn::hash::hash(discriminator_preimage.as_bytes()).to_bytes()[..8],
-        );
-        format!("{:?}", discriminator).parse().unwrap()
+    let (discriminator, disc_len): (proc_macro2::TokenStream, usize) = {
+        let discriminator = args.discriminator(&account_name.to_string());
+        (
+            format!("{:?}", discriminator).parse().unwrap(),
+            discriminator.len(),
+        )
     };
 
     proc_macro::TokenStream::from({
         if is_zero_copy {
             quote! {
                 #[zero_copy]
-                #account_strct
-
-                #[automatically_derived]
-                unsafe impl #impl_gen anchor_lang::__private::bytemuck::Pod for #account_name #type_gen #where_clause {}
-                #[automatically_derived]
-                unsafe impl #impl_gen anchor_lang::__private::bytemuck::Zeroable for #account_name #type_gen #where_clause {}
+                #account_item
 
                 #[automatically_derived]
                 impl #impl_gen anchor_lang::ZeroCopy for #account_name #type_gen #where_clause {}
 
                 #[automatically_derived]
                 impl #impl_gen anchor_lang::Discriminator for #account_name #type_gen #where_clause {
-                    fn discriminator() -> [u8; 8] {
-                        #discriminator
-                    }
+                    const DISCRIMINATOR: &'static [u8] = &#discriminator;
                 }
 
                 // This trait is useful for clients deserializing accounts.
@@ -127,7 +182,7 @@ pub fn account(
                         if buf.len() < #discriminator.len() {
                             return Err(anchor_lang::__private::ErrorCode::AccountDiscriminatorNotFound.into());
                         }
-                        let given_disc = &buf[..8];
+                        let given_disc = &buf[..#disc_len];
                         if &#discriminator != given_disc {
                             return Err(anchor_lang::__private::ErrorCode::AccountDiscriminatorMismatch.into());
                         }
@@ -135,23 +190,89 @@ pub fn account(
                     }
 
                     fn try_deserialize_unchecked(buf: &mut &[u8]) -> std::result::Result<Self, ProgramError> {
-                        let data: &[u8] = &buf[8..];
+                        let data: &[u8] = buf
+                            .get(#disc_len..#disc_len + std::mem::size_of::<Self>())
+                            .ok_or(anchor_lang::__private::ErrorCode::AccountDidNotDeserialize)?;
                         // Re-interpret raw bytes into the POD data structure.
-                        let account = anchor_lang::__private::bytemuck::from_bytes(data);
+                        let account: &Self = anchor_lang::__private::bytemuck::try_from_bytes(data)
+                            .map_err(|_| anchor_lang::__private::ErrorCode::AccountDidNotDeserialize)?;
                         // Copy out the bytes into a new, owned data structure.
                         Ok(*account)
                     }
                 }
             }
         } else {
+            // Versioned accounts store the version after the discriminator,
+            // so the lazy accessors' offsets don't apply.
+            let lazy = match (version, account_strct) {
+                (None, Some(strct)) => generate_lazy_accessors(strct),
+                _ => quote! {},
+            };
+            let (write_version, deserialize) = match version {
+                None => (
+                    quote! {},
+                    quote! {
+                        let mut data: &[u8] = &buf[#disc_len..];
+                        AnchorDeserialize::deserialize(&mut data)
+                            .map_err(|_| anchor_lang::__private::ErrorCode::AccountDidNotDeserialize.into())
+                    },
+                ),
+                Some(version) => {
+                    let migrate = match version {
+                        1 => quote! {},
+                        _ => quote! {
+                            v if v < #version => <Self as anchor_lang::Migrate>::migrate(v, &mut data),
+                        },
+                    };
+                    (
+                        quote! {
+                            writer.write_all(&[#version]).map_err(|_| anchor_lang::__private::ErrorCode::AccountDidNotSerialize)?;
+                        },
+                        quote! {
+                            let version = *buf
+                                .get(#disc_len)
+                                .ok_or(anchor_lang::__private::ErrorCode::AccountDidNotDeserialize)?;
+                            let mut data: &[u8] = &buf[#disc_len + 1..];
+                            match version {
+                                // Zeroed, i.e., uninitialized accounts have the
+                                // current layout.
+                                0 | #version => AnchorDeserialize::deserialize(&mut data)
+                                    .map_err(|_| anchor_lang::__private::ErrorCode::AccountDidNotDeserialize.into()),
+                                #migrate
+                                _ => Err(anchor_lang::__private::ErrorCode::AccountVersionNotSupported.into()),
+                            }
+                        },
+                    )
+                }
+            };
+            let version_len = version.map(|_| {
+                quote! {
+                    const VERSION_LEN: usize = 1;
+                }
+            });
+            let versioned = version.map(|version| {
+                quote! {
+                    #[automatically_derived]
+                    impl #impl_gen anchor_lang::Versioned for #account_name #type_gen #where_clause {
+                        fn version() -> u8 {
+                            #version
+                        }
+                    }
+                }
+            });
             quote! {
                 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-                #account_strct
+                #account_item
+
+                #lazy
 
                 #[automatically_derived]
                 impl #impl_gen anchor_lang::AccountSerialize for #account_name #type_gen #where_clause {
+                    #version_len
+
                     fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> std::result::Result<(), ProgramError> {
                         writer.write_all(&#discriminator).map_err(|_| anchor_lang::__private::ErrorCode::AccountDidNotSerialize)?;
+                        #write_version
                         AnchorSerialize::serialize(
                             self,
                             writer
@@ -167,7 +288,7 @@ pub fn account(
                         if buf.len() < #discriminator.len() {
                             return Err(anchor_lang::__private::ErrorCode::AccountDiscriminatorNotFound.into());
                         }
-                        let given_disc = &buf[..8];
+                        let given_disc = &buf[..#disc_len];
                         if &#discriminator != given_disc {
                             return Err(anchor_lang::__private::ErrorCode::AccountDiscriminatorMismatch.into());
                         }
@@ -175,23 +296,75 @@ pub fn account(
                     }
 
                     fn try_deserialize_unchecked(buf: &mut &[u8]) -> std::result::Result<Self, ProgramError> {
-                        let mut data: &[u8] = &buf[8..];
-                        AnchorDeserialize::deserialize(&mut data)
-                            .map_err(|_| anchor_lang::__private::ErrorCode::AccountDidNotDeserialize.into())
+                        #deserialize
                     }
                 }
 
+                #versioned
+
                 #[automatically_derived]
                 impl #impl_gen anchor_lang::Discriminator for #account_name #type_gen #where_clause {
-                    fn discriminator() -> [u8; 8] {
-                        #discriminator
-                    }
+                    const DISCRIMINATOR: &'static [u8] = &#discriminator;
                 }
             }
         }
     })
 }
 
+// Generates the `Lazy<Name>` trait, with a `load_<field>` method per field,
+// for use with `LazyAccount`. Generic and tuple structs are skipped.
+fn generate_lazy_accessors(strct: &syn::ItemStruct) -> proc_macro2::TokenStream {
+    let fields = match &strct.fields {
+        syn::Fields::Named(fields) if strct.generics.params.is_empty() => &fields.named,
+        _ => return quote! {},
+    };
+    let name = &strct.ident;
+    let vis = &strct.vis;
+    let lazy_trait = format_ident!("Lazy{}", name);
+    let doc = format!(
+        "Partial deserialization of [`{}`] fields, for use with `LazyAccount`.",
+        name
+    );
+
+    let mut prev_tys: Vec<&syn::Type> = vec![];
+    let mut methods = vec![];
+    let mut method_impls = vec![];
+    for f in fields.iter() {
+        let ident = f.ident.as_ref().unwrap();
+        let ty = &f.ty;
+        let method = format_ident!("load_{}", ident);
+        methods.push(quote! {
+            fn #method(&self) -> std::result::Result<#ty, anchor_lang::solana_program::program_error::ProgramError>;
+        });
+        method_impls.push(quote! {
+            fn #method(&self) -> std::result::Result<#ty, anchor_lang::solana_program::program_error::ProgramError> {
+                let data = self.__data()?;
+                let mut data: &[u8] = &data;
+                // Skip over the preceding fields.
+                #(
+                    <#prev_tys as anchor_lang::AnchorDeserialize>::deserialize(&mut data)
+                        .map_err(|_| anchor_lang::__private::ErrorCode::AccountDidNotDeserialize)?;
+                )*
+                <#ty as anchor_lang::AnchorDeserialize>::deserialize(&mut data)
+                    .map_err(|_| anchor_lang::__private::ErrorCode::AccountDidNotDeserialize.into())
+            }
+        });
+        prev_tys.push(ty);
+    }
+
+    quote! {
+        #[doc = #doc]
+        #vis trait #lazy_trait {
+            #(#methods)*
+        }
+
+        #[automatically_derived]
+        impl<'info> #lazy_trait for anchor_lang::LazyAccount<'info, #name> {
+            #(#method_impls)*
+        }
+    }
+}
+
 /// Extends the `#[account]` attribute to allow one to create associated
 /// accounts. This includes a `Default` implementation, which means all fields
 /// in an `#[associated]` struct must implement `Default` and an
@@ -322,23 +495,52 @@ pub fn derive_zero_copy_accessor(item: proc_macro::TokenStream) -> proc_macro::T
 /// A data structure that can be used as an internal field for a zero copy
 /// deserialized account, i.e., a struct marked with `#[account(zero_copy)]`.
 ///
-/// This is just a convenient alias for
+/// This is a convenient alias for
 ///
 /// ```ignore
 /// #[derive(Copy, Clone)]
 /// #[repr(packed)]
 /// struct MyStruct {...}
 /// ```
+///
+/// along with [`Pod`](../bytemuck/trait.Pod.html) and
+/// [`Zeroable`](../bytemuck/trait.Zeroable.html) implementations. Since the
+/// struct is packed, it never contains padding. The implementations require
+/// every field to be `Pod` and `Zeroable`, so a struct containing, e.g., a
+/// `bool` or an enum fails to compile.
 #[proc_macro_attribute]
 pub fn zero_copy(
     _args: proc_macro::TokenStream,
     item: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
     let account_strct = parse_macro_input!(item as syn::ItemStruct);
+    let account_name = &account_strct.ident;
+    let (impl_gen, type_gen, _) = account_strct.generics.split_for_impl();
+
+    // Bound each field's type, so that the unsafe impls only apply when all
+    // fields are themselves `Pod` and `Zeroable`.
+    let field_tys: Vec<&syn::Type> = account_strct.fields.iter().map(|f| &f.ty).collect();
+    let bounded_where_clause = |bound: proc_macro2::TokenStream| {
+        let mut generics = account_strct.generics.clone();
+        let where_clause = generics.make_where_clause();
+        for ty in &field_tys {
+            where_clause
+                .predicates
+                .push(parse_quote! { #ty: anchor_lang::__private::bytemuck::#bound });
+        }
+        where_clause.clone()
+    };
+    let pod_where_clause = bounded_where_clause(quote! { Pod });
+    let zeroable_where_clause = bounded_where_clause(quote! { Zeroable });
 
     proc_macro::TokenStream::from(quote! {
         #[derive(anchor_lang::__private::ZeroCopyAccessor, Copy, Clone)]
         #[repr(packed)]
         #account_strct
+
+        #[automatically_derived]
+        unsafe impl #impl_gen anchor_lang::__private::bytemuck::Pod for #account_name #type_gen #pod_where_clause {}
+        #[automatically_derived]
+        unsafe impl #impl_gen anchor_lang::__private::bytemuck::Zeroable for #account_name #type_gen #zeroable_where_clause {}
     })
 }
@@ -46,6 +46,21 @@ use syn::parse_macro_input;
 ///
 /// The `#[msg(..)]` attribute is inert, and is used only as a marker so that
 /// parsers  and IDLs can map error codes to error messages.
+///
+/// # Offset
+///
+/// Error codes start at 300 by default, i.e., the first variant of the enum
+/// is returned as `ProgramError::Custom(300)`. To avoid collisions with error
+/// codes of other programs, a different offset can be given, which is also
+/// recorded in the IDL as `errorOffset`.
+///
+/// ```ignore
+/// #[error(offset = 6000)]
+/// pub enum MyError {
+///     // Returned as `ProgramError::Custom(6000)`.
+///     Hello,
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn error(
     args: proc_macro::TokenStream,
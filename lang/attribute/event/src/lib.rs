@@ -7,21 +7,36 @@ use syn::parse_macro_input;
 /// [emit!](./macro.emit.html) so that programs can log significant events in
 /// their programs that clients can subscribe to. Currently, this macro is for
 /// structs only.
+///
+/// By default, an event's discriminator is the first 8 bytes of the SHA256 of
+/// `"event:<StructName>"`. It can be configured with the following arguments:
+///
+/// - `namespace = "<ns>"`: hashes `"event:<ns>:<StructName>"` instead, so
+///   that events with the same name in different modules don't collide.
+/// - `version = <n>`: for versions greater than 1, appends `":v<n>"` to the
+///   preimage, so that a changed schema gets a new discriminator. The
+///   version is recorded in the IDL and returned by the client's decoder.
+/// - `discriminator = [<u8>; 8]`: uses the given bytes as is.
+///
+/// ```ignore
+/// #[event(namespace = "staking", version = 2)]
+/// pub struct Deposited {
+///     pub amount: u64,
+///     pub mint: Pubkey,
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn event(
-    _args: proc_macro::TokenStream,
+    args: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
+    let args = parse_macro_input!(args as anchor_syn::EventArgs);
     let event_strct = parse_macro_input!(input as syn::ItemStruct);
 
     let event_name = &event_strct.ident;
 
     let discriminator: proc_macro2::TokenStream = {
-        let discriminator_preimage = format!("event:{}", event_name.to_string());
-        let mut discriminator = [0u8; 8];
-        discriminator.copy_from_slice(
-            &anchor_syn::hash::hash(discriminator_preimage.as_bytes()).to_bytes()[..8],
-        );
+        let discriminator = args.discriminator(&event_name.to_string());
         format!("{:?}", discriminator).parse().unwrap()
     };
 
@@ -38,9 +53,7 @@ pub fn event(
         }
 
         impl anchor_lang::Discriminator for #event_name {
-            fn discriminator() -> [u8; 8] {
-                #discriminator
-            }
+            const DISCRIMINATOR: &'static [u8] = &#discriminator;
         }
     })
 }
@@ -61,6 +74,66 @@ pub fn emit(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     })
 }
 
+/// Emits an [event](./attr.event.html) by invoking the program itself with
+/// the serialized event as instruction data, rather than logging it. Since
+/// program logs may be truncated, this lets indexers reliably recover events
+/// from the transaction's inner instructions.
+///
+/// Must be called from an instruction handler with a `ctx` whose accounts are
+/// marked with [`#[event_cpi]`](./attr.event_cpi.html), e.g.,
+///
+/// ```ignore
+/// pub fn initialize(ctx: Context<Initialize>) -> ProgramResult {
+///     emit_cpi!(MyEvent { data: 5 });
+///     Ok(())
+/// }
+///
+/// #[event_cpi]
+/// #[derive(Accounts)]
+/// pub struct Initialize<'info> {}
+/// ```
+#[proc_macro]
+pub fn emit_cpi(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let data: proc_macro2::TokenStream = input.into();
+    proc_macro::TokenStream::from(quote! {
+        {
+            let authority_info = ctx.accounts.event_authority.to_account_info();
+            let authority_bump = ctx.bumps["event_authority"];
+            let mut ix_data = anchor_lang::event::EVENT_IX_TAG_LE.to_vec();
+            ix_data.append(&mut anchor_lang::Event::data(&#data));
+            let ix = anchor_lang::solana_program::instruction::Instruction::new_with_bytes(
+                *ctx.program_id,
+                &ix_data,
+                vec![anchor_lang::solana_program::instruction::AccountMeta::new_readonly(
+                    *authority_info.key,
+                    true,
+                )],
+            );
+            anchor_lang::solana_program::program::invoke_signed(
+                &ix,
+                &[authority_info, ctx.accounts.program.to_account_info()],
+                &[&[anchor_lang::event::EVENT_AUTHORITY_SEED, &[authority_bump]]],
+            )?;
+        }
+    })
+}
+
+/// Adds the accounts required by [emit_cpi!](./macro.emit_cpi.html) to an
+/// `Accounts` struct: the `event_authority`, a program derived address
+/// signing the self-CPI, and the `program` itself. Must be placed above
+/// `#[derive(Accounts)]`.
+#[proc_macro_attribute]
+pub fn event_cpi(
+    _args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let accounts_strct = parse_macro_input!(input as syn::ItemStruct);
+    match anchor_syn::parser::accounts::event_cpi::add_event_cpi_accounts(&accounts_strct) {
+        Ok(strct) => proc_macro::TokenStream::from(quote! { #strct }),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 // EventIndex is a marker macro. It functionally does nothing other than
 // allow one to mark fields with the `#[index]` inert attribute, which is
 // used to add metadata to IDLs.
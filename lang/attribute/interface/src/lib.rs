@@ -130,11 +130,61 @@ use syn::parse_macro_input;
 /// with the SPL's [Shared Memory Program](https://github.com/solana-labs/solana-program-library/tree/master/shared-memory).
 /// In the future, Anchor will add the ability to return values across CPI
 /// without having to worry about the details of shared memory accounts.
+///
+/// # Implementing with Instructions
+///
+/// Programs without state can implement an interface method with a regular
+/// instruction of the same name, marked with the trait, e.g.,
+///
+/// ```ignore
+/// #[program]
+/// pub mod amm {
+///     use super::*;
+///
+///     #[interface(Swap)]
+///     pub fn swap(ctx: Context<SwapAccounts>, amount: u64) -> ProgramResult {
+///         ...
+///     }
+/// }
+/// ```
+///
+/// The instruction's identifier is then namespaced by the trait rather than
+/// "global", so that any implementation can be invoked via the module
+/// generated for the trait, e.g., `swap::swap(cpi_ctx, amount)`. The module
+/// has the visibility of the trait, so that a router can depend on a crate
+/// defining the interface instead of on each implementation.
+///
+/// To restrict the programs that may be invoked, e.g., to a set of audited
+/// AMMs, use an `Interface` account, whose address is checked against the
+/// allowed program ids:
+///
+/// ```ignore
+/// #[derive(Clone)]
+/// pub struct Amms;
+///
+/// impl anchor_lang::Ids for Amms {
+///     fn ids() -> &'static [Pubkey] {
+///         &[amm_a::ID, amm_b::ID]
+///     }
+/// }
+///
+/// #[derive(Accounts)]
+/// pub struct Route<'info> {
+///     pub amm: Interface<'info, Amms>,
+///     ...
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn interface(
     _args: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
+    // Instructions implementing an interface method are only marked with the
+    // trait for the `#[program]` attribute, so they're emitted unchanged.
+    if let Ok(item_fn) = syn::parse::<syn::ItemFn>(input.clone()) {
+        return proc_macro::TokenStream::from(quote! { #item_fn });
+    }
+
     let item_trait = parse_macro_input!(input as syn::ItemTrait);
 
     let trait_name = item_trait.ident.to_string();
@@ -230,12 +280,14 @@ pub fn interface(
         })
         .collect();
 
+    let vis = &item_trait.vis;
+
     proc_macro::TokenStream::from(quote! {
         #item_trait
 
         /// Anchor generated module for invoking programs implementing an
         /// `#[interface]` via CPI.
-        mod #mod_name {
+        #vis mod #mod_name {
             use super::*;
             #(#methods)*
         }
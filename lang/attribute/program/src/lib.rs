@@ -1,16 +1,105 @@
 extern crate proc_macro;
 
+use anchor_syn::ProgramArgs;
 use quote::ToTokens;
 use syn::parse_macro_input;
 
 /// The `#[program]` attribute defines the module containing all instruction
 /// handlers defining all entries into a Solana program.
+///
+/// A function in the module that doesn't take a `Context`, but rather the
+/// raw entrypoint arguments, is a fallback function. It's invoked with the
+/// full instruction data for any instruction that doesn't match one of the
+/// program's handlers, e.g., to accept instructions in another program's
+/// format. At most one fallback function may be given.
+///
+/// ```ignore
+/// pub fn fallback(
+///     program_id: &Pubkey,
+///     accounts: &[AccountInfo],
+///     data: &[u8],
+/// ) -> Result<()> {
+///     // ...
+/// }
+/// ```
+///
+/// # Custom Entry
+///
+/// To intercept instructions before Anchor's dispatch, e.g., to proxy
+/// instructions in another program's encoding while still having normal
+/// handlers, a custom entry function can be given with
+/// `#[program(custom_entry = <path>)]`. It's invoked with the raw entrypoint
+/// arguments before anything else, and returns whether it handled the
+/// instruction. If it returns `false`, the instruction is dispatched as
+/// usual. The function must be defined outside of the `#[program]` module,
+/// so that it isn't mistaken for a fallback function.
+///
+/// ```ignore
+/// #[program(custom_entry = proxy_entry)]
+/// pub mod my_program {
+///     // ...
+/// }
+///
+/// fn proxy_entry(
+///     program_id: &Pubkey,
+///     accounts: &[AccountInfo],
+///     data: &[u8],
+/// ) -> Result<bool> {
+///     // ...
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn program(
-    _args: proc_macro::TokenStream,
+    args: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    parse_macro_input!(input as anchor_syn::Program)
-        .to_token_stream()
-        .into()
+    let args = match args.is_empty() {
+        true => None,
+        false => Some(parse_macro_input!(args as ProgramArgs)),
+    };
+    let mut program = parse_macro_input!(input as anchor_syn::Program);
+    program.args = args;
+    program.to_token_stream().into()
+}
+
+/// Generates bindings for invoking a program from its IDL, without depending
+/// on the program's crate.
+///
+/// The IDL is read from `idls/<name>.json`, looked up in the directory of
+/// the crate being compiled and its parents, or from the path given as a
+/// second argument, relative to the crate. It must contain the program's
+/// address in `metadata.address`. A module named after the program is
+/// generated, containing
+///
+/// - `ID` and a `<Name>` struct implementing `Id`, for `Program<'info, _>`,
+/// - `accounts`, the program's accounts, for use with `CpiAccount`,
+/// - `types` and `events`, the types and events defined by the program,
+/// - `errors`, an `ErrorCode` enum with the program's error codes,
+/// - `instruction`, the data of each instruction, and
+/// - `cpi`, a function for each instruction taking a `CpiContext` of the
+///   instruction's accounts in `cpi::accounts`.
+///
+/// State instructions aren't supported.
+///
+/// ```ignore
+/// declare_program!(counter);
+///
+/// pub fn increment(ctx: Context<Increment>) -> ProgramResult {
+///     let cpi_ctx = CpiContext::new(
+///         ctx.accounts.counter_program.to_account_info(),
+///         counter::cpi::accounts::Increment {
+///             counter: ctx.accounts.counter.to_account_info(),
+///             authority: ctx.accounts.authority.to_account_info(),
+///         },
+///     );
+///     counter::cpi::increment(cpi_ctx, 1)
+/// }
+/// ```
+#[proc_macro]
+pub fn declare_program(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let args = parse_macro_input!(input as anchor_syn::DeclareProgramArgs);
+    match anchor_syn::codegen::declare_program::generate(&args) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
 }
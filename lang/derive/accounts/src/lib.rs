@@ -33,25 +33,128 @@ use syn::parse_macro_input;
 /// * Signed by `authority`.
 /// * Checked that `&data.authority == authority.key`.
 ///
+/// An account that must sign can also be declared with the `Signer` type,
+/// e.g., `pub authority: Signer<'info>`, which is equivalent to
+/// `#[account(signer)] pub authority: AccountInfo<'info>`.
+///
+/// Similarly, program accounts can be declared with the `Program` type, e.g.,
+/// `pub system_program: Program<'info, System>`, which checks the account is
+/// executable and its address matches `T::id()` for any `T` implementing
+/// `Id`.
+///
+/// Accounts that may be owned by any one of several programs sharing an
+/// interface can be declared with the `InterfaceAccount` type, where `T`
+/// implements `Owners`, e.g.,
+/// `InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>` accepts
+/// token accounts of both SPL Token and Token-2022. The programs themselves can
+/// be declared with the `Interface` type, where `T` implements `Ids`, e.g.,
+/// `Interface<'info, anchor_spl::token_interface::TokenInterface>`.
+///
+/// Large accounts that are only read can be declared with the `LazyAccount`
+/// type, e.g., `LazyAccount<'info, MyData>`, which checks the account's owner
+/// and discriminator, but only deserializes fields as they're accessed.
+///
+/// Wallets receiving lamports can be declared with the `SystemAccount` type,
+/// e.g., `pub recipient: SystemAccount<'info>`, which checks the account is
+/// owned by the system program.
+///
+/// Accounts that intentionally skip validation should be declared with the
+/// `UncheckedAccount` type instead of `AccountInfo`. Such fields must be
+/// documented with a `/// CHECK:` comment explaining why no checks are
+/// necessary, otherwise the struct fails to compile, e.g.,
+///
+/// ```ignore
+/// /// CHECK: Only used as a seed for the PDA.
+/// pub seed_account: UncheckedAccount<'info>,
+/// ```
+///
+/// Any account field can be wrapped in a `Box`, e.g.,
+/// `Box<ProgramAccount<'info, MyData>>`, to move it to the heap. Boxed
+/// accounts accept the same constraints as their inner type. This is useful
+/// for structs with many accounts, which may otherwise exceed the BPF stack.
+///
+/// Any account field can be made optional by wrapping it in an `Option`,
+/// e.g., `Option<ProgramAccount<'info, MyData>>`. An optional account is
+/// `None` when the client passes the program id in its place, or when no
+/// accounts are left to deserialize. Constraints on optional accounts are only
+/// checked when the account is present, and optional accounts cannot be
+/// initialized. Since generated clients and CPIs pass `crate::ID` for absent
+/// accounts, programs with optional accounts must `declare_id!`.
+///
+/// Fields of type `Clock` or `Rent`, rather than `Sysvar<'info, Clock>` or
+/// `Sysvar<'info, Rent>`, are loaded via syscall. They aren't accounts, so
+/// clients don't pass them, and they're left out of the IDL. Use the
+/// `Sysvar` types where a CPI needs the sysvar account.
+///
+/// Accounts structs may be generic, with constraints referring to the type
+/// parameters, e.g., `seeds = [T::SEED], bump, seeds::program = T::id()` on a
+/// `Deposit<'info, T: VaultStrategy>` struct holding a `Program<'info, T>`.
+/// Instructions pick the concrete type through their context, e.g.,
+/// `Context<Deposit<Lending>>`.
+///
+/// The `signer`, `mut`, `has_one`, `owner`, `address`, and `constraint`
+/// attributes accept a custom error to return when the check fails, e.g.,
+/// `#[account(constraint = amount > 0 @ MyError::InvalidAmount)]`. Otherwise,
+/// the framework's default error for the constraint is returned.
+///
+/// Groups of accounts shared by several instructions can be defined once and
+/// embedded as a field of another `Accounts` struct. When the embedded struct's
+/// constraints depend on values it doesn't own, such as an account of the
+/// outer struct or an instruction argument, it declares them with
+/// `#[scope(..)]`, and the outer struct passes them in order with
+/// `#[account(scope(..))]`, e.g.,
+///
+/// ```ignore
+/// #[derive(Accounts)]
+/// #[scope(authority: Pubkey, amount: u64)]
+/// pub struct Vault<'info> {
+///     #[account(seeds = [b"vault", authority.as_ref()], bump)]
+///     pub vault: AccountInfo<'info>,
+///     #[account(constraint = vault_tokens.amount >= amount)]
+///     pub vault_tokens: CpiAccount<'info, TokenAccount>,
+/// }
+///
+/// #[derive(Accounts)]
+/// #[instruction(amount: u64)]
+/// pub struct Withdraw<'info> {
+///     pub authority: Signer<'info>,
+///     #[account(scope(*authority.key, amount))]
+///     pub vault: Vault<'info>,
+/// }
+/// ```
+///
+/// Scope values are evaluated when the embedded struct is deserialized, so
+/// they may only refer to fields declared before it. A scoped struct can't be
+/// used as an instruction's context on its own.
+///
 /// The full list of available attributes is as follows.
 ///
 /// | Attribute | Location | Description |
 /// |:--|:--|:--|
 /// | `#[account(signer)]` | On raw `AccountInfo` structs. | Checks the given account signed the transaction. |
 /// | `#[account(mut)]` | On `AccountInfo`, `ProgramAccount` or `CpiAccount` structs. | Marks the account as mutable and persists the state transition. |
-/// | `#[account(init)]` | On `ProgramAccount` structs. | Marks the account as being initialized, skipping the account discriminator check. When using `init`, a `rent` `Sysvar` must be present in the `Accounts` struct. |
+/// | `#[account(mut, allow_duplicate)]` | On `ProgramAccount` or `Loader` structs | Allows the account to be the same as another mutable account of the same type in the struct. Otherwise, such pairs fail to compile, since the account written last on exit silently overwrites the other. Accounts marked `init` are exempt. |
+/// | `#[account(init)]` | On `ProgramAccount` structs. | Marks the account as being initialized, skipping the account discriminator check. If no `rent` field is present in the `Accounts` struct, the rent sysvar is loaded via syscall. |
+/// | `#[account(init_if_needed)]` | On `ProgramAccount`, `Loader` or `CpiAccount` structs. | Same as `init`, but only initializes the account if it hasn't been initialized yet. When used with `seeds` or `associated`, the account is created only if it's still owned by the system program; otherwise, its owner and space are validated against the expected values. |
 /// | `#[account(close = <target>)]` | On `ProgramAccount` and `Loader` structs. | Marks the account as being closed at the end of the instruction's execution, sending the rent exemption lamports to the specified <target>. |
+/// | `#[account(realloc = <space>, realloc::payer = <target>, realloc::zero = <bool>)]` | On `ProgramAccount` and `Loader` structs. | Resizes the account to the given space before the instruction executes, transferring lamports from the payer to keep the account rent exempt (or refunding the payer when shrinking). If `realloc::zero` is true, newly allocated memory is zero initialized. Requires `mut`, and `system_program` must be present in the `Accounts` struct. |
 /// | `#[account(has_one = <target>)]` | On `ProgramAccount` or `CpiAccount` structs | Checks the `target` field on the account matches the `target` field in the struct deriving `Accounts`. |
-/// | `#[account(seeds = [<seeds>], bump? = <target>, payer? = <target>, space? = <target>, owner? = <target>)]` | On `AccountInfo` structs | Seeds for the program derived address an `AccountInfo` struct represents. If bump is provided, then appends it to the seeds. On initialization, validates the given bump is the bump provided by `Pubkey::find_program_address`.|
+/// | `#[account(seeds = [<seeds>], bump? = <target>, payer? = <target>, space? = <target>, owner? = <target>, seeds::program? = <target>)]` | On `AccountInfo` structs | Seeds for the program derived address an `AccountInfo` struct represents. If bump is provided, then appends it to the seeds. If `bump` is given without a value, the canonical bump is found with `Pubkey::find_program_address` and made available to the handler via `ctx.bumps`, keyed by field name. If `seeds::program` is provided, the address is derived from the given program id instead of the executing program's, which can't be combined with `init`. On initialization, validates the given bump is the bump provided by `Pubkey::find_program_address`. If `space` isn't given, the account size is that of the account type's default value. If `space = auto`, it's calculated from the account type's `#[derive(InitSpace)]` implementation. Either way, the discriminator's length, and the version byte of versioned accounts, is added. |
+/// | `#[account(token::mint = <target>, token::authority = <target>)]` | On `CpiAccount<TokenAccount>` structs | Checks the token account is owned by the SPL token program and has the given mint and authority. When used with `init`, initializes the token account via CPI (either in place or, when used with `seeds` or `associated`, at the program derived address), in which case `token_program` must be present in the `Accounts` struct. |
+/// | `#[account(token::delegate = <target>, token::close_authority = <target>)]` | On `CpiAccount<TokenAccount>` structs | Checks the token account's delegate and close authority, respectively. Either target may instead be `none`, e.g., `token::delegate = none`, to require that it isn't set. |
+/// | `#[account(associated_token::mint = <target>, associated_token::authority = <target>)]` | On `CpiAccount<TokenAccount>` structs | Checks the account is the associated token account of the given authority and mint. When used with `init` (or `init_if_needed`), creates the associated token account via CPI, in which case `payer` must be given and `system_program`, `token_program`, and `associated_token_program` must be present in the `Accounts` struct. |
+/// | `#[account(mint::decimals = <expr>, mint::authority = <target>, mint::freeze_authority? = <target>, mint::token_program? = <target>)]` | On `CpiAccount<Mint>` structs | Checks the mint has each of the given decimals, mint authority and freeze authority, and is owned by the given token program, or by the SPL token program if none is given. Any subset may be given to only validate. When used with `init`, initializes the mint via CPI (either in place or, when used with `seeds` or `associated`, at the program derived address), in which case `decimals` and `authority` are required and, unless `mint::token_program` is given, `token_program` must be present in the `Accounts` struct. |
 /// | `#[account(constraint = <expression>)]` | On any type deriving `Accounts` | Executes the given code as a constraint. The expression should evaluate to a boolean. |
+/// | `#[account(scope(<values>))]` | On composite fields whose type declares `#[scope(..)]` | Passes the given values to the embedded struct's scope, in order. |
 /// | `#[account("<literal>")]` | Deprecated | Executes the given code literal as a constraint. The literal should evaluate to a boolean. |
 /// | `#[account(rent_exempt = <skip>)]` | On `AccountInfo` or `ProgramAccount` structs | Optional attribute to skip the rent exemption check. By default, all accounts marked with `#[account(init)]` will be rent exempt, and so this should rarely (if ever) be used. Similarly, omitting `= skip` will mark the account rent exempt. |
 /// | `#[account(executable)]` | On `AccountInfo` structs | Checks the given account is an executable program. |
+/// | `#[account(instruction_sysvar)]` | On `AccountInfo` or `UncheckedAccount` structs | Checks the account is the instructions sysvar and binds the `anchor_lang::introspection` helpers for it, i.e., `prev_instruction()`, `next_instruction()`, `prev_instruction_is(<program_id>, <discriminator>)`, and `next_instruction_is(<program_id>, <discriminator>)`, for use in the constraints of this and subsequent fields, e.g., `constraint = prev_instruction_is(&ed25519_program::ID, &[])`. |
 /// | `#[account(state = <target>)]` | On `CpiState` structs | Checks the given state is the canonical state account for the target program. |
-/// | `#[account(owner = <target>)]` | On `CpiState`, `CpiAccount`, and `AccountInfo` | Checks the account owner matches the target. |
+/// | `#[account(owner = <target>)]` | On `CpiState`, `CpiAccount`, and `AccountInfo` | Checks the account owner matches the target, which is either another account in the struct or a `Pubkey` expression, e.g., `owner = anchor_spl::token::ID`. |
 // TODO: How do we make the markdown render correctly without putting everything
 //       on absurdly long lines?
-#[proc_macro_derive(Accounts, attributes(account, instruction))]
+#[proc_macro_derive(Accounts, attributes(account, instruction, scope))]
 pub fn derive_anchor_deserialize(item: TokenStream) -> TokenStream {
     parse_macro_input!(item as anchor_syn::AccountsStruct)
         .to_token_stream()
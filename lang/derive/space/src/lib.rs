@@ -0,0 +1,194 @@
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use std::collections::VecDeque;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::token::Comma;
+use syn::{parse_macro_input, DeriveInput, Expr, Fields, GenericArgument, PathArguments, Type};
+
+/// Implements the [`Space`](./trait.Space.html) trait on the given struct or
+/// enum, calculating the maximum number of bytes its borsh serialization can
+/// take. The account discriminator is *not* included, but is added when
+/// initializing an account with `space = auto`.
+///
+/// Types with a variable size, i.e., `String` and `Vec`, must be given a
+/// maximum length with the `max_len` attribute. For nested types, a length
+/// must be given for each variable sized type, from the outermost inwards.
+///
+/// # Example
+///
+/// ```ignore
+/// #[account]
+/// #[derive(InitSpace)]
+/// pub struct MyData {
+///     pub authority: Pubkey,
+///     #[max_len(32)]
+///     pub name: String,
+///     #[max_len(10, 5)]
+///     pub grid: Vec<Vec<u8>>,
+/// }
+///
+/// #[derive(Accounts)]
+/// pub struct Initialize<'info> {
+///     #[account(init, seeds = [b"my-seed".as_ref()], payer = payer, space = auto)]
+///     pub data: ProgramAccount<'info, MyData>,
+///     pub payer: AccountInfo<'info>,
+///     pub rent: Sysvar<'info, Rent>,
+///     pub system_program: AccountInfo<'info>,
+/// }
+/// ```
+#[proc_macro_derive(InitSpace, attributes(max_len))]
+pub fn derive_init_space(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    match init_space(input) {
+        Ok(tts) => TokenStream::from(tts),
+        Err(e) => TokenStream::from(e.to_compile_error()),
+    }
+}
+
+fn init_space(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_gen, ty_gen, where_clause) = input.generics.split_for_impl();
+
+    let space = match &input.data {
+        syn::Data::Struct(strct) => fields_len(&strct.fields)?,
+        syn::Data::Enum(enm) => {
+            let variants = enm
+                .variants
+                .iter()
+                .map(|v| fields_len(&v.fields))
+                .collect::<syn::Result<Vec<TokenStream2>>>()?;
+            // One byte for the variant index plus the largest variant.
+            let max = variants.into_iter().fold(quote! { 0 }, |acc, v| {
+                quote! {
+                    {
+                        let __a = #acc;
+                        let __b = #v;
+                        if __a > __b { __a } else { __b }
+                    }
+                }
+            });
+            quote! { 1 + #max }
+        }
+        syn::Data::Union(_) => {
+            return Err(syn::Error::new(
+                name.span(),
+                "InitSpace cannot be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_gen anchor_lang::Space for #name #ty_gen #where_clause {
+            const INIT_SPACE: usize = #space;
+        }
+    })
+}
+
+// Returns the summed length of all the given fields.
+fn fields_len(fields: &Fields) -> syn::Result<TokenStream2> {
+    let lens = fields
+        .iter()
+        .map(|f| {
+            let mut max_len = max_len_args(&f.attrs)?;
+            let len = ty_len(&f.ty, &mut max_len)?;
+            if let Some(extra) = max_len.front() {
+                return Err(syn::Error::new(
+                    extra.span(),
+                    "max_len was given more lengths than variable sized types",
+                ));
+            }
+            Ok(len)
+        })
+        .collect::<syn::Result<Vec<TokenStream2>>>()?;
+    Ok(quote! { 0 #(+ #lens)* })
+}
+
+// Parses `#[max_len(<len>, ...)]` into a list of lengths, if given.
+fn max_len_args(attrs: &[syn::Attribute]) -> syn::Result<VecDeque<Expr>> {
+    match attrs.iter().find(|a| a.path.is_ident("max_len")) {
+        None => Ok(VecDeque::new()),
+        Some(attr) => Ok(attr
+            .parse_args_with(Punctuated::<Expr, Comma>::parse_terminated)?
+            .into_iter()
+            .collect()),
+    }
+}
+
+fn ty_len(ty: &Type, max_len: &mut VecDeque<Expr>) -> syn::Result<TokenStream2> {
+    match ty {
+        Type::Array(array) => {
+            let elem = ty_len(&array.elem, max_len)?;
+            let len = &array.len;
+            Ok(quote! { (#elem * #len) })
+        }
+        Type::Tuple(tuple) => {
+            let elems = tuple
+                .elems
+                .iter()
+                .map(|elem| ty_len(elem, max_len))
+                .collect::<syn::Result<Vec<TokenStream2>>>()?;
+            Ok(quote! { (0 #(+ #elems)*) })
+        }
+        Type::Path(ty_path) => {
+            let segment = match ty_path.path.segments.last() {
+                None => return Err(syn::Error::new(ty.span(), "invalid type")),
+                Some(s) => s,
+            };
+            match segment.ident.to_string().as_str() {
+                "bool" | "u8" | "i8" => Ok(quote! { 1 }),
+                "u16" | "i16" => Ok(quote! { 2 }),
+                "u32" | "i32" | "f32" => Ok(quote! { 4 }),
+                "u64" | "i64" | "f64" => Ok(quote! { 8 }),
+                "u128" | "i128" => Ok(quote! { 16 }),
+                "Pubkey" => Ok(quote! { 32 }),
+                "String" => {
+                    let len = next_max_len(ty, max_len)?;
+                    Ok(quote! { (4 + #len) })
+                }
+                "Vec" => {
+                    let len = next_max_len(ty, max_len)?;
+                    let inner = ty_len(&generic_arg(segment)?, max_len)?;
+                    Ok(quote! { (4 + #inner * #len) })
+                }
+                "Option" => {
+                    let inner = ty_len(&generic_arg(segment)?, max_len)?;
+                    Ok(quote! { (1 + #inner) })
+                }
+                "Box" => ty_len(&generic_arg(segment)?, max_len),
+                _ => Ok(quote! { <#ty as anchor_lang::Space>::INIT_SPACE }),
+            }
+        }
+        _ => Err(syn::Error::new(
+            ty.span(),
+            "InitSpace cannot be derived for this type",
+        )),
+    }
+}
+
+fn next_max_len(ty: &Type, max_len: &mut VecDeque<Expr>) -> syn::Result<Expr> {
+    max_len.pop_front().ok_or_else(|| {
+        syn::Error::new(
+            ty.span(),
+            "max_len must be provided for variable sized types",
+        )
+    })
+}
+
+// Returns the single generic type argument of the given path segment,
+// e.g., `T` in `Vec<T>`.
+fn generic_arg(segment: &syn::PathSegment) -> syn::Result<Type> {
+    if let PathArguments::AngleBracketed(args) = &segment.arguments {
+        if let Some(GenericArgument::Type(ty)) = args.args.first() {
+            return Ok(ty.clone());
+        }
+    }
+    Err(syn::Error::new(
+        segment.span(),
+        "expected a single generic type argument",
+    ))
+}
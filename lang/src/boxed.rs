@@ -1,18 +1,34 @@
-use crate::{Accounts, AccountsExit, ToAccountInfos, ToAccountMetas};
+use crate::{Accounts, AccountsClose, AccountsExit, AccountsInit, ToAccountInfos, ToAccountMetas};
 use solana_program::account_info::AccountInfo;
 use solana_program::entrypoint::ProgramResult;
 use solana_program::instruction::AccountMeta;
 use solana_program::program_error::ProgramError;
 use solana_program::pubkey::Pubkey;
+use std::collections::BTreeMap;
 use std::ops::Deref;
 
+// Boxed accounts are deserialized in their own stack frame and moved
+// straight to the heap, so that they never take up space in the frame of the
+// `try_accounts` of the struct holding them.
 impl<'info, T: Accounts<'info>> Accounts<'info> for Box<T> {
+    #[inline(never)]
     fn try_accounts(
         program_id: &Pubkey,
         accounts: &mut &[AccountInfo<'info>],
         ix_data: &[u8],
+        bumps: &mut BTreeMap<String, u8>,
     ) -> Result<Self, ProgramError> {
-        T::try_accounts(program_id, accounts, ix_data).map(Box::new)
+        T::try_accounts(program_id, accounts, ix_data, bumps).map(Box::new)
+    }
+}
+
+impl<'info, T: AccountsInit<'info>> AccountsInit<'info> for Box<T> {
+    #[inline(never)]
+    fn try_accounts_init(
+        program_id: &Pubkey,
+        accounts: &mut &[AccountInfo<'info>],
+    ) -> Result<Self, ProgramError> {
+        T::try_accounts_init(program_id, accounts).map(Box::new)
     }
 }
 
@@ -22,6 +38,12 @@ impl<'info, T: AccountsExit<'info>> AccountsExit<'info> for Box<T> {
     }
 }
 
+impl<'info, T: AccountsClose<'info>> AccountsClose<'info> for Box<T> {
+    fn close(&self, sol_destination: AccountInfo<'info>) -> ProgramResult {
+        T::close(self, sol_destination)
+    }
+}
+
 impl<'info, T: ToAccountInfos<'info>> ToAccountInfos<'info> for Box<T> {
     fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
         T::to_account_infos(self)
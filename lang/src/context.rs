@@ -1,7 +1,10 @@
 use crate::{Accounts, ToAccountInfos, ToAccountMetas};
 use solana_program::account_info::AccountInfo;
 use solana_program::instruction::AccountMeta;
+use solana_program::program_error::ProgramError;
 use solana_program::pubkey::Pubkey;
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
 
 /// Provides non-argument inputs to the program.
 pub struct Context<'a, 'b, 'c, 'info, T> {
@@ -12,6 +15,9 @@ pub struct Context<'a, 'b, 'c, 'info, T> {
     /// Remaining accounts given but not deserialized or validated.
     /// Be very careful when using this directly.
     pub remaining_accounts: &'c [AccountInfo<'info>],
+    /// Bump seeds found while validating `seeds` constraints declared with a
+    /// bare `bump`, keyed by account field name.
+    pub bumps: BTreeMap<String, u8>,
 }
 
 impl<'a, 'b, 'c, 'info, T: Accounts<'info>> Context<'a, 'b, 'c, 'info, T> {
@@ -19,13 +25,65 @@ impl<'a, 'b, 'c, 'info, T: Accounts<'info>> Context<'a, 'b, 'c, 'info, T> {
         program_id: &'a Pubkey,
         accounts: &'b mut T,
         remaining_accounts: &'c [AccountInfo<'info>],
+        bumps: BTreeMap<String, u8>,
     ) -> Self {
         Self {
             program_id,
             accounts,
             remaining_accounts,
+            bumps,
         }
     }
+
+    /// Returns an iterator that deserializes the remaining accounts into `A`,
+    /// applying the same validation as declared accounts.
+    pub fn remaining_accounts_iter<A: Accounts<'info>>(
+        &self,
+    ) -> RemainingAccounts<'a, 'c, 'info, A> {
+        RemainingAccounts::new(self.program_id, self.remaining_accounts)
+    }
+}
+
+/// Iterator deserializing a variable length list of accounts, e.g.,
+/// `ctx.remaining_accounts`, into a type implementing `Accounts`.
+///
+/// Unlike declared accounts, mutations aren't persisted automatically.
+/// To do so, call `AccountsExit::exit` on each item after mutating it.
+pub struct RemainingAccounts<'a, 'c, 'info, A> {
+    program_id: &'a Pubkey,
+    accounts: &'c [AccountInfo<'info>],
+    _phantom: PhantomData<A>,
+}
+
+impl<'a, 'c, 'info, A: Accounts<'info>> RemainingAccounts<'a, 'c, 'info, A> {
+    pub fn new(program_id: &'a Pubkey, accounts: &'c [AccountInfo<'info>]) -> Self {
+        Self {
+            program_id,
+            accounts,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Accounts not yet deserialized.
+    pub fn remaining(&self) -> &'c [AccountInfo<'info>] {
+        self.accounts
+    }
+}
+
+impl<'a, 'c, 'info, A: Accounts<'info>> Iterator for RemainingAccounts<'a, 'c, 'info, A> {
+    type Item = Result<A, ProgramError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.accounts.is_empty() {
+            return None;
+        }
+        Some(A::try_accounts(
+            self.program_id,
+            &mut self.accounts,
+            &[],
+            &mut BTreeMap::new(),
+        ))
+    }
 }
 
 /// Context specifying non-argument inputs for cross-program-invocations.
@@ -70,13 +128,18 @@ where
         self
     }
 
+    /// Appends the given accounts to the instruction after `accounts`, keeping
+    /// their writable and signer flags, e.g., for callees reading accounts
+    /// beyond their `Accounts` struct.
     pub fn with_remaining_accounts(mut self, ra: Vec<AccountInfo<'info>>) -> Self {
         self.remaining_accounts = ra;
         self
     }
 }
 
-impl<'info, T: Accounts<'info>> ToAccountInfos<'info> for CpiContext<'_, '_, '_, 'info, T> {
+impl<'info, T: ToAccountMetas + ToAccountInfos<'info>> ToAccountInfos<'info>
+    for CpiContext<'_, '_, '_, 'info, T>
+{
     fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
         let mut infos = self.accounts.to_account_infos();
         infos.extend_from_slice(&self.remaining_accounts);
@@ -85,7 +148,9 @@ impl<'info, T: Accounts<'info>> ToAccountInfos<'info> for CpiContext<'_, '_, '_,
     }
 }
 
-impl<'info, T: Accounts<'info>> ToAccountMetas for CpiContext<'_, '_, '_, 'info, T> {
+impl<'info, T: ToAccountMetas + ToAccountInfos<'info>> ToAccountMetas
+    for CpiContext<'_, '_, '_, 'info, T>
+{
     fn to_account_metas(&self, is_signer: Option<bool>) -> Vec<AccountMeta> {
         let mut metas = self.accounts.to_account_metas(is_signer);
         metas.append(
@@ -144,6 +209,11 @@ impl<'a, 'b, 'c, 'info, T: Accounts<'info>> CpiStateContext<'a, 'b, 'c, 'info, T
         self
     }
 
+    pub fn with_remaining_accounts(mut self, ra: Vec<AccountInfo<'info>>) -> Self {
+        self.cpi_ctx = self.cpi_ctx.with_remaining_accounts(ra);
+        self
+    }
+
     pub fn program(&self) -> &AccountInfo<'info> {
         &self.cpi_ctx.program
     }
@@ -162,7 +232,7 @@ impl<'a, 'b, 'c, 'info, T: Accounts<'info>> ToAccountMetas
             false => AccountMeta::new_readonly(*self.state.key, false),
             true => AccountMeta::new(*self.state.key, false),
         }];
-        metas.append(&mut self.cpi_ctx.accounts.to_account_metas(is_signer));
+        metas.append(&mut self.cpi_ctx.to_account_metas(is_signer));
         metas
     }
 }
@@ -171,9 +241,8 @@ impl<'a, 'b, 'c, 'info, T: Accounts<'info>> ToAccountInfos<'info>
     for CpiStateContext<'a, 'b, 'c, 'info, T>
 {
     fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
-        let mut infos = self.cpi_ctx.accounts.to_account_infos();
+        let mut infos = self.cpi_ctx.to_account_infos();
         infos.push(self.state.clone());
-        infos.push(self.cpi_ctx.program.clone());
         infos
     }
 }
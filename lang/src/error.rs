@@ -1,75 +1,315 @@
-use crate::error;
-
-// Error codes that can be returned by internal framework code.
-#[error(offset = 0)]
-pub enum ErrorCode {
-    // Instructions.
-    #[msg("8 byte instruction identifier not provided")]
-    InstructionMissing = 100,
-    #[msg("Fallback functions are not supported")]
-    InstructionFallbackNotFound,
-    #[msg("The program could not deserialize the given instruction")]
-    InstructionDidNotDeserialize,
-    #[msg("The program could not serialize the given instruction")]
-    InstructionDidNotSerialize,
-
-    // IDL instructions.
-    #[msg("The program was compiled without idl instructions")]
-    IdlInstructionStub = 120,
-    #[msg("Invalid program given to the IDL instruction")]
-    IdlInstructionInvalidProgram,
-
-    // Constraints.
-    #[msg("A mut constraint was violated")]
-    ConstraintMut = 140,
-    #[msg("A has one constraint was violated")]
-    ConstraintHasOne,
-    #[msg("A signer constraint as violated")]
-    ConstraintSigner,
-    #[msg("A raw constraint was violated")]
-    ConstraintRaw,
-    #[msg("An owner constraint was violated")]
-    ConstraintOwner,
-    #[msg("A rent exemption constraint was violated")]
-    ConstraintRentExempt,
-    #[msg("A seeds constraint was violated")]
-    ConstraintSeeds,
-    #[msg("An executable constraint was violated")]
-    ConstraintExecutable,
-    #[msg("A state constraint was violated")]
-    ConstraintState,
-    #[msg("An associated constraint was violated")]
-    ConstraintAssociated,
-    #[msg("An associated init constraint was violated")]
-    ConstraintAssociatedInit,
-    #[msg("A close constraint was violated")]
-    ConstraintClose,
-    #[msg("An address constraint was violated")]
-    ConstraintAddress,
-
-    // Accounts.
-    #[msg("The account discriminator was already set on this account")]
-    AccountDiscriminatorAlreadySet = 160,
-    #[msg("No 8 byte discriminator was found on the account")]
-    AccountDiscriminatorNotFound,
-    #[msg("8 byte discriminator did not match what was expected")]
-    AccountDiscriminatorMismatch,
-    #[msg("Failed to deserialize the account")]
-    AccountDidNotDeserialize,
-    #[msg("Failed to serialize the account")]
-    AccountDidNotSerialize,
-    #[msg("Not enough account keys given to the instruction")]
-    AccountNotEnoughKeys,
-    #[msg("The given account is not mutable")]
-    AccountNotMutable,
-    #[msg("The given account is not owned by the executing program")]
-    AccountNotProgramOwned,
-
-    // State.
-    #[msg("The given state account does not have the correct address")]
-    StateInvalidAddress = 180,
-
-    // Used for APIs that shouldn't be used anymore.
-    #[msg("The API being used is deprecated and should no longer be used")]
-    Deprecated = 299,
+//! Error types returned by the framework and by programs built with it.
+
+use solana_program::msg;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use std::fmt::Display;
+
+pub use codes::ErrorCode;
+
+mod codes {
+    use crate::error;
+
+    // Error codes that can be returned by internal framework code.
+    #[error(offset = 0)]
+    pub enum ErrorCode {
+        // Instructions.
+        #[msg("8 byte instruction identifier not provided")]
+        InstructionMissing = 100,
+        #[msg("Fallback functions are not supported")]
+        InstructionFallbackNotFound,
+        #[msg("The program could not deserialize the given instruction")]
+        InstructionDidNotDeserialize,
+        #[msg("The program could not serialize the given instruction")]
+        InstructionDidNotSerialize,
+        #[msg("The invoked program did not set any return data")]
+        ReturnDataNotSet,
+        #[msg("The return data could not be deserialized")]
+        ReturnDataDidNotDeserialize,
+        #[msg("The instruction was invoked reentrantly")]
+        InstructionReentrant,
+
+        // IDL instructions.
+        #[msg("The program was compiled without idl instructions")]
+        IdlInstructionStub = 120,
+        #[msg("Invalid program given to the IDL instruction")]
+        IdlInstructionInvalidProgram,
+
+        // Constraints.
+        #[msg("A mut constraint was violated")]
+        ConstraintMut = 140,
+        #[msg("A has one constraint was violated")]
+        ConstraintHasOne,
+        #[msg("A signer constraint as violated")]
+        ConstraintSigner,
+        #[msg("A raw constraint was violated")]
+        ConstraintRaw,
+        #[msg("An owner constraint was violated")]
+        ConstraintOwner,
+        #[msg("A rent exemption constraint was violated")]
+        ConstraintRentExempt,
+        #[msg("A seeds constraint was violated")]
+        ConstraintSeeds,
+        #[msg("An executable constraint was violated")]
+        ConstraintExecutable,
+        #[msg("A state constraint was violated")]
+        ConstraintState,
+        #[msg("An associated constraint was violated")]
+        ConstraintAssociated,
+        #[msg("An associated init constraint was violated")]
+        ConstraintAssociatedInit,
+        #[msg("A close constraint was violated")]
+        ConstraintClose,
+        #[msg("An address constraint was violated")]
+        ConstraintAddress,
+        #[msg("A space constraint was violated")]
+        ConstraintSpace,
+        #[msg("A token mint constraint was violated")]
+        ConstraintTokenMint,
+        #[msg("A token owner constraint was violated")]
+        ConstraintTokenOwner,
+        #[msg("A realloc constraint was violated")]
+        ConstraintRealloc,
+        #[msg("An associated token constraint was violated")]
+        ConstraintAssociatedToken,
+        #[msg("A variant constraint was violated")]
+        ConstraintVariant,
+        #[msg("An instruction sysvar constraint was violated")]
+        ConstraintInstructionSysvar,
+
+        // Accounts.
+        #[msg("The account discriminator was already set on this account")]
+        AccountDiscriminatorAlreadySet = 160,
+        #[msg("No 8 byte discriminator was found on the account")]
+        AccountDiscriminatorNotFound,
+        #[msg("8 byte discriminator did not match what was expected")]
+        AccountDiscriminatorMismatch,
+        #[msg("Failed to deserialize the account")]
+        AccountDidNotDeserialize,
+        #[msg("Failed to serialize the account")]
+        AccountDidNotSerialize,
+        #[msg("Not enough account keys given to the instruction")]
+        AccountNotEnoughKeys,
+        #[msg("The given account is not mutable")]
+        AccountNotMutable,
+        #[msg("The given account is not owned by the executing program")]
+        AccountNotProgramOwned,
+        #[msg("The given account did not sign")]
+        AccountNotSigner,
+        #[msg("Program ID was not as expected")]
+        InvalidProgramId,
+        #[msg("Program account is not executable")]
+        InvalidProgramExecutable,
+        #[msg("The given account is not owned by the system program")]
+        AccountNotSystemOwned,
+        #[msg("The given account is owned by a different program than expected")]
+        AccountOwnedByWrongProgram,
+        #[msg("The account's version is newer than the program supports")]
+        AccountVersionNotSupported,
+
+        // State.
+        #[msg("The given state account does not have the correct address")]
+        StateInvalidAddress = 180,
+
+        // Require.
+        #[msg("A require_eq expression was violated")]
+        RequireEqViolated = 190,
+        #[msg("A require_neq expression was violated")]
+        RequireNeqViolated,
+        #[msg("A require_keys_eq expression was violated")]
+        RequireKeysEqViolated,
+        #[msg("A require_keys_neq expression was violated")]
+        RequireKeysNeqViolated,
+        #[msg("A require_gt expression was violated")]
+        RequireGtViolated,
+        #[msg("A require_gte expression was violated")]
+        RequireGteViolated,
+
+        // Events.
+        #[msg("The event discriminator did not match what was expected")]
+        EventDiscriminatorMismatch = 200,
+        #[msg("Failed to deserialize the event")]
+        EventDidNotDeserialize,
+
+        // Mint and token account constraints, continuing the constraints above.
+        #[msg("A mint mint authority constraint was violated")]
+        ConstraintMintMintAuthority = 210,
+        #[msg("A mint freeze authority constraint was violated")]
+        ConstraintMintFreezeAuthority,
+        #[msg("A mint decimals constraint was violated")]
+        ConstraintMintDecimals,
+        #[msg("A mint token program constraint was violated")]
+        ConstraintMintTokenProgram,
+        #[msg("A token delegate constraint was violated")]
+        ConstraintTokenDelegate,
+        #[msg("A token close authority constraint was violated")]
+        ConstraintTokenCloseAuthority,
+
+        // Used for APIs that shouldn't be used anymore.
+        #[msg("The API being used is deprecated and should no longer be used")]
+        Deprecated = 299,
+    }
+}
+
+/// Result type for instruction handlers and CPI helpers, e.g.,
+/// `anchor_lang::error::Result<()>`.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Error wrapping a `ProgramError` together with context describing where it
+/// came from, e.g., the name of an account or the keys that didn't match.
+///
+/// The context is logged when the error is converted back into a
+/// `ProgramError`, which happens at the latest when the program's entrypoint
+/// returns. Any framework or user defined error code can be converted into an
+/// `Error` via `From`, in which case its message is attached, e.g.,
+///
+/// ```ignore
+/// return Err(Error::from(MyError::InvalidOwner)
+///     .with_account_name("vault")
+///     .with_pubkeys((vault.owner, *authority.key)));
+/// ```
+#[derive(Debug)]
+pub struct Error {
+    program_error: ProgramError,
+    messages: Vec<String>,
+    account_name: Option<String>,
+    pubkeys: Option<(Pubkey, Pubkey)>,
+    source: Option<(&'static str, u32)>,
+}
+
+impl Error {
+    /// Attaches a message describing the failure, e.g., the operation that
+    /// was being performed. Messages attached earlier are kept and displayed
+    /// after it, e.g., `token::transfer CPI failed: insufficient funds`.
+    pub fn with_message(mut self, message: impl ToString) -> Self {
+        self.messages.push(message.to_string());
+        self
+    }
+
+    /// Attaches the name of the account that caused the error.
+    pub fn with_account_name(mut self, account_name: impl ToString) -> Self {
+        self.account_name = Some(account_name.to_string());
+        self
+    }
+
+    /// Attaches a pair of keys that were expected to match, but didn't.
+    pub fn with_pubkeys(mut self, pubkeys: (Pubkey, Pubkey)) -> Self {
+        self.pubkeys = Some(pubkeys);
+        self
+    }
+
+    /// Attaches the file and line the error was raised at.
+    pub fn with_source(mut self, file: &'static str, line: u32) -> Self {
+        self.source = Some((file, line));
+        self
+    }
+
+    /// Returns the wrapped `ProgramError`, without logging any context.
+    pub fn program_error(&self) -> &ProgramError {
+        &self.program_error
+    }
+
+    /// Logs the error along with any context attached to it.
+    pub fn log(&self) {
+        msg!("Error: {}", self);
+        if let Some(account_name) = &self.account_name {
+            msg!("Error caused by account: {}", account_name);
+        }
+        if let Some((left, right)) = &self.pubkeys {
+            msg!("Left: {}", left);
+            msg!("Right: {}", right);
+        }
+        if let Some((file, line)) = &self.source {
+            msg!("Error raised at {}:{}", file, line);
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.messages.is_empty() {
+            return Display::fmt(&self.program_error, fmt);
+        }
+        let messages: Vec<&str> = self.messages.iter().rev().map(String::as_str).collect();
+        write!(fmt, "{} ({})", messages.join(": "), self.program_error)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ProgramError> for Error {
+    fn from(program_error: ProgramError) -> Error {
+        Error {
+            program_error,
+            messages: Vec::new(),
+            account_name: None,
+            pubkeys: None,
+            source: None,
+        }
+    }
+}
+
+impl From<Error> for ProgramError {
+    fn from(error: Error) -> ProgramError {
+        error.log();
+        error.program_error
+    }
+}
+
+/// Logs the file and line an error was raised at. Used by the `err!` and
+/// `require!` macros.
+#[doc(hidden)]
+pub fn log_error_origin(file: &str, line: u32) {
+    msg!("Error raised at {}:{}", file, line);
+}
+
+/// Logs the name of the account that violated a constraint, along with the
+/// location of its `Accounts` struct, and returns the given error. Used by
+/// the code generated for account constraints.
+#[doc(hidden)]
+pub fn account_error(
+    error: impl Into<Error>,
+    account_name: &str,
+    file: &'static str,
+    line: u32,
+) -> ProgramError {
+    error
+        .into()
+        .with_account_name(account_name)
+        .with_source(file, line)
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_context() {
+        let error = Error::from(ErrorCode::ConstraintOwner)
+            .with_account_name("vault")
+            .with_pubkeys((Pubkey::default(), Pubkey::default()));
+        assert_eq!(
+            error.to_string(),
+            "An owner constraint was violated (Custom program error: 0x90)"
+        );
+        assert_eq!(
+            ProgramError::from(error),
+            ProgramError::Custom(ErrorCode::ConstraintOwner as u32)
+        );
+
+        let error = Error::from(ProgramError::InvalidArgument);
+        assert_eq!(error.program_error(), &ProgramError::InvalidArgument);
+        assert_eq!(ProgramError::from(error), ProgramError::InvalidArgument);
+    }
+
+    #[test]
+    fn test_error_message_chain() {
+        let error =
+            Error::from(ErrorCode::ConstraintOwner).with_message("token::transfer CPI failed");
+        assert_eq!(
+            error.to_string(),
+            "token::transfer CPI failed: An owner constraint was violated (Custom program error: 0x90)"
+        );
+    }
 }
@@ -0,0 +1,64 @@
+//! Events emitted via self-CPI with [`emit_cpi!`](../macro.emit_cpi.html),
+//! which, unlike logs, can't be truncated.
+//!
+//! The event is passed as the data of an instruction the program invokes on
+//! itself, signed by the event authority so that clients can trust events
+//! found in the transaction's inner instructions were emitted by the program.
+//!
+//! The `decode_event` functions decode events of other programs on-chain,
+//! e.g., from the return data of a CPI callee or from another instruction in
+//! the transaction.
+
+use crate::error::ErrorCode;
+use crate::Event;
+use solana_program::account_info::AccountInfo;
+use solana_program::program::get_return_data;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::sysvar::instructions::load_instruction_at_checked;
+
+// Instruction tag prefixing the data of event self-CPIs. Like the IDL
+// instruction, it's dispatched outside the main program's instruction enum.
+//
+// Sha256(anchor:event)[..8];
+pub const EVENT_IX_TAG: u64 = 0x1d9acb512ea545e4;
+pub const EVENT_IX_TAG_LE: [u8; 8] = EVENT_IX_TAG.to_le_bytes();
+
+/// Seed of the program derived address signing event self-CPIs.
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+/// Decodes an event of type `T` from its serialized data, i.e., the
+/// discriminator followed by the Borsh serialized event. The data of an
+/// event self-CPI, prefixed with [`EVENT_IX_TAG_LE`], is accepted as well.
+pub fn decode_event<T: Event>(data: &[u8]) -> Result<T, ProgramError> {
+    let data = data.strip_prefix(&EVENT_IX_TAG_LE[..]).unwrap_or(data);
+    let data = data
+        .strip_prefix(T::discriminator())
+        .ok_or(ErrorCode::EventDiscriminatorMismatch)?;
+    T::try_from_slice(data).map_err(|_| ErrorCode::EventDidNotDeserialize.into())
+}
+
+/// Decodes an event of type `T` from the return data set by `program_id`,
+/// e.g., by a program invoked via CPI that returns the event it emitted.
+pub fn decode_event_from_return_data<T: Event>(program_id: &Pubkey) -> Result<T, ProgramError> {
+    let (return_program_id, data) = get_return_data().ok_or(ErrorCode::ReturnDataNotSet)?;
+    if return_program_id != *program_id {
+        return Err(ErrorCode::ReturnDataNotSet.into());
+    }
+    decode_event(&data)
+}
+
+/// Decodes an event of type `T` from the data of the instruction at `index`
+/// of the current transaction, read from the instructions sysvar. The
+/// instruction must be an invocation of `program_id`.
+pub fn decode_event_from_instruction<T: Event>(
+    instructions_sysvar: &AccountInfo,
+    index: usize,
+    program_id: &Pubkey,
+) -> Result<T, ProgramError> {
+    let ix = load_instruction_at_checked(index, instructions_sysvar)?;
+    if ix.program_id != *program_id {
+        return Err(ErrorCode::InvalidProgramId.into());
+    }
+    decode_event(&ix.data)
+}
@@ -66,8 +66,9 @@ pub struct IdlCreateBuffer<'info> {
 // Accounts for upgrading the canonical IdlAccount with the buffer.
 #[derive(Accounts)]
 pub struct IdlSetBuffer<'info> {
-    // The buffer with the new idl data.
-    #[account(mut, constraint = buffer.authority == idl.authority)]
+    // The buffer with the new idl data. Passing the idl account itself as the
+    // buffer leaves it unchanged.
+    #[account(mut, allow_duplicate, constraint = buffer.authority == idl.authority)]
     pub buffer: ProgramAccount<'info, IdlAccount>,
     // The idl account to be updated with the buffer's data.
     #[account(mut, has_one = authority)]
@@ -0,0 +1,89 @@
+use crate::error::ErrorCode;
+use crate::{Accounts, AccountsExit, Ids, ToAccountInfo, ToAccountInfos, ToAccountMetas};
+use solana_program::account_info::AccountInfo;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::instruction::AccountMeta;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+/// Container for an executable program account, whose address is checked
+/// against the programs implementing a common interface, `T::ids()`, upon
+/// deserialization, e.g., `Interface<'info, TokenInterface>`.
+#[derive(Clone)]
+pub struct Interface<'info, T: Ids + Clone> {
+    info: AccountInfo<'info>,
+    _phantom: PhantomData<T>,
+}
+
+impl<'info, T: Ids + Clone> Interface<'info, T> {
+    fn new(info: AccountInfo<'info>) -> Interface<'info, T> {
+        Self {
+            info,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Deserializes the given `info` into an `Interface`.
+    pub fn try_from(info: &AccountInfo<'info>) -> Result<Interface<'info, T>, ProgramError> {
+        if !T::ids().contains(info.key) {
+            return Err(ErrorCode::InvalidProgramId.into());
+        }
+        if !info.executable {
+            return Err(ErrorCode::InvalidProgramExecutable.into());
+        }
+        Ok(Interface::new(info.clone()))
+    }
+}
+
+impl<'info, T: Ids + Clone> Accounts<'info> for Interface<'info, T> {
+    fn try_accounts(
+        _program_id: &Pubkey,
+        accounts: &mut &[AccountInfo<'info>],
+        _ix_data: &[u8],
+        _bumps: &mut BTreeMap<String, u8>,
+    ) -> Result<Self, ProgramError> {
+        if accounts.is_empty() {
+            return Err(ErrorCode::AccountNotEnoughKeys.into());
+        }
+        let account = &accounts[0];
+        *accounts = &accounts[1..];
+        Interface::try_from(account)
+    }
+}
+
+impl<'info, T: Ids + Clone> AccountsExit<'info> for Interface<'info, T> {
+    fn exit(&self, _program_id: &Pubkey) -> ProgramResult {
+        // No-op.
+        Ok(())
+    }
+}
+
+impl<'info, T: Ids + Clone> ToAccountMetas for Interface<'info, T> {
+    fn to_account_metas(&self, is_signer: Option<bool>) -> Vec<AccountMeta> {
+        let is_signer = is_signer.unwrap_or(self.info.is_signer);
+        vec![AccountMeta::new_readonly(*self.info.key, is_signer)]
+    }
+}
+
+impl<'info, T: Ids + Clone> ToAccountInfos<'info> for Interface<'info, T> {
+    fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+        vec![self.info.clone()]
+    }
+}
+
+impl<'info, T: Ids + Clone> ToAccountInfo<'info> for Interface<'info, T> {
+    fn to_account_info(&self) -> AccountInfo<'info> {
+        self.info.clone()
+    }
+}
+
+impl<'info, T: Ids + Clone> Deref for Interface<'info, T> {
+    type Target = AccountInfo<'info>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.info
+    }
+}
@@ -0,0 +1,116 @@
+use crate::error::ErrorCode;
+use crate::{
+    AccountDeserialize, Accounts, AccountsExit, Owners, ToAccountInfo, ToAccountInfos,
+    ToAccountMetas,
+};
+use solana_program::account_info::AccountInfo;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::instruction::AccountMeta;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use std::collections::BTreeMap;
+use std::ops::{Deref, DerefMut};
+
+/// Container for an account owned by any one of `T::owners()`, e.g., a token
+/// account owned by either SPL Token or Token-2022.
+#[derive(Clone)]
+pub struct InterfaceAccount<'a, T: AccountDeserialize + Owners + Clone> {
+    info: AccountInfo<'a>,
+    account: Box<T>,
+}
+
+impl<'a, T: AccountDeserialize + Owners + Clone> InterfaceAccount<'a, T> {
+    pub fn new(info: AccountInfo<'a>, account: Box<T>) -> InterfaceAccount<'a, T> {
+        Self { info, account }
+    }
+
+    /// Deserializes the given `info` into an `InterfaceAccount`, checking
+    /// its owner is one of `T::owners()`.
+    #[inline(never)]
+    pub fn try_from(info: &AccountInfo<'a>) -> Result<InterfaceAccount<'a, T>, ProgramError> {
+        if !T::owners().contains(info.owner) {
+            return Err(ErrorCode::AccountOwnedByWrongProgram.into());
+        }
+        let mut data: &[u8] = &info.try_borrow_data()?;
+        Ok(InterfaceAccount::new(
+            info.clone(),
+            Box::new(T::try_deserialize(&mut data)?),
+        ))
+    }
+
+    /// Reloads the account from storage. This is useful, for example, when
+    /// observing side effects after CPI.
+    pub fn reload(&self) -> Result<InterfaceAccount<'a, T>, ProgramError> {
+        Self::try_from(&self.info)
+    }
+}
+
+impl<'info, T> Accounts<'info> for InterfaceAccount<'info, T>
+where
+    T: AccountDeserialize + Owners + Clone,
+{
+    #[inline(never)]
+    fn try_accounts(
+        _program_id: &Pubkey,
+        accounts: &mut &[AccountInfo<'info>],
+        _ix_data: &[u8],
+        _bumps: &mut BTreeMap<String, u8>,
+    ) -> Result<Self, ProgramError> {
+        if accounts.is_empty() {
+            return Err(ErrorCode::AccountNotEnoughKeys.into());
+        }
+        let account = &accounts[0];
+        *accounts = &accounts[1..];
+        InterfaceAccount::try_from(account)
+    }
+}
+
+impl<'info, T: AccountDeserialize + Owners + Clone> ToAccountMetas for InterfaceAccount<'info, T> {
+    fn to_account_metas(&self, is_signer: Option<bool>) -> Vec<AccountMeta> {
+        let is_signer = is_signer.unwrap_or(self.info.is_signer);
+        let meta = match self.info.is_writable {
+            false => AccountMeta::new_readonly(*self.info.key, is_signer),
+            true => AccountMeta::new(*self.info.key, is_signer),
+        };
+        vec![meta]
+    }
+}
+
+impl<'info, T: AccountDeserialize + Owners + Clone> ToAccountInfos<'info>
+    for InterfaceAccount<'info, T>
+{
+    fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+        vec![self.info.clone()]
+    }
+}
+
+impl<'info, T: AccountDeserialize + Owners + Clone> ToAccountInfo<'info>
+    for InterfaceAccount<'info, T>
+{
+    fn to_account_info(&self) -> AccountInfo<'info> {
+        self.info.clone()
+    }
+}
+
+impl<'a, T: AccountDeserialize + Owners + Clone> Deref for InterfaceAccount<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.account
+    }
+}
+
+impl<'a, T: AccountDeserialize + Owners + Clone> DerefMut for InterfaceAccount<'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.account
+    }
+}
+
+impl<'info, T: AccountDeserialize + Owners + Clone> AccountsExit<'info>
+    for InterfaceAccount<'info, T>
+{
+    fn exit(&self, _program_id: &Pubkey) -> ProgramResult {
+        // No-op, since the account isn't owned by the current program.
+        Ok(())
+    }
+}
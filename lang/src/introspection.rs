@@ -0,0 +1,116 @@
+//! Helpers for inspecting the other instructions of the current transaction
+//! via the instructions sysvar, e.g., to require that an instruction is
+//! preceded by an ed25519 signature verification.
+//!
+//! Accounts structs can bind these helpers for use in constraints with the
+//! `instruction_sysvar` constraint, e.g.,
+//!
+//! ```ignore
+//! #[derive(Accounts)]
+//! pub struct Claim<'info> {
+//!     #[account(
+//!         instruction_sysvar,
+//!         constraint = prev_instruction_is(&ed25519_program::ID, &[]),
+//!     )]
+//!     pub instructions: UncheckedAccount<'info>,
+//! }
+//! ```
+//!
+//! The stack height helpers detect whether the current instruction was
+//! invoked via CPI, e.g., to guard against reentrancy. See
+//! [`non_reentrant`](crate::non_reentrant).
+
+use crate::error::ErrorCode;
+use solana_program::account_info::AccountInfo;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::instruction::{get_stack_height, Instruction, TRANSACTION_LEVEL_STACK_HEIGHT};
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::sysvar::instructions::get_instruction_relative;
+
+/// Returns the instruction before the currently executing one.
+pub fn prev_instruction(instructions_sysvar: &AccountInfo) -> Result<Instruction, ProgramError> {
+    get_instruction_relative(-1, instructions_sysvar)
+}
+
+/// Returns the instruction after the currently executing one.
+pub fn next_instruction(instructions_sysvar: &AccountInfo) -> Result<Instruction, ProgramError> {
+    get_instruction_relative(1, instructions_sysvar)
+}
+
+/// Returns true if the previous instruction invokes `program_id` with data
+/// starting with `discriminator`. An empty discriminator matches any data.
+pub fn prev_instruction_is(
+    instructions_sysvar: &AccountInfo,
+    program_id: &Pubkey,
+    discriminator: &[u8],
+) -> bool {
+    prev_instruction(instructions_sysvar)
+        .map(|ix| is_instruction(&ix, program_id, discriminator))
+        .unwrap_or(false)
+}
+
+/// Returns true if the next instruction invokes `program_id` with data
+/// starting with `discriminator`. An empty discriminator matches any data.
+pub fn next_instruction_is(
+    instructions_sysvar: &AccountInfo,
+    program_id: &Pubkey,
+    discriminator: &[u8],
+) -> bool {
+    next_instruction(instructions_sysvar)
+        .map(|ix| is_instruction(&ix, program_id, discriminator))
+        .unwrap_or(false)
+}
+
+/// Returns the height of the currently executing instruction on the call
+/// stack, i.e., `TRANSACTION_LEVEL_STACK_HEIGHT` for instructions of the
+/// transaction itself, plus one for each level of CPI.
+pub fn stack_height() -> usize {
+    get_stack_height()
+}
+
+/// Returns true if the currently executing instruction was invoked via CPI.
+pub fn is_cpi() -> bool {
+    stack_height() > TRANSACTION_LEVEL_STACK_HEIGHT
+}
+
+/// Returns true if the currently executing instruction was invoked via CPI
+/// and the transaction level instruction it descends from invokes
+/// `program_id`.
+///
+/// This is weaker than a reentrancy check: only the bottom of the call stack
+/// is visible to programs, so a program reentered through another program,
+/// e.g., `A -> B -> A` where `B` is invoked by the transaction, isn't
+/// detected. Use [`is_cpi`] to reject all CPIs.
+pub fn is_cpi_from_top_level(
+    program_id: &Pubkey,
+    instructions_sysvar: &AccountInfo,
+) -> Result<bool, ProgramError> {
+    if !is_cpi() {
+        return Ok(false);
+    }
+    let ix = get_instruction_relative(0, instructions_sysvar)?;
+    Ok(ix.program_id == *program_id)
+}
+
+/// Errors with `InstructionReentrant` if the currently executing instruction
+/// was invoked via CPI. Given the instructions sysvar, only CPIs descending
+/// from a transaction level instruction of `program_id` are rejected, see
+/// [`is_cpi_from_top_level`].
+pub fn require_non_reentrant(
+    program_id: &Pubkey,
+    instructions_sysvar: Option<&AccountInfo>,
+) -> ProgramResult {
+    let reentrant = match instructions_sysvar {
+        None => is_cpi(),
+        Some(instructions_sysvar) => is_cpi_from_top_level(program_id, instructions_sysvar)?,
+    };
+    if reentrant {
+        return Err(ErrorCode::InstructionReentrant.into());
+    }
+    Ok(())
+}
+
+fn is_instruction(ix: &Instruction, program_id: &Pubkey, discriminator: &[u8]) -> bool {
+    ix.program_id == *program_id && ix.data.starts_with(discriminator)
+}
@@ -0,0 +1,130 @@
+use crate::error::ErrorCode;
+use crate::{
+    AccountDeserialize, Accounts, AccountsClose, AccountsExit, Discriminator, ToAccountInfo,
+    ToAccountInfos, ToAccountMetas,
+};
+use solana_program::account_info::AccountInfo;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::instruction::AccountMeta;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use std::cell::Ref;
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+
+/// Read only account facilitating on demand, partial deserialization.
+///
+/// Like [`ProgramAccount`](./struct.ProgramAccount.html), the account's owner
+/// and discriminator are checked upon deserialization, but none of its data is
+/// deserialized until accessed. Either call `load` to deserialize the entire
+/// account, or use the `load_<field>` methods generated by the
+/// [`account`](./attr.account.html) attribute, e.g., `LazyMyData::load_count`,
+/// to deserialize a single field.
+///
+/// Since the account is never serialized on exit, any changes must be made
+/// via `ProgramAccount` instead.
+pub struct LazyAccount<'info, T: AccountDeserialize + Discriminator> {
+    info: AccountInfo<'info>,
+    phantom: PhantomData<&'info T>,
+}
+
+impl<'info, T: AccountDeserialize + Discriminator> LazyAccount<'info, T> {
+    fn new(info: AccountInfo<'info>) -> LazyAccount<'info, T> {
+        Self {
+            info,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Constructs a new `LazyAccount`, checking the account's discriminator.
+    #[inline(never)]
+    pub fn try_from(info: &AccountInfo<'info>) -> Result<LazyAccount<'info, T>, ProgramError> {
+        let data: &[u8] = &info.try_borrow_data()?;
+        let disc = T::discriminator();
+        if data.len() < disc.len() {
+            return Err(ErrorCode::AccountDiscriminatorNotFound.into());
+        }
+        if &data[..disc.len()] != disc {
+            return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+        }
+        Ok(LazyAccount::new(info.clone()))
+    }
+
+    /// Deserializes the entire account.
+    #[inline(never)]
+    pub fn load(&self) -> Result<T, ProgramError> {
+        let mut data: &[u8] = &self.info.try_borrow_data()?;
+        T::try_deserialize(&mut data)
+    }
+
+    /// Returns the account's data, excluding the discriminator. Used by the
+    /// generated field accessors.
+    #[doc(hidden)]
+    pub fn __data(&self) -> Result<Ref<[u8]>, ProgramError> {
+        let data = self.info.try_borrow_data()?;
+        Ok(Ref::map(data, |data| &data[T::discriminator().len()..]))
+    }
+}
+
+impl<'info, T: AccountDeserialize + Discriminator> Clone for LazyAccount<'info, T> {
+    fn clone(&self) -> Self {
+        LazyAccount::new(self.info.clone())
+    }
+}
+
+impl<'info, T: AccountDeserialize + Discriminator> Accounts<'info> for LazyAccount<'info, T> {
+    #[inline(never)]
+    fn try_accounts(
+        program_id: &Pubkey,
+        accounts: &mut &[AccountInfo<'info>],
+        _ix_data: &[u8],
+        _bumps: &mut BTreeMap<String, u8>,
+    ) -> Result<Self, ProgramError> {
+        if accounts.is_empty() {
+            return Err(ErrorCode::AccountNotEnoughKeys.into());
+        }
+        let account = &accounts[0];
+        *accounts = &accounts[1..];
+        let la = LazyAccount::try_from(account)?;
+        if la.info.owner != program_id {
+            return Err(ErrorCode::AccountNotProgramOwned.into());
+        }
+        Ok(la)
+    }
+}
+
+impl<'info, T: AccountDeserialize + Discriminator> AccountsExit<'info> for LazyAccount<'info, T> {
+    fn exit(&self, _program_id: &Pubkey) -> ProgramResult {
+        // No-op, since the account is read only.
+        Ok(())
+    }
+}
+
+impl<'info, T: AccountDeserialize + Discriminator> AccountsClose<'info> for LazyAccount<'info, T> {
+    fn close(&self, sol_destination: AccountInfo<'info>) -> ProgramResult {
+        crate::common::close(self.to_account_info(), sol_destination)
+    }
+}
+
+impl<'info, T: AccountDeserialize + Discriminator> ToAccountMetas for LazyAccount<'info, T> {
+    fn to_account_metas(&self, is_signer: Option<bool>) -> Vec<AccountMeta> {
+        let is_signer = is_signer.unwrap_or(self.info.is_signer);
+        let meta = match self.info.is_writable {
+            false => AccountMeta::new_readonly(*self.info.key, is_signer),
+            true => AccountMeta::new(*self.info.key, is_signer),
+        };
+        vec![meta]
+    }
+}
+
+impl<'info, T: AccountDeserialize + Discriminator> ToAccountInfos<'info> for LazyAccount<'info, T> {
+    fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+        vec![self.info.clone()]
+    }
+}
+
+impl<'info, T: AccountDeserialize + Discriminator> ToAccountInfo<'info> for LazyAccount<'info, T> {
+    fn to_account_info(&self) -> AccountInfo<'info> {
+        self.info.clone()
+    }
+}
@@ -29,6 +29,7 @@ use solana_program::entrypoint::ProgramResult;
 use solana_program::instruction::AccountMeta;
 use solana_program::program_error::ProgramError;
 use solana_program::pubkey::Pubkey;
+use std::collections::BTreeMap;
 use std::io::Write;
 
 mod account_info;
@@ -39,30 +40,54 @@ mod context;
 mod cpi_account;
 mod cpi_state;
 mod ctor;
-mod error;
+pub mod error;
+pub mod event;
 #[doc(hidden)]
 pub mod idl;
+mod interface;
+mod interface_account;
+pub mod introspection;
+mod lazy_account;
 mod loader;
+mod option;
+mod program;
 mod program_account;
+mod return_data;
+mod seeds;
+mod signer;
 pub mod state;
+mod system_account;
+pub mod system_program;
 mod sysvar;
+mod unchecked_account;
 mod vec;
 
-pub use crate::context::{Context, CpiContext, CpiStateContext};
+pub use crate::context::{Context, CpiContext, CpiStateContext, RemainingAccounts};
 pub use crate::cpi_account::CpiAccount;
 pub use crate::cpi_state::CpiState;
+pub use crate::interface::Interface;
+pub use crate::interface_account::InterfaceAccount;
+pub use crate::lazy_account::LazyAccount;
 pub use crate::loader::Loader;
+pub use crate::program::Program;
 pub use crate::program_account::ProgramAccount;
+pub use crate::return_data::Return;
+pub use crate::seeds::{Seeds, SignerSeeds};
+pub use crate::signer::Signer;
 pub use crate::state::ProgramState;
+pub use crate::system_account::SystemAccount;
+pub use crate::system_program::System;
 pub use crate::sysvar::Sysvar;
-pub use anchor_attribute_access_control::access_control;
+pub use crate::unchecked_account::UncheckedAccount;
+pub use anchor_attribute_access_control::{access_control, non_reentrant};
 pub use anchor_attribute_account::{account, associated, zero_copy};
 pub use anchor_attribute_error::error;
-pub use anchor_attribute_event::{emit, event};
+pub use anchor_attribute_event::{emit, emit_cpi, event, event_cpi};
 pub use anchor_attribute_interface::interface;
-pub use anchor_attribute_program::program;
+pub use anchor_attribute_program::{declare_program, program};
 pub use anchor_attribute_state::state;
 pub use anchor_derive_accounts::Accounts;
+pub use anchor_derive_space::InitSpace;
 /// Borsh is the default serialization format for instructions and accounts.
 pub use borsh::{BorshDeserialize as AnchorDeserialize, BorshSerialize as AnchorSerialize};
 pub use solana_program;
@@ -84,11 +109,14 @@ pub trait Accounts<'info>: ToAccountMetas + ToAccountInfos<'info> + Sized {
     /// `program_id` is the currently executing program. `accounts` is the
     /// set of accounts to construct the type from. For every account used,
     /// the implementation should mutate the slice, consuming the used entry
-    /// so that it cannot be used again.
+    /// so that it cannot be used again. `bumps` collects the canonical bump
+    /// seed of every program derived address found while validating, keyed
+    /// by field name, and is exposed to handlers as `ctx.bumps`.
     fn try_accounts(
         program_id: &Pubkey,
         accounts: &mut &[AccountInfo<'info>],
         ix_data: &[u8],
+        bumps: &mut BTreeMap<String, u8>,
     ) -> Result<Self, ProgramError>;
 }
 
@@ -152,6 +180,10 @@ pub trait ToAccountInfo<'info> {
 /// In most cases, one can use the default implementation provided by the
 /// [`#[account]`](./attr.account.html) attribute.
 pub trait AccountSerialize {
+    /// The number of bytes written between the discriminator and the account's
+    /// fields, i.e., the version byte of versioned accounts.
+    const VERSION_LEN: usize = 0;
+
     /// Serializes the account data into `writer`.
     fn try_serialize<W: Write>(&self, writer: &mut W) -> Result<(), ProgramError>;
 }
@@ -200,9 +232,79 @@ pub trait EventData: AnchorSerialize + Discriminator {
     fn data(&self) -> Vec<u8>;
 }
 
-/// 8 byte unique identifier for a type.
+/// Unique identifier for a type, prefixing its serialized data. 8 bytes,
+/// unless given explicitly, e.g., via `#[account(discriminator = [1])]`.
+///
+/// Implemented for all `#[account]` and `#[event]` types, so that clients
+/// can reference the discriminator directly, e.g., in a `memcmp` filter:
+///
+/// ```ignore
+/// let filter = Memcmp::new_raw_bytes(0, MyAccount::DISCRIMINATOR.to_vec());
+/// ```
 pub trait Discriminator {
-    fn discriminator() -> [u8; 8];
+    const DISCRIMINATOR: &'static [u8];
+
+    fn discriminator() -> &'static [u8] {
+        Self::DISCRIMINATOR
+    }
+}
+
+/// Version of an account's layout, stored in the byte following the account
+/// discriminator. Implemented via `#[account(version = <n>)]`.
+pub trait Versioned {
+    fn version() -> u8;
+}
+
+/// Upgrades accounts written with an older version of a versioned account's
+/// layout. Must be implemented for all `#[account(version = <n>)]` structs
+/// with a version greater than 1.
+///
+/// Older accounts are migrated lazily, when they're deserialized, and are
+/// written back with the current layout when the instruction exits, if
+/// mutable. Any additional space must be allocated beforehand, e.g., with
+/// the `realloc` constraint.
+///
+/// ```ignore
+/// #[account(version = 2)]
+/// pub struct Data {
+///     pub authority: Pubkey,
+///     pub count: u64,
+///     pub label: String,
+/// }
+///
+/// #[derive(AnchorDeserialize)]
+/// struct DataV1 {
+///     authority: Pubkey,
+///     count: u64,
+/// }
+///
+/// impl Migrate for Data {
+///     fn migrate(version: u8, data: &mut &[u8]) -> Result<Self, ProgramError> {
+///         match version {
+///             1 => {
+///                 let v1 = DataV1::deserialize(data)?;
+///                 Ok(Data {
+///                     authority: v1.authority,
+///                     count: v1.count,
+///                     label: String::new(),
+///                 })
+///             }
+///             _ => Err(anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into()),
+///         }
+///     }
+/// }
+/// ```
+pub trait Migrate: Sized {
+    /// Deserializes `data`, written with the given older `version` of the
+    /// layout, excluding the discriminator and version byte, into the
+    /// current layout.
+    fn migrate(version: u8, data: &mut &[u8]) -> Result<Self, ProgramError>;
+}
+
+/// Calculates the maximum serialized size of a type, excluding the account
+/// discriminator. Implemented via `#[derive(InitSpace)]`.
+pub trait Space {
+    const INIT_SPACE: usize;
 }
 
 /// Bump seed for program derived addresses.
@@ -214,6 +316,23 @@ pub trait Key {
     fn key(&self) -> Pubkey;
 }
 
+/// The address of a program, used to validate `Program` accounts.
+pub trait Id {
+    fn id() -> Pubkey;
+}
+
+/// The addresses of all programs implementing a common interface, used to
+/// validate `Interface` accounts.
+pub trait Ids {
+    fn ids() -> &'static [Pubkey];
+}
+
+/// The programs allowed to own an account, used to validate
+/// `InterfaceAccount`s.
+pub trait Owners {
+    fn owners() -> &'static [Pubkey];
+}
+
 impl<'info, T> Key for T
 where
     T: ToAccountInfo<'info>,
@@ -233,11 +352,15 @@ impl Key for Pubkey {
 /// All programs should include it via `anchor_lang::prelude::*;`.
 pub mod prelude {
     pub use super::{
-        access_control, account, associated, emit, error, event, interface, program, require,
-        state, zero_copy, AccountDeserialize, AccountSerialize, Accounts, AccountsExit,
-        AccountsInit, AnchorDeserialize, AnchorSerialize, Context, CpiAccount, CpiContext,
-        CpiState, CpiStateContext, Loader, ProgramAccount, ProgramState, Sysvar, ToAccountInfo,
-        ToAccountInfos, ToAccountMetas,
+        access_control, account, associated, declare_program, emit, emit_cpi, err, error, event,
+        event_cpi, interface, non_reentrant, pda_invoke, program, require, require_eq, require_gt,
+        require_gte, require_keys_eq, require_keys_neq, require_neq, state, zero_copy,
+        AccountDeserialize, AccountSerialize, Accounts, AccountsExit, AccountsInit,
+        AnchorDeserialize, AnchorSerialize, Context, CpiAccount, CpiContext, CpiState,
+        CpiStateContext, Discriminator, Id, Ids, InitSpace, Interface, InterfaceAccount,
+        LazyAccount, Loader, Migrate, Owners, Program, ProgramAccount, ProgramState, Signer, Space,
+        System, SystemAccount, Sysvar, ToAccountInfo, ToAccountInfos, ToAccountMetas,
+        UncheckedAccount, Versioned,
     };
 
     pub use borsh;
@@ -268,7 +391,7 @@ pub mod __private {
     use solana_program::pubkey::Pubkey;
 
     pub use crate::ctor::Ctor;
-    pub use crate::error::{Error, ErrorCode};
+    pub use crate::error::{account_error, log_error_origin, ErrorCode};
     pub use anchor_attribute_account::ZeroCopyAccessor;
     pub use anchor_attribute_event::EventIndex;
     pub use base64;
@@ -304,6 +427,17 @@ pub mod __private {
     }
 
     pub use crate::state::PROGRAM_STATE_SEED;
+
+    // Creates an account initialized by a constraint, e.g., at a program
+    // derived address, and moves it straight to the heap, so that boxed
+    // accounts never take up space in the frame of `try_accounts`.
+    #[inline(never)]
+    pub fn boxed<T>(
+        init: impl FnOnce() -> Result<T, ProgramError>,
+    ) -> Result<Box<T>, ProgramError> {
+        init().map(Box::new)
+    }
+
     pub const CLOSED_ACCOUNT_DISCRIMINATOR: [u8; 8] = [255, 255, 255, 255, 255, 255, 255, 255];
 }
 
@@ -328,6 +462,33 @@ macro_rules! associated_seeds {
     };
 }
 
+/// Invokes an instruction signed by a program derived address, given its
+/// seeds without the bump. The canonical bump is found for the PDA of
+/// `program_id` and appended to the seeds before signing.
+///
+/// # Example
+///
+/// ```ignore
+/// pda_invoke!(
+///     ctx.program_id,
+///     &ix,
+///     &account_infos,
+///     &[b"open-orders".as_ref(), market.as_ref(), authority.as_ref()]
+/// )?;
+/// ```
+#[macro_export]
+macro_rules! pda_invoke {
+    ($program_id:expr, $ix:expr, $accounts:expr, $seeds:expr $(,)?) => {{
+        let seeds: &[&[u8]] = $seeds;
+        let (_, bump) =
+            anchor_lang::solana_program::pubkey::Pubkey::find_program_address(seeds, $program_id);
+        let bump = [bump];
+        let mut signer_seeds = seeds.to_vec();
+        signer_seeds.push(&bump);
+        anchor_lang::solana_program::program::invoke_signed($ix, $accounts, &[&signer_seeds])
+    }};
+}
+
 /// Ensures a condition is true, otherwise returns the given error.
 /// Use this with a custom error type.
 ///
@@ -349,12 +510,255 @@ macro_rules! associated_seeds {
 /// ```
 ///
 /// which would exit the program with the `InvalidArgument` error code if
-/// `condition` is false.
+/// `condition` is false, logging the file and line of the assertion.
 #[macro_export]
 macro_rules! require {
     ($invariant:expr, $error:tt $(,)?) => {
         if !($invariant) {
+            anchor_lang::__private::log_error_origin(file!(), line!());
             return Err(crate::ErrorCode::$error.into());
         }
     };
 }
+
+/// Returns the given error, logging the file and line it was raised at.
+///
+/// # Example
+///
+/// ```ignore
+/// pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+///     if amount > ctx.accounts.vault.amount {
+///         return err!(MyError::InsufficientFunds);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[macro_export]
+macro_rules! err {
+    ($error:expr $(,)?) => {{
+        anchor_lang::__private::log_error_origin(file!(), line!());
+        Err($error.into())
+    }};
+}
+
+/// Ensures two values are equal, logging both on failure. Use this with a
+/// custom error type, or omit the error to return
+/// `anchor_lang::__private::ErrorCode::RequireEqViolated`.
+///
+/// # Example
+///
+/// ```ignore
+/// require_eq!(ctx.accounts.data.count, 1, InvalidCount);
+/// ```
+#[macro_export]
+macro_rules! require_eq {
+    (@check $value1:expr, $value2:expr, $error:expr) => {
+        match (&$value1, &$value2) {
+            (left, right) => {
+                if *left != *right {
+                    anchor_lang::solana_program::msg!("Left: {}", left);
+                    anchor_lang::solana_program::msg!("Right: {}", right);
+                    anchor_lang::__private::log_error_origin(file!(), line!());
+                    return Err($error.into());
+                }
+            }
+        }
+    };
+    ($value1:expr, $value2:expr, $error:tt $(,)?) => {
+        $crate::require_eq!(@check $value1, $value2, crate::ErrorCode::$error)
+    };
+    ($value1:expr, $value2:expr $(,)?) => {
+        $crate::require_eq!(
+            @check $value1,
+            $value2,
+            anchor_lang::solana_program::program_error::ProgramError::from(
+                anchor_lang::__private::ErrorCode::RequireEqViolated
+            )
+        )
+    };
+}
+
+/// Ensures two values are not equal, logging both on failure. Use this with a
+/// custom error type, or omit the error to return
+/// `anchor_lang::__private::ErrorCode::RequireNeqViolated`.
+///
+/// # Example
+///
+/// ```ignore
+/// require_neq!(ctx.accounts.data.count, 0, InvalidCount);
+/// ```
+#[macro_export]
+macro_rules! require_neq {
+    (@check $value1:expr, $value2:expr, $error:expr) => {
+        match (&$value1, &$value2) {
+            (left, right) => {
+                if *left == *right {
+                    anchor_lang::solana_program::msg!("Left: {}", left);
+                    anchor_lang::solana_program::msg!("Right: {}", right);
+                    anchor_lang::__private::log_error_origin(file!(), line!());
+                    return Err($error.into());
+                }
+            }
+        }
+    };
+    ($value1:expr, $value2:expr, $error:tt $(,)?) => {
+        $crate::require_neq!(@check $value1, $value2, crate::ErrorCode::$error)
+    };
+    ($value1:expr, $value2:expr $(,)?) => {
+        $crate::require_neq!(
+            @check $value1,
+            $value2,
+            anchor_lang::solana_program::program_error::ProgramError::from(
+                anchor_lang::__private::ErrorCode::RequireNeqViolated
+            )
+        )
+    };
+}
+
+/// Ensures two pubkeys are equal, logging both on failure. Use this with a
+/// custom error type, or omit the error to return
+/// `anchor_lang::__private::ErrorCode::RequireKeysEqViolated`.
+///
+/// # Example
+///
+/// ```ignore
+/// require_keys_eq!(ctx.accounts.data.authority, *ctx.accounts.authority.key, InvalidAuthority);
+/// ```
+#[macro_export]
+macro_rules! require_keys_eq {
+    (@check $value1:expr, $value2:expr, $error:expr) => {
+        match (&$value1, &$value2) {
+            (left, right) => {
+                if *left != *right {
+                    anchor_lang::solana_program::msg!("Left: {}", left);
+                    anchor_lang::solana_program::msg!("Right: {}", right);
+                    anchor_lang::__private::log_error_origin(file!(), line!());
+                    return Err($error.into());
+                }
+            }
+        }
+    };
+    ($value1:expr, $value2:expr, $error:tt $(,)?) => {
+        $crate::require_keys_eq!(@check $value1, $value2, crate::ErrorCode::$error)
+    };
+    ($value1:expr, $value2:expr $(,)?) => {
+        $crate::require_keys_eq!(
+            @check $value1,
+            $value2,
+            anchor_lang::solana_program::program_error::ProgramError::from(
+                anchor_lang::__private::ErrorCode::RequireKeysEqViolated
+            )
+        )
+    };
+}
+
+/// Ensures two pubkeys are not equal, logging both on failure. Use this with a
+/// custom error type, or omit the error to return
+/// `anchor_lang::__private::ErrorCode::RequireKeysNeqViolated`.
+///
+/// # Example
+///
+/// ```ignore
+/// require_keys_neq!(ctx.accounts.from.key(), ctx.accounts.to.key(), SameAccount);
+/// ```
+#[macro_export]
+macro_rules! require_keys_neq {
+    (@check $value1:expr, $value2:expr, $error:expr) => {
+        match (&$value1, &$value2) {
+            (left, right) => {
+                if *left == *right {
+                    anchor_lang::solana_program::msg!("Left: {}", left);
+                    anchor_lang::solana_program::msg!("Right: {}", right);
+                    anchor_lang::__private::log_error_origin(file!(), line!());
+                    return Err($error.into());
+                }
+            }
+        }
+    };
+    ($value1:expr, $value2:expr, $error:tt $(,)?) => {
+        $crate::require_keys_neq!(@check $value1, $value2, crate::ErrorCode::$error)
+    };
+    ($value1:expr, $value2:expr $(,)?) => {
+        $crate::require_keys_neq!(
+            @check $value1,
+            $value2,
+            anchor_lang::solana_program::program_error::ProgramError::from(
+                anchor_lang::__private::ErrorCode::RequireKeysNeqViolated
+            )
+        )
+    };
+}
+
+/// Ensures the first value is greater than the second, logging both on failure.
+/// Use this with a custom error type, or omit the error to return
+/// `anchor_lang::__private::ErrorCode::RequireGtViolated`.
+///
+/// # Example
+///
+/// ```ignore
+/// require_gt!(amount, 0, InvalidAmount);
+/// ```
+#[macro_export]
+macro_rules! require_gt {
+    (@check $value1:expr, $value2:expr, $error:expr) => {
+        match (&$value1, &$value2) {
+            (left, right) => {
+                if *left <= *right {
+                    anchor_lang::solana_program::msg!("Left: {}", left);
+                    anchor_lang::solana_program::msg!("Right: {}", right);
+                    anchor_lang::__private::log_error_origin(file!(), line!());
+                    return Err($error.into());
+                }
+            }
+        }
+    };
+    ($value1:expr, $value2:expr, $error:tt $(,)?) => {
+        $crate::require_gt!(@check $value1, $value2, crate::ErrorCode::$error)
+    };
+    ($value1:expr, $value2:expr $(,)?) => {
+        $crate::require_gt!(
+            @check $value1,
+            $value2,
+            anchor_lang::solana_program::program_error::ProgramError::from(
+                anchor_lang::__private::ErrorCode::RequireGtViolated
+            )
+        )
+    };
+}
+
+/// Ensures the first value is greater than or equal to the second, logging both
+/// on failure. Use this with a custom error type, or omit the error to return
+/// `anchor_lang::__private::ErrorCode::RequireGteViolated`.
+///
+/// # Example
+///
+/// ```ignore
+/// require_gte!(balance, amount, InsufficientBalance);
+/// ```
+#[macro_export]
+macro_rules! require_gte {
+    (@check $value1:expr, $value2:expr, $error:expr) => {
+        match (&$value1, &$value2) {
+            (left, right) => {
+                if *left < *right {
+                    anchor_lang::solana_program::msg!("Left: {}", left);
+                    anchor_lang::solana_program::msg!("Right: {}", right);
+                    anchor_lang::__private::log_error_origin(file!(), line!());
+                    return Err($error.into());
+                }
+            }
+        }
+    };
+    ($value1:expr, $value2:expr, $error:tt $(,)?) => {
+        $crate::require_gte!(@check $value1, $value2, crate::ErrorCode::$error)
+    };
+    ($value1:expr, $value2:expr $(,)?) => {
+        $crate::require_gte!(
+            @check $value1,
+            $value2,
+            anchor_lang::solana_program::program_error::ProgramError::from(
+                anchor_lang::__private::ErrorCode::RequireGteViolated
+            )
+        )
+    };
+}
@@ -9,8 +9,10 @@ use solana_program::instruction::AccountMeta;
 use solana_program::program_error::ProgramError;
 use solana_program::pubkey::Pubkey;
 use std::cell::{Ref, RefMut};
+use std::collections::BTreeMap;
 use std::io::Write;
 use std::marker::PhantomData;
+use std::mem;
 use std::ops::DerefMut;
 
 /// Account loader facilitating on demand zero copy deserialization.
@@ -43,11 +45,7 @@ impl<'info, T: ZeroCopy> Loader<'info, T> {
         let data: &[u8] = &acc_info.try_borrow_data()?;
 
         // Discriminator must match.
-        let mut disc_bytes = [0u8; 8];
-        disc_bytes.copy_from_slice(&data[..8]);
-        if disc_bytes != T::discriminator() {
-            return Err(ErrorCode::AccountDiscriminatorMismatch.into());
-        }
+        check_discriminator::<T>(data)?;
 
         Ok(Loader::new(acc_info.clone()))
     }
@@ -58,10 +56,10 @@ impl<'info, T: ZeroCopy> Loader<'info, T> {
         let data = acc_info.try_borrow_data()?;
 
         // The discriminator should be zero, since we're initializing.
-        let mut disc_bytes = [0u8; 8];
-        disc_bytes.copy_from_slice(&data[..8]);
-        let discriminator = u64::from_le_bytes(disc_bytes);
-        if discriminator != 0 {
+        let disc_bytes = data
+            .get(..T::discriminator().len())
+            .ok_or(ErrorCode::AccountDiscriminatorNotFound)?;
+        if disc_bytes.iter().any(|b| *b != 0) {
             return Err(ErrorCode::AccountDiscriminatorAlreadySet.into());
         }
 
@@ -72,13 +70,13 @@ impl<'info, T: ZeroCopy> Loader<'info, T> {
     pub fn load(&self) -> Result<Ref<T>, ProgramError> {
         let data = self.acc_info.try_borrow_data()?;
 
-        let mut disc_bytes = [0u8; 8];
-        disc_bytes.copy_from_slice(&data[..8]);
-        if disc_bytes != T::discriminator() {
-            return Err(ErrorCode::AccountDiscriminatorMismatch.into());
-        }
+        check_discriminator::<T>(&data)?;
 
-        Ok(Ref::map(data, |data| bytemuck::from_bytes(&data[8..])))
+        check_cast::<T>(&data)?;
+        let disc_len = T::discriminator().len();
+        Ok(Ref::map(data, |data| {
+            bytemuck::from_bytes(&data[disc_len..disc_len + mem::size_of::<T>()])
+        }))
     }
 
     /// Returns a `RefMut` to the account data structure for reading or writing.
@@ -91,14 +89,14 @@ impl<'info, T: ZeroCopy> Loader<'info, T> {
 
         let data = self.acc_info.try_borrow_mut_data()?;
 
-        let mut disc_bytes = [0u8; 8];
-        disc_bytes.copy_from_slice(&data[..8]);
-        if disc_bytes != T::discriminator() {
-            return Err(ErrorCode::AccountDiscriminatorMismatch.into());
-        }
+        check_discriminator::<T>(&data)?;
 
+        check_cast::<T>(&data)?;
+        let disc_len = T::discriminator().len();
         Ok(RefMut::map(data, |data| {
-            bytemuck::from_bytes_mut(&mut data.deref_mut()[8..])
+            bytemuck::from_bytes_mut(
+                &mut data.deref_mut()[disc_len..disc_len + mem::size_of::<T>()],
+            )
         }))
     }
 
@@ -114,25 +112,50 @@ impl<'info, T: ZeroCopy> Loader<'info, T> {
         let data = self.acc_info.try_borrow_mut_data()?;
 
         // The discriminator should be zero, since we're initializing.
-        let mut disc_bytes = [0u8; 8];
-        disc_bytes.copy_from_slice(&data[..8]);
-        let discriminator = u64::from_le_bytes(disc_bytes);
-        if discriminator != 0 {
+        let disc_bytes = data
+            .get(..T::discriminator().len())
+            .ok_or(ErrorCode::AccountDiscriminatorNotFound)?;
+        if disc_bytes.iter().any(|b| *b != 0) {
             return Err(ErrorCode::AccountDiscriminatorAlreadySet.into());
         }
 
+        check_cast::<T>(&data)?;
+        let disc_len = T::discriminator().len();
         Ok(RefMut::map(data, |data| {
-            bytemuck::from_bytes_mut(&mut data.deref_mut()[8..])
+            bytemuck::from_bytes_mut(
+                &mut data.deref_mut()[disc_len..disc_len + mem::size_of::<T>()],
+            )
         }))
     }
 }
 
+// Checks the account data starts with the discriminator of `T`.
+fn check_discriminator<T: ZeroCopy>(data: &[u8]) -> Result<(), ProgramError> {
+    if !data.starts_with(T::discriminator()) {
+        return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+    Ok(())
+}
+
+// Checks the account data, excluding the discriminator, can be cast to `T`,
+// so that the casts in the `load` methods never panic.
+fn check_cast<T: ZeroCopy>(data: &[u8]) -> Result<(), ProgramError> {
+    let disc_len = T::discriminator().len();
+    let bytes = data
+        .get(disc_len..disc_len + mem::size_of::<T>())
+        .ok_or(ErrorCode::AccountDidNotDeserialize)?;
+    bytemuck::try_from_bytes::<T>(bytes)
+        .map(|_| ())
+        .map_err(|_| ErrorCode::AccountDidNotDeserialize.into())
+}
+
 impl<'info, T: ZeroCopy> Accounts<'info> for Loader<'info, T> {
     #[inline(never)]
     fn try_accounts(
         program_id: &Pubkey,
         accounts: &mut &[AccountInfo<'info>],
         _ix_data: &[u8],
+        _bumps: &mut BTreeMap<String, u8>,
     ) -> Result<Self, ProgramError> {
         if accounts.is_empty() {
             return Err(ErrorCode::AccountNotEnoughKeys.into());
@@ -172,7 +195,7 @@ impl<'info, T: ZeroCopy> AccountsExit<'info> for Loader<'info, T> {
         let mut data = self.acc_info.try_borrow_mut_data()?;
         let dst: &mut [u8] = &mut data;
         let mut cursor = std::io::Cursor::new(dst);
-        cursor.write_all(&T::discriminator()).unwrap();
+        cursor.write_all(T::discriminator()).unwrap();
         Ok(())
     }
 }
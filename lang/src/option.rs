@@ -0,0 +1,131 @@
+//! Optional accounts.
+//!
+//! An optional account is considered absent if either the accounts slice has
+//! been exhausted or the executing program's own id is given in its place.
+//!
+//! Conversely, `derive(Accounts)` structs convert an absent field into a
+//! readonly account meta for the program's id, `crate::ID`, so programs with
+//! optional accounts must `declare_id!`. The program's account info, which a
+//! `CpiContext` always includes, backs that meta. `Option`'s own impls can't
+//! know the program id, so on their own they omit absent accounts.
+
+use crate::{Accounts, AccountsClose, AccountsExit, ToAccountInfos, ToAccountMetas};
+use solana_program::account_info::AccountInfo;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::instruction::AccountMeta;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use std::collections::BTreeMap;
+
+impl<'info, T: Accounts<'info>> Accounts<'info> for Option<T> {
+    fn try_accounts(
+        program_id: &Pubkey,
+        accounts: &mut &[AccountInfo<'info>],
+        ix_data: &[u8],
+        bumps: &mut BTreeMap<String, u8>,
+    ) -> Result<Self, ProgramError> {
+        if accounts.is_empty() {
+            return Ok(None);
+        }
+        if accounts[0].key == program_id {
+            *accounts = &accounts[1..];
+            return Ok(None);
+        }
+        T::try_accounts(program_id, accounts, ix_data, bumps).map(Some)
+    }
+}
+
+impl<'info, T: AccountsExit<'info>> AccountsExit<'info> for Option<T> {
+    fn exit(&self, program_id: &Pubkey) -> ProgramResult {
+        match self {
+            None => Ok(()),
+            Some(account) => account.exit(program_id),
+        }
+    }
+}
+
+impl<'info, T: AccountsClose<'info>> AccountsClose<'info> for Option<T> {
+    fn close(&self, sol_destination: AccountInfo<'info>) -> ProgramResult {
+        match self {
+            None => Ok(()),
+            Some(account) => account.close(sol_destination),
+        }
+    }
+}
+
+impl<'info, T: ToAccountInfos<'info>> ToAccountInfos<'info> for Option<T> {
+    fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+        match self {
+            None => vec![],
+            Some(account) => account.to_account_infos(),
+        }
+    }
+}
+
+impl<T: ToAccountMetas> ToAccountMetas for Option<T> {
+    fn to_account_metas(&self, is_signer: Option<bool>) -> Vec<AccountMeta> {
+        match self {
+            None => vec![],
+            Some(account) => account.to_account_metas(is_signer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_program::clock::Epoch;
+
+    fn try_optional<'info>(
+        program_id: &Pubkey,
+        accounts: &mut &[AccountInfo<'info>],
+    ) -> Option<AccountInfo<'info>> {
+        Option::<AccountInfo>::try_accounts(program_id, accounts, &[], &mut BTreeMap::new())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_accounts_trait_for_option() {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let owner = Pubkey::default();
+        let mut lamports1 = 0;
+        let mut data1 = vec![];
+        let mut lamports2 = 0;
+        let mut data2 = vec![];
+        let account = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports1,
+            &mut data1,
+            &owner,
+            false,
+            Epoch::default(),
+        );
+        let placeholder = AccountInfo::new(
+            &program_id,
+            false,
+            false,
+            &mut lamports2,
+            &mut data2,
+            &owner,
+            true,
+            Epoch::default(),
+        );
+        let infos = [account, placeholder];
+        let mut accounts = &infos[..];
+
+        // Present.
+        let present = try_optional(&program_id, &mut accounts);
+        assert_eq!(present.map(|a| *a.key), Some(key));
+        assert_eq!(accounts.len(), 1);
+
+        // Program id given as a placeholder.
+        assert!(try_optional(&program_id, &mut accounts).is_none());
+        assert!(accounts.is_empty());
+
+        // Accounts slice exhausted.
+        assert!(try_optional(&program_id, &mut accounts).is_none());
+    }
+}
@@ -0,0 +1,88 @@
+use crate::error::ErrorCode;
+use crate::{Accounts, AccountsExit, Id, ToAccountInfo, ToAccountInfos, ToAccountMetas};
+use solana_program::account_info::AccountInfo;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::instruction::AccountMeta;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+/// Container for an executable program account, whose address is checked
+/// against `T::id()` upon deserialization, e.g., `Program<'info, System>`.
+#[derive(Clone)]
+pub struct Program<'info, T: Id + Clone> {
+    info: AccountInfo<'info>,
+    _phantom: PhantomData<T>,
+}
+
+impl<'info, T: Id + Clone> Program<'info, T> {
+    fn new(info: AccountInfo<'info>) -> Program<'info, T> {
+        Self {
+            info,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Deserializes the given `info` into a `Program`.
+    pub fn try_from(info: &AccountInfo<'info>) -> Result<Program<'info, T>, ProgramError> {
+        if info.key != &T::id() {
+            return Err(ErrorCode::InvalidProgramId.into());
+        }
+        if !info.executable {
+            return Err(ErrorCode::InvalidProgramExecutable.into());
+        }
+        Ok(Program::new(info.clone()))
+    }
+}
+
+impl<'info, T: Id + Clone> Accounts<'info> for Program<'info, T> {
+    fn try_accounts(
+        _program_id: &Pubkey,
+        accounts: &mut &[AccountInfo<'info>],
+        _ix_data: &[u8],
+        _bumps: &mut BTreeMap<String, u8>,
+    ) -> Result<Self, ProgramError> {
+        if accounts.is_empty() {
+            return Err(ErrorCode::AccountNotEnoughKeys.into());
+        }
+        let account = &accounts[0];
+        *accounts = &accounts[1..];
+        Program::try_from(account)
+    }
+}
+
+impl<'info, T: Id + Clone> AccountsExit<'info> for Program<'info, T> {
+    fn exit(&self, _program_id: &Pubkey) -> ProgramResult {
+        // No-op.
+        Ok(())
+    }
+}
+
+impl<'info, T: Id + Clone> ToAccountMetas for Program<'info, T> {
+    fn to_account_metas(&self, is_signer: Option<bool>) -> Vec<AccountMeta> {
+        let is_signer = is_signer.unwrap_or(self.info.is_signer);
+        vec![AccountMeta::new_readonly(*self.info.key, is_signer)]
+    }
+}
+
+impl<'info, T: Id + Clone> ToAccountInfos<'info> for Program<'info, T> {
+    fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+        vec![self.info.clone()]
+    }
+}
+
+impl<'info, T: Id + Clone> ToAccountInfo<'info> for Program<'info, T> {
+    fn to_account_info(&self) -> AccountInfo<'info> {
+        self.info.clone()
+    }
+}
+
+impl<'info, T: Id + Clone> Deref for Program<'info, T> {
+    type Target = AccountInfo<'info>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.info
+    }
+}
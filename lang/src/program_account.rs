@@ -1,13 +1,14 @@
 use crate::error::ErrorCode;
 use crate::{
     AccountDeserialize, AccountSerialize, Accounts, AccountsClose, AccountsExit, AccountsInit,
-    CpiAccount, ToAccountInfo, ToAccountInfos, ToAccountMetas,
+    CpiAccount, Discriminator, ToAccountInfo, ToAccountInfos, ToAccountMetas,
 };
 use solana_program::account_info::AccountInfo;
 use solana_program::entrypoint::ProgramResult;
 use solana_program::instruction::AccountMeta;
 use solana_program::program_error::ProgramError;
 use solana_program::pubkey::Pubkey;
+use std::collections::BTreeMap;
 use std::ops::{Deref, DerefMut};
 
 /// Boxed container for a deserialized `account`. Use this to reference any
@@ -40,6 +41,12 @@ impl<'a, T: AccountSerialize + AccountDeserialize + Clone> ProgramAccount<'a, T>
         ))
     }
 
+    pub fn into_inner(self) -> T {
+        self.inner.account
+    }
+}
+
+impl<'a, T: AccountSerialize + AccountDeserialize + Discriminator + Clone> ProgramAccount<'a, T> {
     /// Deserializes the zero-initialized `info` into a `ProgramAccount` without
     /// checking the account type. This should only be used upon program account
     /// initialization (since the entire account data array is zeroed and thus
@@ -49,10 +56,10 @@ impl<'a, T: AccountSerialize + AccountDeserialize + Clone> ProgramAccount<'a, T>
         let mut data: &[u8] = &info.try_borrow_data()?;
 
         // The discriminator should be zero, since we're initializing.
-        let mut disc_bytes = [0u8; 8];
-        disc_bytes.copy_from_slice(&data[..8]);
-        let discriminator = u64::from_le_bytes(disc_bytes);
-        if discriminator != 0 {
+        let disc_bytes = data
+            .get(..T::discriminator().len())
+            .ok_or(ErrorCode::AccountDiscriminatorNotFound)?;
+        if disc_bytes.iter().any(|b| *b != 0) {
             return Err(ErrorCode::AccountDiscriminatorAlreadySet.into());
         }
 
@@ -61,10 +68,6 @@ impl<'a, T: AccountSerialize + AccountDeserialize + Clone> ProgramAccount<'a, T>
             T::try_deserialize_unchecked(&mut data)?,
         ))
     }
-
-    pub fn into_inner(self) -> T {
-        self.inner.account
-    }
 }
 
 impl<'info, T> Accounts<'info> for ProgramAccount<'info, T>
@@ -76,6 +79,7 @@ where
         program_id: &Pubkey,
         accounts: &mut &[AccountInfo<'info>],
         _ix_data: &[u8],
+        _bumps: &mut BTreeMap<String, u8>,
     ) -> Result<Self, ProgramError> {
         if accounts.is_empty() {
             return Err(ErrorCode::AccountNotEnoughKeys.into());
@@ -92,7 +96,7 @@ where
 
 impl<'info, T> AccountsInit<'info> for ProgramAccount<'info, T>
 where
-    T: AccountSerialize + AccountDeserialize + Clone,
+    T: AccountSerialize + AccountDeserialize + Discriminator + Clone,
 {
     #[inline(never)]
     fn try_accounts_init(
@@ -0,0 +1,48 @@
+use crate::error::ErrorCode;
+use crate::AnchorDeserialize;
+use solana_program::program::get_return_data;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use std::marker::PhantomData;
+
+/// Value returned by an instruction invoked via CPI, for handlers declared
+/// as returning `Result<T>`.
+///
+/// The callee Borsh serializes the value into the transaction's return data,
+/// which is read and deserialized on `get`, e.g.,
+///
+/// ```ignore
+/// let price = oracle::cpi::price(cpi_ctx)?.get();
+/// ```
+pub struct Return<T: AnchorDeserialize> {
+    program_id: Pubkey,
+    phantom: PhantomData<T>,
+}
+
+impl<T: AnchorDeserialize> Return<T> {
+    #[doc(hidden)]
+    pub fn new(program_id: Pubkey) -> Self {
+        Self {
+            program_id,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Deserializes the value returned by the invoked program.
+    ///
+    /// Panics if the program didn't set any return data or if it can't be
+    /// deserialized. Use `try_get` to handle these cases.
+    pub fn get(&self) -> T {
+        self.try_get().unwrap()
+    }
+
+    /// Deserializes the value returned by the invoked program, checking the
+    /// return data was set by that program.
+    pub fn try_get(&self) -> Result<T, ProgramError> {
+        let (program_id, data) = get_return_data().ok_or(ErrorCode::ReturnDataNotSet)?;
+        if program_id != self.program_id {
+            return Err(ErrorCode::ReturnDataNotSet.into());
+        }
+        T::try_from_slice(&data).map_err(|_| ErrorCode::ReturnDataDidNotDeserialize.into())
+    }
+}
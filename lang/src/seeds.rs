@@ -0,0 +1,87 @@
+use solana_program::pubkey::{Pubkey, PubkeyError};
+use std::ops::Deref;
+
+/// Builder for the seeds of a single program derived address, constructed
+/// from typed components instead of nested byte slices.
+///
+/// `Seeds` dereferences to `[&[u8]]`, and [`SignerSeeds`] to `[&[&[u8]]]`,
+/// so they can be passed wherever signer seeds are expected, e.g.,
+///
+/// ```ignore
+/// let seeds = Seeds::new()
+///     .push(b"vault")
+///     .push(ctx.accounts.market.key)
+///     .bump(&bump);
+/// let signer = SignerSeeds::new(&seeds);
+/// let cpi_ctx = CpiContext::new_with_signer(program, accounts, &signer);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Seeds<'a> {
+    seeds: Vec<&'a [u8]>,
+}
+
+impl<'a> Seeds<'a> {
+    pub fn new() -> Self {
+        Self { seeds: Vec::new() }
+    }
+
+    /// Appends a seed, e.g., a byte string or a `Pubkey`.
+    pub fn push<T: AsRef<[u8]> + ?Sized>(mut self, seed: &'a T) -> Self {
+        self.seeds.push(seed.as_ref());
+        self
+    }
+
+    /// Appends the bump seed.
+    pub fn bump(mut self, bump: &'a u8) -> Self {
+        self.seeds.push(std::slice::from_ref(bump));
+        self
+    }
+
+    /// Returns the address derived from the seeds, which must include the
+    /// bump.
+    pub fn create_program_address(&self, program_id: &Pubkey) -> Result<Pubkey, PubkeyError> {
+        Pubkey::create_program_address(&self.seeds, program_id)
+    }
+
+    /// Returns the canonical address and bump for the seeds, which must not
+    /// include a bump.
+    pub fn find_program_address(&self, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&self.seeds, program_id)
+    }
+}
+
+impl<'a> Deref for Seeds<'a> {
+    type Target = [&'a [u8]];
+
+    fn deref(&self) -> &Self::Target {
+        &self.seeds
+    }
+}
+
+/// The seeds of all program derived addresses signing an instruction.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SignerSeeds<'a, 'b> {
+    signers: Vec<&'a [&'b [u8]]>,
+}
+
+impl<'a, 'b> SignerSeeds<'a, 'b> {
+    pub fn new(seeds: &'a Seeds<'b>) -> Self {
+        Self {
+            signers: vec![seeds],
+        }
+    }
+
+    /// Adds another signer.
+    pub fn signer(mut self, seeds: &'a Seeds<'b>) -> Self {
+        self.signers.push(seeds);
+        self
+    }
+}
+
+impl<'a, 'b> Deref for SignerSeeds<'a, 'b> {
+    type Target = [&'a [&'b [u8]]];
+
+    fn deref(&self) -> &Self::Target {
+        &self.signers
+    }
+}
@@ -0,0 +1,85 @@
+use crate::error::ErrorCode;
+use crate::{Accounts, AccountsExit, ToAccountInfo, ToAccountInfos, ToAccountMetas};
+use solana_program::account_info::AccountInfo;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::instruction::AccountMeta;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use std::collections::BTreeMap;
+use std::ops::Deref;
+
+/// Container for an account that must have signed the transaction. Checks
+/// `is_signer` upon deserialization, so it can be used in place of
+/// `#[account(signer)] AccountInfo<'info>`.
+#[derive(Clone)]
+pub struct Signer<'info> {
+    info: AccountInfo<'info>,
+}
+
+impl<'info> Signer<'info> {
+    fn new(info: AccountInfo<'info>) -> Signer<'info> {
+        Self { info }
+    }
+
+    /// Deserializes the given `info` into a `Signer`.
+    pub fn try_from(info: &AccountInfo<'info>) -> Result<Signer<'info>, ProgramError> {
+        if !info.is_signer {
+            return Err(ErrorCode::AccountNotSigner.into());
+        }
+        Ok(Signer::new(info.clone()))
+    }
+}
+
+impl<'info> Accounts<'info> for Signer<'info> {
+    fn try_accounts(
+        _program_id: &Pubkey,
+        accounts: &mut &[AccountInfo<'info>],
+        _ix_data: &[u8],
+        _bumps: &mut BTreeMap<String, u8>,
+    ) -> Result<Self, ProgramError> {
+        if accounts.is_empty() {
+            return Err(ErrorCode::AccountNotEnoughKeys.into());
+        }
+        let account = &accounts[0];
+        *accounts = &accounts[1..];
+        Signer::try_from(account)
+    }
+}
+
+impl<'info> AccountsExit<'info> for Signer<'info> {
+    fn exit(&self, _program_id: &Pubkey) -> ProgramResult {
+        // No-op.
+        Ok(())
+    }
+}
+
+impl<'info> ToAccountMetas for Signer<'info> {
+    fn to_account_metas(&self, is_signer: Option<bool>) -> Vec<AccountMeta> {
+        let is_signer = is_signer.unwrap_or(self.info.is_signer);
+        let meta = match self.info.is_writable {
+            false => AccountMeta::new_readonly(*self.info.key, is_signer),
+            true => AccountMeta::new(*self.info.key, is_signer),
+        };
+        vec![meta]
+    }
+}
+
+impl<'info> ToAccountInfos<'info> for Signer<'info> {
+    fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+        vec![self.info.clone()]
+    }
+}
+
+impl<'info> ToAccountInfo<'info> for Signer<'info> {
+    fn to_account_info(&self) -> AccountInfo<'info> {
+        self.info.clone()
+    }
+}
+
+impl<'info> Deref for Signer<'info> {
+    type Target = AccountInfo<'info>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.info
+    }
+}
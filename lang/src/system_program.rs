@@ -0,0 +1,14 @@
+use crate::Id;
+use solana_program::pubkey::Pubkey;
+
+pub use solana_program::system_program::ID;
+
+/// The native system program, for use with `Program<'info, System>`.
+#[derive(Clone)]
+pub struct System;
+
+impl Id for System {
+    fn id() -> Pubkey {
+        ID
+    }
+}
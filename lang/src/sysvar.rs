@@ -5,6 +5,7 @@ use solana_program::entrypoint::ProgramResult;
 use solana_program::instruction::AccountMeta;
 use solana_program::program_error::ProgramError;
 use solana_program::pubkey::Pubkey;
+use std::collections::BTreeMap;
 use std::ops::{Deref, DerefMut};
 
 /// Container for sysvars.
@@ -38,6 +39,7 @@ impl<'info, T: solana_program::sysvar::Sysvar> Accounts<'info> for Sysvar<'info,
         _program_id: &Pubkey,
         accounts: &mut &[AccountInfo<'info>],
         _ix_data: &[u8],
+        _bumps: &mut BTreeMap<String, u8>,
     ) -> Result<Self, ProgramError> {
         if accounts.is_empty() {
             return Err(ErrorCode::AccountNotEnoughKeys.into());
@@ -86,3 +88,35 @@ impl<'info, T: solana_program::sysvar::Sysvar> AccountsExit<'info> for Sysvar<'i
         Ok(())
     }
 }
+
+// `Clock` and `Rent` fields are loaded via syscall rather than from the
+// accounts slice, so clients needn't pass them.
+macro_rules! impl_syscall_sysvar {
+    ($sysvar:ty) => {
+        impl<'info> Accounts<'info> for $sysvar {
+            fn try_accounts(
+                _program_id: &Pubkey,
+                _accounts: &mut &[AccountInfo<'info>],
+                _ix_data: &[u8],
+                _bumps: &mut BTreeMap<String, u8>,
+            ) -> Result<Self, ProgramError> {
+                <$sysvar as solana_program::sysvar::Sysvar>::get()
+            }
+        }
+
+        impl ToAccountMetas for $sysvar {
+            fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+                vec![]
+            }
+        }
+
+        impl<'info> ToAccountInfos<'info> for $sysvar {
+            fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+                vec![]
+            }
+        }
+    };
+}
+
+impl_syscall_sysvar!(solana_program::sysvar::clock::Clock);
+impl_syscall_sysvar!(solana_program::sysvar::rent::Rent);
@@ -0,0 +1,82 @@
+use crate::error::ErrorCode;
+use crate::{Accounts, AccountsExit, ToAccountInfo, ToAccountInfos, ToAccountMetas};
+use solana_program::account_info::AccountInfo;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::instruction::AccountMeta;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use std::collections::BTreeMap;
+use std::ops::Deref;
+
+/// Explicit wrapper for an account that is not checked upon deserialization.
+/// Fields of this type must carry a `/// CHECK:` doc comment explaining why
+/// no checks are necessary, otherwise `#[derive(Accounts)]` fails to compile.
+#[derive(Clone)]
+pub struct UncheckedAccount<'info> {
+    info: AccountInfo<'info>,
+}
+
+impl<'info> UncheckedAccount<'info> {
+    fn new(info: AccountInfo<'info>) -> UncheckedAccount<'info> {
+        Self { info }
+    }
+
+    /// Wraps the given `info` without performing any checks.
+    pub fn try_from(info: &AccountInfo<'info>) -> UncheckedAccount<'info> {
+        UncheckedAccount::new(info.clone())
+    }
+}
+
+impl<'info> Accounts<'info> for UncheckedAccount<'info> {
+    fn try_accounts(
+        _program_id: &Pubkey,
+        accounts: &mut &[AccountInfo<'info>],
+        _ix_data: &[u8],
+        _bumps: &mut BTreeMap<String, u8>,
+    ) -> Result<Self, ProgramError> {
+        if accounts.is_empty() {
+            return Err(ErrorCode::AccountNotEnoughKeys.into());
+        }
+        let account = &accounts[0];
+        *accounts = &accounts[1..];
+        Ok(UncheckedAccount::try_from(account))
+    }
+}
+
+impl<'info> AccountsExit<'info> for UncheckedAccount<'info> {
+    fn exit(&self, _program_id: &Pubkey) -> ProgramResult {
+        // No-op.
+        Ok(())
+    }
+}
+
+impl<'info> ToAccountMetas for UncheckedAccount<'info> {
+    fn to_account_metas(&self, is_signer: Option<bool>) -> Vec<AccountMeta> {
+        let is_signer = is_signer.unwrap_or(self.info.is_signer);
+        let meta = match self.info.is_writable {
+            false => AccountMeta::new_readonly(*self.info.key, is_signer),
+            true => AccountMeta::new(*self.info.key, is_signer),
+        };
+        vec![meta]
+    }
+}
+
+impl<'info> ToAccountInfos<'info> for UncheckedAccount<'info> {
+    fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+        vec![self.info.clone()]
+    }
+}
+
+impl<'info> ToAccountInfo<'info> for UncheckedAccount<'info> {
+    fn to_account_info(&self) -> AccountInfo<'info> {
+        self.info.clone()
+    }
+}
+
+impl<'info> Deref for UncheckedAccount<'info> {
+    type Target = AccountInfo<'info>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.info
+    }
+}
@@ -17,6 +17,7 @@ pub fn generate(accs: &AccountsStruct) -> proc_macro2::TokenStream {
     let account_struct_fields: Vec<proc_macro2::TokenStream> = accs
         .fields
         .iter()
+        .filter(|f| f.is_account())
         .map(|f: &AccountField| match f {
             AccountField::CompositeField(s) => {
                 let name = &s.ident;
@@ -33,8 +34,13 @@ pub fn generate(accs: &AccountsStruct) -> proc_macro2::TokenStream {
             }
             AccountField::Field(f) => {
                 let name = &f.ident;
-                quote! {
-                    pub #name: anchor_lang::solana_program::pubkey::Pubkey
+                match f.is_optional {
+                    false => quote! {
+                        pub #name: anchor_lang::solana_program::pubkey::Pubkey
+                    },
+                    true => quote! {
+                        pub #name: Option<anchor_lang::solana_program::pubkey::Pubkey>
+                    },
                 }
             }
         })
@@ -43,6 +49,7 @@ pub fn generate(accs: &AccountsStruct) -> proc_macro2::TokenStream {
     let account_struct_metas: Vec<proc_macro2::TokenStream> = accs
         .fields
         .iter()
+        .filter(|f| f.is_account())
         .map(|f: &AccountField| match f {
             AccountField::CompositeField(s) => {
                 let name = &s.ident;
@@ -51,7 +58,7 @@ pub fn generate(accs: &AccountsStruct) -> proc_macro2::TokenStream {
                 }
             }
             AccountField::Field(f) => {
-                let is_signer = match f.constraints.is_signer() {
+                let is_signer = match f.is_signer() {
                     false => quote! {false},
                     true => quote! {true},
                 };
@@ -60,8 +67,20 @@ pub fn generate(accs: &AccountsStruct) -> proc_macro2::TokenStream {
                     true => quote! { anchor_lang::solana_program::instruction::AccountMeta::new },
                 };
                 let name = &f.ident;
-                quote! {
-                    account_metas.push(#meta(self.#name, #is_signer));
+                match f.is_optional {
+                    false => quote! {
+                        account_metas.push(#meta(self.#name, #is_signer));
+                    },
+                    // Absent optional accounts are replaced by the program id,
+                    // keeping the accounts that follow in place.
+                    true => quote! {
+                        match self.#name {
+                            Some(#name) => account_metas.push(#meta(#name, #is_signer)),
+                            None => account_metas.push(
+                                anchor_lang::solana_program::instruction::AccountMeta::new_readonly(crate::ID, false),
+                            ),
+                        }
+                    },
                 }
             }
         })
@@ -1,18 +1,30 @@
 use crate::{
-    CompositeField, Constraint, ConstraintAddress, ConstraintAssociatedGroup, ConstraintClose,
-    ConstraintExecutable, ConstraintGroup, ConstraintHasOne, ConstraintInit, ConstraintLiteral,
-    ConstraintMut, ConstraintOwner, ConstraintRaw, ConstraintRentExempt, ConstraintSeedsGroup,
-    ConstraintSigner, ConstraintState, Field, PdaKind, Ty,
+    CompositeField, Constraint, ConstraintAddress, ConstraintAssociatedGroup,
+    ConstraintAssociatedTokenGroup, ConstraintClose, ConstraintExecutable, ConstraintGroup,
+    ConstraintHasOne, ConstraintInit, ConstraintInstructionSysvar, ConstraintLiteral,
+    ConstraintMintGroup, ConstraintMut, ConstraintOwner, ConstraintRaw, ConstraintReallocGroup,
+    ConstraintRentExempt, ConstraintSeedsGroup, ConstraintSigner, ConstraintState,
+    ConstraintTokenAccountGroup, ConstraintTokenCloseAuthority, ConstraintTokenDelegate,
+    ConstraintVariant, Field, PdaKind, Ty,
 };
 use proc_macro2_diagnostics::SpanDiagnosticExt;
 use quote::quote;
-use syn::Expr;
+use syn::{Expr, Ident};
 
 pub fn generate(f: &Field) -> proc_macro2::TokenStream {
     let checks: Vec<proc_macro2::TokenStream> = linearize(&f.constraints)
         .iter()
         .map(|c| generate_constraint(f, c))
         .collect();
+    // Constraints on optional accounts are only checked when present.
+    if f.is_optional && !checks.is_empty() {
+        let ident = &f.ident;
+        return quote! {
+            if let Some(#ident) = &#ident {
+                #(#checks)*
+            }
+        };
+    }
     quote! {
         #(#checks)*
     }
@@ -54,6 +66,18 @@ pub fn linearize(c_group: &ConstraintGroup) -> Vec<Constraint> {
         associated,
         close,
         address,
+        variant,
+        // Scope values are passed in when the field is deserialized.
+        scope: _,
+        instruction_sysvar,
+        // Only used by the parser's duplicate mutable account check.
+        allow_duplicate: _,
+        token_account,
+        associated_token,
+        mint,
+        token_delegate,
+        token_close_authority,
+        realloc,
     } = c_group.clone();
 
     let mut constraints = Vec::new();
@@ -67,12 +91,33 @@ pub fn linearize(c_group: &ConstraintGroup) -> Vec<Constraint> {
     if let Some(c) = init {
         constraints.push(Constraint::Init(c));
     }
+    if let Some(c) = token_account {
+        constraints.push(Constraint::TokenAccount(c));
+    }
+    if let Some(c) = associated_token {
+        constraints.push(Constraint::AssociatedToken(c));
+    }
+    if let Some(c) = mint {
+        constraints.push(Constraint::Mint(c));
+    }
+    if let Some(c) = token_delegate {
+        constraints.push(Constraint::TokenDelegate(c));
+    }
+    if let Some(c) = token_close_authority {
+        constraints.push(Constraint::TokenCloseAuthority(c));
+    }
     if let Some(c) = mutable {
         constraints.push(Constraint::Mut(c));
     }
     if let Some(c) = signer {
         constraints.push(Constraint::Signer(c));
     }
+    if let Some(c) = variant {
+        constraints.push(Constraint::Variant(c));
+    }
+    if let Some(c) = instruction_sysvar {
+        constraints.push(Constraint::InstructionSysvar(c));
+    }
     constraints.append(&mut has_one.into_iter().map(Constraint::HasOne).collect());
     constraints.append(&mut literal.into_iter().map(Constraint::Literal).collect());
     constraints.append(&mut raw.into_iter().map(Constraint::Raw).collect());
@@ -94,6 +139,9 @@ pub fn linearize(c_group: &ConstraintGroup) -> Vec<Constraint> {
     if let Some(c) = address {
         constraints.push(Constraint::Address(c));
     }
+    if let Some(c) = realloc {
+        constraints.push(Constraint::Realloc(c));
+    }
     constraints
 }
 
@@ -104,7 +152,7 @@ fn generate_constraint(f: &Field, c: &Constraint) -> proc_macro2::TokenStream {
         Constraint::HasOne(c) => generate_constraint_has_one(f, c),
         Constraint::Signer(c) => generate_constraint_signer(f, c),
         Constraint::Literal(c) => generate_constraint_literal(c),
-        Constraint::Raw(c) => generate_constraint_raw(c),
+        Constraint::Raw(c) => generate_constraint_raw(&f.ident, c),
         Constraint::Owner(c) => generate_constraint_owner(f, c),
         Constraint::RentExempt(c) => generate_constraint_rent_exempt(f, c),
         Constraint::Seeds(c) => generate_constraint_seeds(f, c),
@@ -113,12 +161,20 @@ fn generate_constraint(f: &Field, c: &Constraint) -> proc_macro2::TokenStream {
         Constraint::AssociatedGroup(c) => generate_constraint_associated(f, c),
         Constraint::Close(c) => generate_constraint_close(f, c),
         Constraint::Address(c) => generate_constraint_address(f, c),
+        Constraint::Variant(c) => generate_constraint_variant(f, c),
+        Constraint::InstructionSysvar(c) => generate_constraint_instruction_sysvar(f, c),
+        Constraint::TokenAccount(c) => generate_constraint_token_account(f, c),
+        Constraint::AssociatedToken(c) => generate_constraint_associated_token(f, c),
+        Constraint::Mint(c) => generate_constraint_mint(f, c),
+        Constraint::TokenDelegate(c) => generate_constraint_token_delegate(f, c),
+        Constraint::TokenCloseAuthority(c) => generate_constraint_token_close_authority(f, c),
+        Constraint::Realloc(c) => generate_constraint_realloc(f, c),
     }
 }
 
-fn generate_constraint_composite(_f: &CompositeField, c: &Constraint) -> proc_macro2::TokenStream {
+fn generate_constraint_composite(f: &CompositeField, c: &Constraint) -> proc_macro2::TokenStream {
     match c {
-        Constraint::Raw(c) => generate_constraint_raw(c),
+        Constraint::Raw(c) => generate_constraint_raw(&f.ident, c),
         Constraint::Literal(c) => generate_constraint_literal(c),
         _ => panic!("Invariant violation"),
     }
@@ -127,9 +183,25 @@ fn generate_constraint_composite(_f: &CompositeField, c: &Constraint) -> proc_ma
 fn generate_constraint_address(f: &Field, c: &ConstraintAddress) -> proc_macro2::TokenStream {
     let field = &f.ident;
     let addr = &c.address;
+    let error = generate_custom_error(field, &c.error, quote! { ConstraintAddress });
     quote! {
         if #field.to_account_info().key != &#addr {
-            return Err(anchor_lang::__private::ErrorCode::ConstraintAddress.into());
+            return Err(#error);
+        }
+    }
+}
+
+fn generate_constraint_variant(f: &Field, c: &ConstraintVariant) -> proc_macro2::TokenStream {
+    let field = &f.ident;
+    let (account_ty, _, _) = parse_ty(f);
+    let variant = &c.variant;
+    let error = generate_custom_error(field, &c.error, quote! { ConstraintVariant });
+    quote! {
+        {
+            let __account: &#account_ty = &#field;
+            if !matches!(__account, #variant { .. }) {
+                return Err(#error);
+            }
         }
     }
 }
@@ -140,19 +212,305 @@ pub fn generate_constraint_init(_f: &Field, _c: &ConstraintInit) -> proc_macro2:
 
 pub fn generate_constraint_close(f: &Field, c: &ConstraintClose) -> proc_macro2::TokenStream {
     let field = &f.ident;
+    let close_error = generate_error(field, quote! { ConstraintClose });
     let target = &c.sol_dest;
     quote! {
         if #field.to_account_info().key == #target.to_account_info().key {
-            return Err(anchor_lang::__private::ErrorCode::ConstraintClose.into());
+            return Err(#close_error);
+        }
+    }
+}
+
+pub fn generate_constraint_token_account(
+    f: &Field,
+    c: &ConstraintTokenAccountGroup,
+) -> proc_macro2::TokenStream {
+    let field = &f.ident;
+    let owner_error = generate_error(field, quote! { ConstraintOwner });
+    let token_mint_error = generate_error(field, quote! { ConstraintTokenMint });
+    let token_owner_error = generate_error(field, quote! { ConstraintTokenOwner });
+    let mint = &c.mint;
+    let authority = &c.authority;
+    let init = match c.is_init {
+        false => quote! {},
+        true => bind_init(
+            f,
+            quote! { anchor_lang::CpiAccount<anchor_spl::token::TokenAccount> },
+            quote! {
+                let cpi_program = token_program.to_account_info();
+                let accounts = anchor_spl::token::InitializeAccount3 {
+                    account: #field.to_account_info(),
+                    mint: #mint.to_account_info(),
+                    authority: #authority.to_account_info(),
+                };
+                let cpi_ctx = anchor_lang::CpiContext::new(cpi_program, accounts);
+                anchor_spl::token::initialize_account3(cpi_ctx)?;
+                anchor_lang::CpiAccount::try_from_init(
+                    &#field.to_account_info(),
+                )?
+            },
+        ),
+    };
+    quote! {
+        #init
+        if #field.to_account_info().owner != &anchor_spl::token::ID {
+            return Err(#owner_error);
+        }
+        if &#field.mint != #mint.to_account_info().key {
+            return Err(#token_mint_error);
+        }
+        if &#field.owner != #authority.to_account_info().key {
+            return Err(#token_owner_error);
+        }
+    }
+}
+
+pub fn generate_constraint_associated_token(
+    f: &Field,
+    c: &ConstraintAssociatedTokenGroup,
+) -> proc_macro2::TokenStream {
+    let field = &f.ident;
+    let associated_token_error = generate_error(field, quote! { ConstraintAssociatedToken });
+    let owner_error = generate_error(field, quote! { ConstraintOwner });
+    let token_mint_error = generate_error(field, quote! { ConstraintTokenMint });
+    let token_owner_error = generate_error(field, quote! { ConstraintTokenOwner });
+    let mint = &c.mint;
+    let authority = &c.authority;
+    let init = match c.is_init {
+        false => quote! {},
+        true => {
+            let payer = &c.payer;
+            // With init_if_needed, only create the account if it doesn't
+            // exist yet.
+            let create_check = match c.if_needed {
+                false => quote! { true },
+                true => quote! {
+                    #field.to_account_info().owner == &anchor_lang::solana_program::system_program::ID
+                },
+            };
+            bind_init(
+                f,
+                quote! { anchor_lang::CpiAccount<anchor_spl::token::TokenAccount> },
+                quote! {
+                    if #create_check {
+                        let cpi_program = associated_token_program.to_account_info();
+                        let accounts = anchor_spl::associated_token::Create {
+                            payer: #payer.to_account_info(),
+                            associated_token: #field.to_account_info(),
+                            authority: #authority.to_account_info(),
+                            mint: #mint.to_account_info(),
+                            system_program: system_program.to_account_info(),
+                            token_program: token_program.to_account_info(),
+                        };
+                        let cpi_ctx = anchor_lang::CpiContext::new(cpi_program, accounts);
+                        anchor_spl::associated_token::create(cpi_ctx)?;
+                    }
+                    anchor_lang::CpiAccount::try_from_init(
+                        &#field.to_account_info(),
+                    )?
+                },
+            )
+        }
+    };
+    quote! {
+        #init
+        {
+            let __associated_token_address = anchor_spl::associated_token::get_associated_token_address(
+                #authority.to_account_info().key,
+                #mint.to_account_info().key,
+            );
+            if #field.to_account_info().key != &__associated_token_address {
+                return Err(#associated_token_error);
+            }
+        }
+        if #field.to_account_info().owner != &anchor_spl::token::ID {
+            return Err(#owner_error);
+        }
+        if &#field.mint != #mint.to_account_info().key {
+            return Err(#token_mint_error);
+        }
+        if &#field.owner != #authority.to_account_info().key {
+            return Err(#token_owner_error);
+        }
+    }
+}
+
+pub fn generate_constraint_mint(f: &Field, c: &ConstraintMintGroup) -> proc_macro2::TokenStream {
+    let field = &f.ident;
+    let (account_ty, _, _) = parse_ty(f);
+    let token_program = match &c.token_program {
+        None => quote! { token_program },
+        Some(p) => quote! { #p },
+    };
+    let init = match (&c.decimals, &c.authority) {
+        (Some(decimals), Some(authority)) if c.is_init => {
+            let freeze_authority = match &c.freeze_authority {
+                None => quote! { None },
+                Some(f) => quote! { Some(#f.to_account_info().key) },
+            };
+            bind_init(
+                f,
+                quote! { anchor_lang::CpiAccount<#account_ty> },
+                quote! {
+                    let cpi_program = #token_program.to_account_info();
+                    let accounts = anchor_spl::token_interface::InitializeMint2 {
+                        mint: #field.to_account_info(),
+                    };
+                    let cpi_ctx = anchor_lang::CpiContext::new(cpi_program, accounts);
+                    anchor_spl::token_interface::initialize_mint2(
+                        cpi_ctx,
+                        #decimals,
+                        #authority.to_account_info().key,
+                        #freeze_authority,
+                    )?;
+                    anchor_lang::CpiAccount::try_from_init(
+                        &#field.to_account_info(),
+                    )?
+                },
+            )
+        }
+        _ => quote! {},
+    };
+    let owner_check = match &c.token_program {
+        None => {
+            let owner_error = generate_error(field, quote! { ConstraintOwner });
+            quote! {
+                if #field.to_account_info().owner != &anchor_spl::token::ID {
+                    return Err(#owner_error);
+                }
+            }
+        }
+        Some(p) => {
+            let token_program_error = generate_error(field, quote! { ConstraintMintTokenProgram });
+            quote! {
+                if #field.to_account_info().owner != #p.to_account_info().key {
+                    return Err(#token_program_error);
+                }
+            }
+        }
+    };
+    let decimals_check = c.decimals.as_ref().map(|decimals| {
+        let error = generate_error(field, quote! { ConstraintMintDecimals });
+        quote! {
+            if #field.decimals != #decimals {
+                return Err(#error);
+            }
+        }
+    });
+    let authority_check = c.authority.as_ref().map(|authority| {
+        let error = generate_error(field, quote! { ConstraintMintMintAuthority });
+        quote! {
+            if #field.mint_authority != anchor_lang::solana_program::program_option::COption::Some(*#authority.to_account_info().key) {
+                return Err(#error);
+            }
+        }
+    });
+    let freeze_authority_check = c.freeze_authority.as_ref().map(|freeze_authority| {
+        let error = generate_error(field, quote! { ConstraintMintFreezeAuthority });
+        quote! {
+            if #field.freeze_authority != anchor_lang::solana_program::program_option::COption::Some(*#freeze_authority.to_account_info().key) {
+                return Err(#error);
+            }
+        }
+    });
+    quote! {
+        #init
+        #owner_check
+        #decimals_check
+        #authority_check
+        #freeze_authority_check
+    }
+}
+
+pub fn generate_constraint_token_delegate(
+    f: &Field,
+    c: &ConstraintTokenDelegate,
+) -> proc_macro2::TokenStream {
+    let field = &f.ident;
+    let error = generate_error(field, quote! { ConstraintTokenDelegate });
+    let delegate = match &c.delegate {
+        None => quote! { anchor_lang::solana_program::program_option::COption::None },
+        Some(d) => quote! {
+            anchor_lang::solana_program::program_option::COption::Some(*#d.to_account_info().key)
+        },
+    };
+    quote! {
+        if #field.delegate != #delegate {
+            return Err(#error);
         }
     }
 }
 
-pub fn generate_constraint_mut(f: &Field, _c: &ConstraintMut) -> proc_macro2::TokenStream {
+pub fn generate_constraint_token_close_authority(
+    f: &Field,
+    c: &ConstraintTokenCloseAuthority,
+) -> proc_macro2::TokenStream {
+    let field = &f.ident;
+    let error = generate_error(field, quote! { ConstraintTokenCloseAuthority });
+    let close_authority = match &c.close_auth {
+        None => quote! { anchor_lang::solana_program::program_option::COption::None },
+        Some(a) => quote! {
+            anchor_lang::solana_program::program_option::COption::Some(*#a.to_account_info().key)
+        },
+    };
+    quote! {
+        if #field.close_authority != #close_authority {
+            return Err(#error);
+        }
+    }
+}
+
+pub fn generate_constraint_realloc(
+    f: &Field,
+    c: &ConstraintReallocGroup,
+) -> proc_macro2::TokenStream {
+    let field = &f.ident;
+    let new_space = &c.space;
+    let payer = &c.payer;
+    let zero = &c.zero;
+    quote! {
+        {
+            let __new_space: usize = #new_space;
+            let __field_info = #field.to_account_info();
+            let __payer_info = #payer.to_account_info();
+            if __new_space != __field_info.try_data_len()? {
+                let __new_rent_minimum = rent.minimum_balance(__new_space);
+                let __lamports = __field_info.lamports();
+                if __new_rent_minimum > __lamports {
+                    // Top up the account so that it remains rent exempt.
+                    anchor_lang::solana_program::program::invoke(
+                        &anchor_lang::solana_program::system_instruction::transfer(
+                            __payer_info.key,
+                            __field_info.key,
+                            __new_rent_minimum - __lamports,
+                        ),
+                        &[
+                            __payer_info.clone(),
+                            __field_info.clone(),
+                            system_program.to_account_info(),
+                        ],
+                    )?;
+                } else {
+                    // Refund the excess lamports to the payer.
+                    let __excess = __lamports - __new_rent_minimum;
+                    **__field_info.lamports.borrow_mut() = __new_rent_minimum;
+                    **__payer_info.lamports.borrow_mut() = __payer_info
+                        .lamports()
+                        .checked_add(__excess)
+                        .ok_or(anchor_lang::__private::ErrorCode::ConstraintRealloc)?;
+                }
+                __field_info.realloc(__new_space, #zero)?;
+            }
+        }
+    }
+}
+
+pub fn generate_constraint_mut(f: &Field, c: &ConstraintMut) -> proc_macro2::TokenStream {
     let ident = &f.ident;
+    let error = generate_custom_error(ident, &c.error, quote! { ConstraintMut });
     quote! {
         if !#ident.to_account_info().is_writable {
-            return Err(anchor_lang::__private::ErrorCode::ConstraintMut.into());
+            return Err(#error);
         }
     }
 }
@@ -161,23 +519,28 @@ pub fn generate_constraint_has_one(f: &Field, c: &ConstraintHasOne) -> proc_macr
     let target = c.join_target.clone();
     let ident = &f.ident;
     let field = match &f.ty {
-        Ty::Loader(_) => quote! {#ident.load()?},
+        Ty::Loader(_) | Ty::LazyAccount(_) => quote! {#ident.load()?},
         _ => quote! {#ident},
     };
+    let error = generate_custom_error(ident, &c.error, quote! { ConstraintHasOne });
     quote! {
         if &#field.#target != #target.to_account_info().key {
-            return Err(anchor_lang::__private::ErrorCode::ConstraintHasOne.into());
+            return Err(#error);
         }
     }
 }
 
-pub fn generate_constraint_signer(f: &Field, _c: &ConstraintSigner) -> proc_macro2::TokenStream {
+pub fn generate_constraint_signer(f: &Field, c: &ConstraintSigner) -> proc_macro2::TokenStream {
     let ident = &f.ident;
     let info = match f.ty {
         Ty::AccountInfo => quote! { #ident },
+        Ty::Signer => quote! { #ident.to_account_info() },
+        Ty::SystemAccount => quote! { #ident.to_account_info() },
+        Ty::UncheckedAccount => quote! { #ident.to_account_info() },
         Ty::ProgramAccount(_) => quote! { #ident.to_account_info() },
         _ => panic!("Invalid syntax: signer cannot be specified."),
     };
+    let error = generate_custom_error(ident, &c.error, quote! { ConstraintSigner });
     quote! {
         // Don't enforce on CPI, since usually a program is signing and so
         // the `try_accounts` deserializatoin will fail *if* the one
@@ -186,7 +549,7 @@ pub fn generate_constraint_signer(f: &Field, _c: &ConstraintSigner) -> proc_macr
         // This check will be performed on the other end of the invocation.
         if cfg!(not(feature = "cpi")) {
             if !#info.to_account_info().is_signer {
-                return Err(anchor_lang::__private::ErrorCode::ConstraintSigner.into());
+                return Err(#error);
             }
         }
     }
@@ -210,11 +573,12 @@ pub fn generate_constraint_literal(c: &ConstraintLiteral) -> proc_macro2::TokenS
     }
 }
 
-pub fn generate_constraint_raw(c: &ConstraintRaw) -> proc_macro2::TokenStream {
+pub fn generate_constraint_raw(ident: &Ident, c: &ConstraintRaw) -> proc_macro2::TokenStream {
     let raw = &c.raw;
+    let error = generate_custom_error(ident, &c.error, quote! { ConstraintRaw });
     quote! {
         if !(#raw) {
-            return Err(anchor_lang::__private::ErrorCode::ConstraintRaw.into());
+            return Err(#error);
         }
     }
 }
@@ -222,9 +586,11 @@ pub fn generate_constraint_raw(c: &ConstraintRaw) -> proc_macro2::TokenStream {
 pub fn generate_constraint_owner(f: &Field, c: &ConstraintOwner) -> proc_macro2::TokenStream {
     let ident = &f.ident;
     let owner_target = c.owner_target.clone();
+    let error = generate_custom_error(ident, &c.error, quote! { ConstraintOwner });
     quote! {
-        if #ident.to_account_info().owner != #owner_target.to_account_info().key {
-            return Err(anchor_lang::__private::ErrorCode::ConstraintOwner.into());
+        // The target is either an account or a `Pubkey` expression.
+        if #ident.to_account_info().owner != &anchor_lang::Key::key(&#owner_target) {
+            return Err(#error);
         }
     }
 }
@@ -234,6 +600,7 @@ pub fn generate_constraint_rent_exempt(
     c: &ConstraintRentExempt,
 ) -> proc_macro2::TokenStream {
     let ident = &f.ident;
+    let rent_exempt_error = generate_error(ident, quote! { ConstraintRentExempt });
     let info = quote! {
         #ident.to_account_info()
     };
@@ -241,7 +608,7 @@ pub fn generate_constraint_rent_exempt(
         ConstraintRentExempt::Skip => quote! {},
         ConstraintRentExempt::Enforce => quote! {
             if !rent.is_exempt(#info.lamports(), #info.try_data_len()?) {
-                return Err(anchor_lang::__private::ErrorCode::ConstraintRentExempt.into());
+                return Err(#rent_exempt_error);
             }
         },
     }
@@ -266,6 +633,10 @@ fn generate_constraint_seeds_init(f: &Field, c: &ConstraintSeedsGroup) -> proc_m
     let seeds_with_nonce = {
         let s = &c.seeds;
         match c.bump.as_ref() {
+            // The canonical bump found by the seeds constraint.
+            None if c.find_bump => quote! {
+                [#s, &[__bump]]
+            },
             None => quote! {
                 [#s]
             },
@@ -290,10 +661,31 @@ fn generate_constraint_seeds_address(
     c: &ConstraintSeedsGroup,
 ) -> proc_macro2::TokenStream {
     let name = &f.ident;
+    let seeds_error = generate_error(name, quote! { ConstraintSeeds });
+
+    // PDAs of other programs are derived against the given program id.
+    let program = match &c.program_seed {
+        None => quote! { program_id },
+        Some(p) => quote! { &#p },
+    };
 
-    // If the bump is provided on *initialization*, then force it to be the
-    // canonical nonce.
-    if c.is_init && c.bump.is_some() {
+    if c.find_bump {
+        // No bump was given, so find the canonical one and record it so that
+        // it's available to the handler via `ctx.bumps`.
+        let s = &c.seeds;
+        quote! {
+            let (__program_signer, __bump) = anchor_lang::solana_program::pubkey::Pubkey::find_program_address(
+                &[#s],
+                #program,
+            );
+            __bumps.insert(stringify!(#name).to_string(), __bump);
+            if #name.to_account_info().key != &__program_signer {
+                return Err(#seeds_error);
+            }
+        }
+    } else if c.is_init && c.bump.is_some() {
+        // If the bump is provided on *initialization*, then force it to be the
+        // canonical nonce.
         let s = &c.seeds;
         let b = c.bump.as_ref().unwrap();
         quote! {
@@ -302,10 +694,10 @@ fn generate_constraint_seeds_address(
                 program_id,
             );
             if #name.to_account_info().key != &__program_signer {
-                return Err(anchor_lang::__private::ErrorCode::ConstraintSeeds.into());
+                return Err(#seeds_error);
             }
             if __bump != #b {
-                return Err(anchor_lang::__private::ErrorCode::ConstraintSeeds.into());
+                return Err(#seeds_error);
             }
         }
     } else {
@@ -326,10 +718,10 @@ fn generate_constraint_seeds_address(
         quote! {
             let __program_signer = Pubkey::create_program_address(
                 &#seeds,
-                program_id,
-            ).map_err(|_| anchor_lang::__private::ErrorCode::ConstraintSeeds)?;
+                #program,
+            ).map_err(|_| #seeds_error)?;
             if #name.to_account_info().key != &__program_signer {
-                return Err(anchor_lang::__private::ErrorCode::ConstraintSeeds.into());
+                return Err(#seeds_error);
             }
         }
     }
@@ -393,6 +785,29 @@ pub fn generate_constraint_associated_init(
     )
 }
 
+// Binds the account created by an init constraint to the field's name. A
+// boxed account is created by a non-inlined helper and moved straight to
+// the heap, so that it never takes up space in the frame of `try_accounts`.
+fn bind_init(
+    f: &Field,
+    ty: proc_macro2::TokenStream,
+    init: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let field = &f.ident;
+    match f.is_boxed {
+        false => quote! {
+            let #field: #ty = {
+                #init
+            };
+        },
+        true => quote! {
+            let #field: Box<#ty> = anchor_lang::__private::boxed(|| Ok({
+                #init
+            }))?;
+        },
+    }
+}
+
 fn parse_ty(f: &Field) -> (proc_macro2::TokenStream, proc_macro2::TokenStream, bool) {
     match &f.ty {
         Ty::ProgramAccount(ty) => {
@@ -452,23 +867,38 @@ pub fn generate_pda(
     kind: &PdaKind,
 ) -> proc_macro2::TokenStream {
     let field = &f.ident;
+    let owner_error = generate_error(field, quote! { ConstraintOwner });
+    let token_mint_error = generate_error(field, quote! { ConstraintTokenMint });
+    let token_owner_error = generate_error(field, quote! { ConstraintTokenOwner });
+    let space_error = generate_error(field, quote! { ConstraintSpace });
     let (account_ty, account_wrapper_ty, is_zero_copy) = parse_ty(f);
 
     let space = match space {
         // If no explicit space param was given, serialize the type to bytes
-        // and take the length (with +8 for the discriminator.)
+        // and take the length, plus the length of the discriminator and, for
+        // versioned accounts, the version.
         None => match is_zero_copy {
             false => {
                 quote! {
-                    let space = 8 + #account_ty::default().try_to_vec().unwrap().len();
+                    let space = <#account_ty as anchor_lang::Discriminator>::DISCRIMINATOR.len()
+                        + <#account_ty as anchor_lang::AccountSerialize>::VERSION_LEN
+                        + #account_ty::default().try_to_vec().unwrap().len();
                 }
             }
             true => {
                 quote! {
-                    let space = 8 + anchor_lang::__private::bytemuck::bytes_of(&#account_ty::default()).len();
+                    let space = <#account_ty as anchor_lang::Discriminator>::DISCRIMINATOR.len()
+                        + anchor_lang::__private::bytemuck::bytes_of(&#account_ty::default()).len();
                 }
             }
         },
+        // Account size calculated by `#[derive(InitSpace)]`, plus the length
+        // of the discriminator and version.
+        Some(Expr::Path(p)) if p.path.is_ident("auto") => quote! {
+            let space = <#account_ty as anchor_lang::Discriminator>::DISCRIMINATOR.len()
+                + <#account_ty as anchor_lang::AccountSerialize>::VERSION_LEN
+                + <#account_ty as anchor_lang::Space>::INIT_SPACE;
+        },
         // Explicit account size given. Use it.
         Some(s) => quote! {
             let space = #s;
@@ -491,7 +921,7 @@ pub fn generate_pda(
         },
     };
 
-    let (combined_account_ty, try_from) = match f.ty {
+    let (combined_account_ty, try_from, try_from_existing) = match f.ty {
         Ty::AccountInfo => (
             quote! {
                 AccountInfo
@@ -499,6 +929,9 @@ pub fn generate_pda(
             quote! {
                 #field.to_account_info()
             },
+            quote! {
+                #field.to_account_info()
+            },
         ),
         _ => (
             quote! {
@@ -509,16 +942,22 @@ pub fn generate_pda(
                     &#field.to_account_info(),
                 )?
             },
+            quote! {
+                #account_wrapper_ty::try_from(
+                    &#field.to_account_info(),
+                )?
+            },
         ),
     };
 
-    match kind {
-        PdaKind::Token { owner, mint } => quote! {
-            let #field: #combined_account_ty = {
-                #space
-                #payer
-                #seeds_constraint
+    // With `init_if_needed`, the account is only created when it's still
+    // owned by the system program. Otherwise, it's deserialized as an
+    // existing account and checked against the expected owner and space.
+    let if_needed = f.constraints.is_init_if_needed();
 
+    match kind {
+        PdaKind::Token { owner, mint } => {
+            let create = quote! {
                 // Fund the account for rent exemption.
                 let required_lamports = rent
                     .minimum_balance(anchor_spl::token::TokenAccount::LEN)
@@ -547,7 +986,7 @@ pub fn generate_pda(
                     ),
                     &[
                         #field.to_account_info(),
-                        system_program.clone(),
+                        system_program.to_account_info(),
                     ],
                     &[&#seeds_with_nonce[..]],
                 )?;
@@ -568,19 +1007,173 @@ pub fn generate_pda(
 
                 // Initialize the token account.
                 let cpi_program = token_program.to_account_info();
-                let accounts = anchor_spl::token::InitializeAccount {
+                let accounts = anchor_spl::token::InitializeAccount3 {
                     account: #field.to_account_info(),
                     mint: #mint.to_account_info(),
                     authority: #owner.to_account_info(),
-                    rent: rent.to_account_info(),
                 };
                 let cpi_ctx = CpiContext::new(cpi_program, accounts);
-                anchor_spl::token::initialize_account(cpi_ctx)?;
+                anchor_spl::token::initialize_account3(cpi_ctx)?;
                 anchor_lang::CpiAccount::try_from_init(
                     &#field.to_account_info(),
                 )?
             };
-        },
+            let create_or_load = match if_needed {
+                false => create,
+                true => quote! {
+                    if #field.to_account_info().owner == &anchor_lang::solana_program::system_program::ID {
+                        #create
+                    } else {
+                        if #field.to_account_info().owner != token_program.to_account_info().key {
+                            return Err(#owner_error);
+                        }
+                        let pa: #combined_account_ty = anchor_lang::CpiAccount::try_from(
+                            &#field.to_account_info(),
+                        )?;
+                        if &pa.mint != #mint.to_account_info().key {
+                            return Err(#token_mint_error);
+                        }
+                        if &pa.owner != #owner.to_account_info().key {
+                            return Err(#token_owner_error);
+                        }
+                        pa
+                    }
+                },
+            };
+            bind_init(
+                f,
+                combined_account_ty,
+                quote! {
+                    #space
+                    #payer
+                    #seeds_constraint
+                    #create_or_load
+                },
+            )
+        }
+        PdaKind::Mint {
+            decimals,
+            authority,
+            freeze_authority,
+            token_program,
+        } => {
+            let token_program = match token_program {
+                None => quote! { token_program },
+                Some(p) => quote! { #p },
+            };
+            let freeze_authority_key = match freeze_authority {
+                None => quote! { None },
+                Some(f) => quote! { Some(#f.to_account_info().key) },
+            };
+            let mint_decimals_error = generate_error(field, quote! { ConstraintMintDecimals });
+            let mint_authority_error =
+                generate_error(field, quote! { ConstraintMintMintAuthority });
+            let mint_freeze_authority_error =
+                generate_error(field, quote! { ConstraintMintFreezeAuthority });
+            let freeze_authority_check = freeze_authority.as_ref().map(|f| {
+                quote! {
+                    if pa.freeze_authority != anchor_lang::solana_program::program_option::COption::Some(*#f.to_account_info().key) {
+                        return Err(#mint_freeze_authority_error);
+                    }
+                }
+            });
+            let create = quote! {
+                // Fund the account for rent exemption.
+                let required_lamports = rent
+                    .minimum_balance(anchor_spl::token::Mint::LEN)
+                    .max(1)
+                    .saturating_sub(#field.to_account_info().lamports());
+                if required_lamports > 0 {
+                    anchor_lang::solana_program::program::invoke(
+                        &anchor_lang::solana_program::system_instruction::transfer(
+                            payer.to_account_info().key,
+                            #field.to_account_info().key,
+                            required_lamports,
+                        ),
+                        &[
+                            payer.to_account_info(),
+                            #field.to_account_info(),
+                            system_program.to_account_info().clone(),
+                        ],
+                    )?;
+                }
+
+                // Allocate space.
+                anchor_lang::solana_program::program::invoke_signed(
+                    &anchor_lang::solana_program::system_instruction::allocate(
+                        #field.to_account_info().key,
+                        anchor_spl::token::Mint::LEN as u64,
+                    ),
+                    &[
+                        #field.to_account_info(),
+                        system_program.to_account_info(),
+                    ],
+                    &[&#seeds_with_nonce[..]],
+                )?;
+
+                // Assign to the token program.
+                let __ix = anchor_lang::solana_program::system_instruction::assign(
+                    #field.to_account_info().key,
+                    #token_program.to_account_info().key,
+                );
+                anchor_lang::solana_program::program::invoke_signed(
+                    &__ix,
+                    &[
+                        #field.to_account_info(),
+                        system_program.to_account_info(),
+                    ],
+                    &[&#seeds_with_nonce[..]],
+                )?;
+
+                // Initialize the mint.
+                let cpi_program = #token_program.to_account_info();
+                let accounts = anchor_spl::token_interface::InitializeMint2 {
+                    mint: #field.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new(cpi_program, accounts);
+                anchor_spl::token_interface::initialize_mint2(
+                    cpi_ctx,
+                    #decimals,
+                    #authority.to_account_info().key,
+                    #freeze_authority_key,
+                )?;
+                anchor_lang::CpiAccount::try_from_init(
+                    &#field.to_account_info(),
+                )?
+            };
+            let create_or_load = match if_needed {
+                false => create,
+                true => quote! {
+                    if #field.to_account_info().owner == &anchor_lang::solana_program::system_program::ID {
+                        #create
+                    } else {
+                        if #field.to_account_info().owner != #token_program.to_account_info().key {
+                            return Err(#owner_error);
+                        }
+                        let pa: #combined_account_ty = anchor_lang::CpiAccount::try_from(
+                            &#field.to_account_info(),
+                        )?;
+                        if pa.decimals != #decimals {
+                            return Err(#mint_decimals_error);
+                        }
+                        if pa.mint_authority != anchor_lang::solana_program::program_option::COption::Some(*#authority.to_account_info().key) {
+                            return Err(#mint_authority_error);
+                        }
+                        #freeze_authority_check
+                        pa
+                    }
+                },
+            };
+            bind_init(
+                f,
+                combined_account_ty,
+                quote! {
+                    #payer
+                    #seeds_constraint
+                    #create_or_load
+                },
+            )
+        }
         PdaKind::Program { owner } => {
             // Owner of the account being created. If not specified,
             // default to the currently executing program.
@@ -592,43 +1185,64 @@ pub fn generate_pda(
                     &#o
                 },
             };
-            quote! {
-                let #field = {
-                    #space
-                    #payer
-                    #seeds_constraint
+            let create = quote! {
+                let lamports = rent.minimum_balance(space);
+                let ix = anchor_lang::solana_program::system_instruction::create_account(
+                    payer.to_account_info().key,
+                    #field.to_account_info().key,
+                    lamports,
+                    space as u64,
+                    #owner,
+                );
 
-                    let lamports = rent.minimum_balance(space);
-                    let ix = anchor_lang::solana_program::system_instruction::create_account(
-                        payer.to_account_info().key,
-                        #field.to_account_info().key,
-                        lamports,
-                        space as u64,
-                        #owner,
-                    );
+                anchor_lang::solana_program::program::invoke_signed(
+                    &ix,
+                    &[
 
-                    anchor_lang::solana_program::program::invoke_signed(
-                        &ix,
-                        &[
+                        #field.to_account_info(),
+                        payer.to_account_info(),
+                        system_program.to_account_info(),
+                    ],
+                    &[&#seeds_with_nonce[..]]
+                ).map_err(|e| {
+                    anchor_lang::solana_program::msg!("Unable to create associated account");
+                    e
+                })?;
 
-                            #field.to_account_info(),
-                            payer.to_account_info(),
-                            system_program.to_account_info(),
-                        ],
-                        &[&#seeds_with_nonce[..]]
-                    ).map_err(|e| {
-                        anchor_lang::solana_program::msg!("Unable to create associated account");
-                        e
-                    })?;
-
-                    // For now, we assume all accounts created with the `associated`
-                    // attribute have a `nonce` field in their account.
-                    let mut pa: #combined_account_ty = #try_from;
-
-                    #nonce_assignment
-                    pa
-                };
-            }
+                // For now, we assume all accounts created with the `associated`
+                // attribute have a `nonce` field in their account.
+                let mut pa: #combined_account_ty = #try_from;
+
+                #nonce_assignment
+                pa
+            };
+            let create_or_load = match if_needed {
+                false => create,
+                true => quote! {
+                    if #field.to_account_info().owner == &anchor_lang::solana_program::system_program::ID {
+                        #create
+                    } else {
+                        if #field.to_account_info().owner != #owner {
+                            return Err(#owner_error);
+                        }
+                        if #field.to_account_info().try_data_len()? != space {
+                            return Err(#space_error);
+                        }
+                        let pa: #combined_account_ty = #try_from_existing;
+                        pa
+                    }
+                },
+            };
+            bind_init(
+                f,
+                combined_account_ty,
+                quote! {
+                    #space
+                    #payer
+                    #seeds_constraint
+                    #create_or_load
+                },
+            )
         }
     }
 }
@@ -638,6 +1252,7 @@ pub fn generate_constraint_associated_seeds(
     c: &ConstraintAssociatedGroup,
 ) -> proc_macro2::TokenStream {
     let field = &f.ident;
+    let associated_init_error = generate_error(field, quote! { ConstraintAssociatedInit });
     let associated_target = c.associated_target.clone();
     let seeds_no_nonce = if c.associated_seeds.is_empty() {
         quote! {
@@ -655,7 +1270,7 @@ pub fn generate_constraint_associated_seeds(
 
     let is_find_nonce = match &f.ty {
         Ty::CpiAccount(_) => true,
-        Ty::AccountInfo => true,
+        Ty::AccountInfo | Ty::Signer | Ty::SystemAccount | Ty::UncheckedAccount => true,
         _ => c.is_init,
     };
     let associated_field = if is_find_nonce {
@@ -684,7 +1299,7 @@ pub fn generate_constraint_associated_seeds(
     quote! {
         #associated_field
         if &__associated_field != #field.to_account_info().key {
-            return Err(anchor_lang::__private::ErrorCode::ConstraintAssociatedInit.into());
+            return Err(#associated_init_error);
         }
     }
 }
@@ -694,16 +1309,45 @@ pub fn generate_constraint_executable(
     _c: &ConstraintExecutable,
 ) -> proc_macro2::TokenStream {
     let name = &f.ident;
+    let executable_error = generate_error(name, quote! { ConstraintExecutable });
     quote! {
         if !#name.to_account_info().executable {
-            return Err(anchor_lang::__private::ErrorCode::ConstraintExecutable.into());
+            return Err(#executable_error);
         }
     }
 }
 
+pub fn generate_constraint_instruction_sysvar(
+    f: &Field,
+    _c: &ConstraintInstructionSysvar,
+) -> proc_macro2::TokenStream {
+    let name = &f.ident;
+    let error = generate_error(name, quote! { ConstraintInstructionSysvar });
+    quote! {
+        if #name.to_account_info().key != &anchor_lang::solana_program::sysvar::instructions::ID {
+            return Err(#error);
+        }
+        #[allow(unused_variables)]
+        let __instructions_sysvar = #name.to_account_info();
+        #[allow(unused_variables)]
+        let prev_instruction = || anchor_lang::introspection::prev_instruction(&__instructions_sysvar);
+        #[allow(unused_variables)]
+        let next_instruction = || anchor_lang::introspection::next_instruction(&__instructions_sysvar);
+        #[allow(unused_variables)]
+        let prev_instruction_is = |program_id: &anchor_lang::solana_program::pubkey::Pubkey, discriminator: &[u8]| {
+            anchor_lang::introspection::prev_instruction_is(&__instructions_sysvar, program_id, discriminator)
+        };
+        #[allow(unused_variables)]
+        let next_instruction_is = |program_id: &anchor_lang::solana_program::pubkey::Pubkey, discriminator: &[u8]| {
+            anchor_lang::introspection::next_instruction_is(&__instructions_sysvar, program_id, discriminator)
+        };
+    }
+}
+
 pub fn generate_constraint_state(f: &Field, c: &ConstraintState) -> proc_macro2::TokenStream {
     let program_target = c.program_target.clone();
     let ident = &f.ident;
+    let state_error = generate_error(ident, quote! { ConstraintState });
     let account_ty = match &f.ty {
         Ty::CpiState(ty) => &ty.account_type_path,
         _ => panic!("Invalid state constraint"),
@@ -712,10 +1356,10 @@ pub fn generate_constraint_state(f: &Field, c: &ConstraintState) -> proc_macro2:
         // Checks the given state account is the canonical state account for
         // the target program.
         if #ident.to_account_info().key != &anchor_lang::CpiState::<#account_ty>::address(#program_target.to_account_info().key) {
-            return Err(anchor_lang::__private::ErrorCode::ConstraintState.into());
+            return Err(#state_error);
         }
         if #ident.to_account_info().owner != #program_target.to_account_info().key {
-            return Err(anchor_lang::__private::ErrorCode::ConstraintState.into());
+            return Err(#state_error);
         }
     }
 }
@@ -746,3 +1390,30 @@ fn to_seeds_tts(seeds: &[syn::Expr]) -> proc_macro2::TokenStream {
     }
     tts
 }
+
+// Returns the error for a violated constraint: the custom error given with
+// `@`, if any, or the given framework default. The account's name and the
+// location of the `Accounts` struct are logged before it's returned.
+fn generate_custom_error(
+    account: &Ident,
+    custom: &Option<Expr>,
+    default: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let error = match custom {
+        Some(error) => quote! { #error },
+        None => quote! { anchor_lang::__private::ErrorCode::#default },
+    };
+    quote! {
+        anchor_lang::__private::account_error(
+            #error,
+            stringify!(#account),
+            file!(),
+            line!(),
+        )
+    }
+}
+
+// Returns the given framework error for a violated constraint.
+fn generate_error(account: &Ident, default: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    generate_custom_error(account, &None, default)
+}
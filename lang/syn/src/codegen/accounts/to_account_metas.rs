@@ -9,18 +9,30 @@ pub fn generate(accs: &AccountsStruct) -> proc_macro2::TokenStream {
         .fields
         .iter()
         .map(|f: &AccountField| {
-            let (name, is_signer) = match f {
-                AccountField::CompositeField(s) => (&s.ident, quote! {None}),
+            let (name, is_signer, is_optional) = match f {
+                AccountField::CompositeField(s) => (&s.ident, quote! {None}, false),
                 AccountField::Field(f) => {
-                    let is_signer = match f.constraints.is_signer() {
+                    let is_signer = match f.is_signer() {
                         false => quote! {None},
                         true => quote! {Some(true)},
                     };
-                    (&f.ident, is_signer)
+                    (&f.ident, is_signer, f.is_optional)
                 }
             };
-            quote! {
-                account_metas.extend(self.#name.to_account_metas(#is_signer));
+            match is_optional {
+                false => quote! {
+                    account_metas.extend(self.#name.to_account_metas(#is_signer));
+                },
+                // Absent optional accounts are replaced by the program id, so
+                // that the accounts that follow keep their positions.
+                true => quote! {
+                    match &self.#name {
+                        Some(account) => account_metas.extend(account.to_account_metas(#is_signer)),
+                        None => account_metas.push(
+                            anchor_lang::solana_program::instruction::AccountMeta::new_readonly(crate::ID, false),
+                        ),
+                    }
+                },
             }
         })
         .collect();
@@ -23,10 +23,22 @@ pub fn generate(accs: &AccountsStruct) -> proc_macro2::TokenStream {
                 AccountField::CompositeField(s) => {
                     let name = &s.ident;
                     let ty = &s.raw_field.ty;
-                    quote! {
-                        #[cfg(feature = "anchor-debug")]
-                        ::solana_program::log::sol_log(stringify!(#name));
-                        let #name: #ty = anchor_lang::Accounts::try_accounts(program_id, accounts, ix_data)?;
+                    match s.constraints.scope() {
+                        None => quote! {
+                            #[cfg(feature = "anchor-debug")]
+                            ::solana_program::log::sol_log(stringify!(#name));
+                            let #name: #ty = anchor_lang::Accounts::try_accounts(program_id, accounts, ix_data, __bumps)?;
+                        },
+                        // Scoped structs take the values they depend on as
+                        // arguments, evaluated against the fields before them.
+                        Some(scope) => {
+                            let args = &scope.args;
+                            quote! {
+                                #[cfg(feature = "anchor-debug")]
+                                ::solana_program::log::sol_log(stringify!(#name));
+                                let #name: #ty = <#ty>::try_accounts_scoped(program_id, accounts, ix_data, __bumps, #args)?;
+                            }
+                        }
                     }
                 }
                 AccountField::Field(f) => {
@@ -41,11 +53,34 @@ pub fn generate(accs: &AccountsStruct) -> proc_macro2::TokenStream {
                         }
                     } else {
                         let name = typed_ident(f);
+                        if f.constraints.is_init_if_needed() {
+                            // Only initialize the account if its discriminator
+                            // hasn't been set yet. Otherwise, deserialize it as
+                            // an existing account.
+                            let discriminator_len = discriminator_len(f);
+                            return quote! {
+                                #[cfg(feature = "anchor-debug")]
+                                ::solana_program::log::sol_log(stringify!(#name));
+                                let #name = {
+                                    let __is_initialized = match accounts.get(0) {
+                                        None => false,
+                                        Some(__acc) => {
+                                            let __data = __acc.try_borrow_data()?;
+                                            __data.iter().take(#discriminator_len).any(|b| *b != 0)
+                                        }
+                                    };
+                                    match __is_initialized {
+                                        true => anchor_lang::Accounts::try_accounts(program_id, accounts, ix_data, __bumps)?,
+                                        false => anchor_lang::AccountsInit::try_accounts_init(program_id, accounts)?,
+                                    }
+                                };
+                            };
+                        }
                         match f.constraints.is_init() {
                             false => quote! {
                                 #[cfg(feature = "anchor-debug")]
                                 ::solana_program::log::sol_log(stringify!(#name));
-                                let #name = anchor_lang::Accounts::try_accounts(program_id, accounts, ix_data)?;
+                                let #name = anchor_lang::Accounts::try_accounts(program_id, accounts, ix_data, __bumps)?;
                             },
                             true => quote! {
                                 #[cfg(feature = "anchor-debug")]
@@ -92,29 +127,61 @@ pub fn generate(accs: &AccountsStruct) -> proc_macro2::TokenStream {
         }
     };
 
-    quote! {
-        #[automatically_derived]
-        impl<#combined_generics> anchor_lang::Accounts<#trait_generics> for #name<#struct_generics> #where_clause {
-            #[inline(never)]
-            fn try_accounts(
-                program_id: &anchor_lang::solana_program::pubkey::Pubkey,
-                accounts: &mut &[anchor_lang::solana_program::account_info::AccountInfo<'info>],
-                ix_data: &[u8],
-            ) -> std::result::Result<Self, anchor_lang::solana_program::program_error::ProgramError> {
-                // Deserialize instruction, if declared.
-                #ix_de
-                // Deserialize each account.
-                #(#deser_fields)*
-                // Execute accounts constraints.
-                #constraints
-                // Success. Return the validated accounts.
-                Ok(#accounts_instance)
+    let body = quote! {
+        // Deserialize instruction, if declared.
+        #ix_de
+        // Deserialize each account.
+        #(#deser_fields)*
+        // Execute accounts constraints.
+        #constraints
+        // Success. Return the validated accounts.
+        Ok(#accounts_instance)
+    };
+
+    match &accs.scope {
+        None => quote! {
+            #[automatically_derived]
+            impl<#combined_generics> anchor_lang::Accounts<#trait_generics> for #name<#struct_generics> #where_clause {
+                #[inline(never)]
+                fn try_accounts(
+                    program_id: &anchor_lang::solana_program::pubkey::Pubkey,
+                    accounts: &mut &[anchor_lang::solana_program::account_info::AccountInfo<'info>],
+                    ix_data: &[u8],
+                    __bumps: &mut std::collections::BTreeMap<String, u8>,
+                ) -> std::result::Result<Self, anchor_lang::solana_program::program_error::ProgramError> {
+                    #body
+                }
+            }
+        },
+        // A scoped struct can only be embedded in another accounts struct,
+        // which provides the scope, so it doesn't implement `Accounts`.
+        Some(scope) => {
+            let scope_args = scope.iter().map(|expr: &Expr| match expr {
+                Expr::Type(_) => quote! { #expr },
+                _ => panic!("Invalid scope declaration"),
+            });
+            quote! {
+                #[automatically_derived]
+                impl<#combined_generics> #name<#struct_generics> #where_clause {
+                    #[inline(never)]
+                    pub fn try_accounts_scoped(
+                        program_id: &anchor_lang::solana_program::pubkey::Pubkey,
+                        accounts: &mut &[anchor_lang::solana_program::account_info::AccountInfo<'info>],
+                        ix_data: &[u8],
+                        __bumps: &mut std::collections::BTreeMap<String, u8>,
+                        #(#scope_args),*
+                    ) -> std::result::Result<Self, anchor_lang::solana_program::program_error::ProgramError> {
+                        #body
+                    }
+                }
             }
         }
     }
 }
 
-// Returns true if the given AccountField has an associated init constraint.
+// Returns true if the given AccountField has an associated init constraint,
+// or is a token account or mint initialized in place or at its associated
+// address.
 fn is_pda_init(af: &AccountField) -> bool {
     match af {
         AccountField::CompositeField(_s) => false,
@@ -129,6 +196,21 @@ fn is_pda_init(af: &AccountField) -> bool {
                     .as_ref()
                     .map(|f| f.is_init)
                     .unwrap_or(false)
+                || f.constraints
+                    .token_account
+                    .as_ref()
+                    .map(|f| f.is_init)
+                    .unwrap_or(false)
+                || f.constraints
+                    .associated_token
+                    .as_ref()
+                    .map(|f| f.is_init)
+                    .unwrap_or(false)
+                || f.constraints
+                    .mint
+                    .as_ref()
+                    .map(|f| f.is_init)
+                    .unwrap_or(false)
         }
     }
 }
@@ -138,6 +220,21 @@ fn typed_ident(field: &Field) -> TokenStream {
 
     let ty = match &field.ty {
         Ty::AccountInfo => quote! { AccountInfo },
+        Ty::Signer => quote! { Signer },
+        Ty::SystemAccount => quote! { SystemAccount },
+        Ty::UncheckedAccount => quote! { UncheckedAccount },
+        Ty::Program(ty) => {
+            let program = &ty.account_type_path;
+            quote! {
+                Program<#program>
+            }
+        }
+        Ty::Interface(ty) => {
+            let interface = &ty.account_type_path;
+            quote! {
+                Interface<#interface>
+            }
+        }
         Ty::ProgramState(ty) => {
             let account = &ty.account_type_path;
             quote! {
@@ -162,12 +259,24 @@ fn typed_ident(field: &Field) -> TokenStream {
                 Loader<#account>
             }
         }
+        Ty::LazyAccount(ty) => {
+            let account = &ty.account_type_path;
+            quote! {
+                LazyAccount<#account>
+            }
+        }
         Ty::CpiAccount(ty) => {
             let account = &ty.account_type_path;
             quote! {
                 CpiAccount<#account>
             }
         }
+        Ty::InterfaceAccount(ty) => {
+            let account = &ty.account_type_path;
+            quote! {
+                InterfaceAccount<#account>
+            }
+        }
         Ty::Sysvar(ty) => {
             let account = match ty {
                 SysvarTy::Clock => quote! {Clock},
@@ -185,13 +294,44 @@ fn typed_ident(field: &Field) -> TokenStream {
                 Sysvar<#account>
             }
         }
+        Ty::SyscallSysvar(ty) => match ty {
+            SysvarTy::Clock => quote! { Clock },
+            SysvarTy::Rent => quote! { Rent },
+            _ => panic!("Invalid syntax: only Clock and Rent are loaded via syscall"),
+        },
     };
 
+    // Boxed accounts are deserialized straight onto the heap, by the
+    // non-inlined `Accounts` and `AccountsInit` implementations for `Box`.
+    let ty = match field.is_boxed {
+        false => ty,
+        true => quote! {
+            Box<#ty>
+        },
+    };
+    if field.is_optional {
+        return quote! {
+            #name: Option<#ty>
+        };
+    }
     quote! {
         #name: #ty
     }
 }
 
+// Returns the length of the discriminator of the field's account type. Plain
+// `AccountInfo`s have none, so their first 8 bytes are checked instead.
+fn discriminator_len(field: &Field) -> TokenStream {
+    let account = match &field.ty {
+        Ty::ProgramAccount(ty) => &ty.account_type_path,
+        Ty::Loader(ty) => &ty.account_type_path,
+        _ => return quote! { 8 },
+    };
+    quote! {
+        <#account as anchor_lang::Discriminator>::DISCRIMINATOR.len()
+    }
+}
+
 pub fn generate_constraints(accs: &AccountsStruct) -> proc_macro2::TokenStream {
     // All fields without an `#[account(associated)]` attribute.
     let non_associated_fields: Vec<&AccountField> =
@@ -221,25 +361,54 @@ pub fn generate_constraints(accs: &AccountsStruct) -> proc_macro2::TokenStream {
         })
         .collect();
 
+    let rent = generate_rent(accs);
+
     quote! {
+        #rent
         #(#init_associated_fields)*
         #(#access_checks)*
     }
 }
 
+// Loads the rent sysvar via syscall when a constraint requires it but the
+// struct doesn't declare a `rent` account, so that clients needn't pass one.
+fn generate_rent(accs: &AccountsStruct) -> proc_macro2::TokenStream {
+    let fields = accs.fields.iter().filter_map(|af| match af {
+        AccountField::CompositeField(_) => None,
+        AccountField::Field(f) => Some(f),
+    });
+    let mut has_rent_account = false;
+    let mut requires_rent = false;
+    for f in fields {
+        has_rent_account |= f.ident == "rent";
+        requires_rent |= f.constraints.requires_rent();
+    }
+    if has_rent_account || !requires_rent {
+        return quote! {};
+    }
+    quote! {
+        let rent = <anchor_lang::solana_program::sysvar::rent::Rent as anchor_lang::solana_program::sysvar::Sysvar>::get()?;
+    }
+}
+
 pub fn generate_accounts_instance(accs: &AccountsStruct) -> proc_macro2::TokenStream {
     let name = &accs.ident;
     // Each field in the final deserialized accounts struct.
     let return_tys: Vec<proc_macro2::TokenStream> = accs
         .fields
         .iter()
-        .map(|f: &AccountField| {
-            let name = match f {
-                AccountField::CompositeField(s) => &s.ident,
-                AccountField::Field(f) => &f.ident,
-            };
-            quote! {
-                #name
+        .map(|f: &AccountField| match f {
+            AccountField::CompositeField(s) => {
+                let name = &s.ident;
+                quote! {
+                    #name
+                }
+            }
+            AccountField::Field(f) => {
+                let name = &f.ident;
+                quote! {
+                    #name
+                }
             }
         })
         .collect();
@@ -0,0 +1,565 @@
+use crate::codegen::program::common::{sighash, SIGHASH_GLOBAL_NAMESPACE};
+use crate::idl::*;
+use crate::DeclareProgramArgs;
+use heck::{CamelCase, SnakeCase};
+use quote::{format_ident, quote};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use syn::parse::{Error as ParseError, Result as ParseResult};
+
+// Generates the bindings of `declare_program!`, i.e., a module named after
+// the program with the program's id, accounts, types, events, errors,
+// instruction data, and CPI helpers, all derived from its IDL.
+pub fn generate(args: &DeclareProgramArgs) -> ParseResult<proc_macro2::TokenStream> {
+    let name = &args.name;
+    let idl_path = match &args.path {
+        None => find_idl(&name.to_string()).ok_or_else(|| {
+            ParseError::new(
+                name.span(),
+                format!("idls/{}.json not found in any parent directory", name),
+            )
+        })?,
+        Some(path) => Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join(path.value()),
+    };
+    let idl: Idl = std::fs::read_to_string(&idl_path)
+        .map_err(|e| e.to_string())
+        .and_then(|idl| serde_json::from_str(&idl).map_err(|e| e.to_string()))
+        .map_err(|e| ParseError::new(name.span(), format!("invalid IDL: {}", e)))?;
+    let address = idl
+        .metadata
+        .as_ref()
+        .and_then(|m| m.get("address"))
+        .and_then(|a| a.as_str())
+        .ok_or_else(|| ParseError::new(name.span(), "the IDL has no metadata.address"))?;
+
+    let idl_path = idl_path.display().to_string();
+    let program_ident = format_ident!("{}", name.to_string().to_camel_case());
+    let accounts = generate_accounts(&idl);
+    let types = generate_types(&idl.types);
+    let events = generate_events(&idl);
+    let errors = generate_errors(&idl);
+    let instruction = generate_instruction(&idl);
+    let cpi = generate_cpi(&idl);
+
+    Ok(quote! {
+        pub mod #name {
+            use anchor_lang::prelude::*;
+
+            // Rebuild whenever the IDL changes.
+            const _: &str = include_str!(#idl_path);
+
+            anchor_lang::solana_program::declare_id!(#address);
+
+            /// The program, for use with `Program<'info, _>`.
+            #[derive(Clone)]
+            pub struct #program_ident;
+
+            impl anchor_lang::Id for #program_ident {
+                fn id() -> Pubkey {
+                    ID
+                }
+            }
+
+            #accounts
+            #types
+            #events
+            #errors
+            #instruction
+            #cpi
+        }
+    })
+}
+
+// Looks for `idls/<name>.json`, starting from the directory of the crate
+// being compiled and moving up, so that a workspace can share its IDLs.
+fn find_idl(name: &str) -> Option<PathBuf> {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").ok()?;
+    let mut dir = Some(Path::new(&manifest_dir));
+    while let Some(d) = dir {
+        let path = d.join("idls").join(format!("{}.json", name));
+        if path.exists() {
+            return Some(path);
+        }
+        dir = d.parent();
+    }
+    None
+}
+
+fn generate_accounts(idl: &Idl) -> proc_macro2::TokenStream {
+    let accounts: Vec<proc_macro2::TokenStream> = idl
+        .accounts
+        .iter()
+        .map(|acc| {
+            let name = format_ident!("{}", acc.name);
+            // Sha256("account:<name>")[..8], as for the `#[account]` attribute,
+            // unless the IDL gives it explicitly.
+            let discriminator = match &acc.discriminator {
+                Some(discriminator) => discriminator.to_vec(),
+                None => sighash("account", &acc.name).to_vec(),
+            };
+            let disc_len = discriminator.len();
+            let discriminator = bytes(&discriminator);
+            let strct = generate_type_definition(acc);
+            let version_len = acc.version.map(|_| {
+                quote! {
+                    const VERSION_LEN: usize = 1;
+                }
+            });
+            // Versioned accounts store their version after the discriminator.
+            // Older versions can't be migrated without the program's code.
+            let (write_version, deserialize) = match acc.version {
+                None => (
+                    quote! {},
+                    quote! {
+                        let mut data: &[u8] = &buf[#disc_len..];
+                        AnchorDeserialize::deserialize(&mut data)
+                            .map_err(|_| anchor_lang::__private::ErrorCode::AccountDidNotDeserialize.into())
+                    },
+                ),
+                Some(version) => (
+                    quote! {
+                        writer.write_all(&[#version]).map_err(|_| anchor_lang::__private::ErrorCode::AccountDidNotSerialize)?;
+                    },
+                    quote! {
+                        let version = *buf
+                            .get(#disc_len)
+                            .ok_or(anchor_lang::__private::ErrorCode::AccountDidNotDeserialize)?;
+                        if version != 0 && version != #version {
+                            return Err(anchor_lang::__private::ErrorCode::AccountVersionNotSupported.into());
+                        }
+                        let mut data: &[u8] = &buf[#disc_len + 1..];
+                        AnchorDeserialize::deserialize(&mut data)
+                            .map_err(|_| anchor_lang::__private::ErrorCode::AccountDidNotDeserialize.into())
+                    },
+                ),
+            };
+            quote! {
+                #strct
+
+                impl anchor_lang::Discriminator for #name {
+                    const DISCRIMINATOR: &'static [u8] = &#discriminator;
+                }
+
+                impl anchor_lang::AccountSerialize for #name {
+                    #version_len
+
+                    fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> std::result::Result<(), ProgramError> {
+                        writer.write_all(&#discriminator).map_err(|_| anchor_lang::__private::ErrorCode::AccountDidNotSerialize)?;
+                        #write_version
+                        AnchorSerialize::serialize(self, writer)
+                            .map_err(|_| anchor_lang::__private::ErrorCode::AccountDidNotSerialize)?;
+                        Ok(())
+                    }
+                }
+
+                impl anchor_lang::AccountDeserialize for #name {
+                    fn try_deserialize(buf: &mut &[u8]) -> std::result::Result<Self, ProgramError> {
+                        if buf.len() < #discriminator.len() {
+                            return Err(anchor_lang::__private::ErrorCode::AccountDiscriminatorNotFound.into());
+                        }
+                        if #discriminator != buf[..#disc_len] {
+                            return Err(anchor_lang::__private::ErrorCode::AccountDiscriminatorMismatch.into());
+                        }
+                        Self::try_deserialize_unchecked(buf)
+                    }
+
+                    fn try_deserialize_unchecked(buf: &mut &[u8]) -> std::result::Result<Self, ProgramError> {
+                        #deserialize
+                    }
+                }
+            }
+        })
+        .collect();
+    quote! {
+        /// Accounts owned by the program.
+        pub mod accounts {
+            use super::*;
+            use super::types::*;
+
+            #(#accounts)*
+        }
+    }
+}
+
+fn generate_types(types: &[IdlTypeDefinition]) -> proc_macro2::TokenStream {
+    let types = types.iter().map(generate_type_definition);
+    quote! {
+        /// Types used by the program's accounts, instructions, and events.
+        pub mod types {
+            use super::*;
+            use super::accounts::*;
+
+            #(#types)*
+        }
+    }
+}
+
+fn generate_events(idl: &Idl) -> proc_macro2::TokenStream {
+    let events: Vec<proc_macro2::TokenStream> = idl
+        .events
+        .iter()
+        .flatten()
+        .map(|event| {
+            let name = format_ident!("{}", event.name);
+            let fields = event.fields.iter().map(|f| {
+                let name = format_ident!("{}", f.name.to_snake_case());
+                let ty = generate_ty(&f.ty);
+                quote! { pub #name: #ty }
+            });
+            let discriminator = bytes(&event.discriminator.unwrap_or_else(|| {
+                let mut discriminator = [0u8; 8];
+                discriminator.copy_from_slice(
+                    &crate::hash::hash(format!("event:{}", event.name).as_bytes()).to_bytes()[..8],
+                );
+                discriminator
+            }));
+            quote! {
+                #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+                pub struct #name {
+                    #(#fields),*
+                }
+
+                impl anchor_lang::Event for #name {
+                    fn data(&self) -> Vec<u8> {
+                        let mut d = #discriminator.to_vec();
+                        d.append(&mut self.try_to_vec().unwrap());
+                        d
+                    }
+                }
+
+                impl anchor_lang::Discriminator for #name {
+                    const DISCRIMINATOR: &'static [u8] = &#discriminator;
+                }
+            }
+        })
+        .collect();
+    quote! {
+        /// Events emitted by the program, e.g., for use with
+        /// `anchor_lang::event::decode_event`.
+        pub mod events {
+            use super::*;
+            use super::types::*;
+
+            #(#events)*
+        }
+    }
+}
+
+fn generate_errors(idl: &Idl) -> proc_macro2::TokenStream {
+    let codes: Vec<&IdlErrorCode> = idl.errors.iter().flatten().collect();
+    // A `#[repr(u32)]` enum can't be empty.
+    if codes.is_empty() {
+        return quote! {};
+    }
+    let variants = codes.iter().map(|code| {
+        let name = format_ident!("{}", code.name);
+        let id = code.code;
+        quote! { #name = #id }
+    });
+    let msgs = codes.iter().map(|code| {
+        let name = format_ident!("{}", code.name);
+        let msg = code.msg.clone().unwrap_or_else(|| code.name.clone());
+        quote! { ErrorCode::#name => #msg }
+    });
+    quote! {
+        /// Errors returned by the program. The discriminants are the codes
+        /// given in the IDL, i.e., they already include the error offset.
+        pub mod errors {
+            use super::*;
+
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            #[repr(u32)]
+            pub enum ErrorCode {
+                #(#variants),*
+            }
+
+            impl std::fmt::Display for ErrorCode {
+                fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    let msg = match self {
+                        #(#msgs),*
+                    };
+                    write!(fmt, "{}", msg)
+                }
+            }
+
+            impl From<ErrorCode> for ProgramError {
+                fn from(e: ErrorCode) -> ProgramError {
+                    ProgramError::Custom(e as u32)
+                }
+            }
+        }
+    }
+}
+
+fn generate_instruction(idl: &Idl) -> proc_macro2::TokenStream {
+    let ixs = idl.instructions.iter().map(|ix| {
+        let name = format_ident!("{}", ix.name.to_camel_case());
+        let namespace = ix.interface.as_deref().unwrap_or(SIGHASH_GLOBAL_NAMESPACE);
+        let sighash = bytes(&sighash(namespace, &ix.name.to_snake_case()));
+        let fields = ix.args.iter().map(|arg| {
+            let name = format_ident!("{}", arg.name.to_snake_case());
+            let ty = generate_ty(&arg.ty);
+            quote! { pub #name: #ty }
+        });
+        quote! {
+            #[derive(AnchorSerialize, AnchorDeserialize)]
+            pub struct #name {
+                #(#fields),*
+            }
+
+            impl anchor_lang::InstructionData for #name {
+                fn data(&self) -> Vec<u8> {
+                    let mut d = #sighash.to_vec();
+                    d.append(&mut self.try_to_vec().expect("Should always serialize"));
+                    d
+                }
+            }
+        }
+    });
+    quote! {
+        /// Instruction data, for building instructions for the program.
+        pub mod instruction {
+            use super::*;
+            use super::types::*;
+
+            #(#ixs)*
+        }
+    }
+}
+
+fn generate_cpi(idl: &Idl) -> proc_macro2::TokenStream {
+    // Composite accounts are shared by name between instructions.
+    let mut composites = HashMap::new();
+    let accounts_strcts: Vec<proc_macro2::TokenStream> = idl
+        .instructions
+        .iter()
+        .map(|ix| generate_cpi_accounts(&ix.name, &ix.accounts, &mut composites))
+        .collect();
+    let composites = composites.values();
+
+    let methods = idl.instructions.iter().map(|ix| {
+        let method_name = format_ident!("{}", ix.name.to_snake_case());
+        let accounts_ident = format_ident!("{}", ix.name.to_camel_case());
+        let args = ix.args.iter().map(|arg| {
+            let name = format_ident!("{}", arg.name.to_snake_case());
+            let ty = generate_ty(&arg.ty);
+            quote! { #name: #ty }
+        });
+        let arg_names = ix
+            .args
+            .iter()
+            .map(|arg| format_ident!("{}", arg.name.to_snake_case()));
+        let (ret_ty, ret_value) = match &ix.returns {
+            None => (quote! { ProgramResult }, quote! {}),
+            Some(ty) => {
+                let ty = generate_ty(ty);
+                (
+                    quote! { std::result::Result<anchor_lang::Return<#ty>, ProgramError> },
+                    quote! { .map(|_| anchor_lang::Return::new(*ctx.program.key)) },
+                )
+            }
+        };
+        quote! {
+            pub fn #method_name<'a, 'b, 'c, 'info>(
+                ctx: CpiContext<'a, 'b, 'c, 'info, accounts::#accounts_ident<'info>>,
+                #(#args),*
+            ) -> #ret_ty {
+                let ix = anchor_lang::solana_program::instruction::Instruction {
+                    program_id: *ctx.program.key,
+                    accounts: ctx.to_account_metas(None),
+                    data: anchor_lang::InstructionData::data(&instruction::#accounts_ident {
+                        #(#arg_names),*
+                    }),
+                };
+                let mut acc_infos = ctx.to_account_infos();
+                acc_infos.push(ctx.program.clone());
+                anchor_lang::solana_program::program::invoke_signed(
+                    &ix,
+                    &acc_infos,
+                    ctx.signer_seeds,
+                )#ret_value
+            }
+        }
+    });
+
+    quote! {
+        /// Helpers for invoking the program via CPI.
+        pub mod cpi {
+            use super::*;
+            use super::types::*;
+
+            /// The accounts of each instruction, for use with `CpiContext`.
+            pub mod accounts {
+                use super::*;
+
+                #(#accounts_strcts)*
+                #(#composites)*
+            }
+
+            #(#methods)*
+        }
+    }
+}
+
+fn generate_cpi_accounts(
+    name: &str,
+    accounts: &[IdlAccountItem],
+    composites: &mut HashMap<String, proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    let strct_name = format_ident!("{}", name.to_camel_case());
+    let mut fields = vec![];
+    let mut to_acc_metas = vec![];
+    let mut to_acc_infos = vec![];
+    for item in accounts {
+        let (field, ty, is_signer, is_optional) = match item {
+            IdlAccountItem::IdlAccount(acc) => {
+                let ty = match acc.is_optional {
+                    false => quote! { AccountInfo<'info> },
+                    true => quote! { Option<AccountInfo<'info>> },
+                };
+                let is_signer = match acc.is_signer {
+                    false => quote! { None },
+                    true => quote! { Some(true) },
+                };
+                (&acc.name, ty, is_signer, acc.is_optional)
+            }
+            IdlAccountItem::IdlAccounts(accs) => {
+                if !composites.contains_key(&accs.name) {
+                    let composite = generate_cpi_accounts(&accs.name, &accs.accounts, composites);
+                    composites.insert(accs.name.clone(), composite);
+                }
+                let composite_name = format_ident!("{}", accs.name.to_camel_case());
+                (
+                    &accs.name,
+                    quote! { #composite_name<'info> },
+                    quote! { None },
+                    false,
+                )
+            }
+        };
+        let field = format_ident!("{}", field.to_snake_case());
+        fields.push(quote! { pub #field: #ty });
+        to_acc_metas.push(match is_optional {
+            false => quote! {
+                account_metas.extend(self.#field.to_account_metas(#is_signer));
+            },
+            // The program's id stands in for absent optional accounts.
+            true => quote! {
+                match &self.#field {
+                    Some(account) => account_metas.extend(account.to_account_metas(#is_signer)),
+                    None => account_metas.push(AccountMeta::new_readonly(ID, false)),
+                }
+            },
+        });
+        to_acc_infos.push(quote! {
+            account_infos.extend(self.#field.to_account_infos());
+        });
+    }
+    quote! {
+        pub struct #strct_name<'info> {
+            #(#fields),*
+        }
+
+        impl<'info> anchor_lang::ToAccountMetas for #strct_name<'info> {
+            fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+                let mut account_metas = vec![];
+                #(#to_acc_metas)*
+                account_metas
+            }
+        }
+
+        impl<'info> anchor_lang::ToAccountInfos<'info> for #strct_name<'info> {
+            fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+                let mut account_infos = vec![];
+                #(#to_acc_infos)*
+                account_infos
+            }
+        }
+    }
+}
+
+fn generate_type_definition(ty_def: &IdlTypeDefinition) -> proc_macro2::TokenStream {
+    let name = format_ident!("{}", ty_def.name);
+    match &ty_def.ty {
+        IdlTypeDefinitionTy::Struct { fields } => {
+            let fields = fields.iter().map(|f| {
+                let name = format_ident!("{}", f.name.to_snake_case());
+                let ty = generate_ty(&f.ty);
+                quote! { pub #name: #ty }
+            });
+            quote! {
+                #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+                pub struct #name {
+                    #(#fields),*
+                }
+            }
+        }
+        IdlTypeDefinitionTy::Enum { variants } => {
+            let variants = variants.iter().map(|v| {
+                let name = format_ident!("{}", v.name);
+                match &v.fields {
+                    None => quote! { #name },
+                    Some(EnumFields::Named(fields)) => {
+                        let fields = fields.iter().map(|f| {
+                            let name = format_ident!("{}", f.name.to_snake_case());
+                            let ty = generate_ty(&f.ty);
+                            quote! { #name: #ty }
+                        });
+                        quote! { #name { #(#fields),* } }
+                    }
+                    Some(EnumFields::Tuple(tys)) => {
+                        let tys = tys.iter().map(generate_ty);
+                        quote! { #name(#(#tys),*) }
+                    }
+                }
+            });
+            quote! {
+                #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+                pub enum #name {
+                    #(#variants),*
+                }
+            }
+        }
+    }
+}
+
+fn generate_ty(ty: &IdlType) -> proc_macro2::TokenStream {
+    match ty {
+        IdlType::Bool => quote! { bool },
+        IdlType::U8 => quote! { u8 },
+        IdlType::I8 => quote! { i8 },
+        IdlType::U16 => quote! { u16 },
+        IdlType::I16 => quote! { i16 },
+        IdlType::U32 => quote! { u32 },
+        IdlType::I32 => quote! { i32 },
+        IdlType::U64 => quote! { u64 },
+        IdlType::I64 => quote! { i64 },
+        IdlType::U128 => quote! { u128 },
+        IdlType::I128 => quote! { i128 },
+        IdlType::Bytes => quote! { Vec<u8> },
+        IdlType::String => quote! { String },
+        IdlType::PublicKey => quote! { Pubkey },
+        IdlType::Defined(name) => {
+            // Types are flattened into the `types` module, so only the last
+            // path segment is used.
+            let name = format_ident!("{}", name.rsplit("::").next().unwrap());
+            quote! { #name }
+        }
+        IdlType::Option(ty) => {
+            let ty = generate_ty(ty);
+            quote! { Option<#ty> }
+        }
+        IdlType::Vec(ty) => {
+            let ty = generate_ty(ty);
+            quote! { Vec<#ty> }
+        }
+        IdlType::Array(ty, len) => {
+            let ty = generate_ty(ty);
+            quote! { [#ty; #len] }
+        }
+    }
+}
+
+fn bytes(bytes: &[u8]) -> proc_macro2::TokenStream {
+    format!("{:?}", bytes).parse().unwrap()
+}
@@ -32,6 +32,20 @@ pub fn generate(error: Error) -> proc_macro2::TokenStream {
         })
         .collect();
 
+    // Each arm converting an error code, including the offset, back into its
+    // variant.
+    let code_dispatch: Vec<proc_macro2::TokenStream> = error
+        .raw_enum
+        .variants
+        .iter()
+        .map(|variant| {
+            let ident = &variant.ident;
+            quote! {
+                code if code == #enum_name::#ident as u32 + __offset => Ok(#enum_name::#ident)
+            }
+        })
+        .collect();
+
     let offset = match error.args {
         None => quote! { anchor_lang::__private::ERROR_CODE_OFFSET},
         Some(args) => {
@@ -71,6 +85,34 @@ pub fn generate(error: Error) -> proc_macro2::TokenStream {
 
         impl std::error::Error for #enum_name {}
 
+        impl std::convert::TryFrom<u32> for #enum_name {
+            type Error = u32;
+
+            fn try_from(code: u32) -> std::result::Result<Self, u32> {
+                let __offset: u32 = #offset;
+                match code {
+                    #(#code_dispatch,)*
+                    code => Err(code),
+                }
+            }
+        }
+
+        impl std::convert::TryFrom<anchor_lang::solana_program::program_error::ProgramError> for #enum_name {
+            type Error = anchor_lang::solana_program::program_error::ProgramError;
+
+            fn try_from(
+                e: anchor_lang::solana_program::program_error::ProgramError,
+            ) -> std::result::Result<Self, anchor_lang::solana_program::program_error::ProgramError> {
+                match e {
+                    anchor_lang::solana_program::program_error::ProgramError::Custom(code) => {
+                        <Self as std::convert::TryFrom<u32>>::try_from(code)
+                            .map_err(|_| anchor_lang::solana_program::program_error::ProgramError::Custom(code))
+                    }
+                    e => Err(e),
+                }
+            }
+        }
+
         impl std::convert::From<Error> for anchor_lang::solana_program::program_error::ProgramError {
             fn from(e: Error) -> anchor_lang::solana_program::program_error::ProgramError {
                 match e {
@@ -86,5 +128,18 @@ pub fn generate(error: Error) -> proc_macro2::TokenStream {
                 err.into()
             }
         }
+
+        impl std::convert::From<#enum_name> for anchor_lang::error::Error {
+            fn from(e: #enum_name) -> anchor_lang::error::Error {
+                let program_error: anchor_lang::solana_program::program_error::ProgramError = e.into();
+                anchor_lang::error::Error::from(program_error).with_message(e)
+            }
+        }
+
+        impl std::convert::From<anchor_lang::error::Error> for Error {
+            fn from(e: anchor_lang::error::Error) -> Error {
+                Error::ProgramError(e.into())
+            }
+        }
     }
 }
@@ -1,3 +1,5 @@
 pub mod accounts;
+#[cfg(feature = "idl")]
+pub mod declare_program;
 pub mod error;
 pub mod program;
@@ -0,0 +1,123 @@
+use crate::codegen::program::common::generate_ix_variant;
+use crate::Program;
+use crate::StateIx;
+use quote::quote;
+
+pub fn generate(program: &Program) -> proc_macro2::TokenStream {
+    // Generate builders for the state struct's methods. Like CPI, the Ctor is
+    // left out, since it is a one time use function.
+    let state_builders: Vec<proc_macro2::TokenStream> = program
+        .state
+        .as_ref()
+        .and_then(|state| state.impl_block_and_methods.as_ref())
+        .map(|(_, methods)| {
+            methods
+                .iter()
+                .map(|method: &StateIx| {
+                    let accounts_ident = &method.anchor_ident;
+                    let ix_variant =
+                        generate_ix_variant(method.raw_method.sig.ident.to_string(), &method.args);
+                    let method_name = &method.ident;
+                    let args: Vec<&syn::PatType> =
+                        method.args.iter().map(|arg| &arg.raw_arg).collect();
+                    quote! {
+                        pub fn #method_name(
+                            program_id: &anchor_lang::solana_program::pubkey::Pubkey,
+                            accounts: super::super::accounts::#accounts_ident,
+                            #(#args),*
+                        ) -> anchor_lang::solana_program::instruction::Instruction {
+                            super::super::instruction(
+                                program_id,
+                                accounts,
+                                super::super::args::state::#ix_variant,
+                            )
+                        }
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    // Generate builders for global methods.
+    let global_builders: Vec<proc_macro2::TokenStream> = program
+        .ixs
+        .iter()
+        .map(|ix| {
+            let accounts_ident = &ix.anchor_ident;
+            let ix_variant = generate_ix_variant(ix.raw_method.sig.ident.to_string(), &ix.args);
+            let method_name = &ix.ident;
+            let args: Vec<&syn::PatType> = ix.args.iter().map(|arg| &arg.raw_arg).collect();
+            quote! {
+                pub fn #method_name(
+                    program_id: &anchor_lang::solana_program::pubkey::Pubkey,
+                    accounts: super::accounts::#accounts_ident,
+                    #(#args),*
+                ) -> anchor_lang::solana_program::instruction::Instruction {
+                    super::instruction(program_id, accounts, super::args::#ix_variant)
+                }
+            }
+        })
+        .collect();
+
+    quote! {
+        /// An Anchor generated module for building the program's instructions
+        /// off-chain, enabled by the `client` feature, given the pubkey of each
+        /// account and the instruction's arguments, e.g.,
+        ///
+        /// ```ignore
+        /// let ix = my_program::client::instructions::initialize(
+        ///     &program_id,
+        ///     my_program::client::accounts::Initialize {
+        ///         data: data_pubkey,
+        ///         authority: authority_pubkey,
+        ///     },
+        ///     5,
+        /// );
+        /// ```
+        #[cfg(feature = "client")]
+        pub mod client {
+            /// Structs mirroring the structs deriving `Accounts`, where each
+            /// field is a `Pubkey`, implementing `ToAccountMetas`. These are
+            /// generated by `#[derive(Accounts)]` and aggregated by the
+            /// program's `accounts` module, which is re-exported here.
+            pub mod accounts {
+                pub use crate::accounts::*;
+            }
+
+            /// Structs for the arguments of each instruction, implementing
+            /// `InstructionData`. This is the program's `instruction` module,
+            /// re-exported here.
+            pub mod args {
+                pub use crate::instruction::*;
+            }
+
+            /// Builders for each instruction, taking the instruction's
+            /// accounts and its arguments, in order.
+            pub mod instructions {
+                use super::super::*;
+
+                #(#global_builders)*
+
+                /// Builders for `#[state]` methods.
+                pub mod state {
+                    use super::*;
+
+                    #(#state_builders)*
+                }
+            }
+
+            /// Returns the instruction invoking `program_id` with the given
+            /// accounts and arguments.
+            pub fn instruction(
+                program_id: &anchor_lang::solana_program::pubkey::Pubkey,
+                accounts: impl anchor_lang::ToAccountMetas,
+                args: impl anchor_lang::InstructionData,
+            ) -> anchor_lang::solana_program::instruction::Instruction {
+                anchor_lang::solana_program::instruction::Instruction {
+                    program_id: *program_id,
+                    accounts: accounts.to_account_metas(None),
+                    data: args.data(),
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::parser;
-use crate::{IxArg, State};
+use crate::{Ix, IxArg, State};
 use heck::CamelCase;
 use quote::quote;
 
@@ -22,6 +22,15 @@ pub fn sighash(namespace: &str, name: &str) -> [u8; 8] {
     sighash
 }
 
+// Namespace for calculating a global instruction's sighash, which is the trait
+// name if the instruction implements an `#[interface]` method.
+pub fn ix_namespace(ix: &Ix) -> String {
+    ix.interface
+        .as_ref()
+        .map(|iface| iface.to_string())
+        .unwrap_or_else(|| SIGHASH_GLOBAL_NAMESPACE.to_string())
+}
+
 pub fn sighash_ctor() -> [u8; 8] {
     sighash(SIGHASH_STATE_NAMESPACE, "new")
 }
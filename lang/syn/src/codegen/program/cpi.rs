@@ -1,4 +1,4 @@
-use crate::codegen::program::common::{generate_ix_variant, sighash, SIGHASH_GLOBAL_NAMESPACE};
+use crate::codegen::program::common::{generate_ix_variant, ix_namespace, sighash};
 use crate::Program;
 use crate::StateIx;
 use quote::quote;
@@ -18,6 +18,7 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
                         .iter()
                         .map(|method: &StateIx| {
                             let accounts_ident = &method.anchor_ident;
+                            let accounts_generics = &method.anchor_generics;
                             let ix_variant = generate_ix_variant(
                                 method.raw_method.sig.ident.to_string(),
                                 &method.args,
@@ -28,7 +29,7 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
 
                             quote! {
                                 pub fn #method_name<'a, 'b, 'c, 'info>(
-                                    ctx: CpiStateContext<'a, 'b, 'c, 'info, #accounts_ident<'info>>,
+                                    ctx: CpiStateContext<'a, 'b, 'c, 'info, #accounts_ident<'info, #(#accounts_generics),*>>,
                                     #(#args),*
                                 ) -> ProgramResult {
                                     let ix = {
@@ -61,19 +62,36 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
         .iter()
         .map(|ix| {
             let accounts_ident = &ix.anchor_ident;
+            let accounts_generics = &ix.anchor_generics;
             let cpi_method = {
                 let ix_variant = generate_ix_variant(ix.raw_method.sig.ident.to_string(), &ix.args);
                 let method_name = &ix.ident;
                 let args: Vec<&syn::PatType> = ix.args.iter().map(|arg| &arg.raw_arg).collect();
                 let name = &ix.raw_method.sig.ident.to_string();
-                let sighash_arr = sighash(SIGHASH_GLOBAL_NAMESPACE, name);
+                let sighash_arr = sighash(&ix_namespace(ix), name);
                 let sighash_tts: proc_macro2::TokenStream =
                     format!("{:?}", sighash_arr).parse().unwrap();
+                // Handlers returning a value give the caller a handle to
+                // deserialize it from the return data.
+                let (ret_ty, ret_value) = match &ix.returns {
+                    None => (quote! { ProgramResult }, quote! {}),
+                    Some(ty) => (
+                        quote! {
+                            std::result::Result<
+                                anchor_lang::Return<#ty>,
+                                anchor_lang::solana_program::program_error::ProgramError,
+                            >
+                        },
+                        quote! {
+                            .map(|_| anchor_lang::Return::new(*ctx.program.key))
+                        },
+                    ),
+                };
                 quote! {
                     pub fn #method_name<'a, 'b, 'c, 'info>(
-                        ctx: CpiContext<'a, 'b, 'c, 'info, #accounts_ident<'info>>,
+                        ctx: CpiContext<'a, 'b, 'c, 'info, #accounts_ident<'info, #(#accounts_generics),*>>,
                         #(#args),*
-                    ) -> ProgramResult {
+                    ) -> #ret_ty {
                         let ix = {
                             let ix = instruction::#ix_variant;
                             let mut ix_data = AnchorSerialize::try_to_vec(&ix)
@@ -93,7 +111,7 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
                             &ix,
                             &acc_infos,
                             ctx.signer_seeds,
-                        )
+                        )#ret_value
                     }
                 }
             };
@@ -102,9 +120,25 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
         })
         .collect();
     quote! {
+        /// An Anchor generated module for invoking the program from other
+        /// programs, enabled by the `cpi` feature, which also removes the
+        /// program's entrypoint, so that the crate can be used as a
+        /// dependency, e.g.,
+        ///
+        /// ```ignore
+        /// my_program::cpi::initialize(
+        ///     CpiContext::new(program, my_program::cpi::Initialize { .. }),
+        ///     value,
+        /// )?;
+        /// ```
+        ///
+        /// The program's `Accounts` structs, account types, and instruction
+        /// data structs, under `instruction`, are all re-exported here.
         #[cfg(feature = "cpi")]
         pub mod cpi {
-            use super::*;
+            // Re-exports the program's public items, e.g., its error enum and
+            // events, so that callers can decode them from CPIs.
+            pub use super::*;
 
             pub mod state {
                 use super::*;
@@ -99,7 +99,7 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
         .iter()
         .map(|ix| {
             let ix_method_name = &ix.raw_method.sig.ident;
-            let sighash_arr = sighash(SIGHASH_GLOBAL_NAMESPACE, &ix_method_name.to_string());
+            let sighash_arr = sighash(&ix_namespace(ix), &ix_method_name.to_string());
             let sighash_tts: proc_macro2::TokenStream =
                 format!("{:?}", sighash_arr).parse().unwrap();
             quote! {
@@ -162,6 +162,16 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
                 }
             }
 
+            // If the method identifier is the event tag, then the program
+            // invoked itself to emit an event via `emit_cpi!`.
+            if sighash == anchor_lang::event::EVENT_IX_TAG_LE {
+                return __private::__events::__event_dispatch(
+                    program_id,
+                    accounts,
+                    &ix_data,
+                );
+            }
+
             match sighash {
                 #ctor_state_dispatch_arm
                 #(#state_dispatch_arms)*
@@ -180,8 +190,10 @@ pub fn gen_fallback(program: &Program) -> Option<proc_macro2::TokenStream> {
         let program_name = &program.name;
         let method = &fallback_fn.raw_method;
         let fn_name = &method.sig.ident;
+        // The fallback may return either a `ProgramResult` or an
+        // `anchor_lang::error::Result`.
         quote! {
-            #program_name::#fn_name(program_id, accounts, data)
+            #program_name::#fn_name(program_id, accounts, data).map_err(Into::into)
         }
     })
 }
@@ -6,8 +6,23 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
     let fallback_maybe = dispatch::gen_fallback(program).unwrap_or(quote! {
         Err(anchor_lang::__private::ErrorCode::InstructionMissing.into());
     });
+    let custom_entry_maybe = program.args.as_ref().map(|args| {
+        let custom_entry = &args.custom_entry;
+        quote! {
+            let handled: bool = #custom_entry(program_id, accounts, data)
+                .map_err(Into::<ProgramError>::into)?;
+            if handled {
+                return Ok(());
+            }
+        }
+    });
     quote! {
-        #[cfg(not(feature = "no-entrypoint"))]
+        // The entrypoint defines the program's global allocator and panic
+        // handler, so it's left out whenever the crate is a dependency, even
+        // if the `cpi` feature doesn't enable `no-entrypoint`. The `entry`
+        // function itself is always available, e.g., for running the program
+        // natively in tests.
+        #[cfg(not(any(feature = "no-entrypoint", feature = "cpi")))]
         anchor_lang::solana_program::entrypoint!(entry);
         /// The Anchor codegen exposes a programming model where a user defines
         /// a set of methods inside of a `#[program]` module in a way similar
@@ -31,6 +46,8 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
         /// The execution flow of the generated code can be roughly outlined:
         ///
         /// * Start program via the entrypoint.
+        /// * If a custom entry was given via `#[program(custom_entry = ..)]`,
+        ///   invoke it. If it handled the instruction, then exit.
         /// * Strip method identifier off the first 8 bytes of the instruction
         ///   data and invoke the identified method. The method identifier
         ///   is a variant of sighash. See docs.rs for `anchor_lang` for details.
@@ -49,12 +66,12 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
         ///
         /// The `entry` function here, defines the standard entry to a Solana
         /// program, where execution begins.
-        #[cfg(not(feature = "no-entrypoint"))]
         pub fn entry(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
             #[cfg(feature = "anchor-debug")]
             {
                 msg!("anchor-debug is active");
             }
+            #custom_entry_maybe
             if data.len() < 8 {
                 return #fallback_maybe
             }
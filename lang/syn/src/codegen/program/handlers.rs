@@ -26,31 +26,31 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
                 match ix {
                     anchor_lang::idl::IdlInstruction::Create { data_len } => {
                         let mut accounts =
-                            anchor_lang::idl::IdlCreateAccounts::try_accounts(program_id, &mut accounts, &[])?;
+                            anchor_lang::idl::IdlCreateAccounts::try_accounts(program_id, &mut accounts, &[], &mut std::collections::BTreeMap::new())?;
                         __idl_create_account(program_id, &mut accounts, data_len)?;
                         accounts.exit(program_id)?;
                     },
                     anchor_lang::idl::IdlInstruction::CreateBuffer => {
                         let mut accounts =
-                            anchor_lang::idl::IdlCreateBuffer::try_accounts(program_id, &mut accounts, &[])?;
+                            anchor_lang::idl::IdlCreateBuffer::try_accounts(program_id, &mut accounts, &[], &mut std::collections::BTreeMap::new())?;
                         __idl_create_buffer(program_id, &mut accounts)?;
                         accounts.exit(program_id)?;
                     },
                     anchor_lang::idl::IdlInstruction::Write { data } => {
                         let mut accounts =
-                            anchor_lang::idl::IdlAccounts::try_accounts(program_id, &mut accounts, &[])?;
+                            anchor_lang::idl::IdlAccounts::try_accounts(program_id, &mut accounts, &[], &mut std::collections::BTreeMap::new())?;
                         __idl_write(program_id, &mut accounts, data)?;
                         accounts.exit(program_id)?;
                     },
                     anchor_lang::idl::IdlInstruction::SetAuthority { new_authority } => {
                         let mut accounts =
-                            anchor_lang::idl::IdlAccounts::try_accounts(program_id, &mut accounts, &[])?;
+                            anchor_lang::idl::IdlAccounts::try_accounts(program_id, &mut accounts, &[], &mut std::collections::BTreeMap::new())?;
                         __idl_set_authority(program_id, &mut accounts, new_authority)?;
                         accounts.exit(program_id)?;
                     },
                     anchor_lang::idl::IdlInstruction::SetBuffer => {
                         let mut accounts =
-                            anchor_lang::idl::IdlSetBuffer::try_accounts(program_id, &mut accounts, &[])?;
+                            anchor_lang::idl::IdlSetBuffer::try_accounts(program_id, &mut accounts, &[], &mut std::collections::BTreeMap::new())?;
                         __idl_set_buffer(program_id, &mut accounts)?;
                         accounts.exit(program_id)?;
                     },
@@ -192,8 +192,9 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
 
                             // Deserialize accounts.
                             let mut remaining_accounts: &[AccountInfo] = accounts;
-                            let ctor_accounts = anchor_lang::__private::Ctor::try_accounts(program_id, &mut remaining_accounts, &[])?;
-                            let mut ctor_user_def_accounts = #anchor_ident::try_accounts(program_id, &mut remaining_accounts, ix_data)?;
+                            let ctor_accounts = anchor_lang::__private::Ctor::try_accounts(program_id, &mut remaining_accounts, &[], &mut std::collections::BTreeMap::new())?;
+                            let mut __bumps = std::collections::BTreeMap::new();
+                            let mut ctor_user_def_accounts = #anchor_ident::try_accounts(program_id, &mut remaining_accounts, ix_data, &mut __bumps)?;
 
                             // Create the solana account for the ctor data.
                             let from = ctor_accounts.from.key;
@@ -237,6 +238,7 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
                                         program_id,
                                         &mut ctor_user_def_accounts,
                                         remaining_accounts,
+                                        __bumps,
                                     ),
                                     #(#ctor_untyped_args),*
                                 )?;
@@ -262,8 +264,9 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
 
                             // Deserialize accounts.
                             let mut remaining_accounts: &[AccountInfo] = accounts;
-                            let ctor_accounts = anchor_lang::__private::Ctor::try_accounts(program_id, &mut remaining_accounts, &[])?;
-                            let mut ctor_user_def_accounts = #anchor_ident::try_accounts(program_id, &mut remaining_accounts, ix_data)?;
+                            let ctor_accounts = anchor_lang::__private::Ctor::try_accounts(program_id, &mut remaining_accounts, &[], &mut std::collections::BTreeMap::new())?;
+                            let mut __bumps = std::collections::BTreeMap::new();
+                            let mut ctor_user_def_accounts = #anchor_ident::try_accounts(program_id, &mut remaining_accounts, ix_data, &mut __bumps)?;
 
                             // Invoke the ctor.
                             let instance = #mod_name::#name::new(
@@ -271,6 +274,7 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
                                     program_id,
                                     &mut ctor_user_def_accounts,
                                     remaining_accounts,
+                                    __bumps,
                                 ),
                                 #(#ctor_untyped_args),*
                             )?;
@@ -368,12 +372,14 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
                                     remaining_accounts = &remaining_accounts[1..];
 
                                     // Deserialize accounts.
+                                    let mut __bumps = std::collections::BTreeMap::new();
                                     let mut accounts = #anchor_ident::try_accounts(
                                         program_id,
                                         &mut remaining_accounts,
                                         ix_data,
+                                        &mut __bumps,
                                     )?;
-                                    let ctx = Context::new(program_id, &mut accounts, remaining_accounts);
+                                    let ctx = Context::new(program_id, &mut accounts, remaining_accounts, __bumps);
 
                                     // Execute user defined function.
                                     {
@@ -417,12 +423,14 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
                                     remaining_accounts = &remaining_accounts[1..];
 
                                     // Deserialize accounts.
+                                    let mut __bumps = std::collections::BTreeMap::new();
                                     let mut accounts = #anchor_ident::try_accounts(
                                         program_id,
                                         &mut remaining_accounts,
                                         ix_data,
+                                        &mut __bumps,
                                     )?;
-                                    let ctx = Context::new(program_id, &mut accounts, remaining_accounts);
+                                    let ctx = Context::new(program_id, &mut accounts, remaining_accounts, __bumps);
 
                                     // Execute user defined function.
                                     state.#ix_method_name(
@@ -532,12 +540,14 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
                                             remaining_accounts = &remaining_accounts[1..];
 
                                             // Deserialize accounts.
+                                            let mut __bumps = std::collections::BTreeMap::new();
                                             let mut accounts = #anchor_ident::try_accounts(
                                                 program_id,
                                                 &mut remaining_accounts,
                                                 ix_data,
+                                                &mut __bumps,
                                             )?;
-                                            let ctx = Context::new(program_id, &mut accounts, remaining_accounts);
+                                            let ctx = Context::new(program_id, &mut accounts, remaining_accounts, __bumps);
 
                                             // Execute user defined function.
                                             state.#ix_method_name(
@@ -569,15 +579,17 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
 
                                             // Deserialize accounts.
                                             let mut remaining_accounts: &[AccountInfo] = accounts;
+                                            let mut __bumps = std::collections::BTreeMap::new();
                                             let mut accounts = #anchor_ident::try_accounts(
                                                 program_id,
                                                 &mut remaining_accounts,
                                                 ix_data,
+                                                &mut __bumps,
                                             )?;
 
                                             // Execute user defined function.
                                             #state_name::#ix_method_name(
-                                                Context::new(program_id, &mut accounts, remaining_accounts),
+                                                Context::new(program_id, &mut accounts, remaining_accounts, __bumps),
                                                 #(#ix_arg_names),*
                                             )?;
 
@@ -602,6 +614,36 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
             let ix_method_name = &ix.raw_method.sig.ident;
             let anchor = &ix.anchor_ident;
             let variant_arm = generate_ix_variant(ix.raw_method.sig.ident.to_string(), &ix.args);
+            // Handlers returning a value serialize it into the return data
+            // once the accounts have been persisted.
+            let invoke_and_exit = match &ix.returns {
+                None => quote! {
+                    // Invoke user defined handler.
+                    #program_name::#ix_method_name(
+                        Context::new(program_id, &mut accounts, remaining_accounts, __bumps),
+                        #(#ix_arg_names),*
+                    )?;
+
+                    // Exit routine.
+                    accounts.exit(program_id)
+                },
+                Some(_) => quote! {
+                    // Invoke user defined handler.
+                    let result = #program_name::#ix_method_name(
+                        Context::new(program_id, &mut accounts, remaining_accounts, __bumps),
+                        #(#ix_arg_names),*
+                    )?;
+
+                    // Exit routine.
+                    accounts.exit(program_id)?;
+
+                    // Return the result to the caller.
+                    let return_data = AnchorSerialize::try_to_vec(&result)
+                        .map_err(|_| anchor_lang::__private::ErrorCode::InstructionDidNotSerialize)?;
+                    anchor_lang::solana_program::program::set_return_data(&return_data);
+                    Ok(())
+                },
+            };
 
             quote! {
                 #[inline(never)]
@@ -617,20 +659,15 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
 
                     // Deserialize accounts.
                     let mut remaining_accounts: &[AccountInfo] = accounts;
+                    let mut __bumps = std::collections::BTreeMap::new();
                     let mut accounts = #anchor::try_accounts(
                         program_id,
                         &mut remaining_accounts,
                         ix_data,
+                        &mut __bumps,
                     )?;
 
-                    // Invoke user defined handler.
-                    #program_name::#ix_method_name(
-                        Context::new(program_id, &mut accounts, remaining_accounts),
-                        #(#ix_arg_names),*
-                    )?;
-
-                    // Exit routine.
-                    accounts.exit(program_id)
+                    #invoke_and_exit
                 }
             }
         })
@@ -649,6 +686,36 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
                 #non_inlined_idl
             }
 
+            /// __events mod defines the handler for events emitted via
+            /// self-CPI.
+            pub mod __events {
+                use super::*;
+
+                // The event is recorded by the instruction data alone, so
+                // only check it was signed by the program's event authority.
+                #[inline(never)]
+                pub fn __event_dispatch(
+                    program_id: &Pubkey,
+                    accounts: &[AccountInfo],
+                    _event_data: &[u8],
+                ) -> ProgramResult {
+                    let given_event_authority = accounts
+                        .first()
+                        .ok_or(anchor_lang::__private::ErrorCode::AccountNotEnoughKeys)?;
+                    if !given_event_authority.is_signer {
+                        return Err(anchor_lang::__private::ErrorCode::ConstraintSigner.into());
+                    }
+                    let (event_authority, _) = Pubkey::find_program_address(
+                        &[anchor_lang::event::EVENT_AUTHORITY_SEED],
+                        program_id,
+                    );
+                    if given_event_authority.key != &event_authority {
+                        return Err(anchor_lang::__private::ErrorCode::ConstraintSeeds.into());
+                    }
+                    Ok(())
+                }
+            }
+
             /// __state mod defines wrapped handlers for state instructions.
             pub mod __state {
                 use super::*;
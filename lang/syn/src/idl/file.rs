@@ -1,11 +1,11 @@
 use crate::idl::*;
 use crate::parser::context::CrateContext;
 use crate::parser::{self, accounts, error, program};
-use crate::{AccountField, AccountsStruct, StateIx};
+use crate::{AccountArgs, AccountField, AccountsStruct, ErrorArgs, EventArgs, StateIx};
 use anyhow::Result;
 use heck::MixedCase;
 use quote::ToTokens;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::path::Path;
 
 const DERIVE_NAME: &str = "Accounts";
@@ -52,6 +52,8 @@ pub fn parse(filename: impl AsRef<Path>) -> Result<Idl> {
                                     name,
                                     accounts,
                                     args,
+                                    returns: None,
+                                    interface: None,
                                 }
                             })
                             .collect::<Vec<_>>()
@@ -91,6 +93,8 @@ pub fn parse(filename: impl AsRef<Path>) -> Result<Idl> {
                         name,
                         accounts,
                         args,
+                        returns: None,
+                        interface: None,
                     }
                 };
 
@@ -116,6 +120,8 @@ pub fn parse(filename: impl AsRef<Path>) -> Result<Idl> {
                     IdlTypeDefinition {
                         name: state.name,
                         ty: IdlTypeDefinitionTy::Struct { fields },
+                        version: None,
+                        discriminator: None,
                     }
                 };
 
@@ -123,12 +129,22 @@ pub fn parse(filename: impl AsRef<Path>) -> Result<Idl> {
             }
         },
     };
-    let error = parse_error_enum(&ctx).map(|mut e| error::parse(&mut e, None));
+    let error = parse_error_enum(&ctx).map(|mut e| {
+        let args = parse_error_args(&e);
+        error::parse(&mut e, args)
+    });
+    let error_offset = error.as_ref().and_then(|e| {
+        e.args.as_ref().map(|args| {
+            args.offset
+                .base10_parse::<u32>()
+                .expect("Error offset must be a u32")
+        })
+    });
     let error_codes = error.as_ref().map(|e| {
         e.codes
             .iter()
             .map(|code| IdlErrorCode {
-                code: ERROR_CODE_OFFSET + code.id,
+                code: error_offset.unwrap_or(ERROR_CODE_OFFSET) + code.id,
                 name: code.ident.to_string(),
                 msg: code.msg.clone(),
             })
@@ -155,10 +171,17 @@ pub fn parse(filename: impl AsRef<Path>) -> Result<Idl> {
             // todo: don't unwrap
             let accounts_strct = accs.get(&ix.anchor_ident.to_string()).unwrap();
             let accounts = idl_accounts(accounts_strct, &accs);
+            let returns = ix.returns.as_ref().map(|ty| {
+                let mut tts = proc_macro2::TokenStream::new();
+                ty.to_tokens(&mut tts);
+                tts.to_string().parse().unwrap()
+            });
             IdlInstruction {
                 name: ix.ident.to_string().to_mixed_case(),
                 accounts,
                 args,
+                returns,
+                interface: ix.interface.as_ref().map(|iface| iface.to_string()),
             }
         })
         .collect::<Vec<_>>();
@@ -186,8 +209,16 @@ pub fn parse(filename: impl AsRef<Path>) -> Result<Idl> {
                 })
                 .collect::<Vec<IdlEventField>>();
 
+            let args = parse_event_args(e);
+            let name = e.ident.to_string();
             IdlEvent {
-                name: e.ident.to_string(),
+                discriminator: match args.is_custom() {
+                    true => Some(args.discriminator(&name)),
+                    false => None,
+                },
+                namespace: args.namespace,
+                version: args.version,
+                name,
                 fields,
             }
         })
@@ -198,11 +229,7 @@ pub fn parse(filename: impl AsRef<Path>) -> Result<Idl> {
     let mut types = vec![];
     let ty_defs = parse_ty_defs(&ctx)?;
 
-    let account_structs = parse_accounts(&ctx);
-    let account_names: HashSet<String> = account_structs
-        .iter()
-        .map(|a| a.ident.to_string())
-        .collect::<HashSet<_>>();
+    let account_args = parse_accounts(&ctx);
 
     let error_name = error.map(|e| e.name).unwrap_or_else(|| "".to_string());
 
@@ -210,8 +237,16 @@ pub fn parse(filename: impl AsRef<Path>) -> Result<Idl> {
     for ty_def in ty_defs {
         // Don't add the error type to the types or accounts sections.
         if ty_def.name != error_name {
-            if account_names.contains(&ty_def.name) {
-                accounts.push(ty_def);
+            if let Some(args) = account_args.get(&ty_def.name) {
+                let discriminator = match args.is_custom() {
+                    true => Some(args.discriminator(&ty_def.name)),
+                    false => None,
+                };
+                accounts.push(IdlTypeDefinition {
+                    version: args.version,
+                    discriminator,
+                    ..ty_def
+                });
             } else if !events.iter().any(|e| e.name == ty_def.name) {
                 types.push(ty_def);
             }
@@ -231,6 +266,7 @@ pub fn parse(filename: impl AsRef<Path>) -> Result<Idl> {
             Some(events)
         },
         errors: error_codes,
+        error_offset,
         metadata: None,
     })
 }
@@ -282,6 +318,21 @@ fn parse_error_enum(ctx: &CrateContext) -> Option<syn::ItemEnum> {
         .cloned()
 }
 
+fn parse_error_args(error_enum: &syn::ItemEnum) -> Option<ErrorArgs> {
+    let attr = error_enum
+        .attrs
+        .iter()
+        .find(|attr| attr.path.segments.last().unwrap().ident == "error")
+        .unwrap();
+    match attr.tokens.is_empty() {
+        true => None,
+        false => Some(
+            attr.parse_args::<ErrorArgs>()
+                .expect("Invalid error attribute"),
+        ),
+    }
+}
+
 fn parse_events(ctx: &CrateContext) -> Vec<&syn::ItemStruct> {
     ctx.structs()
         .filter_map(|item_strct| {
@@ -302,26 +353,49 @@ fn parse_events(ctx: &CrateContext) -> Vec<&syn::ItemStruct> {
         .collect()
 }
 
-fn parse_accounts(ctx: &CrateContext) -> Vec<&syn::ItemStruct> {
-    ctx.structs()
-        .filter_map(|item_strct| {
-            let attrs_count = item_strct
-                .attrs
-                .iter()
-                .filter(|attr| {
-                    let segment = attr.path.segments.last().unwrap();
-                    segment.ident == "account" || segment.ident == "associated"
-                })
-                .count();
-            match attrs_count {
-                0 => None,
-                1 => Some(item_strct),
-                _ => panic!("Invalid syntax: one event attribute allowed"),
+fn parse_event_args(event_strct: &syn::ItemStruct) -> EventArgs {
+    let attr = event_strct
+        .attrs
+        .iter()
+        .find(|attr| attr.path.segments.last().unwrap().ident == "event")
+        .unwrap();
+    match attr.tokens.is_empty() {
+        true => EventArgs::default(),
+        false => attr
+            .parse_args::<EventArgs>()
+            .expect("Invalid event attribute"),
+    }
+}
+
+// Parses all structs and enums with an account attribute, keyed by name.
+fn parse_accounts(ctx: &CrateContext) -> HashMap<String, AccountArgs> {
+    let structs = ctx.structs().map(|s| (&s.ident, &s.attrs));
+    let enums = ctx.enums().map(|e| (&e.ident, &e.attrs));
+    structs
+        .chain(enums)
+        .filter_map(|(ident, attrs)| {
+            let mut account_attrs = attrs.iter().filter(|attr| {
+                let segment = attr.path.segments.last().unwrap();
+                segment.ident == "account" || segment.ident == "associated"
+            });
+            let attr = account_attrs.next()?;
+            if account_attrs.next().is_some() {
+                panic!("Invalid syntax: one account attribute allowed");
             }
+            Some((ident.to_string(), parse_account_args(attr)))
         })
         .collect()
 }
 
+fn parse_account_args(attr: &syn::Attribute) -> AccountArgs {
+    match attr.tokens.is_empty() {
+        true => AccountArgs::default(),
+        false => attr
+            .parse_args::<AccountArgs>()
+            .expect("Invalid account attribute"),
+    }
+}
+
 // Parse all structs implementing the `Accounts` trait.
 fn parse_account_derives(ctx: &CrateContext) -> HashMap<String, AccountsStruct> {
     // TODO: parse manual implementations. Currently we only look
@@ -330,7 +404,14 @@ fn parse_account_derives(ctx: &CrateContext) -> HashMap<String, AccountsStruct>
         .filter_map(|i_strct| {
             for attr in &i_strct.attrs {
                 if attr.tokens.to_string().contains(DERIVE_NAME) {
-                    let strct = accounts::parse(i_strct).expect("Code not parseable");
+                    // The accounts added by `#[event_cpi]` aren't in the
+                    // source, since the attribute isn't expanded here.
+                    let i_strct = match accounts::event_cpi::has_event_cpi_attr(i_strct) {
+                        false => i_strct.clone(),
+                        true => accounts::event_cpi::add_event_cpi_accounts(i_strct)
+                            .expect("Code not parseable"),
+                    };
+                    let strct = accounts::parse(&i_strct).expect("Code not parseable");
                     return Some((strct.ident.to_string(), strct));
                 }
             }
@@ -386,6 +467,8 @@ fn parse_ty_defs(ctx: &CrateContext) -> Result<Vec<IdlTypeDefinition>> {
             Some(fields.map(|fields| IdlTypeDefinition {
                 name,
                 ty: IdlTypeDefinitionTy::Struct { fields },
+                version: None,
+                discriminator: None,
             }))
         })
         .chain(ctx.enums().map(|enm| {
@@ -421,6 +504,8 @@ fn parse_ty_defs(ctx: &CrateContext) -> Result<Vec<IdlTypeDefinition>> {
             Ok(IdlTypeDefinition {
                 name,
                 ty: IdlTypeDefinitionTy::Enum { variants },
+                version: None,
+                discriminator: None,
             })
         }))
         .collect()
@@ -439,6 +524,7 @@ fn idl_accounts(
     accounts
         .fields
         .iter()
+        .filter(|acc| acc.is_account())
         .map(|acc: &AccountField| match acc {
             AccountField::CompositeField(comp_f) => {
                 let accs_strct = global_accs
@@ -453,7 +539,8 @@ fn idl_accounts(
             AccountField::Field(acc) => IdlAccountItem::IdlAccount(IdlAccount {
                 name: acc.ident.to_string().to_mixed_case(),
                 is_mut: acc.constraints.is_mutable(),
-                is_signer: acc.constraints.is_signer(),
+                is_signer: acc.is_signer(),
+                is_optional: acc.is_optional,
             }),
         })
         .collect::<Vec<_>>()
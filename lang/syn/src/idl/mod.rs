@@ -18,6 +18,13 @@ pub struct Idl {
     pub events: Option<Vec<IdlEvent>>,
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub errors: Option<Vec<IdlErrorCode>>,
+    // Only set if given explicitly via `#[error(offset = ..)]`.
+    #[serde(
+        rename = "errorOffset",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    pub error_offset: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub metadata: Option<JsonValue>,
 }
@@ -34,6 +41,10 @@ pub struct IdlInstruction {
     pub name: String,
     pub accounts: Vec<IdlAccountItem>,
     pub args: Vec<IdlField>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub returns: Option<IdlType>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub interface: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -56,6 +67,8 @@ pub struct IdlAccount {
     pub name: String,
     pub is_mut: bool,
     pub is_signer: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub is_optional: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -69,6 +82,13 @@ pub struct IdlField {
 pub struct IdlEvent {
     pub name: String,
     pub fields: Vec<IdlEventField>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub namespace: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub version: Option<u8>,
+    // Only set if it isn't the default derived from the name.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub discriminator: Option<[u8; 8]>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -84,6 +104,13 @@ pub struct IdlTypeDefinition {
     pub name: String,
     #[serde(rename = "type")]
     pub ty: IdlTypeDefinitionTy,
+    // Only set for accounts declared with `#[account(version = ..)]`.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub version: Option<u8>,
+    // Only set for accounts whose discriminator isn't the default derived
+    // from the name.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub discriminator: Option<Vec<u8>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
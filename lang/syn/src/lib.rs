@@ -11,8 +11,8 @@ use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::token::Comma;
 use syn::{
-    Expr, Generics, Ident, ImplItemMethod, ItemEnum, ItemFn, ItemImpl, ItemMod, ItemStruct, LitInt,
-    LitStr, PatType, Token, TypePath,
+    Expr, GenericArgument, Generics, Ident, ImplItemMethod, ItemEnum, ItemFn, ItemImpl, ItemMod,
+    ItemStruct, LitInt, LitStr, PatType, Token, Type, TypePath,
 };
 
 pub mod codegen;
@@ -31,6 +31,53 @@ pub struct Program {
     pub name: Ident,
     pub program_mod: ItemMod,
     pub fallback_fn: Option<FallbackFn>,
+    pub args: Option<ProgramArgs>,
+}
+
+// Arguments to `declare_program!`, e.g., `declare_program!(my_program)` or
+// `declare_program!(my_program, "path/to/my_program.json")`.
+#[derive(Debug)]
+pub struct DeclareProgramArgs {
+    pub name: Ident,
+    pub path: Option<LitStr>,
+}
+
+impl Parse for DeclareProgramArgs {
+    fn parse(stream: ParseStream) -> ParseResult<Self> {
+        let name = stream.parse()?;
+        let path = match stream.is_empty() {
+            true => None,
+            false => {
+                stream.parse::<Token![,]>()?;
+                Some(stream.parse()?)
+            }
+        };
+        Ok(DeclareProgramArgs { name, path })
+    }
+}
+
+// Arguments to the `#[program]` attribute, e.g.,
+// `#[program(custom_entry = my_entry)]`.
+#[derive(Debug)]
+pub struct ProgramArgs {
+    pub custom_entry: syn::Path,
+}
+
+impl Parse for ProgramArgs {
+    fn parse(stream: ParseStream) -> ParseResult<Self> {
+        let custom_entry_span = stream.span();
+        let custom_entry = stream.call(Ident::parse_any)?;
+        if custom_entry.to_string().as_str() != "custom_entry" {
+            return Err(ParseError::new(
+                custom_entry_span,
+                "expected keyword custom_entry",
+            ));
+        }
+        stream.parse::<Token![=]>()?;
+        Ok(ProgramArgs {
+            custom_entry: stream.parse()?,
+        })
+    }
 }
 
 impl Parse for Program {
@@ -68,6 +115,7 @@ pub struct StateIx {
     pub ident: Ident,
     pub args: Vec<IxArg>,
     pub anchor_ident: Ident,
+    pub anchor_generics: Vec<GenericArgument>,
     // True if there exists a &self on the method.
     pub has_receiver: bool,
 }
@@ -85,6 +133,12 @@ pub struct Ix {
     pub args: Vec<IxArg>,
     // The ident for the struct deriving Accounts.
     pub anchor_ident: Ident,
+    // The type and const arguments of the struct deriving Accounts, if generic.
+    pub anchor_generics: Vec<GenericArgument>,
+    // The type returned by the handler via return data, if not unit.
+    pub returns: Option<Type>,
+    // The `#[interface]` trait whose method the handler implements, if any.
+    pub interface: Option<Ident>,
 }
 
 #[derive(Debug)]
@@ -108,6 +162,9 @@ pub struct AccountsStruct {
     pub fields: Vec<AccountField>,
     // Instruction data api expression.
     instruction_api: Option<Punctuated<Expr, Comma>>,
+    // Values the struct requires from the struct embedding it, declared with
+    // `#[scope(..)]`.
+    scope: Option<Punctuated<Expr, Comma>>,
 }
 
 impl Parse for AccountsStruct {
@@ -134,6 +191,7 @@ impl AccountsStruct {
         strct: ItemStruct,
         fields: Vec<AccountField>,
         instruction_api: Option<Punctuated<Expr, Comma>>,
+        scope: Option<Punctuated<Expr, Comma>>,
     ) -> Self {
         let ident = strct.ident.clone();
         let generics = strct.generics;
@@ -142,6 +200,7 @@ impl AccountsStruct {
             generics,
             fields,
             instruction_api,
+            scope,
         }
     }
 }
@@ -159,6 +218,24 @@ pub struct Field {
     pub constraints: ConstraintGroup,
     pub instruction_constraints: ConstraintGroup,
     pub ty: Ty,
+    // True if the field is wrapped in an `Option`.
+    pub is_optional: bool,
+    // True if the field is wrapped in a `Box`.
+    pub is_boxed: bool,
+}
+
+impl Field {
+    // True if the account must sign, either by type or by constraint.
+    pub fn is_signer(&self) -> bool {
+        self.ty == Ty::Signer || self.constraints.is_signer()
+    }
+}
+
+impl AccountField {
+    // False for sysvars loaded via syscall, which clients don't pass.
+    pub fn is_account(&self) -> bool {
+        !matches!(self, AccountField::Field(f) if matches!(f.ty, Ty::SyscallSysvar(_)))
+    }
 }
 
 #[derive(Debug)]
@@ -174,12 +251,22 @@ pub struct CompositeField {
 #[derive(Debug, PartialEq)]
 pub enum Ty {
     AccountInfo,
+    Signer,
+    SystemAccount,
+    UncheckedAccount,
+    Program(ProgramTy),
+    Interface(InterfaceTy),
     ProgramState(ProgramStateTy),
     CpiState(CpiStateTy),
     ProgramAccount(ProgramAccountTy),
     Loader(LoaderTy),
+    LazyAccount(LazyAccountTy),
     CpiAccount(CpiAccountTy),
+    InterfaceAccount(InterfaceAccountTy),
     Sysvar(SysvarTy),
+    // A `Clock` or `Rent` field, loaded via syscall rather than passed as an
+    // account.
+    SyscallSysvar(SysvarTy),
 }
 
 #[derive(Debug, PartialEq)]
@@ -218,6 +305,30 @@ pub struct CpiAccountTy {
     pub account_type_path: TypePath,
 }
 
+#[derive(Debug, PartialEq)]
+pub struct ProgramTy {
+    // The struct type of the program, implementing `Id`.
+    pub account_type_path: TypePath,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InterfaceTy {
+    // The struct type of the interface, implementing `Ids`.
+    pub account_type_path: TypePath,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InterfaceAccountTy {
+    // The struct type of the account, implementing `Owners`.
+    pub account_type_path: TypePath,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct LazyAccountTy {
+    // The struct type of the account.
+    pub account_type_path: TypePath,
+}
+
 #[derive(Debug, PartialEq)]
 pub struct LoaderTy {
     // The struct type of the account.
@@ -259,6 +370,255 @@ pub struct ErrorCode {
     pub msg: Option<String>,
 }
 
+// Arguments to the `#[event]` attribute, e.g.,
+// `#[event(namespace = "staking", version = 2)]` or
+// `#[event(discriminator = [1, 2, 3, 4, 5, 6, 7, 8])]`.
+#[derive(Debug, Default)]
+pub struct EventArgs {
+    pub namespace: Option<String>,
+    pub version: Option<u8>,
+    pub discriminator: Option<[u8; 8]>,
+}
+
+impl EventArgs {
+    // Returns the discriminator of the event with the given name.
+    //
+    // Unless given explicitly, it's the first 8 bytes of the SHA256 of
+    // "event:<name>", where the name is prefixed with "<namespace>:" if a
+    // namespace is given and suffixed with ":v<version>" for versions other
+    // than the first, so that adding `version = 1` to an existing event
+    // doesn't change its discriminator.
+    pub fn discriminator(&self, name: &str) -> [u8; 8] {
+        if let Some(discriminator) = self.discriminator {
+            return discriminator;
+        }
+        let mut preimage = match &self.namespace {
+            None => format!("event:{}", name),
+            Some(namespace) => format!("event:{}:{}", namespace, name),
+        };
+        if let Some(version) = self.version.filter(|v| *v > 1) {
+            preimage.push_str(&format!(":v{}", version));
+        }
+        let mut discriminator = [0u8; 8];
+        discriminator.copy_from_slice(&hash::hash(preimage.as_bytes()).to_bytes()[..8]);
+        discriminator
+    }
+
+    // True if the discriminator isn't the default one derived from the name.
+    pub fn is_custom(&self) -> bool {
+        self.namespace.is_some()
+            || self.discriminator.is_some()
+            || self.version.filter(|v| *v > 1).is_some()
+    }
+}
+
+impl Parse for EventArgs {
+    fn parse(stream: ParseStream) -> ParseResult<Self> {
+        let mut args = EventArgs::default();
+        while !stream.is_empty() {
+            let key = stream.call(Ident::parse_any)?;
+            stream.parse::<Token![=]>()?;
+            match key.to_string().as_str() {
+                "namespace" => {
+                    let namespace: LitStr = stream.parse()?;
+                    args.namespace = Some(namespace.value());
+                }
+                "version" => {
+                    let version: LitInt = stream.parse()?;
+                    let version = version.base10_parse::<u8>()?;
+                    if version == 0 {
+                        return Err(ParseError::new(key.span(), "versions start at 1"));
+                    }
+                    args.version = Some(version);
+                }
+                "discriminator" => {
+                    let array: syn::ExprArray = stream.parse()?;
+                    let bytes = parse_discriminator(&array)?;
+                    if bytes.len() != 8 {
+                        return Err(ParseError::new(
+                            array.span(),
+                            "the discriminator must be 8 bytes",
+                        ));
+                    }
+                    let mut discriminator = [0u8; 8];
+                    discriminator.copy_from_slice(&bytes);
+                    args.discriminator = Some(discriminator);
+                }
+                _ => {
+                    return Err(ParseError::new(
+                        key.span(),
+                        "expected namespace, version, or discriminator",
+                    ))
+                }
+            }
+            if !stream.is_empty() {
+                stream.parse::<Token![,]>()?;
+            }
+        }
+        if args.discriminator.is_some() && args.namespace.is_some() {
+            return Err(ParseError::new(
+                stream.span(),
+                "namespace can't be used with an explicit discriminator",
+            ));
+        }
+        Ok(args)
+    }
+}
+
+// Arguments to the `#[account]` attribute, e.g., `#[account("namespace")]`,
+// `#[account(zero_copy)]`, `#[account(version = 2)]`, or
+// `#[account(discriminator = [1])]`.
+#[derive(Debug, Default)]
+pub struct AccountArgs {
+    pub zero_copy: bool,
+    pub namespace: Option<String>,
+    pub version: Option<u8>,
+    pub discriminator: Option<Vec<u8>>,
+}
+
+impl AccountArgs {
+    // Returns the discriminator of the account with the given name.
+    //
+    // Unless given explicitly, it's the first 8 bytes of the SHA256 of
+    // "account:<name>", or of "<namespace>:<name>" if a namespace is given.
+    pub fn discriminator(&self, name: &str) -> Vec<u8> {
+        if let Some(discriminator) = &self.discriminator {
+            return discriminator.clone();
+        }
+        let preimage = match &self.namespace {
+            None => format!("account:{}", name),
+            Some(namespace) => format!("{}:{}", namespace, name),
+        };
+        hash::hash(preimage.as_bytes()).to_bytes()[..8].to_vec()
+    }
+
+    // True if the discriminator isn't the default one derived from the name.
+    pub fn is_custom(&self) -> bool {
+        self.namespace.is_some() || self.discriminator.is_some()
+    }
+}
+
+impl Parse for AccountArgs {
+    fn parse(stream: ParseStream) -> ParseResult<Self> {
+        let mut args = AccountArgs::default();
+        while !stream.is_empty() {
+            if stream.peek(LitStr) {
+                let namespace: LitStr = stream.parse()?;
+                args.namespace = Some(namespace.value());
+            } else {
+                let key = stream.call(Ident::parse_any)?;
+                if stream.peek(Token![=]) {
+                    stream.parse::<Token![=]>()?;
+                    match key.to_string().as_str() {
+                        "version" => {
+                            let version: LitInt = stream.parse()?;
+                            let version = version.base10_parse::<u8>()?;
+                            if version == 0 {
+                                return Err(ParseError::new(key.span(), "versions start at 1"));
+                            }
+                            args.version = Some(version);
+                        }
+                        "discriminator" => {
+                            let array: syn::ExprArray = stream.parse()?;
+                            let bytes = parse_discriminator(&array)?;
+                            if bytes.is_empty() || bytes.len() > 8 {
+                                return Err(ParseError::new(
+                                    array.span(),
+                                    "the discriminator must be 1 to 8 bytes",
+                                ));
+                            }
+                            args.discriminator = Some(bytes);
+                        }
+                        _ => {
+                            return Err(ParseError::new(
+                                key.span(),
+                                "expected version or discriminator",
+                            ))
+                        }
+                    }
+                } else if key == "zero_copy" {
+                    args.zero_copy = true;
+                } else {
+                    // Unquoted namespaces are accepted for backwards
+                    // compatibility.
+                    args.namespace = Some(key.to_string());
+                }
+            }
+            if !stream.is_empty() {
+                stream.parse::<Token![,]>()?;
+            }
+        }
+        if args.discriminator.is_some() && args.namespace.is_some() {
+            return Err(ParseError::new(
+                stream.span(),
+                "namespace can't be used with an explicit discriminator",
+            ));
+        }
+        if args.zero_copy && args.version.is_some() {
+            return Err(ParseError::new(
+                stream.span(),
+                "zero copy accounts can't be versioned",
+            ));
+        }
+        Ok(args)
+    }
+}
+
+// Parses an explicit discriminator, given as an array of u8 literals.
+fn parse_discriminator(array: &syn::ExprArray) -> ParseResult<Vec<u8>> {
+    array
+        .elems
+        .iter()
+        .map(|elem| match elem {
+            Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(int),
+                ..
+            }) => int.base10_parse::<u8>(),
+            _ => Err(ParseError::new(elem.span(), "expected a u8 literal")),
+        })
+        .collect()
+}
+
+// Arguments to the `#[access_control]` attribute, i.e., a list of guard
+// calls, optionally separated by commas, e.g.,
+// `#[access_control(is_admin(&ctx) price = valid_price(&ctx, amount))]`.
+#[derive(Debug)]
+pub struct AccessControlArgs {
+    pub guards: Vec<AccessControlGuard>,
+}
+
+// A single guard call, whose `Ok` value is bound to `binding`, if given.
+#[derive(Debug)]
+pub struct AccessControlGuard {
+    pub binding: Option<Ident>,
+    pub call: Expr,
+}
+
+impl Parse for AccessControlArgs {
+    fn parse(stream: ParseStream) -> ParseResult<Self> {
+        let mut guards = vec![];
+        while !stream.is_empty() {
+            let binding =
+                match stream.peek(Ident) && stream.peek2(Token![=]) && !stream.peek2(Token![==]) {
+                    false => None,
+                    true => {
+                        let binding: Ident = stream.parse()?;
+                        stream.parse::<Token![=]>()?;
+                        Some(binding)
+                    }
+                };
+            guards.push(AccessControlGuard {
+                binding,
+                call: stream.parse()?,
+            });
+            if stream.peek(Token![,]) {
+                stream.parse::<Token![,]>()?;
+            }
+        }
+        Ok(AccessControlArgs { guards })
+    }
+}
+
 // All well formed constraints on a single `Accounts` field.
 #[derive(Debug, Default, Clone)]
 pub struct ConstraintGroup {
@@ -276,6 +636,16 @@ pub struct ConstraintGroup {
     raw: Vec<ConstraintRaw>,
     close: Option<ConstraintClose>,
     address: Option<ConstraintAddress>,
+    variant: Option<ConstraintVariant>,
+    scope: Option<ConstraintScope>,
+    instruction_sysvar: Option<ConstraintInstructionSysvar>,
+    allow_duplicate: Option<ConstraintAllowDuplicate>,
+    token_account: Option<ConstraintTokenAccountGroup>,
+    associated_token: Option<ConstraintAssociatedTokenGroup>,
+    mint: Option<ConstraintMintGroup>,
+    token_delegate: Option<ConstraintTokenDelegate>,
+    token_close_authority: Option<ConstraintTokenCloseAuthority>,
+    realloc: Option<ConstraintReallocGroup>,
 }
 
 impl ConstraintGroup {
@@ -283,6 +653,10 @@ impl ConstraintGroup {
         self.init.is_some()
     }
 
+    pub fn is_init_if_needed(&self) -> bool {
+        self.init.as_ref().map(|i| i.if_needed).unwrap_or(false)
+    }
+
     pub fn is_mutable(&self) -> bool {
         self.mutable.is_some()
     }
@@ -294,6 +668,22 @@ impl ConstraintGroup {
     pub fn is_close(&self) -> bool {
         self.close.is_some()
     }
+
+    pub fn is_allow_duplicate(&self) -> bool {
+        self.allow_duplicate.is_some()
+    }
+
+    pub fn scope(&self) -> Option<&ConstraintScope> {
+        self.scope.as_ref()
+    }
+
+    // True if any constraint reads the rent sysvar to compute balances.
+    pub fn requires_rent(&self) -> bool {
+        matches!(self.rent_exempt, Some(ConstraintRentExempt::Enforce))
+            || self.realloc.is_some()
+            || self.seeds.as_ref().map(|s| s.is_init).unwrap_or(false)
+            || self.associated.as_ref().map(|a| a.is_init).unwrap_or(false)
+    }
 }
 
 // A single account constraint *after* merging all tokens into a well formed
@@ -316,6 +706,14 @@ pub enum Constraint {
     AssociatedGroup(ConstraintAssociatedGroup),
     Close(ConstraintClose),
     Address(ConstraintAddress),
+    Variant(ConstraintVariant),
+    InstructionSysvar(ConstraintInstructionSysvar),
+    TokenAccount(ConstraintTokenAccountGroup),
+    AssociatedToken(ConstraintAssociatedTokenGroup),
+    Mint(ConstraintMintGroup),
+    TokenDelegate(ConstraintTokenDelegate),
+    TokenCloseAuthority(ConstraintTokenCloseAuthority),
+    Realloc(ConstraintReallocGroup),
 }
 
 // Constraint token is a single keyword in a `#[account(<TOKEN>)]` attribute.
@@ -331,6 +729,7 @@ pub enum ConstraintToken {
     Owner(Context<ConstraintOwner>),
     RentExempt(Context<ConstraintRentExempt>),
     Seeds(Context<ConstraintSeeds>),
+    SeedsProgram(Context<ConstraintSeedsProgram>),
     Executable(Context<ConstraintExecutable>),
     State(Context<ConstraintState>),
     Close(Context<ConstraintClose>),
@@ -339,9 +738,24 @@ pub enum ConstraintToken {
     AssociatedSpace(Context<ConstraintAssociatedSpace>),
     AssociatedWith(Context<ConstraintAssociatedWith>),
     Address(Context<ConstraintAddress>),
+    Variant(Context<ConstraintVariant>),
+    Scope(Context<ConstraintScope>),
+    InstructionSysvar(Context<ConstraintInstructionSysvar>),
+    AllowDuplicate(Context<ConstraintAllowDuplicate>),
     TokenMint(Context<ConstraintTokenMint>),
     TokenAuthority(Context<ConstraintTokenAuthority>),
     Bump(Context<ConstraintTokenBump>),
+    AssociatedTokenMint(Context<ConstraintAssociatedTokenMint>),
+    AssociatedTokenAuthority(Context<ConstraintAssociatedTokenAuthority>),
+    MintDecimals(Context<ConstraintMintDecimals>),
+    MintAuthority(Context<ConstraintMintAuthority>),
+    MintFreezeAuthority(Context<ConstraintMintFreezeAuthority>),
+    MintTokenProgram(Context<ConstraintMintTokenProgram>),
+    TokenDelegate(Context<ConstraintTokenDelegate>),
+    TokenCloseAuthority(Context<ConstraintTokenCloseAuthority>),
+    Realloc(Context<ConstraintRealloc>),
+    ReallocPayer(Context<ConstraintReallocPayer>),
+    ReallocZero(Context<ConstraintReallocZero>),
 }
 
 impl Parse for ConstraintToken {
@@ -351,17 +765,24 @@ impl Parse for ConstraintToken {
 }
 
 #[derive(Debug, Clone)]
-pub struct ConstraintInit {}
+pub struct ConstraintInit {
+    pub if_needed: bool,
+}
 
 #[derive(Debug, Clone)]
-pub struct ConstraintMut {}
+pub struct ConstraintMut {
+    pub error: Option<Expr>,
+}
 
 #[derive(Debug, Clone)]
-pub struct ConstraintSigner {}
+pub struct ConstraintSigner {
+    pub error: Option<Expr>,
+}
 
 #[derive(Debug, Clone)]
 pub struct ConstraintHasOne {
     pub join_target: Expr,
+    pub error: Option<Expr>,
 }
 
 #[derive(Debug, Clone)]
@@ -372,18 +793,42 @@ pub struct ConstraintLiteral {
 #[derive(Debug, Clone)]
 pub struct ConstraintRaw {
     pub raw: Expr,
+    pub error: Option<Expr>,
 }
 
 #[derive(Debug, Clone)]
 pub struct ConstraintOwner {
     pub owner_target: Expr,
+    pub error: Option<Expr>,
 }
 
 #[derive(Debug, Clone)]
 pub struct ConstraintAddress {
     pub address: Expr,
+    pub error: Option<Expr>,
+}
+
+// The variant an enum account must be, given by its path, e.g.,
+// `variant = Market::Active`.
+#[derive(Debug, Clone)]
+pub struct ConstraintVariant {
+    pub variant: syn::Path,
+    pub error: Option<Expr>,
+}
+
+// The values passed to a composite field's `#[scope(..)]`, in order, e.g.,
+// `scope(authority.key(), amount)`.
+#[derive(Debug, Clone)]
+pub struct ConstraintScope {
+    pub args: Punctuated<Expr, Comma>,
 }
 
+// Checks the account is the instructions sysvar and binds the
+// `anchor_lang::introspection` helpers for it, e.g., `prev_instruction()`, for
+// use in the constraints that follow.
+#[derive(Debug, Clone)]
+pub struct ConstraintInstructionSysvar {}
+
 #[derive(Debug, Clone)]
 pub enum ConstraintRentExempt {
     Enforce,
@@ -398,6 +843,10 @@ pub struct ConstraintSeedsGroup {
     pub space: Option<Expr>,
     pub kind: PdaKind,
     pub bump: Option<Expr>,
+    // True if `bump` is given without a value, in which case the canonical
+    // bump is found and recorded in `ctx.bumps`.
+    pub find_bump: bool,
+    pub program_seed: Option<Expr>,
 }
 
 #[derive(Debug, Clone)]
@@ -405,9 +854,18 @@ pub struct ConstraintSeeds {
     pub seeds: Punctuated<Expr, Token![,]>,
 }
 
+#[derive(Debug, Clone)]
+pub struct ConstraintSeedsProgram {
+    pub program: Expr,
+}
+
 #[derive(Debug, Clone)]
 pub struct ConstraintExecutable {}
 
+// Opts a mutable account out of the duplicate mutable account check.
+#[derive(Debug, Clone)]
+pub struct ConstraintAllowDuplicate {}
+
 #[derive(Debug, Clone)]
 pub struct ConstraintState {
     pub program_target: Ident,
@@ -446,8 +904,41 @@ pub struct ConstraintAssociatedSpace {
 #[derive(Debug, Clone)]
 #[allow(clippy::large_enum_variant)]
 pub enum PdaKind {
-    Program { owner: Option<Expr> },
-    Token { owner: Expr, mint: Expr },
+    Program {
+        owner: Option<Expr>,
+    },
+    Token {
+        owner: Expr,
+        mint: Expr,
+    },
+    Mint {
+        decimals: Expr,
+        authority: Expr,
+        freeze_authority: Option<Expr>,
+        token_program: Option<Expr>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstraintReallocGroup {
+    pub payer: Expr,
+    pub space: Expr,
+    pub zero: Expr,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstraintRealloc {
+    pub space: Expr,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstraintReallocPayer {
+    pub target: Expr,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstraintReallocZero {
+    pub zero: Expr,
 }
 
 #[derive(Debug, Clone)]
@@ -455,6 +946,32 @@ pub struct ConstraintClose {
     pub sol_dest: Ident,
 }
 
+#[derive(Debug, Clone)]
+pub struct ConstraintTokenAccountGroup {
+    pub is_init: bool,
+    pub mint: Expr,
+    pub authority: Expr,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstraintAssociatedTokenGroup {
+    pub is_init: bool,
+    pub if_needed: bool,
+    pub mint: Expr,
+    pub authority: Expr,
+    pub payer: Option<Ident>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstraintAssociatedTokenMint {
+    mint: Expr,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstraintAssociatedTokenAuthority {
+    auth: Expr,
+}
+
 #[derive(Debug, Clone)]
 pub struct ConstraintTokenMint {
     mint: Expr,
@@ -465,9 +982,53 @@ pub struct ConstraintTokenAuthority {
     auth: Expr,
 }
 
+// The token account's delegate, which must not be set when `None`, i.e.,
+// given as `token::delegate = none`.
+#[derive(Debug, Clone)]
+pub struct ConstraintTokenDelegate {
+    pub delegate: Option<Expr>,
+}
+
+// Likewise, the token account's close authority.
+#[derive(Debug, Clone)]
+pub struct ConstraintTokenCloseAuthority {
+    pub close_auth: Option<Expr>,
+}
+
+// A mint that's initialized in place, when `is_init`, or only validated.
+// Unless `token_program` is given, the mint must be owned by SPL Token.
+#[derive(Debug, Clone)]
+pub struct ConstraintMintGroup {
+    pub is_init: bool,
+    pub decimals: Option<Expr>,
+    pub authority: Option<Expr>,
+    pub freeze_authority: Option<Expr>,
+    pub token_program: Option<Expr>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstraintMintDecimals {
+    decimals: Expr,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstraintMintAuthority {
+    mint_auth: Expr,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstraintMintFreezeAuthority {
+    mint_freeze_auth: Expr,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstraintMintTokenProgram {
+    token_program: Expr,
+}
+
 #[derive(Debug, Clone)]
 pub struct ConstraintTokenBump {
-    bump: Expr,
+    bump: Option<Expr>,
 }
 
 // Syntaxt context object for preserving metadata about the inner item.
@@ -4,7 +4,7 @@ use syn::parse::{Error as ParseError, Parse, ParseStream, Result as ParseResult}
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::token::Comma;
-use syn::{bracketed, Expr, Ident, LitStr, Token};
+use syn::{bracketed, parenthesized, Expr, Ident, LitStr, Token};
 
 pub fn parse(
     f: &syn::Field,
@@ -58,15 +58,64 @@ pub fn parse_token(stream: ParseStream) -> ParseResult<ConstraintToken> {
     }
 
     let ident = stream.call(Ident::parse_any)?;
-    let kw = ident.to_string();
+    // Namespaced constraints, e.g., `realloc::payer`, are joined into a
+    // single keyword.
+    let kw = match stream.peek(Token![::]) {
+        false => ident.to_string(),
+        true => {
+            stream.parse::<Token![::]>()?;
+            let sub = stream.call(Ident::parse_any)?;
+            format!("{}::{}", ident, sub)
+        }
+    };
 
     let c = match kw.as_str() {
-        "init" => ConstraintToken::Init(Context::new(ident.span(), ConstraintInit {})),
-        "mut" => ConstraintToken::Mut(Context::new(ident.span(), ConstraintMut {})),
-        "signer" => ConstraintToken::Signer(Context::new(ident.span(), ConstraintSigner {})),
+        "init" => ConstraintToken::Init(Context::new(
+            ident.span(),
+            ConstraintInit { if_needed: false },
+        )),
+        "init_if_needed" => ConstraintToken::Init(Context::new(
+            ident.span(),
+            ConstraintInit { if_needed: true },
+        )),
+        "mut" => ConstraintToken::Mut(Context::new(
+            ident.span(),
+            ConstraintMut {
+                error: parse_optional_custom_error(stream)?,
+            },
+        )),
+        "signer" => ConstraintToken::Signer(Context::new(
+            ident.span(),
+            ConstraintSigner {
+                error: parse_optional_custom_error(stream)?,
+            },
+        )),
         "executable" => {
             ConstraintToken::Executable(Context::new(ident.span(), ConstraintExecutable {}))
         }
+        "instruction_sysvar" => ConstraintToken::InstructionSysvar(Context::new(
+            ident.span(),
+            ConstraintInstructionSysvar {},
+        )),
+        "allow_duplicate" => {
+            ConstraintToken::AllowDuplicate(Context::new(ident.span(), ConstraintAllowDuplicate {}))
+        }
+        // A bare `bump` derives the canonical bump instead of taking it as
+        // an argument.
+        "bump" if !stream.peek(Token![=]) => ConstraintToken::Bump(Context::new(
+            ident.span(),
+            ConstraintTokenBump { bump: None },
+        )),
+        "scope" => {
+            let content;
+            parenthesized!(content in stream);
+            ConstraintToken::Scope(Context::new(
+                ident.span(),
+                ConstraintScope {
+                    args: content.parse_terminated(Expr::parse)?,
+                },
+            ))
+        }
         _ => {
             stream.parse::<Token![=]>()?;
             let span = ident
@@ -85,12 +134,14 @@ pub fn parse_token(stream: ParseStream) -> ParseResult<ConstraintToken> {
                     span,
                     ConstraintHasOne {
                         join_target: stream.parse()?,
+                        error: parse_optional_custom_error(stream)?,
                     },
                 )),
                 "owner" => ConstraintToken::Owner(Context::new(
                     span,
                     ConstraintOwner {
                         owner_target: stream.parse()?,
+                        error: parse_optional_custom_error(stream)?,
                     },
                 )),
                 "rent_exempt" => ConstraintToken::RentExempt(Context::new(
@@ -146,10 +197,17 @@ pub fn parse_token(stream: ParseStream) -> ParseResult<ConstraintToken> {
                         },
                     ))
                 }
+                "seeds::program" => ConstraintToken::SeedsProgram(Context::new(
+                    span,
+                    ConstraintSeedsProgram {
+                        program: stream.parse()?,
+                    },
+                )),
                 "constraint" => ConstraintToken::Raw(Context::new(
                     span,
                     ConstraintRaw {
                         raw: stream.parse()?,
+                        error: parse_optional_custom_error(stream)?,
                     },
                 )),
                 "close" => ConstraintToken::Close(Context::new(
@@ -162,24 +220,100 @@ pub fn parse_token(stream: ParseStream) -> ParseResult<ConstraintToken> {
                     span,
                     ConstraintAddress {
                         address: stream.parse()?,
+                        error: parse_optional_custom_error(stream)?,
                     },
                 )),
-                "token" => ConstraintToken::TokenMint(Context::new(
+                "variant" => ConstraintToken::Variant(Context::new(
+                    span,
+                    ConstraintVariant {
+                        variant: stream.parse()?,
+                        error: parse_optional_custom_error(stream)?,
+                    },
+                )),
+                "token" | "token::mint" => ConstraintToken::TokenMint(Context::new(
                     ident.span(),
                     ConstraintTokenMint {
                         mint: stream.parse()?,
                     },
                 )),
-                "authority" => ConstraintToken::TokenAuthority(Context::new(
+                "authority" | "token::authority" => ConstraintToken::TokenAuthority(Context::new(
                     ident.span(),
                     ConstraintTokenAuthority {
                         auth: stream.parse()?,
                     },
                 )),
+                "token::delegate" => ConstraintToken::TokenDelegate(Context::new(
+                    span,
+                    ConstraintTokenDelegate {
+                        delegate: parse_optional_target(stream)?,
+                    },
+                )),
+                "token::close_authority" => ConstraintToken::TokenCloseAuthority(Context::new(
+                    span,
+                    ConstraintTokenCloseAuthority {
+                        close_auth: parse_optional_target(stream)?,
+                    },
+                )),
                 "bump" => ConstraintToken::Bump(Context::new(
                     ident.span(),
                     ConstraintTokenBump {
-                        bump: stream.parse()?,
+                        bump: Some(stream.parse()?),
+                    },
+                )),
+                "associated_token::mint" => ConstraintToken::AssociatedTokenMint(Context::new(
+                    span,
+                    ConstraintAssociatedTokenMint {
+                        mint: stream.parse()?,
+                    },
+                )),
+                "associated_token::authority" => {
+                    ConstraintToken::AssociatedTokenAuthority(Context::new(
+                        span,
+                        ConstraintAssociatedTokenAuthority {
+                            auth: stream.parse()?,
+                        },
+                    ))
+                }
+                "mint::decimals" => ConstraintToken::MintDecimals(Context::new(
+                    span,
+                    ConstraintMintDecimals {
+                        decimals: stream.parse()?,
+                    },
+                )),
+                "mint::authority" => ConstraintToken::MintAuthority(Context::new(
+                    span,
+                    ConstraintMintAuthority {
+                        mint_auth: stream.parse()?,
+                    },
+                )),
+                "mint::freeze_authority" => ConstraintToken::MintFreezeAuthority(Context::new(
+                    span,
+                    ConstraintMintFreezeAuthority {
+                        mint_freeze_auth: stream.parse()?,
+                    },
+                )),
+                "mint::token_program" => ConstraintToken::MintTokenProgram(Context::new(
+                    span,
+                    ConstraintMintTokenProgram {
+                        token_program: stream.parse()?,
+                    },
+                )),
+                "realloc" => ConstraintToken::Realloc(Context::new(
+                    span,
+                    ConstraintRealloc {
+                        space: stream.parse()?,
+                    },
+                )),
+                "realloc::payer" => ConstraintToken::ReallocPayer(Context::new(
+                    span,
+                    ConstraintReallocPayer {
+                        target: stream.parse()?,
+                    },
+                )),
+                "realloc::zero" => ConstraintToken::ReallocZero(Context::new(
+                    span,
+                    ConstraintReallocZero {
+                        zero: stream.parse()?,
                     },
                 )),
                 _ => return Err(ParseError::new(ident.span(), "Invalid attribute")),
@@ -190,6 +324,27 @@ pub fn parse_token(stream: ParseStream) -> ParseResult<ConstraintToken> {
     Ok(c)
 }
 
+// Parses the optional `@ <error>` suffix of a constraint, returning the
+// custom error to use instead of the default when the constraint is violated.
+fn parse_optional_custom_error(stream: ParseStream) -> ParseResult<Option<Expr>> {
+    if stream.peek(Token![@]) {
+        stream.parse::<Token![@]>()?;
+        stream.parse().map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+// Parses the target of a constraint that may instead be `none`, returning
+// `None` in that case.
+fn parse_optional_target(stream: ParseStream) -> ParseResult<Option<Expr>> {
+    let target: Expr = stream.parse()?;
+    match &target {
+        Expr::Path(p) if p.path.is_ident("none") => Ok(None),
+        _ => Ok(Some(target)),
+    }
+}
+
 #[derive(Default)]
 pub struct ConstraintGroupBuilder<'ty> {
     pub f_ty: Option<&'ty Ty>,
@@ -202,6 +357,7 @@ pub struct ConstraintGroupBuilder<'ty> {
     pub owner: Option<Context<ConstraintOwner>>,
     pub rent_exempt: Option<Context<ConstraintRentExempt>>,
     pub seeds: Option<Context<ConstraintSeeds>>,
+    pub seeds_program: Option<Context<ConstraintSeedsProgram>>,
     pub executable: Option<Context<ConstraintExecutable>>,
     pub state: Option<Context<ConstraintState>>,
     pub associated: Option<Context<ConstraintAssociated>>,
@@ -210,9 +366,24 @@ pub struct ConstraintGroupBuilder<'ty> {
     pub associated_with: Vec<Context<ConstraintAssociatedWith>>,
     pub close: Option<Context<ConstraintClose>>,
     pub address: Option<Context<ConstraintAddress>>,
+    pub variant: Option<Context<ConstraintVariant>>,
+    pub scope: Option<Context<ConstraintScope>>,
+    pub instruction_sysvar: Option<Context<ConstraintInstructionSysvar>>,
+    pub allow_duplicate: Option<Context<ConstraintAllowDuplicate>>,
     pub token_mint: Option<Context<ConstraintTokenMint>>,
     pub token_authority: Option<Context<ConstraintTokenAuthority>>,
     pub bump: Option<Context<ConstraintTokenBump>>,
+    pub associated_token_mint: Option<Context<ConstraintAssociatedTokenMint>>,
+    pub associated_token_authority: Option<Context<ConstraintAssociatedTokenAuthority>>,
+    pub mint_decimals: Option<Context<ConstraintMintDecimals>>,
+    pub mint_authority: Option<Context<ConstraintMintAuthority>>,
+    pub mint_freeze_authority: Option<Context<ConstraintMintFreezeAuthority>>,
+    pub mint_token_program: Option<Context<ConstraintMintTokenProgram>>,
+    pub token_delegate: Option<Context<ConstraintTokenDelegate>>,
+    pub token_close_authority: Option<Context<ConstraintTokenCloseAuthority>>,
+    pub realloc: Option<Context<ConstraintRealloc>>,
+    pub realloc_payer: Option<Context<ConstraintReallocPayer>>,
+    pub realloc_zero: Option<Context<ConstraintReallocZero>>,
 }
 
 impl<'ty> ConstraintGroupBuilder<'ty> {
@@ -228,6 +399,7 @@ impl<'ty> ConstraintGroupBuilder<'ty> {
             owner: None,
             rent_exempt: None,
             seeds: None,
+            seeds_program: None,
             executable: None,
             state: None,
             associated: None,
@@ -236,9 +408,24 @@ impl<'ty> ConstraintGroupBuilder<'ty> {
             associated_with: Vec::new(),
             close: None,
             address: None,
+            variant: None,
+            scope: None,
+            instruction_sysvar: None,
+            allow_duplicate: None,
             token_mint: None,
             token_authority: None,
             bump: None,
+            associated_token_mint: None,
+            associated_token_authority: None,
+            mint_decimals: None,
+            mint_authority: None,
+            mint_freeze_authority: None,
+            mint_token_program: None,
+            token_delegate: None,
+            token_close_authority: None,
+            realloc: None,
+            realloc_payer: None,
+            realloc_zero: None,
         }
     }
     pub fn build(mut self) -> ParseResult<ConstraintGroup> {
@@ -253,7 +440,7 @@ impl<'ty> ConstraintGroupBuilder<'ty> {
                 }
                 None => self
                     .mutable
-                    .replace(Context::new(i.span(), ConstraintMut {})),
+                    .replace(Context::new(i.span(), ConstraintMut { error: None })),
             };
             if self.rent_exempt.is_none() {
                 self.rent_exempt
@@ -269,11 +456,98 @@ impl<'ty> ConstraintGroupBuilder<'ty> {
             }
         }
 
+        if let Some(d) = &self.allow_duplicate {
+            if self.mutable.is_none() {
+                return Err(ParseError::new(
+                    d.span(),
+                    "allow_duplicate must be provided with mut",
+                ));
+            }
+        }
+
+        if let Some(p) = &self.seeds_program {
+            if self.init.is_some() {
+                return Err(ParseError::new(
+                    p.span(),
+                    "seeds::program cannot be provided with init",
+                ));
+            }
+        }
+
+        if let Some(r) = &self.realloc {
+            if self.realloc_payer.is_none() {
+                return Err(ParseError::new(
+                    r.span(),
+                    "realloc::payer must be provided when using realloc",
+                ));
+            }
+            if self.realloc_zero.is_none() {
+                return Err(ParseError::new(
+                    r.span(),
+                    "realloc::zero must be provided when using realloc",
+                ));
+            }
+        }
+
         if let Some(token_mint) = &self.token_mint {
-            if self.init.is_none() || (self.associated.is_none() && self.seeds.is_none()) {
+            let is_pda = self.associated.is_some() || self.seeds.is_some();
+            if !is_pda && self.token_authority.is_none() {
                 return Err(ParseError::new(
                     token_mint.span(),
-                    "init is required for a pda token",
+                    "token::authority must be provided with token::mint",
+                ));
+            }
+            if !is_pda && self.init.as_ref().map(|i| i.if_needed).unwrap_or(false) {
+                return Err(ParseError::new(
+                    token_mint.span(),
+                    "init_if_needed requires seeds for a token account",
+                ));
+            }
+        }
+
+        if let Some(m) = &self.associated_token_mint {
+            if self.token_mint.is_some() || self.seeds.is_some() || self.associated.is_some() {
+                return Err(ParseError::new(
+                    m.span(),
+                    "associated_token cannot be combined with token, seeds, or associated",
+                ));
+            }
+            if self.associated_token_authority.is_none() {
+                return Err(ParseError::new(
+                    m.span(),
+                    "associated_token::authority must be provided with associated_token::mint",
+                ));
+            }
+            if self.init.is_some() && self.associated_payer.is_none() {
+                return Err(ParseError::new(
+                    m.span(),
+                    "payer must be provided when creating an associated token account",
+                ));
+            }
+        }
+
+        let has_mint = self.mint_decimals.is_some()
+            || self.mint_authority.is_some()
+            || self.mint_freeze_authority.is_some()
+            || self.mint_token_program.is_some();
+        if let Some(i) = self.init.as_ref().filter(|_| has_mint) {
+            if self.token_mint.is_some() || self.associated_token_mint.is_some() {
+                return Err(ParseError::new(
+                    i.span(),
+                    "mint cannot be combined with token or associated_token",
+                ));
+            }
+            if self.mint_decimals.is_none() || self.mint_authority.is_none() {
+                return Err(ParseError::new(
+                    i.span(),
+                    "mint::decimals and mint::authority must be provided to initialize a mint",
+                ));
+            }
+            let is_pda = self.associated.is_some() || self.seeds.is_some();
+            if !is_pda && i.if_needed {
+                return Err(ParseError::new(
+                    i.span(),
+                    "init_if_needed requires seeds for a mint",
                 ));
             }
         }
@@ -289,6 +563,7 @@ impl<'ty> ConstraintGroupBuilder<'ty> {
             owner,
             rent_exempt,
             seeds,
+            seeds_program,
             executable,
             state,
             associated,
@@ -297,9 +572,24 @@ impl<'ty> ConstraintGroupBuilder<'ty> {
             associated_with,
             close,
             address,
+            variant,
+            scope,
+            instruction_sysvar,
+            allow_duplicate,
             token_mint,
             token_authority,
             bump,
+            associated_token_mint,
+            associated_token_authority,
+            mint_decimals,
+            mint_authority,
+            mint_freeze_authority,
+            mint_token_program,
+            token_delegate,
+            token_close_authority,
+            realloc,
+            realloc_payer,
+            realloc_zero,
         } = self;
 
         // Converts Option<Context<T>> -> Option<T>.
@@ -327,6 +617,57 @@ impl<'ty> ConstraintGroupBuilder<'ty> {
         };
 
         let is_init = init.is_some();
+        let is_init_if_needed = init.as_ref().map(|i| i.if_needed).unwrap_or(false);
+        let is_pda_init = is_init && (seeds.is_some() || associated.is_some());
+
+        // Token accounts that aren't created via a program derived address
+        // are either initialized in place or only validated.
+        let token_account = match (&token_mint, &token_authority) {
+            (Some(tm), Some(ta)) if !is_pda_init => Some(ConstraintTokenAccountGroup {
+                is_init,
+                mint: tm.clone().into_inner().mint,
+                authority: ta.clone().into_inner().auth,
+            }),
+            _ => None,
+        };
+
+        // Likewise, mints that aren't created via a program derived address
+        // are either initialized in place or only validated.
+        let mint_decimals = into_inner!(mint_decimals).map(|m| m.decimals);
+        let mint_authority = into_inner!(mint_authority).map(|m| m.mint_auth);
+        let mint_freeze_authority = into_inner!(mint_freeze_authority).map(|m| m.mint_freeze_auth);
+        let mint_token_program = into_inner!(mint_token_program).map(|m| m.token_program);
+        let mint_kind = match (&mint_decimals, &mint_authority) {
+            (Some(decimals), Some(authority)) if is_init => Some(PdaKind::Mint {
+                decimals: decimals.clone(),
+                authority: authority.clone(),
+                freeze_authority: mint_freeze_authority.clone(),
+                token_program: mint_token_program.clone(),
+            }),
+            _ => None,
+        };
+        let mint = match has_mint && !is_pda_init {
+            false => None,
+            true => Some(ConstraintMintGroup {
+                is_init,
+                decimals: mint_decimals,
+                authority: mint_authority,
+                freeze_authority: mint_freeze_authority,
+                token_program: mint_token_program,
+            }),
+        };
+
+        let associated_token = match (associated_token_mint, associated_token_authority) {
+            (Some(m), Some(a)) => Some(ConstraintAssociatedTokenGroup {
+                is_init,
+                if_needed: is_init_if_needed,
+                mint: m.into_inner().mint,
+                authority: a.into_inner().auth,
+                payer: into_inner!(associated_payer.clone()).map(|p| p.target),
+            }),
+            _ => None,
+        };
+
         Ok(ConstraintGroup {
             init: into_inner!(init),
             mutable: into_inner!(mutable),
@@ -344,8 +685,11 @@ impl<'ty> ConstraintGroupBuilder<'ty> {
                         payer: into_inner!(associated_payer.clone()).map(|a| a.target),
                         space: associated_space.clone().map(|s| s.space.clone()),
                         kind: match &token_mint {
-                            None => PdaKind::Program {
-                                owner: pda_owner.clone(),
+                            None => match &mint_kind {
+                                Some(kind) => kind.clone(),
+                                None => PdaKind::Program {
+                                    owner: pda_owner.clone(),
+                                },
                             },
                             Some(tm) => PdaKind::Token {
                                 mint: tm.clone().into_inner().mint,
@@ -358,7 +702,9 @@ impl<'ty> ConstraintGroupBuilder<'ty> {
                                 },
                             },
                         },
-                        bump: into_inner!(bump).map(|b| b.bump),
+                        bump: into_inner!(bump.clone()).and_then(|b| b.bump),
+                        find_bump: bump.as_ref().map(|b| b.bump.is_none()).unwrap_or(false),
+                        program_seed: into_inner!(seeds_program).map(|p| p.program),
                     })
                 })
                 .transpose()?,
@@ -371,7 +717,10 @@ impl<'ty> ConstraintGroupBuilder<'ty> {
                 payer: associated_payer.map(|p| p.target.clone()),
                 space: associated_space.map(|s| s.space.clone()),
                 kind: match token_mint {
-                    None => PdaKind::Program { owner: pda_owner },
+                    None => match mint_kind {
+                        Some(kind) => kind,
+                        None => PdaKind::Program { owner: pda_owner },
+                    },
                     Some(tm) => PdaKind::Token {
                         mint: tm.into_inner().mint,
                         owner: match token_authority {
@@ -383,6 +732,20 @@ impl<'ty> ConstraintGroupBuilder<'ty> {
             }),
             close: into_inner!(close),
             address: into_inner!(address),
+            variant: into_inner!(variant),
+            scope: into_inner!(scope),
+            instruction_sysvar: into_inner!(instruction_sysvar),
+            allow_duplicate: into_inner!(allow_duplicate),
+            token_account,
+            associated_token,
+            mint,
+            token_delegate: into_inner!(token_delegate),
+            token_close_authority: into_inner!(token_close_authority),
+            realloc: realloc.as_ref().map(|r| ConstraintReallocGroup {
+                payer: into_inner!(realloc_payer).unwrap().target,
+                space: r.space.clone(),
+                zero: into_inner!(realloc_zero).unwrap().zero,
+            }),
         })
     }
 
@@ -397,6 +760,7 @@ impl<'ty> ConstraintGroupBuilder<'ty> {
             ConstraintToken::Owner(c) => self.add_owner(c),
             ConstraintToken::RentExempt(c) => self.add_rent_exempt(c),
             ConstraintToken::Seeds(c) => self.add_seeds(c),
+            ConstraintToken::SeedsProgram(c) => self.add_seeds_program(c),
             ConstraintToken::Executable(c) => self.add_executable(c),
             ConstraintToken::State(c) => self.add_state(c),
             ConstraintToken::Associated(c) => self.add_associated(c),
@@ -405,9 +769,24 @@ impl<'ty> ConstraintGroupBuilder<'ty> {
             ConstraintToken::AssociatedWith(c) => self.add_associated_with(c),
             ConstraintToken::Close(c) => self.add_close(c),
             ConstraintToken::Address(c) => self.add_address(c),
+            ConstraintToken::Variant(c) => self.add_variant(c),
+            ConstraintToken::Scope(c) => self.add_scope(c),
+            ConstraintToken::InstructionSysvar(c) => self.add_instruction_sysvar(c),
+            ConstraintToken::AllowDuplicate(c) => self.add_allow_duplicate(c),
             ConstraintToken::TokenAuthority(c) => self.add_token_authority(c),
             ConstraintToken::TokenMint(c) => self.add_token_mint(c),
             ConstraintToken::Bump(c) => self.add_bump(c),
+            ConstraintToken::AssociatedTokenMint(c) => self.add_associated_token_mint(c),
+            ConstraintToken::AssociatedTokenAuthority(c) => self.add_associated_token_authority(c),
+            ConstraintToken::MintDecimals(c) => self.add_mint_decimals(c),
+            ConstraintToken::MintAuthority(c) => self.add_mint_authority(c),
+            ConstraintToken::MintFreezeAuthority(c) => self.add_mint_freeze_authority(c),
+            ConstraintToken::MintTokenProgram(c) => self.add_mint_token_program(c),
+            ConstraintToken::TokenDelegate(c) => self.add_token_delegate(c),
+            ConstraintToken::TokenCloseAuthority(c) => self.add_token_close_authority(c),
+            ConstraintToken::Realloc(c) => self.add_realloc(c),
+            ConstraintToken::ReallocPayer(c) => self.add_realloc_payer(c),
+            ConstraintToken::ReallocZero(c) => self.add_realloc_zero(c),
         }
     }
 
@@ -419,6 +798,62 @@ impl<'ty> ConstraintGroupBuilder<'ty> {
         Ok(())
     }
 
+    fn add_realloc(&mut self, c: Context<ConstraintRealloc>) -> ParseResult<()> {
+        if !matches!(self.f_ty, Some(Ty::ProgramAccount(_)))
+            && !matches!(self.f_ty, Some(Ty::Loader(_)))
+        {
+            return Err(ParseError::new(
+                c.span(),
+                "realloc must be on a ProgramAccount or Loader",
+            ));
+        }
+        if self.mutable.is_none() {
+            return Err(ParseError::new(
+                c.span(),
+                "mut must be provided before realloc",
+            ));
+        }
+        if self.init.is_some() {
+            return Err(ParseError::new(
+                c.span(),
+                "realloc cannot be provided with init",
+            ));
+        }
+        if self.realloc.is_some() {
+            return Err(ParseError::new(c.span(), "realloc already provided"));
+        }
+        self.realloc.replace(c);
+        Ok(())
+    }
+
+    fn add_realloc_payer(&mut self, c: Context<ConstraintReallocPayer>) -> ParseResult<()> {
+        if self.realloc.is_none() {
+            return Err(ParseError::new(
+                c.span(),
+                "realloc must be provided before realloc::payer",
+            ));
+        }
+        if self.realloc_payer.is_some() {
+            return Err(ParseError::new(c.span(), "realloc::payer already provided"));
+        }
+        self.realloc_payer.replace(c);
+        Ok(())
+    }
+
+    fn add_realloc_zero(&mut self, c: Context<ConstraintReallocZero>) -> ParseResult<()> {
+        if self.realloc.is_none() {
+            return Err(ParseError::new(
+                c.span(),
+                "realloc must be provided before realloc::zero",
+            ));
+        }
+        if self.realloc_zero.is_some() {
+            return Err(ParseError::new(c.span(), "realloc::zero already provided"));
+        }
+        self.realloc_zero.replace(c);
+        Ok(())
+    }
+
     fn add_close(&mut self, c: Context<ConstraintClose>) -> ParseResult<()> {
         if !matches!(self.f_ty, Some(Ty::ProgramAccount(_)))
             && !matches!(self.f_ty, Some(Ty::Loader(_)))
@@ -449,14 +884,77 @@ impl<'ty> ConstraintGroupBuilder<'ty> {
         Ok(())
     }
 
+    fn add_variant(&mut self, c: Context<ConstraintVariant>) -> ParseResult<()> {
+        if !matches!(self.f_ty, Some(Ty::ProgramAccount(_)))
+            && !matches!(self.f_ty, Some(Ty::CpiAccount(_)))
+        {
+            return Err(ParseError::new(
+                c.span(),
+                "variant must be on a ProgramAccount or CpiAccount",
+            ));
+        }
+        if self.variant.is_some() {
+            return Err(ParseError::new(c.span(), "variant already provided"));
+        }
+        self.variant.replace(c);
+        Ok(())
+    }
+
+    fn add_scope(&mut self, c: Context<ConstraintScope>) -> ParseResult<()> {
+        if self.f_ty.is_some() {
+            return Err(ParseError::new(
+                c.span(),
+                "scope must be on a composite field",
+            ));
+        }
+        if self.scope.is_some() {
+            return Err(ParseError::new(c.span(), "scope already provided"));
+        }
+        self.scope.replace(c);
+        Ok(())
+    }
+
+    fn add_instruction_sysvar(
+        &mut self,
+        c: Context<ConstraintInstructionSysvar>,
+    ) -> ParseResult<()> {
+        if !matches!(self.f_ty, Some(Ty::AccountInfo))
+            && !matches!(self.f_ty, Some(Ty::UncheckedAccount))
+        {
+            return Err(ParseError::new(
+                c.span(),
+                "instruction_sysvar must be on an AccountInfo or UncheckedAccount",
+            ));
+        }
+        if self.instruction_sysvar.is_some() {
+            return Err(ParseError::new(
+                c.span(),
+                "instruction_sysvar already provided",
+            ));
+        }
+        self.instruction_sysvar.replace(c);
+        Ok(())
+    }
+
+    fn add_allow_duplicate(&mut self, c: Context<ConstraintAllowDuplicate>) -> ParseResult<()> {
+        if self.allow_duplicate.is_some() {
+            return Err(ParseError::new(
+                c.span(),
+                "allow_duplicate already provided",
+            ));
+        }
+        self.allow_duplicate.replace(c);
+        Ok(())
+    }
+
     fn add_token_mint(&mut self, c: Context<ConstraintTokenMint>) -> ParseResult<()> {
         if self.token_mint.is_some() {
             return Err(ParseError::new(c.span(), "token mint already provided"));
         }
-        if self.init.is_none() {
+        if !matches!(self.f_ty, Some(Ty::CpiAccount(_))) {
             return Err(ParseError::new(
                 c.span(),
-                "init must be provided before token",
+                "token::mint must be on a CpiAccount",
             ));
         }
         self.token_mint.replace(c);
@@ -494,6 +992,154 @@ impl<'ty> ConstraintGroupBuilder<'ty> {
         Ok(())
     }
 
+    fn add_associated_token_mint(
+        &mut self,
+        c: Context<ConstraintAssociatedTokenMint>,
+    ) -> ParseResult<()> {
+        if self.associated_token_mint.is_some() {
+            return Err(ParseError::new(
+                c.span(),
+                "associated_token::mint already provided",
+            ));
+        }
+        if !matches!(self.f_ty, Some(Ty::CpiAccount(_))) {
+            return Err(ParseError::new(
+                c.span(),
+                "associated_token::mint must be on a CpiAccount",
+            ));
+        }
+        self.associated_token_mint.replace(c);
+        Ok(())
+    }
+
+    fn add_associated_token_authority(
+        &mut self,
+        c: Context<ConstraintAssociatedTokenAuthority>,
+    ) -> ParseResult<()> {
+        if self.associated_token_mint.is_none() {
+            return Err(ParseError::new(
+                c.span(),
+                "associated_token::mint must be provided before associated_token::authority",
+            ));
+        }
+        if self.associated_token_authority.is_some() {
+            return Err(ParseError::new(
+                c.span(),
+                "associated_token::authority already provided",
+            ));
+        }
+        self.associated_token_authority.replace(c);
+        Ok(())
+    }
+
+    fn add_mint_decimals(&mut self, c: Context<ConstraintMintDecimals>) -> ParseResult<()> {
+        if !matches!(self.f_ty, Some(Ty::CpiAccount(_))) {
+            return Err(ParseError::new(
+                c.span(),
+                "mint::decimals must be on a CpiAccount",
+            ));
+        }
+        if self.mint_decimals.is_some() {
+            return Err(ParseError::new(c.span(), "mint::decimals already provided"));
+        }
+        self.mint_decimals.replace(c);
+        Ok(())
+    }
+
+    fn add_mint_authority(&mut self, c: Context<ConstraintMintAuthority>) -> ParseResult<()> {
+        if !matches!(self.f_ty, Some(Ty::CpiAccount(_))) {
+            return Err(ParseError::new(
+                c.span(),
+                "mint::authority must be on a CpiAccount",
+            ));
+        }
+        if self.mint_authority.is_some() {
+            return Err(ParseError::new(
+                c.span(),
+                "mint::authority already provided",
+            ));
+        }
+        self.mint_authority.replace(c);
+        Ok(())
+    }
+
+    fn add_mint_freeze_authority(
+        &mut self,
+        c: Context<ConstraintMintFreezeAuthority>,
+    ) -> ParseResult<()> {
+        if !matches!(self.f_ty, Some(Ty::CpiAccount(_))) {
+            return Err(ParseError::new(
+                c.span(),
+                "mint::freeze_authority must be on a CpiAccount",
+            ));
+        }
+        if self.mint_freeze_authority.is_some() {
+            return Err(ParseError::new(
+                c.span(),
+                "mint::freeze_authority already provided",
+            ));
+        }
+        self.mint_freeze_authority.replace(c);
+        Ok(())
+    }
+
+    fn add_mint_token_program(
+        &mut self,
+        c: Context<ConstraintMintTokenProgram>,
+    ) -> ParseResult<()> {
+        if !matches!(self.f_ty, Some(Ty::CpiAccount(_))) {
+            return Err(ParseError::new(
+                c.span(),
+                "mint::token_program must be on a CpiAccount",
+            ));
+        }
+        if self.mint_token_program.is_some() {
+            return Err(ParseError::new(
+                c.span(),
+                "mint::token_program already provided",
+            ));
+        }
+        self.mint_token_program.replace(c);
+        Ok(())
+    }
+
+    fn add_token_delegate(&mut self, c: Context<ConstraintTokenDelegate>) -> ParseResult<()> {
+        if !matches!(self.f_ty, Some(Ty::CpiAccount(_))) {
+            return Err(ParseError::new(
+                c.span(),
+                "token::delegate must be on a CpiAccount",
+            ));
+        }
+        if self.token_delegate.is_some() {
+            return Err(ParseError::new(
+                c.span(),
+                "token::delegate already provided",
+            ));
+        }
+        self.token_delegate.replace(c);
+        Ok(())
+    }
+
+    fn add_token_close_authority(
+        &mut self,
+        c: Context<ConstraintTokenCloseAuthority>,
+    ) -> ParseResult<()> {
+        if !matches!(self.f_ty, Some(Ty::CpiAccount(_))) {
+            return Err(ParseError::new(
+                c.span(),
+                "token::close_authority must be on a CpiAccount",
+            ));
+        }
+        if self.token_close_authority.is_some() {
+            return Err(ParseError::new(
+                c.span(),
+                "token::close_authority already provided",
+            ));
+        }
+        self.token_close_authority.replace(c);
+        Ok(())
+    }
+
     fn add_mut(&mut self, c: Context<ConstraintMut>) -> ParseResult<()> {
         if self.mutable.is_some() {
             return Err(ParseError::new(c.span(), "mut already provided"));
@@ -564,6 +1210,20 @@ impl<'ty> ConstraintGroupBuilder<'ty> {
         Ok(())
     }
 
+    fn add_seeds_program(&mut self, c: Context<ConstraintSeedsProgram>) -> ParseResult<()> {
+        if self.seeds.is_none() {
+            return Err(ParseError::new(
+                c.span(),
+                "seeds must be provided before seeds::program",
+            ));
+        }
+        if self.seeds_program.is_some() {
+            return Err(ParseError::new(c.span(), "seeds::program already provided"));
+        }
+        self.seeds_program.replace(c);
+        Ok(())
+    }
+
     fn add_executable(&mut self, c: Context<ConstraintExecutable>) -> ParseResult<()> {
         if self.executable.is_some() {
             return Err(ParseError::new(c.span(), "executable already provided"));
@@ -595,10 +1255,11 @@ impl<'ty> ConstraintGroupBuilder<'ty> {
     }
 
     fn add_associated_payer(&mut self, c: Context<ConstraintAssociatedPayer>) -> ParseResult<()> {
-        if self.associated.is_none() && self.seeds.is_none() {
+        if self.associated.is_none() && self.seeds.is_none() && self.associated_token_mint.is_none()
+        {
             return Err(ParseError::new(
                 c.span(),
-                "associated or seeds must be provided before payer",
+                "associated, seeds, or associated_token must be provided before payer",
             ));
         }
         if self.associated_payer.is_some() {
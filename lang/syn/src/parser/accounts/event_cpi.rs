@@ -0,0 +1,49 @@
+use syn::parse::{Error as ParseError, Result as ParseResult};
+
+/// Name of the attribute adding the accounts required by `emit_cpi!`.
+pub const EVENT_CPI_ATTR: &str = "event_cpi";
+
+pub fn has_event_cpi_attr(strct: &syn::ItemStruct) -> bool {
+    strct
+        .attrs
+        .iter()
+        .any(|attr| attr.path.is_ident(EVENT_CPI_ATTR))
+}
+
+// Appends the accounts needed to emit events via self-CPI to the given
+// struct: the program derived address signing the CPI, and the program itself,
+// which must be passed to invoke it.
+pub fn add_event_cpi_accounts(strct: &syn::ItemStruct) -> ParseResult<syn::ItemStruct> {
+    let has_info_lifetime = strct
+        .generics
+        .lifetimes()
+        .any(|def| def.lifetime.ident == "info");
+    if !has_info_lifetime {
+        return Err(ParseError::new_spanned(
+            &strct.ident,
+            "#[event_cpi] requires the struct to have an 'info lifetime",
+        ));
+    }
+    let mut strct = strct.clone();
+    strct
+        .attrs
+        .retain(|attr| !attr.path.is_ident(EVENT_CPI_ATTR));
+    let event_cpi_fields: syn::FieldsNamed = syn::parse_quote! {{
+        /// CHECK: Only used to sign the event self-CPI.
+        #[account(seeds = [anchor_lang::event::EVENT_AUTHORITY_SEED], bump)]
+        pub event_authority: AccountInfo<'info>,
+        /// CHECK: Checked to be the executing program.
+        #[account(address = *program_id)]
+        pub program: AccountInfo<'info>,
+    }};
+    match &mut strct.fields {
+        syn::Fields::Named(fields) => fields.named.extend(event_cpi_fields.named),
+        _ => {
+            return Err(ParseError::new_spanned(
+                &strct.fields,
+                "fields must be named",
+            ))
+        }
+    }
+    Ok(strct)
+}
@@ -1,6 +1,7 @@
 use crate::{
-    AccountField, AccountsStruct, CompositeField, CpiAccountTy, CpiStateTy, Field, LoaderTy,
-    ProgramAccountTy, ProgramStateTy, SysvarTy, Ty,
+    AccountField, AccountsStruct, CompositeField, CpiAccountTy, CpiStateTy, Field,
+    InterfaceAccountTy, InterfaceTy, LazyAccountTy, LoaderTy, ProgramAccountTy, ProgramStateTy,
+    ProgramTy, SysvarTy, Ty,
 };
 use syn::parse::{Error as ParseError, Result as ParseResult};
 use syn::punctuated::Punctuated;
@@ -9,18 +10,11 @@ use syn::token::Comma;
 use syn::Expr;
 
 pub mod constraints;
+pub mod event_cpi;
 
 pub fn parse(strct: &syn::ItemStruct) -> ParseResult<AccountsStruct> {
-    let instruction_api: Option<Punctuated<Expr, Comma>> = strct
-        .attrs
-        .iter()
-        .find(|a| {
-            a.path
-                .get_ident()
-                .map_or(false, |ident| ident == "instruction")
-        })
-        .map(|ix_attr| ix_attr.parse_args_with(Punctuated::<Expr, Comma>::parse_terminated))
-        .transpose()?;
+    let instruction_api = parse_struct_args(strct, "instruction")?;
+    let scope = parse_struct_args(strct, "scope")?;
     let fields = match &strct.fields {
         syn::Fields::Named(fields) => fields
             .named
@@ -34,31 +28,146 @@ pub fn parse(strct: &syn::ItemStruct) -> ParseResult<AccountsStruct> {
             ))
         }
     };
-    Ok(AccountsStruct::new(strct.clone(), fields, instruction_api))
+    check_duplicate_mut(&fields)?;
+    Ok(AccountsStruct::new(
+        strct.clone(),
+        fields,
+        instruction_api,
+        scope,
+    ))
+}
+
+// Two mutable fields of the same account type could be given the same account,
+// in which case the field written last when the instruction exits silently
+// overwrites the other. Such pairs must opt in with `allow_duplicate`, unless
+// one of them is being initialized, since an initialized account can't be
+// deserialized as a new one.
+fn check_duplicate_mut(fields: &[AccountField]) -> ParseResult<()> {
+    let candidates: Vec<&Field> = fields
+        .iter()
+        .filter_map(|af| match af {
+            AccountField::Field(f) => Some(f),
+            AccountField::CompositeField(_) => None,
+        })
+        .filter(|f| matches!(f.ty, Ty::ProgramAccount(_) | Ty::Loader(_)))
+        .filter(|f| f.constraints.is_mutable() && !f.constraints.is_allow_duplicate())
+        .filter(|f| !f.constraints.is_init() || f.constraints.is_init_if_needed())
+        .collect();
+    for (i, f) in candidates.iter().enumerate() {
+        if let Some(other) = candidates[..i].iter().find(|other| other.ty == f.ty) {
+            return Err(ParseError::new(
+                f.ident.span(),
+                format!(
+                    "`{}` and `{}` are mutable accounts of the same type and could be the same account; add `allow_duplicate` to one of them if this is intended",
+                    other.ident, f.ident
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+// `Clock` and `Rent` fields aren't accounts, so they take no constraints.
+fn check_syscall_sysvar(f: &syn::Field, ty: &Ty) -> ParseResult<()> {
+    if !matches!(ty, Ty::SyscallSysvar(_)) {
+        return Ok(());
+    }
+    match f.attrs.iter().find(|attr| attr.path.is_ident("account")) {
+        None => Ok(()),
+        Some(attr) => Err(ParseError::new(
+            attr.span(),
+            "sysvars loaded via syscall can't have account constraints",
+        )),
+    }
+}
+
+// Parses the `name: Type` list of a struct level attribute, e.g.,
+// `#[instruction(..)]`.
+fn parse_struct_args(
+    strct: &syn::ItemStruct,
+    attr_name: &str,
+) -> ParseResult<Option<Punctuated<Expr, Comma>>> {
+    strct
+        .attrs
+        .iter()
+        .find(|a| a.path.get_ident().map_or(false, |ident| ident == attr_name))
+        .map(|attr| attr.parse_args_with(Punctuated::<Expr, Comma>::parse_terminated))
+        .transpose()
 }
 
 pub fn parse_account_field(f: &syn::Field, has_instruction_api: bool) -> ParseResult<AccountField> {
     let ident = f.ident.clone().unwrap();
-    let account_field = match is_field_primitive(f)? {
+    // Optional accounts are parsed as their inner type.
+    if let Some(inner_ty) = wrapped_inner_ty(&f.ty, "Option") {
+        let (inner, is_boxed) = unbox_field(&syn::Field {
+            ty: inner_ty,
+            ..f.clone()
+        });
+        if !is_field_primitive(&inner)? {
+            return Err(ParseError::new(
+                f.ty.span(),
+                "optional composite accounts are not supported",
+            ));
+        }
+        let ty = parse_ty(&inner)?;
+        if matches!(ty, Ty::SyscallSysvar(_)) {
+            return Err(ParseError::new(
+                f.ty.span(),
+                "sysvars loaded via syscall are always present and can't be optional",
+            ));
+        }
+        check_unchecked_account_doc(f, &ty)?;
+        let (account_constraints, instruction_constraints) =
+            constraints::parse(&inner, Some(&ty), has_instruction_api)?;
+        if account_constraints.is_init() {
+            return Err(ParseError::new(
+                f.ty.span(),
+                "init cannot be used on an optional account",
+            ));
+        }
+        return Ok(AccountField::Field(Field {
+            ident,
+            ty,
+            constraints: account_constraints,
+            instruction_constraints,
+            is_optional: true,
+            is_boxed,
+        }));
+    }
+    // Boxed accounts are parsed as their inner type, which their constraints
+    // apply to, but deserialized straight onto the heap. Boxed composite
+    // fields keep their raw type, since `Box<T>` implements `Accounts`.
+    let (unboxed, is_boxed) = unbox_field(f);
+    let account_field = match is_field_primitive(&unboxed)? {
         true => {
-            let ty = parse_ty(f)?;
+            let ty = parse_ty(&unboxed)?;
+            check_unchecked_account_doc(f, &ty)?;
+            check_syscall_sysvar(f, &ty)?;
             let (account_constraints, instruction_constraints) =
-                constraints::parse(f, Some(&ty), has_instruction_api)?;
+                constraints::parse(&unboxed, Some(&ty), has_instruction_api)?;
             AccountField::Field(Field {
                 ident,
                 ty,
                 constraints: account_constraints,
                 instruction_constraints,
+                is_optional: false,
+                is_boxed,
             })
         }
         false => {
             let (account_constraints, instruction_constraints) =
                 constraints::parse(f, None, has_instruction_api)?;
+            if is_boxed && account_constraints.scope().is_some() {
+                return Err(ParseError::new(
+                    f.ty.span(),
+                    "scope cannot be used on a boxed composite field",
+                ));
+            }
             AccountField::CompositeField(CompositeField {
                 ident,
                 constraints: account_constraints,
                 instruction_constraints,
-                symbol: ident_string(f)?,
+                symbol: ident_string(&unboxed)?,
                 raw_field: f.clone(),
             })
         }
@@ -66,6 +175,63 @@ pub fn parse_account_field(f: &syn::Field, has_instruction_api: bool) -> ParseRe
     Ok(account_field)
 }
 
+// Unchecked accounts skip all validation, so they must document why that's
+// safe with a `/// CHECK:` doc comment.
+fn check_unchecked_account_doc(f: &syn::Field, ty: &Ty) -> ParseResult<()> {
+    if ty != &Ty::UncheckedAccount {
+        return Ok(());
+    }
+    let has_check = f.attrs.iter().any(|attr| match attr.parse_meta() {
+        Ok(syn::Meta::NameValue(syn::MetaNameValue {
+            path,
+            lit: syn::Lit::Str(doc),
+            ..
+        })) if path.is_ident("doc") => doc.value().trim_start().starts_with("CHECK"),
+        _ => false,
+    });
+    if !has_check {
+        return Err(ParseError::new(
+            f.ident.span(),
+            "UncheckedAccount fields must have a `/// CHECK:` doc comment explaining why no checks are necessary",
+        ));
+    }
+    Ok(())
+}
+
+// Returns the given field with `Box<T>` replaced by `T`, along with whether
+// or not the field was boxed.
+fn unbox_field(f: &syn::Field) -> (syn::Field, bool) {
+    match wrapped_inner_ty(&f.ty, "Box") {
+        None => (f.clone(), false),
+        Some(inner_ty) => (
+            syn::Field {
+                ty: inner_ty,
+                ..f.clone()
+            },
+            true,
+        ),
+    }
+}
+
+// Returns the `T` in `Wrapper<T>`, e.g., `Option<T>`, if the given type is
+// wrapped by `wrapper`.
+fn wrapped_inner_ty(ty: &syn::Type, wrapper: &str) -> Option<syn::Type> {
+    let path = match ty {
+        syn::Type::Path(ty_path) => &ty_path.path,
+        _ => return None,
+    };
+    if path.segments.len() != 1 || path.segments[0].ident != wrapper {
+        return None;
+    }
+    match &path.segments[0].arguments {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            syn::GenericArgument::Type(ty) => Some(ty.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 fn is_field_primitive(f: &syn::Field) -> ParseResult<bool> {
     let r = matches!(
         ident_string(f)?.as_str(),
@@ -74,8 +240,17 @@ fn is_field_primitive(f: &syn::Field) -> ParseResult<bool> {
             | "CpiAccount"
             | "Sysvar"
             | "AccountInfo"
+            | "Signer"
+            | "SystemAccount"
+            | "UncheckedAccount"
+            | "Program"
+            | "Interface"
+            | "InterfaceAccount"
             | "CpiState"
             | "Loader"
+            | "LazyAccount"
+            | "Clock"
+            | "Rent"
     );
     Ok(r)
 }
@@ -92,7 +267,16 @@ fn parse_ty(f: &syn::Field) -> ParseResult<Ty> {
         "CpiAccount" => Ty::CpiAccount(parse_cpi_account(&path)?),
         "Sysvar" => Ty::Sysvar(parse_sysvar(&path)?),
         "AccountInfo" => Ty::AccountInfo,
+        "Signer" => Ty::Signer,
+        "SystemAccount" => Ty::SystemAccount,
+        "UncheckedAccount" => Ty::UncheckedAccount,
+        "Program" => Ty::Program(parse_program_ty(&path)?),
+        "Interface" => Ty::Interface(parse_interface_ty(&path)?),
+        "InterfaceAccount" => Ty::InterfaceAccount(parse_interface_account(&path)?),
         "Loader" => Ty::Loader(parse_program_account_zero_copy(&path)?),
+        "LazyAccount" => Ty::LazyAccount(parse_lazy_account(&path)?),
+        "Clock" => Ty::SyscallSysvar(SysvarTy::Clock),
+        "Rent" => Ty::SyscallSysvar(SysvarTy::Rent),
         _ => return Err(ParseError::new(f.ty.span(), "invalid account type given")),
     };
 
@@ -137,6 +321,34 @@ fn parse_cpi_account(path: &syn::Path) -> ParseResult<CpiAccountTy> {
     })
 }
 
+fn parse_program_ty(path: &syn::Path) -> ParseResult<ProgramTy> {
+    let account_ident = parse_account(path)?;
+    Ok(ProgramTy {
+        account_type_path: account_ident,
+    })
+}
+
+fn parse_interface_ty(path: &syn::Path) -> ParseResult<InterfaceTy> {
+    let account_ident = parse_account(path)?;
+    Ok(InterfaceTy {
+        account_type_path: account_ident,
+    })
+}
+
+fn parse_interface_account(path: &syn::Path) -> ParseResult<InterfaceAccountTy> {
+    let account_ident = parse_account(path)?;
+    Ok(InterfaceAccountTy {
+        account_type_path: account_ident,
+    })
+}
+
+fn parse_lazy_account(path: &syn::Path) -> ParseResult<LazyAccountTy> {
+    let account_ident = parse_account(path)?;
+    Ok(LazyAccountTy {
+        account_type_path: account_ident,
+    })
+}
+
 fn parse_program_account(path: &syn::Path) -> ParseResult<ProgramAccountTy> {
     let account_ident = parse_account(path)?;
     Ok(ProgramAccountTy {
@@ -1,4 +1,4 @@
-use crate::parser::program::ctx_accounts_ident;
+use crate::parser::program::{ctx_accounts_generics, ctx_accounts_ident};
 use crate::{FallbackFn, Ix, IxArg};
 use syn::parse::{Error as ParseError, Result as ParseResult};
 use syn::spanned::Spanned;
@@ -24,11 +24,17 @@ pub fn parse(program_mod: &syn::ItemMod) -> ParseResult<(Vec<Ix>, Option<Fallbac
         .map(|method: &syn::ItemFn| {
             let (ctx, args) = parse_args(method)?;
             let anchor_ident = ctx_accounts_ident(&ctx.raw_arg)?;
+            let anchor_generics = ctx_accounts_generics(&ctx.raw_arg)?;
+            let returns = parse_return(method);
+            let interface = parse_interface(method)?;
             Ok(Ix {
                 raw_method: method.clone(),
                 ident: method.sig.ident.clone(),
                 args,
                 anchor_ident,
+                anchor_generics,
+                returns,
+                interface,
             })
         })
         .collect::<ParseResult<Vec<Ix>>>()?;
@@ -63,6 +69,17 @@ pub fn parse(program_mod: &syn::ItemMod) -> ParseResult<(Vec<Ix>, Option<Fallbac
     Ok((ixs, fallback_fn))
 }
 
+// Parses the trait given by `#[interface(MyTrait)]`, marking the handler as
+// an implementation of the trait's method of the same name.
+fn parse_interface(method: &syn::ItemFn) -> ParseResult<Option<syn::Ident>> {
+    method
+        .attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("interface"))
+        .map(|attr| attr.parse_args())
+        .transpose()
+}
+
 pub fn parse_args(method: &syn::ItemFn) -> ParseResult<(IxArg, Vec<IxArg>)> {
     let mut args: Vec<IxArg> = method
         .sig
@@ -91,3 +108,28 @@ pub fn parse_args(method: &syn::ItemFn) -> ParseResult<(IxArg, Vec<IxArg>)> {
 
     Ok((ctx, args))
 }
+
+// Returns `T` for handlers returning `Result<T>` (or `Result<T, E>`), unless
+// `T` is unit. `ProgramResult` and any other return type return nothing.
+pub fn parse_return(method: &syn::ItemFn) -> Option<syn::Type> {
+    let ty = match &method.sig.output {
+        syn::ReturnType::Default => return None,
+        syn::ReturnType::Type(_, ty) => ty,
+    };
+    let segment = match &**ty {
+        syn::Type::Path(ty_path) => ty_path.path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident != "Result" {
+        return None;
+    }
+    let generic_args = match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => args,
+        _ => return None,
+    };
+    match generic_args.args.first()? {
+        syn::GenericArgument::Type(syn::Type::Tuple(tuple)) if tuple.elems.is_empty() => None,
+        syn::GenericArgument::Type(ty) => Some(ty.clone()),
+        _ => None,
+    }
+}
@@ -14,10 +14,32 @@ pub fn parse(program_mod: syn::ItemMod) -> ParseResult<Program> {
         name: program_mod.ident.clone(),
         program_mod,
         fallback_fn,
+        args: None,
     })
 }
 
 fn ctx_accounts_ident(path_ty: &syn::PatType) -> ParseResult<proc_macro2::Ident> {
+    let path = ctx_accounts_path(path_ty)?;
+    Ok(path.segments[0].ident.clone())
+}
+
+// Returns the type and const arguments of a generic accounts struct, e.g.,
+// `MyStrategy` in `Context<Deposit<MyStrategy>>`, skipping lifetimes.
+fn ctx_accounts_generics(path_ty: &syn::PatType) -> ParseResult<Vec<syn::GenericArgument>> {
+    let path = ctx_accounts_path(path_ty)?;
+    let generics = match &path.segments[0].arguments {
+        syn::PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter(|arg| !matches!(arg, syn::GenericArgument::Lifetime(_)))
+            .cloned()
+            .collect(),
+        _ => Vec::new(),
+    };
+    Ok(generics)
+}
+
+fn ctx_accounts_path(path_ty: &syn::PatType) -> ParseResult<&syn::Path> {
     let p = match &*path_ty.ty {
         syn::Type::Path(p) => &p.path,
         _ => return Err(ParseError::new(path_ty.ty.span(), "invalid type")),
@@ -50,5 +72,5 @@ fn ctx_accounts_ident(path_ty: &syn::PatType) -> ParseResult<proc_macro2::Ident>
             ))
         }
     };
-    Ok(path.segments[0].ident.clone())
+    Ok(path)
 }
@@ -1,5 +1,5 @@
 use crate::parser;
-use crate::parser::program::ctx_accounts_ident;
+use crate::parser::program::{ctx_accounts_generics, ctx_accounts_ident};
 use crate::{IxArg, State, StateInterface, StateIx};
 use syn::parse::{Error as ParseError, Result as ParseResult};
 use syn::spanned::Spanned;
@@ -193,12 +193,14 @@ pub fn parse(program_mod: &syn::ItemMod) -> ParseResult<Option<State>> {
                     // Remove the Anchor accounts argument
                     let anchor = args.remove(0);
                     let anchor_ident = ctx_accounts_ident(&anchor.raw_arg)?;
+                    let anchor_generics = ctx_accounts_generics(&anchor.raw_arg)?;
 
                     Ok(StateIx {
                         raw_method: m.clone(),
                         ident: m.sig.ident.clone(),
                         args,
                         anchor_ident,
+                        anchor_generics,
                         has_receiver: true,
                     })
                 })
@@ -269,12 +271,14 @@ pub fn parse(program_mod: &syn::ItemMod) -> ParseResult<Option<State>> {
                                     // Remove the Anchor accounts argument
                                     let anchor = args.remove(0);
                                     let anchor_ident = ctx_accounts_ident(&anchor.raw_arg)?;
+                                    let anchor_generics = ctx_accounts_generics(&anchor.raw_arg)?;
 
                                     Ok(StateIx {
                                         raw_method: m.clone(),
                                         ident: m.sig.ident.clone(),
                                         args,
                                         anchor_ident,
+                                        anchor_generics,
                                         has_receiver,
                                     })
                                 }
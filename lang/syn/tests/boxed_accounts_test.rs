@@ -0,0 +1,52 @@
+use anchor_syn::AccountsStruct;
+use quote::quote;
+
+// Returns the `Accounts` implementation generated for the given struct.
+fn generate(strct: &str) -> String {
+    let accounts: AccountsStruct = syn::parse_str(strct).unwrap();
+    quote!(#accounts).to_string()
+}
+
+#[test]
+fn test_boxed_accounts_are_never_bound_unboxed() {
+    let code = generate(
+        r#"
+        pub struct Boxed<'info> {
+            pub existing: Box<ProgramAccount<'info, Data>>,
+            #[account(init)]
+            pub created: Box<ProgramAccount<'info, Data>>,
+            #[account(init_if_needed)]
+            pub created_if_needed: Box<ProgramAccount<'info, Data>>,
+            #[account(init, seeds = [b"pda".as_ref()], bump, payer = payer)]
+            pub pda: Box<ProgramAccount<'info, Data>>,
+            pub optional: Option<Box<ProgramAccount<'info, Data>>>,
+            #[account(mut, signer)]
+            pub payer: AccountInfo<'info>,
+            pub system_program: AccountInfo<'info>,
+        }
+        "#,
+    );
+
+    for name in &["existing", "created", "created_if_needed"] {
+        assert!(code.contains(&format!("let {} : Box < ProgramAccount < Data > > =", name)));
+    }
+    assert!(code.contains(
+        "let pda : Box < anchor_lang :: ProgramAccount < Data > > = anchor_lang :: __private :: boxed"
+    ));
+    assert!(code.contains("let optional : Option < Box < ProgramAccount < Data > > > ="));
+
+    // No account is deserialized into a local of its inner type, and the
+    // struct is built from the boxes as they are.
+    for name in &[
+        "existing",
+        "created",
+        "created_if_needed",
+        "pda",
+        "optional",
+    ] {
+        assert!(!code.contains(&format!("let {} : ProgramAccount", name)));
+        assert!(!code.contains(&format!("let {} : anchor_lang :: ProgramAccount", name)));
+        assert!(!code.contains(&format!("let {} : Option < ProgramAccount", name)));
+    }
+    assert!(!code.contains("Box :: new"));
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+fn parse_amount(data: &[u8]) -> std::result::Result<u64, ProgramError> {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(data.get(..8).ok_or(ProgramError::InvalidArgument)?);
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn nonzero(amount: u64) -> ProgramResult {
+    if amount == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(())
+}
+
+#[access_control(amount = parse_amount(data) nonzero(amount))]
+fn double(data: &[u8]) -> std::result::Result<u64, ProgramError> {
+    Ok(amount * 2)
+}
+
+#[access_control(nonzero(*amount), nonzero(*amount + 1))]
+fn increment(amount: &mut u64) -> ProgramResult {
+    *amount += 1;
+    Ok(())
+}
+
+#[test]
+fn test_access_control_binding() {
+    assert_eq!(double(&5u64.to_le_bytes()).unwrap(), 10);
+    assert_eq!(
+        double(&0u64.to_le_bytes()).unwrap_err(),
+        ProgramError::InvalidArgument
+    );
+    assert_eq!(double(&[]).unwrap_err(), ProgramError::InvalidArgument);
+}
+
+#[test]
+fn test_access_control_guards() {
+    let mut amount = 1;
+    increment(&mut amount).unwrap();
+    assert_eq!(amount, 2);
+
+    let mut amount = 0;
+    assert!(increment(&mut amount).is_err());
+    assert_eq!(amount, 0);
+}
@@ -0,0 +1,253 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_stubs::{self, SyscallStubs};
+use anchor_lang::solana_program::system_program;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+#[account(discriminator = [1])]
+#[derive(Debug, Default, PartialEq)]
+pub struct Short {
+    pub data: u64,
+}
+
+#[account(discriminator = [2])]
+pub struct Flag {
+    pub set: bool,
+}
+
+#[account(discriminator = [0xde, 0xad, 0xbe, 0xef, 0, 0, 0, 1])]
+#[derive(Debug, PartialEq)]
+pub struct Legacy {
+    pub data: u64,
+}
+
+#[account("my_namespace")]
+pub struct Namespaced {
+    pub data: u64,
+}
+
+#[program]
+pub mod discriminators {
+    use super::*;
+
+    pub fn init_flag(_ctx: Context<InitFlag>) -> ProgramResult {
+        Ok(())
+    }
+
+    pub fn create_short(_ctx: Context<CreateShort>) -> ProgramResult {
+        Ok(())
+    }
+
+    pub fn init_short_if_needed(_ctx: Context<InitShortIfNeeded>) -> ProgramResult {
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitFlag<'info> {
+    #[account(init)]
+    pub flag: ProgramAccount<'info, Flag>,
+}
+
+#[derive(Accounts)]
+pub struct InitShortIfNeeded<'info> {
+    #[account(init_if_needed)]
+    pub short: ProgramAccount<'info, Short>,
+}
+
+#[derive(Accounts)]
+pub struct CreateShort<'info> {
+    #[account(init, seeds = [b"short".as_ref()], bump, payer = payer)]
+    pub short: ProgramAccount<'info, Short>,
+    #[account(mut, signer)]
+    pub payer: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+// The space of the last account created via the system program.
+static CREATED_SPACE: AtomicU64 = AtomicU64::new(0);
+
+// Serves the rent sysvar and, instead of creating accounts, records their
+// space, as given to `system_instruction::create_account`.
+struct Stubs;
+
+impl SyscallStubs for Stubs {
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        0
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        _account_infos: &[AccountInfo],
+        _signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let mut space = [0u8; 8];
+        space.copy_from_slice(&instruction.data[12..20]);
+        CREATED_SPACE.store(u64::from_le_bytes(space), Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_custom_discriminator() {
+    assert_eq!(Short::DISCRIMINATOR, [1]);
+    assert_eq!(Short::discriminator(), Short::DISCRIMINATOR);
+    assert_eq!(
+        Legacy::discriminator(),
+        [0xde, 0xad, 0xbe, 0xef, 0, 0, 0, 1]
+    );
+    assert_eq!(
+        Namespaced::discriminator(),
+        &anchor_lang::solana_program::hash::hash(b"my_namespace:Namespaced").to_bytes()[..8]
+    );
+}
+
+#[test]
+fn test_short_discriminator_roundtrip() {
+    let account = Short { data: 5 };
+    let mut data = vec![];
+    account.try_serialize(&mut data).unwrap();
+    assert_eq!(data, [&[1][..], &5u64.to_le_bytes()].concat());
+    assert_eq!(Short::try_deserialize(&mut &data[..]).unwrap(), account);
+
+    data[0] = 2;
+    assert_eq!(
+        Short::try_deserialize(&mut &data[..]).unwrap_err(),
+        anchor_lang::__private::ErrorCode::AccountDiscriminatorMismatch.into()
+    );
+    assert_eq!(
+        Short::try_deserialize(&mut &[][..]).unwrap_err(),
+        anchor_lang::__private::ErrorCode::AccountDiscriminatorNotFound.into()
+    );
+}
+
+#[test]
+fn test_explicit_discriminator_roundtrip() {
+    let account = Legacy { data: 7 };
+    let mut data = vec![];
+    account.try_serialize(&mut data).unwrap();
+    assert_eq!(&data[..8], Legacy::discriminator());
+    assert_eq!(Legacy::try_deserialize(&mut &data[..]).unwrap(), account);
+}
+
+#[test]
+fn test_init_short_discriminator() {
+    program_stubs::set_syscall_stubs(Box::new(Stubs));
+    let program_id = Pubkey::new_unique();
+    let key = Pubkey::new_unique();
+    let mut lamports = 1_000_000_000;
+    let mut data = vec![0; 2];
+    let info = AccountInfo::new(
+        &key,
+        false,
+        true,
+        &mut lamports,
+        &mut data,
+        &program_id,
+        false,
+        0,
+    );
+
+    // The account is shorter than 8 bytes, but its discriminator is zeroed.
+    let infos = [info.clone()];
+    let init =
+        InitFlag::try_accounts(&program_id, &mut &infos[..], &[], &mut BTreeMap::new()).unwrap();
+    assert!(!init.flag.set);
+
+    info.try_borrow_mut_data().unwrap()[0] = 2;
+    let infos = [info];
+    assert_eq!(
+        InitFlag::try_accounts(&program_id, &mut &infos[..], &[], &mut BTreeMap::new())
+            .map(|_| ())
+            .unwrap_err(),
+        anchor_lang::__private::ErrorCode::AccountDiscriminatorAlreadySet.into()
+    );
+}
+
+#[test]
+fn test_init_if_needed_checks_only_the_discriminator() {
+    program_stubs::set_syscall_stubs(Box::new(Stubs));
+    let program_id = Pubkey::new_unique();
+    let key = Pubkey::new_unique();
+    let mut lamports = 1_000_000_000;
+    // The discriminator is zeroed, but the payload after it isn't.
+    let mut data = [&[0][..], &5u64.to_le_bytes()].concat();
+    let info = AccountInfo::new(
+        &key,
+        false,
+        true,
+        &mut lamports,
+        &mut data,
+        &program_id,
+        false,
+        0,
+    );
+
+    let infos = [info.clone()];
+    let init =
+        InitShortIfNeeded::try_accounts(&program_id, &mut &infos[..], &[], &mut BTreeMap::new())
+            .unwrap();
+    assert_eq!(init.short.data, 5);
+    init.exit(&program_id).unwrap();
+    assert_eq!(info.try_borrow_data().unwrap()[0], 1);
+
+    // Once set, the account is deserialized as an existing one.
+    let infos = [info];
+    let existing =
+        InitShortIfNeeded::try_accounts(&program_id, &mut &infos[..], &[], &mut BTreeMap::new())
+            .unwrap();
+    assert_eq!(*existing.short, Short { data: 5 });
+}
+
+#[test]
+fn test_init_space_includes_discriminator_length() {
+    program_stubs::set_syscall_stubs(Box::new(Stubs));
+    let program_id = Pubkey::new_unique();
+    let (short, _) = Pubkey::find_program_address(&[b"short"], &program_id);
+    let payer = Pubkey::new_unique();
+    let system_program_id = system_program::ID;
+    let mut short_lamports = 1_000_000_000;
+    let mut short_data = vec![0; 9];
+    let mut payer_lamports = 1_000_000_000;
+    let mut payer_data = vec![];
+    let mut system_lamports = 0;
+    let mut system_data = vec![];
+    let infos = [
+        AccountInfo::new(
+            &short,
+            false,
+            true,
+            &mut short_lamports,
+            &mut short_data,
+            &program_id,
+            false,
+            0,
+        ),
+        AccountInfo::new(
+            &payer,
+            true,
+            true,
+            &mut payer_lamports,
+            &mut payer_data,
+            &system_program_id,
+            false,
+            0,
+        ),
+        AccountInfo::new(
+            &system_program_id,
+            false,
+            false,
+            &mut system_lamports,
+            &mut system_data,
+            &system_program_id,
+            true,
+            0,
+        ),
+    ];
+
+    CreateShort::try_accounts(&program_id, &mut &infos[..], &[], &mut BTreeMap::new()).unwrap();
+    assert_eq!(CREATED_SPACE.load(Ordering::SeqCst), 1 + 8);
+}
@@ -0,0 +1,213 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_stubs::{self, SyscallStubs};
+use anchor_lang::solana_program::system_program;
+use anchor_lang::Discriminator;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+#[account(version = 2)]
+#[derive(Debug, Default, PartialEq, InitSpace)]
+pub struct Data {
+    pub count: u64,
+    #[max_len(16)]
+    pub label: String,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct DataV1 {
+    pub count: u64,
+}
+
+impl Migrate for Data {
+    fn migrate(version: u8, data: &mut &[u8]) -> std::result::Result<Self, ProgramError> {
+        match version {
+            1 => {
+                let v1 = DataV1::deserialize(data)
+                    .map_err(|_| anchor_lang::__private::ErrorCode::AccountDidNotDeserialize)?;
+                Ok(Data {
+                    count: v1.count,
+                    label: "migrated".to_string(),
+                })
+            }
+            _ => Err(anchor_lang::__private::ErrorCode::AccountVersionNotSupported.into()),
+        }
+    }
+}
+
+#[program]
+pub mod versions {
+    use super::*;
+
+    pub fn create(_ctx: Context<Create>) -> ProgramResult {
+        Ok(())
+    }
+
+    pub fn create_auto(_ctx: Context<CreateAuto>) -> ProgramResult {
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Create<'info> {
+    #[account(init, seeds = [b"data".as_ref()], bump, payer = payer)]
+    pub data: ProgramAccount<'info, Data>,
+    #[account(mut, signer)]
+    pub payer: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreateAuto<'info> {
+    #[account(init, seeds = [b"data".as_ref()], bump, payer = payer, space = auto)]
+    pub data: ProgramAccount<'info, Data>,
+    #[account(mut, signer)]
+    pub payer: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+// The space of the last account created via the system program.
+static CREATED_SPACE: AtomicU64 = AtomicU64::new(0);
+
+// Serves the rent sysvar and, instead of creating accounts, records their
+// space, as given to `system_instruction::create_account`.
+struct Stubs;
+
+impl SyscallStubs for Stubs {
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        0
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        _account_infos: &[AccountInfo],
+        _signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let mut space = [0u8; 8];
+        space.copy_from_slice(&instruction.data[12..20]);
+        CREATED_SPACE.store(u64::from_le_bytes(space), Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+// Initializes the `data` PDA with an account of the given size, returning the
+// space it was created with.
+fn init(program_id: &Pubkey, len: usize, f: impl FnOnce(&[AccountInfo])) -> u64 {
+    let (data, _) = Pubkey::find_program_address(&[b"data"], program_id);
+    let payer = Pubkey::new_unique();
+    let system_program_id = system_program::ID;
+    let mut data_lamports = 1_000_000_000;
+    let mut data_data = vec![0; len];
+    let mut payer_lamports = 1_000_000_000;
+    let mut payer_data = vec![];
+    let mut system_lamports = 0;
+    let mut system_data = vec![];
+    let infos = [
+        AccountInfo::new(
+            &data,
+            false,
+            true,
+            &mut data_lamports,
+            &mut data_data,
+            program_id,
+            false,
+            0,
+        ),
+        AccountInfo::new(
+            &payer,
+            true,
+            true,
+            &mut payer_lamports,
+            &mut payer_data,
+            &system_program_id,
+            false,
+            0,
+        ),
+        AccountInfo::new(
+            &system_program_id,
+            false,
+            false,
+            &mut system_lamports,
+            &mut system_data,
+            &system_program_id,
+            true,
+            0,
+        ),
+    ];
+    f(&infos);
+    CREATED_SPACE.load(Ordering::SeqCst)
+}
+
+fn account_data(version: u8, body: &[u8]) -> Vec<u8> {
+    let mut data = Data::discriminator().to_vec();
+    data.push(version);
+    data.extend_from_slice(body);
+    data
+}
+
+#[test]
+fn test_versioned_account_roundtrip() {
+    assert_eq!(<Data as Versioned>::version(), 2);
+
+    let account = Data {
+        count: 3,
+        label: "hello".to_string(),
+    };
+    let mut data = vec![];
+    account.try_serialize(&mut data).unwrap();
+    assert_eq!(&data[..8], Data::discriminator());
+    assert_eq!(data[8], 2);
+    assert_eq!(Data::try_deserialize(&mut &data[..]).unwrap(), account);
+}
+
+#[test]
+fn test_versioned_account_migrate() {
+    let body = DataV1 { count: 7 }.try_to_vec().unwrap();
+    let data = account_data(1, &body);
+    assert_eq!(
+        Data::try_deserialize(&mut &data[..]).unwrap(),
+        Data {
+            count: 7,
+            label: "migrated".to_string(),
+        }
+    );
+
+    // Uninitialized accounts have the current layout.
+    let data = account_data(0, &[0; 12]);
+    assert_eq!(
+        Data::try_deserialize_unchecked(&mut &data[..]).unwrap(),
+        Data {
+            count: 0,
+            label: String::new(),
+        }
+    );
+}
+
+#[test]
+fn test_versioned_account_too_new() {
+    let data = account_data(3, &[0; 12]);
+    assert_eq!(
+        Data::try_deserialize(&mut &data[..]).unwrap_err(),
+        anchor_lang::__private::ErrorCode::AccountVersionNotSupported.into()
+    );
+}
+
+#[test]
+fn test_versioned_account_init_space() {
+    program_stubs::set_syscall_stubs(Box::new(Stubs));
+    let program_id = Pubkey::new_unique();
+
+    // Discriminator, version, count, and an empty label.
+    let space = init(&program_id, 8 + 1 + 8 + 4, |infos| {
+        Create::try_accounts(&program_id, &mut &infos[..], &[], &mut BTreeMap::new()).unwrap();
+    });
+    assert_eq!(space, 8 + 1 + 8 + 4);
+
+    // Discriminator, version, and the maximum size of the fields.
+    let space = init(&program_id, 8 + 1 + Data::INIT_SPACE, |infos| {
+        CreateAuto::try_accounts(&program_id, &mut &infos[..], &[], &mut BTreeMap::new()).unwrap();
+    });
+    assert_eq!(space, 8 + 1 + 8 + 4 + 16);
+}
@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_stubs::{self, SyscallStubs};
+use anchor_lang::solana_program::system_program;
+use std::collections::BTreeMap;
+
+anchor_lang::solana_program::declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[account]
+#[derive(Default)]
+pub struct Data {
+    pub value: u64,
+}
+
+#[program]
+pub mod boxed {
+    use super::*;
+
+    pub fn update(_ctx: Context<Update>) -> ProgramResult {
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Update<'info> {
+    pub existing: Box<ProgramAccount<'info, Data>>,
+    #[account(init)]
+    pub created: Box<ProgramAccount<'info, Data>>,
+    #[account(init, seeds = [b"data".as_ref()], bump, payer = payer)]
+    pub pda: Box<ProgramAccount<'info, Data>>,
+    pub optional: Option<Box<ProgramAccount<'info, Data>>>,
+    #[account(mut, signer)]
+    pub payer: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+// Serves the rent sysvar and, instead of creating accounts, does nothing.
+struct Stubs;
+
+impl SyscallStubs for Stubs {
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        0
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        _instruction: &Instruction,
+        _account_infos: &[AccountInfo],
+        _signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_boxed_accounts() {
+    program_stubs::set_syscall_stubs(Box::new(Stubs));
+    let program_id = ID;
+    let existing = Pubkey::new_unique();
+    let created = Pubkey::new_unique();
+    let (pda, _) = Pubkey::find_program_address(&[b"data"], &program_id);
+    let payer = Pubkey::new_unique();
+    let system_program_id = system_program::ID;
+    let mut existing_lamports = 1_000_000_000;
+    let mut existing_data = vec![];
+    Data { value: 7 }.try_serialize(&mut existing_data).unwrap();
+    let mut created_lamports = 1_000_000_000;
+    let mut created_data = vec![0; 16];
+    let mut pda_lamports = 1_000_000_000;
+    let mut pda_data = vec![0; 16];
+    let mut program_lamports = 0;
+    let mut program_data = vec![];
+    let mut payer_lamports = 1_000_000_000;
+    let mut payer_data = vec![];
+    let mut system_lamports = 0;
+    let mut system_data = vec![];
+    let infos = [
+        AccountInfo::new(
+            &existing,
+            false,
+            false,
+            &mut existing_lamports,
+            &mut existing_data,
+            &program_id,
+            false,
+            0,
+        ),
+        AccountInfo::new(
+            &created,
+            false,
+            true,
+            &mut created_lamports,
+            &mut created_data,
+            &program_id,
+            false,
+            0,
+        ),
+        AccountInfo::new(
+            &pda,
+            false,
+            true,
+            &mut pda_lamports,
+            &mut pda_data,
+            &program_id,
+            false,
+            0,
+        ),
+        // The optional account is omitted.
+        AccountInfo::new(
+            &program_id,
+            false,
+            false,
+            &mut program_lamports,
+            &mut program_data,
+            &system_program_id,
+            true,
+            0,
+        ),
+        AccountInfo::new(
+            &payer,
+            true,
+            true,
+            &mut payer_lamports,
+            &mut payer_data,
+            &system_program_id,
+            false,
+            0,
+        ),
+        AccountInfo::new(
+            &system_program_id,
+            false,
+            false,
+            &mut system_lamports,
+            &mut system_data,
+            &system_program_id,
+            true,
+            0,
+        ),
+    ];
+
+    let update =
+        Update::try_accounts(&program_id, &mut &infos[..], &[], &mut BTreeMap::new()).unwrap();
+    assert_eq!(update.existing.value, 7);
+    assert_eq!(update.created.value, 0);
+    assert_eq!(update.pda.value, 0);
+    assert!(update.optional.is_none());
+}
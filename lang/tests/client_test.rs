@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::InstructionData;
+
+anchor_lang::solana_program::declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[program]
+pub mod counter {
+    use super::*;
+
+    pub fn initialize(_ctx: Context<Initialize>, start: u64, label: String) -> ProgramResult {
+        let _ = (start, label);
+        Ok(())
+    }
+
+    pub fn reset(_ctx: Context<Reset>) -> ProgramResult {
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub counter: AccountInfo<'info>,
+    #[account(signer)]
+    pub authority: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Reset<'info> {
+    #[account(mut)]
+    pub counter: AccountInfo<'info>,
+}
+
+#[test]
+fn test_client_instruction_from_pubkeys() {
+    let counter = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let ix = client::instructions::initialize(
+        &ID,
+        client::accounts::Initialize { counter, authority },
+        5,
+        "counter".to_string(),
+    );
+    assert_eq!(
+        ix,
+        Instruction {
+            program_id: ID,
+            accounts: vec![
+                AccountMeta::new(counter, false),
+                AccountMeta::new_readonly(authority, true),
+            ],
+            data: client::args::Initialize {
+                start: 5,
+                label: "counter".to_string(),
+            }
+            .data(),
+        }
+    );
+}
+
+#[test]
+fn test_client_instruction_without_args() {
+    let counter = Pubkey::new_unique();
+    let ix = client::instructions::reset(&ID, client::accounts::Reset { counter });
+    assert_eq!(
+        ix,
+        client::instruction(
+            &ID,
+            client::accounts::Reset { counter },
+            client::args::Reset
+        )
+    );
+    assert_eq!(ix.accounts, vec![AccountMeta::new(counter, false)]);
+    assert_eq!(ix.data, instruction::Reset.data());
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use std::collections::BTreeMap;
+
+#[derive(Accounts)]
+#[scope(authority: Pubkey, amount: u64)]
+pub struct Vault<'info> {
+    #[account(seeds = [b"vault", authority.as_ref()], bump, constraint = vault.lamports() >= amount)]
+    pub vault: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct Withdraw<'info> {
+    pub authority: AccountInfo<'info>,
+    #[account(scope(*authority.key, amount))]
+    pub vault: Vault<'info>,
+}
+
+fn try_withdraw(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    vault: &Pubkey,
+    amount: u64,
+) -> std::result::Result<BTreeMap<String, u8>, ProgramError> {
+    let owner = Pubkey::default();
+    let mut authority_lamports = 0;
+    let mut vault_lamports = 100;
+    let mut authority_data = vec![];
+    let mut vault_data = vec![];
+    let accounts = [
+        AccountInfo::new(
+            authority,
+            true,
+            false,
+            &mut authority_lamports,
+            &mut authority_data,
+            &owner,
+            false,
+            0,
+        ),
+        AccountInfo::new(
+            vault,
+            false,
+            true,
+            &mut vault_lamports,
+            &mut vault_data,
+            program_id,
+            false,
+            0,
+        ),
+    ];
+    let mut bumps = BTreeMap::new();
+    Withdraw::try_accounts(
+        program_id,
+        &mut &accounts[..],
+        &amount.to_le_bytes(),
+        &mut bumps,
+    )?;
+    Ok(bumps)
+}
+
+#[test]
+fn test_scoped_composite() {
+    let program_id = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let (vault, bump) = Pubkey::find_program_address(&[b"vault", authority.as_ref()], &program_id);
+
+    let bumps = try_withdraw(&program_id, &authority, &vault, 100).unwrap();
+    assert_eq!(bumps.get("vault"), Some(&bump));
+}
+
+#[test]
+fn test_scoped_composite_constraints() {
+    let program_id = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let (vault, _) = Pubkey::find_program_address(&[b"vault", authority.as_ref()], &program_id);
+
+    // The vault's seeds are derived from the outer struct's authority.
+    assert_eq!(
+        try_withdraw(&program_id, &Pubkey::new_unique(), &vault, 100).unwrap_err(),
+        anchor_lang::__private::ErrorCode::ConstraintSeeds.into()
+    );
+    // The amount is the outer struct's instruction argument.
+    assert_eq!(
+        try_withdraw(&program_id, &authority, &vault, 101).unwrap_err(),
+        anchor_lang::__private::ErrorCode::ConstraintRaw.into()
+    );
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct Callee<'info> {
+    pub authority: Signer<'info>,
+}
+
+#[test]
+fn test_cpi_context_remaining_accounts() {
+    let owner = Pubkey::default();
+    let keys = [
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ];
+    let mut lamports = [0; 4];
+    let mut data: [Vec<u8>; 4] = Default::default();
+    let mut infos = keys
+        .iter()
+        .zip(lamports.iter_mut())
+        .zip(data.iter_mut())
+        .map(|((key, lamports), data)| {
+            AccountInfo::new(key, false, false, lamports, data, &owner, false, 0)
+        })
+        .collect::<Vec<_>>();
+    infos[0].is_signer = true;
+    infos[1].is_writable = true;
+    infos[2].is_signer = true;
+
+    let ctx = CpiContext::new(
+        infos[3].clone(),
+        Callee {
+            authority: Signer::try_from(&infos[0]).unwrap(),
+        },
+    )
+    .with_remaining_accounts(vec![infos[1].clone(), infos[2].clone()]);
+
+    assert_eq!(
+        ctx.to_account_metas(None),
+        vec![
+            AccountMeta::new_readonly(keys[0], true),
+            AccountMeta::new(keys[1], false),
+            AccountMeta::new_readonly(keys[2], true),
+        ]
+    );
+    assert_eq!(
+        ctx.to_account_infos()
+            .iter()
+            .map(|info| *info.key)
+            .collect::<Vec<_>>(),
+        vec![keys[0], keys[1], keys[2], keys[3]]
+    );
+
+    // State instructions take the state account first.
+    let ctx = CpiStateContext::new(
+        infos[3].clone(),
+        infos[1].clone(),
+        Callee {
+            authority: Signer::try_from(&infos[0]).unwrap(),
+        },
+    )
+    .with_remaining_accounts(vec![infos[2].clone()]);
+    assert_eq!(
+        ctx.to_account_metas(None),
+        vec![
+            AccountMeta::new(keys[1], false),
+            AccountMeta::new_readonly(keys[0], true),
+            AccountMeta::new_readonly(keys[2], true),
+        ]
+    );
+}
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::{Discriminator, InstructionData};
+
+declare_program!(counter, "tests/idls/counter.json");
+
+use counter::types::Kind;
+
+#[test]
+fn test_declare_program_ids() {
+    assert_eq!(
+        counter::ID.to_string(),
+        "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"
+    );
+    assert_eq!(<counter::Counter as Id>::id(), counter::ID);
+}
+
+#[test]
+fn test_declare_program_accounts() {
+    assert_eq!(
+        counter::accounts::Counter::discriminator(),
+        &hash(b"account:Counter").to_bytes()[..8]
+    );
+
+    let account = counter::accounts::Counter {
+        authority: Pubkey::new_unique(),
+        count: 3,
+        kind: Kind::Capped { max_count: 10 },
+        history: [1, 2, 3, 0],
+    };
+    let mut data = vec![];
+    account.try_serialize(&mut data).unwrap();
+    let decoded = counter::accounts::Counter::try_deserialize(&mut &data[..]).unwrap();
+    assert_eq!(decoded.authority, account.authority);
+    assert_eq!(decoded.count, 3);
+    assert_eq!(decoded.history, [1, 2, 3, 0]);
+
+    data[0] ^= 1;
+    assert!(counter::accounts::Counter::try_deserialize(&mut &data[..]).is_err());
+}
+
+#[test]
+fn test_declare_program_custom_discriminator() {
+    assert_eq!(counter::accounts::Config::discriminator(), [1, 2]);
+
+    let config = counter::accounts::Config { max_count: 9 };
+    let mut data = vec![];
+    config.try_serialize(&mut data).unwrap();
+    assert_eq!(data[..2], [1, 2]);
+    assert_eq!(data.len(), 2 + 8);
+    let decoded = counter::accounts::Config::try_deserialize(&mut &data[..]).unwrap();
+    assert_eq!(decoded.max_count, 9);
+}
+
+#[test]
+fn test_declare_program_instruction() {
+    let data = counter::instruction::IncrementBy { amount: 5 }.data();
+    assert_eq!(data[..8], hash(b"global:increment_by").to_bytes()[..8]);
+    assert_eq!(data[8..], 5u64.to_le_bytes());
+
+    let data = counter::instruction::Initialize {
+        authority: Pubkey::default(),
+        kind: Kind::Tagged(Some("tag".to_string())),
+    }
+    .data();
+    assert_eq!(data[..8], hash(b"global:initialize").to_bytes()[..8]);
+}
+
+#[test]
+fn test_declare_program_cpi_accounts() {
+    let program_id = counter::ID;
+    let keys: Vec<Pubkey> = (0..2).map(|_| Pubkey::new_unique()).collect();
+    let mut lamports = [0u64; 2];
+    let mut data = vec![vec![0u8; 0]; 2];
+    let infos: Vec<AccountInfo> = keys
+        .iter()
+        .zip(lamports.iter_mut())
+        .zip(data.iter_mut())
+        .map(|((key, lamports), data)| {
+            AccountInfo::new(key, false, true, lamports, data, &program_id, false, 0)
+        })
+        .collect();
+
+    let accounts = counter::cpi::accounts::IncrementBy {
+        auth: counter::cpi::accounts::Auth {
+            counter: infos[0].clone(),
+            authority: infos[1].clone(),
+        },
+        referrer: None,
+    };
+    let metas = accounts.to_account_metas(None);
+    assert_eq!(metas.len(), 3);
+    assert_eq!(metas[0].pubkey, keys[0]);
+    assert!(!metas[0].is_signer);
+    // Signers are marked as such, even if the account info isn't.
+    assert_eq!(metas[1].pubkey, keys[1]);
+    assert!(metas[1].is_signer);
+    // The program id stands in for the absent optional account.
+    assert_eq!(metas[2], AccountMeta::new_readonly(program_id, false));
+    assert_eq!(accounts.to_account_infos().len(), 2);
+}
+
+#[test]
+fn test_declare_program_events_and_errors() {
+    let event = counter::events::Incremented { count: 7 };
+    let data = anchor_lang::Event::data(&event);
+    let decoded = anchor_lang::event::decode_event::<counter::events::Incremented>(&data).unwrap();
+    assert_eq!(decoded.count, 7);
+
+    assert_eq!(
+        ProgramError::from(counter::errors::ErrorCode::Overflow),
+        ProgramError::Custom(6000)
+    );
+    assert_eq!(
+        counter::errors::ErrorCode::Overflow.to_string(),
+        "The counter overflowed"
+    );
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use std::collections::BTreeMap;
+
+#[account]
+pub struct Counter {
+    pub count: u64,
+}
+
+// Without `allow_duplicate`, the struct fails to compile, since `from` and
+// `to` could be given the same account.
+#[derive(Accounts)]
+pub struct Transfer<'info> {
+    #[account(mut)]
+    pub from: ProgramAccount<'info, Counter>,
+    #[account(mut, allow_duplicate)]
+    pub to: ProgramAccount<'info, Counter>,
+}
+
+#[test]
+fn test_allow_duplicate() {
+    let program_id = Pubkey::new_unique();
+    let key = Pubkey::new_unique();
+    let mut lamports = 0;
+    let mut data = vec![];
+    Counter { count: 1 }.try_serialize(&mut data).unwrap();
+    let info = AccountInfo::new(
+        &key,
+        false,
+        true,
+        &mut lamports,
+        &mut data,
+        &program_id,
+        false,
+        0,
+    );
+
+    let accounts = [info.clone(), info];
+    let transfer =
+        Transfer::try_accounts(&program_id, &mut &accounts[..], &[], &mut BTreeMap::new()).unwrap();
+    assert_eq!(
+        transfer.from.to_account_info().key,
+        transfer.to.to_account_info().key
+    );
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use std::collections::BTreeMap;
+
+#[account]
+#[derive(Debug, PartialEq)]
+pub enum Market {
+    Pending { authority: Pubkey },
+    Active { authority: Pubkey, price: u64 },
+    Closed,
+}
+
+#[derive(Accounts)]
+pub struct Trade<'info> {
+    #[account(variant = Market::Active)]
+    pub market: ProgramAccount<'info, Market>,
+}
+
+fn try_trade(program_id: &Pubkey, market: &Market) -> std::result::Result<(), ProgramError> {
+    let key = Pubkey::new_unique();
+    let mut lamports = 0;
+    let mut data = vec![];
+    market.try_serialize(&mut data).unwrap();
+    let info = AccountInfo::new(
+        &key,
+        false,
+        false,
+        &mut lamports,
+        &mut data,
+        program_id,
+        false,
+        0,
+    );
+    Trade::try_accounts(program_id, &mut &[info][..], &[], &mut BTreeMap::new()).map(|_| ())
+}
+
+#[test]
+fn test_enum_account_roundtrip() {
+    let market = Market::Active {
+        authority: Pubkey::new_unique(),
+        price: 10,
+    };
+    let mut data = vec![];
+    market.try_serialize(&mut data).unwrap();
+    assert_eq!(&data[..8], Market::DISCRIMINATOR);
+    // The variant's Borsh tag follows the discriminator.
+    assert_eq!(data[8], 1);
+    assert_eq!(Market::try_deserialize(&mut &data[..]).unwrap(), market);
+}
+
+#[test]
+fn test_variant_constraint() {
+    let program_id = Pubkey::new_unique();
+    let active = Market::Active {
+        authority: Pubkey::new_unique(),
+        price: 10,
+    };
+    assert!(try_trade(&program_id, &active).is_ok());
+    assert_eq!(
+        try_trade(&program_id, &Market::Closed).unwrap_err(),
+        anchor_lang::__private::ErrorCode::ConstraintVariant.into()
+    );
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use std::convert::TryFrom;
+
+#[error]
+pub enum MyError {
+    #[msg("First")]
+    First,
+    Second,
+}
+
+mod offset {
+    use super::*;
+
+    #[error(offset = 7000)]
+    pub enum OffsetError {
+        Only,
+    }
+}
+use offset::OffsetError;
+
+#[test]
+fn test_error_code_try_from() {
+    let offset = anchor_lang::__private::ERROR_CODE_OFFSET;
+    assert!(matches!(MyError::try_from(offset), Ok(MyError::First)));
+    assert!(matches!(MyError::try_from(offset + 1), Ok(MyError::Second)));
+    assert_eq!(MyError::try_from(offset + 2).unwrap_err(), offset + 2);
+    assert!(matches!(OffsetError::try_from(7000), Ok(OffsetError::Only)));
+    assert!(OffsetError::try_from(offset).is_err());
+}
+
+#[test]
+fn test_error_code_from_program_error() {
+    let err: ProgramError = MyError::Second.into();
+    assert!(matches!(MyError::try_from(err), Ok(MyError::Second)));
+    assert_eq!(
+        MyError::try_from(ProgramError::InvalidArgument).unwrap_err(),
+        ProgramError::InvalidArgument
+    );
+    let err: ProgramError = OffsetError::Only.into();
+    assert_eq!(
+        MyError::try_from(err).unwrap_err(),
+        ProgramError::Custom(7000)
+    );
+}
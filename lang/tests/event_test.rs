@@ -0,0 +1,51 @@
+use anchor_lang::event::{decode_event, EVENT_IX_TAG_LE};
+use anchor_lang::prelude::*;
+
+#[event]
+#[derive(Debug, PartialEq)]
+pub struct MyEvent {
+    pub data: u64,
+    pub label: String,
+}
+
+#[event(namespace = "test", version = 2)]
+#[derive(Debug, PartialEq)]
+pub struct MyVersionedEvent {
+    pub data: u64,
+}
+
+#[test]
+fn test_decode_event() {
+    let event = MyEvent {
+        data: 5,
+        label: "hello".to_string(),
+    };
+    let data = anchor_lang::Event::data(&event);
+    assert_eq!(decode_event::<MyEvent>(&data).unwrap(), event);
+
+    // Events emitted via self-CPI are prefixed with the event tag.
+    let mut ix_data = EVENT_IX_TAG_LE.to_vec();
+    ix_data.extend_from_slice(&data);
+    assert_eq!(decode_event::<MyEvent>(&ix_data).unwrap(), event);
+
+    assert_eq!(
+        decode_event::<MyVersionedEvent>(&data).unwrap_err(),
+        anchor_lang::__private::ErrorCode::EventDiscriminatorMismatch.into()
+    );
+    assert_eq!(
+        decode_event::<MyEvent>(&data[..10]).unwrap_err(),
+        anchor_lang::__private::ErrorCode::EventDidNotDeserialize.into()
+    );
+}
+
+#[test]
+fn test_event_discriminator() {
+    assert_eq!(
+        MyEvent::DISCRIMINATOR,
+        &anchor_lang::solana_program::hash::hash(b"event:MyEvent").to_bytes()[..8]
+    );
+    assert_eq!(
+        MyVersionedEvent::discriminator(),
+        &anchor_lang::solana_program::hash::hash(b"event:test:MyVersionedEvent:v2").to_bytes()[..8]
+    );
+}
@@ -3,6 +3,7 @@
 use anchor_lang::prelude::borsh::maybestd::io::Write;
 use anchor_lang::prelude::*;
 use borsh::{BorshDeserialize, BorshSerialize};
+use std::collections::BTreeMap;
 
 #[derive(Accounts)]
 pub struct GenericsTest<'info, T, U, const N: usize>
@@ -16,6 +17,31 @@ where
     pub associated: CpiAccount<'info, Associated<U>>,
 }
 
+pub trait VaultStrategy: Id + Clone {
+    const SEED: &'static [u8];
+}
+
+#[derive(Clone)]
+pub struct Lending;
+
+impl Id for Lending {
+    fn id() -> Pubkey {
+        Pubkey::new_from_array([1; 32])
+    }
+}
+
+impl VaultStrategy for Lending {
+    const SEED: &'static [u8] = b"lending";
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info, T: VaultStrategy> {
+    #[account(seeds = [T::SEED, authority.key.as_ref()], bump, seeds::program = T::id())]
+    pub vault: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+    pub strategy: Program<'info, T>,
+}
+
 #[account(zero_copy)]
 pub struct Account<const N: usize> {
     pub data: WrappedU8Array<N>,
@@ -31,7 +57,11 @@ where
 }
 
 #[derive(Copy, Clone)]
+#[repr(transparent)]
 pub struct WrappedU8Array<const N: usize>(u8);
+// Zero copy fields must be `Pod`.
+unsafe impl<const N: usize> anchor_lang::__private::bytemuck::Pod for WrappedU8Array<N> {}
+unsafe impl<const N: usize> anchor_lang::__private::bytemuck::Zeroable for WrappedU8Array<N> {}
 impl<const N: usize> BorshSerialize for WrappedU8Array<N> {
     fn serialize<W: Write>(&self, _writer: &mut W) -> borsh::maybestd::io::Result<()> {
         todo!()
@@ -42,3 +72,67 @@ impl<const N: usize> BorshDeserialize for WrappedU8Array<N> {
         todo!()
     }
 }
+
+#[test]
+fn test_generic_constraints() {
+    let program_id = Pubkey::new_unique();
+    let owner = Pubkey::default();
+    let authority = Pubkey::new_unique();
+    let strategy = Lending::id();
+    let (vault, bump) =
+        Pubkey::find_program_address(&[Lending::SEED, authority.as_ref()], &strategy);
+
+    let mut lamports = [0; 3];
+    let mut data: [Vec<u8>; 3] = Default::default();
+    let [vault_lamports, authority_lamports, strategy_lamports] = &mut lamports;
+    let [vault_data, authority_data, strategy_data] = &mut data;
+    let accounts = [
+        AccountInfo::new(
+            &vault,
+            false,
+            false,
+            vault_lamports,
+            vault_data,
+            &strategy,
+            false,
+            0,
+        ),
+        AccountInfo::new(
+            &authority,
+            true,
+            false,
+            authority_lamports,
+            authority_data,
+            &owner,
+            false,
+            0,
+        ),
+        AccountInfo::new(
+            &strategy,
+            false,
+            false,
+            strategy_lamports,
+            strategy_data,
+            &owner,
+            true,
+            0,
+        ),
+    ];
+
+    let mut bumps = BTreeMap::new();
+    Deposit::<Lending>::try_accounts(&program_id, &mut &accounts[..], &[], &mut bumps).unwrap();
+    assert_eq!(bumps.get("vault"), Some(&bump));
+
+    // The vault must be derived from the strategy's seeds.
+    let accounts = [
+        accounts[1].clone(),
+        accounts[1].clone(),
+        accounts[2].clone(),
+    ];
+    assert_eq!(
+        Deposit::<Lending>::try_accounts(&program_id, &mut &accounts[..], &[], &mut bumps)
+            .map(|_| ())
+            .unwrap_err(),
+        anchor_lang::__private::ErrorCode::ConstraintSeeds.into()
+    );
+}
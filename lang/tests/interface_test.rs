@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_stubs::{self, SyscallStubs};
+use anchor_lang::InstructionData;
+
+anchor_lang::solana_program::declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[interface]
+pub trait Swap<'info, T: Accounts<'info>> {
+    fn swap(ctx: Context<T>, amount: u64) -> ProgramResult;
+}
+
+#[program]
+pub mod amm {
+    use super::*;
+
+    #[interface(Swap)]
+    pub fn swap(_ctx: Context<SwapAccounts>, amount: u64) -> ProgramResult {
+        if amount == 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SwapAccounts {}
+
+// Executes CPIs by dispatching directly to the amm program.
+struct Stubs;
+
+impl SyscallStubs for Stubs {
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        _signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        entry(&instruction.program_id, account_infos, &instruction.data)
+    }
+}
+
+#[test]
+fn test_interface_instruction_sighash() {
+    let data = instruction::Swap { amount: 5 }.data();
+    assert_eq!(data[..8], hash(b"Swap:swap").to_bytes()[..8]);
+
+    assert!(entry(&ID, &[], &data).is_ok());
+    assert_eq!(
+        entry(&ID, &[], &instruction::Swap { amount: 0 }.data()).unwrap_err(),
+        ProgramError::InvalidArgument
+    );
+
+    let mut global = hash(b"global:swap").to_bytes()[..8].to_vec();
+    global.extend_from_slice(&5u64.to_le_bytes());
+    assert_eq!(
+        entry(&ID, &[], &global).unwrap_err(),
+        anchor_lang::__private::ErrorCode::InstructionFallbackNotFound.into()
+    );
+}
+
+#[test]
+fn test_interface_cpi() {
+    program_stubs::set_syscall_stubs(Box::new(Stubs));
+    let owner = Pubkey::default();
+    let mut lamports = 0;
+    let mut data = vec![];
+    let program = AccountInfo::new(&ID, false, false, &mut lamports, &mut data, &owner, true, 0);
+    assert!(swap::swap(CpiContext::new(program.clone(), SwapAccounts {}), 5).is_ok());
+    assert_eq!(
+        swap::swap(CpiContext::new(program, SwapAccounts {}), 0).unwrap_err(),
+        ProgramError::InvalidArgument
+    );
+}
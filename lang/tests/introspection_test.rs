@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{
+    self, construct_instructions_data, store_current_index, BorrowedInstruction,
+};
+use std::collections::BTreeMap;
+
+#[derive(Accounts)]
+pub struct Claim<'info> {
+    /// CHECK: Checked by the instruction_sysvar constraint.
+    #[account(
+        instruction_sysvar,
+        constraint = prev_instruction()?.program_id == VERIFY_PROGRAM_ID,
+        constraint = next_instruction_is(&SETTLE_PROGRAM_ID, &[1, 2]),
+    )]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+const VERIFY_PROGRAM_ID: Pubkey = Pubkey::new_from_array([1; 32]);
+const SETTLE_PROGRAM_ID: Pubkey = Pubkey::new_from_array([2; 32]);
+
+fn instructions_data(ixs: &[(Pubkey, &[u8])], current: u16) -> Vec<u8> {
+    let ixs: Vec<BorrowedInstruction> = ixs
+        .iter()
+        .map(|(program_id, data)| BorrowedInstruction {
+            program_id,
+            accounts: vec![],
+            data,
+        })
+        .collect();
+    let mut data = construct_instructions_data(&ixs);
+    store_current_index(&mut data, current);
+    data
+}
+
+fn try_claim(key: &Pubkey, mut data: Vec<u8>) -> std::result::Result<(), ProgramError> {
+    let program_id = Pubkey::new_unique();
+    let owner = Pubkey::default();
+    let mut lamports = 0;
+    let info = AccountInfo::new(
+        key,
+        false,
+        false,
+        &mut lamports,
+        &mut data,
+        &owner,
+        false,
+        0,
+    );
+    Claim::try_accounts(&program_id, &mut &[info][..], &[], &mut BTreeMap::new()).map(|_| ())
+}
+
+#[test]
+fn test_instruction_sysvar_constraints() {
+    let program_id = Pubkey::new_unique();
+    let ixs = [
+        (VERIFY_PROGRAM_ID, &[][..]),
+        (program_id, &[0][..]),
+        (SETTLE_PROGRAM_ID, &[1, 2, 3][..]),
+    ];
+    assert!(try_claim(&instructions::ID, instructions_data(&ixs, 1)).is_ok());
+
+    // No instruction follows.
+    assert_eq!(
+        try_claim(&instructions::ID, instructions_data(&ixs[..2], 1)).unwrap_err(),
+        anchor_lang::__private::ErrorCode::ConstraintRaw.into()
+    );
+    // No instruction precedes, so the helper's error is returned.
+    assert_eq!(
+        try_claim(&instructions::ID, instructions_data(&ixs[1..], 0)).unwrap_err(),
+        ProgramError::InvalidArgument
+    );
+}
+
+#[test]
+fn test_instruction_sysvar_address() {
+    assert_eq!(
+        try_claim(&Pubkey::new_unique(), vec![]).unwrap_err(),
+        anchor_lang::__private::ErrorCode::ConstraintInstructionSysvar.into()
+    );
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_stubs::{self, SyscallStubs};
+use anchor_lang::solana_program::sysvar::instructions::{
+    self, construct_instructions_data, BorrowedInstruction,
+};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static STACK_HEIGHT: AtomicU64 = AtomicU64::new(1);
+
+struct Stubs;
+
+impl SyscallStubs for Stubs {
+    fn sol_get_stack_height(&self) -> u64 {
+        STACK_HEIGHT.load(Ordering::SeqCst)
+    }
+}
+
+#[derive(Accounts)]
+pub struct FlashLoan<'info> {
+    /// CHECK: Checked by the instruction_sysvar constraint.
+    #[account(instruction_sysvar)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+#[non_reentrant(ctx.accounts.instructions)]
+fn flash_loan(ctx: Context<FlashLoan>) -> ProgramResult {
+    Ok(())
+}
+
+#[non_reentrant]
+fn repay(ctx: Context<FlashLoan>) -> anchor_lang::error::Result<()> {
+    Ok(())
+}
+
+fn try_repay(ctx: Context<FlashLoan>) -> ProgramResult {
+    repay(ctx).map_err(Into::into)
+}
+
+fn invoke(
+    program_id: &Pubkey,
+    top_level_program_id: &Pubkey,
+    handler: fn(Context<FlashLoan>) -> std::result::Result<(), ProgramError>,
+) -> std::result::Result<(), ProgramError> {
+    let owner = Pubkey::default();
+    let mut lamports = 0;
+    let mut data = construct_instructions_data(&[BorrowedInstruction {
+        program_id: top_level_program_id,
+        accounts: vec![],
+        data: &[],
+    }]);
+    let info = AccountInfo::new(
+        &instructions::ID,
+        false,
+        false,
+        &mut lamports,
+        &mut data,
+        &owner,
+        false,
+        0,
+    );
+    let mut accounts =
+        FlashLoan::try_accounts(program_id, &mut &[info][..], &[], &mut BTreeMap::new())?;
+    handler(Context::new(
+        program_id,
+        &mut accounts,
+        &[],
+        BTreeMap::new(),
+    ))
+}
+
+#[test]
+fn test_non_reentrant() {
+    program_stubs::set_syscall_stubs(Box::new(Stubs));
+    let program_id = Pubkey::new_unique();
+    let other_program_id = Pubkey::new_unique();
+    let reentrant: ProgramError = anchor_lang::__private::ErrorCode::InstructionReentrant.into();
+
+    // Transaction level.
+    assert!(invoke(&program_id, &program_id, flash_loan).is_ok());
+    assert!(invoke(&program_id, &program_id, try_repay).is_ok());
+
+    // CPI by another program.
+    STACK_HEIGHT.store(2, Ordering::SeqCst);
+    assert!(invoke(&program_id, &other_program_id, flash_loan).is_ok());
+    assert_eq!(
+        invoke(&program_id, &other_program_id, try_repay).unwrap_err(),
+        reentrant
+    );
+
+    // CPI back into the program.
+    assert_eq!(
+        invoke(&program_id, &program_id, flash_loan).unwrap_err(),
+        reentrant
+    );
+
+    // CPI back into the program through another one at the transaction
+    // level, which only the strict form detects.
+    STACK_HEIGHT.store(3, Ordering::SeqCst);
+    assert!(invoke(&program_id, &other_program_id, flash_loan).is_ok());
+    assert_eq!(
+        invoke(&program_id, &other_program_id, try_repay).unwrap_err(),
+        reentrant
+    );
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::clock::Epoch;
+
+anchor_lang::solana_program::declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[program]
+pub mod optional {
+    use super::*;
+
+    pub fn update(_ctx: Context<Update>) -> ProgramResult {
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Update<'info> {
+    #[account(mut)]
+    pub my_account: Option<AccountInfo<'info>>,
+    #[account(signer)]
+    pub authority: AccountInfo<'info>,
+}
+
+#[test]
+fn test_absent_client_account_keeps_positions() {
+    let authority = Pubkey::new_unique();
+    let metas = accounts::Update {
+        my_account: None,
+        authority,
+    }
+    .to_account_metas(None);
+    assert_eq!(
+        metas,
+        vec![
+            AccountMeta::new_readonly(ID, false),
+            AccountMeta::new_readonly(authority, true),
+        ]
+    );
+}
+
+#[test]
+fn test_absent_cpi_account_keeps_positions() {
+    let authority = Pubkey::new_unique();
+    let owner = Pubkey::default();
+    let mut lamports = 0;
+    let mut data = vec![];
+    let authority_info = AccountInfo::new(
+        &authority,
+        true,
+        false,
+        &mut lamports,
+        &mut data,
+        &owner,
+        false,
+        Epoch::default(),
+    );
+    let metas = Update {
+        my_account: None,
+        authority: authority_info,
+    }
+    .to_account_metas(None);
+    assert_eq!(
+        metas,
+        vec![
+            AccountMeta::new_readonly(ID, false),
+            AccountMeta::new_readonly(authority, true),
+        ]
+    );
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_stubs::{self, SyscallStubs};
+
+struct Stubs {
+    program_id: Pubkey,
+}
+
+impl SyscallStubs for Stubs {
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        _account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let signer = Pubkey::create_program_address(signers_seeds[0], &self.program_id)
+            .map_err(|_| ProgramError::InvalidSeeds)?;
+        if instruction.accounts[0].pubkey != signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_pda_invoke() {
+    let program_id = Pubkey::new_unique();
+    program_stubs::set_syscall_stubs(Box::new(Stubs { program_id }));
+
+    let market = Pubkey::new_unique();
+    let (pda, _) = Pubkey::find_program_address(&[b"vault", market.as_ref()], &program_id);
+    let ix = Instruction {
+        program_id: Pubkey::new_unique(),
+        accounts: vec![AccountMeta::new_readonly(pda, true)],
+        data: vec![],
+    };
+    pda_invoke!(&program_id, &ix, &[], &[b"vault".as_ref(), market.as_ref()]).unwrap();
+    assert_eq!(
+        pda_invoke!(&program_id, &ix, &[], &[b"other".as_ref(), market.as_ref()]).unwrap_err(),
+        ProgramError::MissingRequiredSignature
+    );
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use anchor_lang::{Seeds, SignerSeeds};
+
+#[test]
+fn test_seeds() {
+    let program_id = Pubkey::new_unique();
+    let market = Pubkey::new_unique();
+    let index = 7u64.to_le_bytes();
+
+    let seeds = Seeds::new().push(b"vault").push(&market).push(&index);
+    let (address, bump) = seeds.find_program_address(&program_id);
+    assert_eq!(
+        (address, bump),
+        Pubkey::find_program_address(&[b"vault", market.as_ref(), &index], &program_id)
+    );
+
+    let seeds = seeds.bump(&bump);
+    assert_eq!(seeds.create_program_address(&program_id), Ok(address));
+    assert_eq!(
+        &*seeds,
+        &[&b"vault"[..], market.as_ref(), &index, &[bump]][..]
+    );
+}
+
+#[test]
+fn test_signer_seeds() {
+    let bump = 1;
+    let vault = Seeds::new().push(b"vault").bump(&bump);
+    let authority = Seeds::new().push(b"authority");
+    let signer = SignerSeeds::new(&vault).signer(&authority);
+
+    let expected: &[&[&[u8]]] = &[&[b"vault", &[1]], &[b"authority"]];
+    let signer_seeds: &[&[&[u8]]] = &signer;
+    assert_eq!(signer_seeds, expected);
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_stubs::{self, SyscallStubs};
+use std::collections::BTreeMap;
+
+#[program]
+pub mod sysvars {
+    use super::*;
+
+    pub fn check(_ctx: Context<Check>) -> ProgramResult {
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Check<'info> {
+    pub authority: AccountInfo<'info>,
+    pub clock: Clock,
+    pub rent: Rent,
+}
+
+// Serves the clock and rent sysvars, as the runtime would.
+struct Stubs;
+
+impl SyscallStubs for Stubs {
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = Clock {
+            slot: 7,
+            ..Clock::default()
+        };
+        unsafe { *(var_addr as *mut Clock) = clock };
+        0
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        0
+    }
+}
+
+#[test]
+fn test_syscall_sysvars_take_no_accounts() {
+    program_stubs::set_syscall_stubs(Box::new(Stubs));
+    let program_id = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let owner = Pubkey::default();
+    let mut lamports = 0;
+    let mut data = vec![];
+    let infos = [AccountInfo::new(
+        &authority,
+        false,
+        false,
+        &mut lamports,
+        &mut data,
+        &owner,
+        false,
+        0,
+    )];
+    let mut remaining = &infos[..];
+
+    let check =
+        Check::try_accounts(&program_id, &mut remaining, &[], &mut BTreeMap::new()).unwrap();
+    assert!(remaining.is_empty());
+    assert_eq!(check.clock.slot, 7);
+    assert_eq!(check.rent, Rent::default());
+    assert_eq!(check.to_account_metas(None).len(), 1);
+    assert_eq!(check.to_account_infos().len(), 1);
+
+    // Clients only pass the authority.
+    let metas = accounts::Check { authority }.to_account_metas(None);
+    assert_eq!(metas, vec![AccountMeta::new_readonly(authority, false)]);
+}
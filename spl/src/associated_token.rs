@@ -0,0 +1,132 @@
+use anchor_lang::solana_program::account_info::AccountInfo;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::{Accounts, CpiContext};
+
+pub use spl_associated_token_account::{
+    get_associated_token_address, get_associated_token_address_with_program_id, ID,
+};
+
+pub fn create<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, Create<'info>>,
+) -> ProgramResult {
+    let ix = spl_associated_token_account::instruction::create_associated_token_account(
+        ctx.accounts.payer.key,
+        ctx.accounts.authority.key,
+        ctx.accounts.mint.key,
+        &spl_token::ID,
+    );
+    crate::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.payer,
+            ctx.accounts.associated_token,
+            ctx.accounts.authority,
+            ctx.accounts.mint,
+            ctx.accounts.system_program,
+            ctx.accounts.token_program,
+            ctx.program,
+        ],
+        ctx.signer_seeds,
+        "associated_token::create",
+    )
+}
+
+/// Creates the associated token account, unless it already exists, for the
+/// token program given by `token_program`, e.g., Token-2022.
+pub fn create_idempotent<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, CreateIdempotent<'info>>,
+) -> ProgramResult {
+    let ix = spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+        ctx.accounts.payer.key,
+        ctx.accounts.authority.key,
+        ctx.accounts.mint.key,
+        ctx.accounts.token_program.key,
+    );
+    crate::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.payer,
+            ctx.accounts.associated_token,
+            ctx.accounts.authority,
+            ctx.accounts.mint,
+            ctx.accounts.system_program,
+            ctx.accounts.token_program,
+            ctx.program,
+        ],
+        ctx.signer_seeds,
+        "associated_token::create_idempotent",
+    )
+}
+
+/// Recovers the tokens of an associated token account owned by another of
+/// the wallet's associated token accounts, i.e., the `owner_associated_token`
+/// for the `owner_mint`, transferring them to the wallet's own associated
+/// token account for the `nested_mint` and closing the nested account.
+pub fn recover_nested<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, RecoverNested<'info>>,
+) -> ProgramResult {
+    let ix = spl_associated_token_account::instruction::recover_nested(
+        ctx.accounts.wallet.key,
+        ctx.accounts.owner_mint.key,
+        ctx.accounts.nested_mint.key,
+        ctx.accounts.token_program.key,
+    );
+    crate::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.nested,
+            ctx.accounts.nested_mint,
+            ctx.accounts.destination,
+            ctx.accounts.owner_associated_token,
+            ctx.accounts.owner_mint,
+            ctx.accounts.wallet,
+            ctx.accounts.token_program,
+            ctx.program,
+        ],
+        ctx.signer_seeds,
+        "associated_token::recover_nested",
+    )
+}
+
+#[derive(Accounts)]
+pub struct Create<'info> {
+    pub payer: AccountInfo<'info>,
+    pub associated_token: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreateIdempotent<'info> {
+    pub payer: AccountInfo<'info>,
+    pub associated_token: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RecoverNested<'info> {
+    pub nested: AccountInfo<'info>,
+    pub nested_mint: AccountInfo<'info>,
+    pub destination: AccountInfo<'info>,
+    pub owner_associated_token: AccountInfo<'info>,
+    pub owner_mint: AccountInfo<'info>,
+    pub wallet: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+/// The associated token account program, for use with
+/// `Program<'info, AssociatedToken>`.
+#[derive(Clone)]
+pub struct AssociatedToken;
+
+impl anchor_lang::Id for AssociatedToken {
+    fn id() -> Pubkey {
+        ID
+    }
+}
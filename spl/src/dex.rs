@@ -1,5 +1,12 @@
 use anchor_lang::solana_program::account_info::AccountInfo;
 use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::solana_program::system_instruction;
+use anchor_lang::solana_program::sysvar::rent::Rent;
+use anchor_lang::solana_program::sysvar::Sysvar;
 use anchor_lang::{Accounts, CpiContext, ToAccountInfos};
 use serum_dex::instruction::SelfTradeBehavior;
 use serum_dex::matching::{OrderType, Side};
@@ -13,6 +20,130 @@ anchor_lang::solana_program::declare_id!("9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9P
 #[cfg(feature = "devnet")]
 anchor_lang::solana_program::declare_id!("DESVgJVGajEgKGXhb6XmqDHGz3VjdgP7rEVESBgxmroY");
 
+// Tag of the DEX's prune instruction.
+const PRUNE: u32 = 16;
+
+// Sizes of the accounts created by `initialize_market`, matching Serum's
+// own tooling. Each includes the DEX's 12 bytes of padding.
+pub const MARKET_LEN: usize = 388;
+pub const REQUEST_QUEUE_LEN: usize = 5120 + 12;
+pub const EVENT_QUEUE_LEN: usize = 262144 + 12;
+pub const ORDERBOOK_LEN: usize = 65536 + 12;
+
+/// Returns the market's vault signer, which owns its vaults, and the nonce
+/// it's derived with.
+pub fn vault_signer_address(market: &Pubkey) -> (Pubkey, u64) {
+    let mut nonce: u64 = 0;
+    loop {
+        if let Ok(address) =
+            Pubkey::create_program_address(&[market.as_ref(), &nonce.to_le_bytes()], &ID)
+        {
+            return (address, nonce);
+        }
+        nonce += 1;
+    }
+}
+
+/// Creates the market and all of its accounts, i.e., its queues, orderbook
+/// and vaults, funded by `payer`, then initializes it. Each account to be
+/// created must sign, either as a keypair or via `ctx.signer_seeds`.
+///
+/// For a permissioned market, its open orders authority and, optionally,
+/// prune authority may be given, in that order, as `ctx.remaining_accounts`.
+pub fn initialize_market<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, InitializeMarket<'info>>,
+    coin_lot_size: u64,
+    pc_lot_size: u64,
+    pc_dust_threshold: u64,
+) -> ProgramResult {
+    let (vault_signer, vault_signer_nonce) = vault_signer_address(ctx.accounts.market.key);
+    if ctx.accounts.vault_signer.key != &vault_signer {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let rent = Rent::from_account_info(&ctx.accounts.rent)?;
+    let account_infos = ToAccountInfos::to_account_infos(&ctx);
+    let payer = ctx.accounts.payer.key;
+
+    // Create the market's accounts, owned by the DEX, and its vaults.
+    for (account, len, owner) in [
+        (&ctx.accounts.market, MARKET_LEN, &ID),
+        (&ctx.accounts.request_queue, REQUEST_QUEUE_LEN, &ID),
+        (&ctx.accounts.event_queue, EVENT_QUEUE_LEN, &ID),
+        (&ctx.accounts.bids, ORDERBOOK_LEN, &ID),
+        (&ctx.accounts.asks, ORDERBOOK_LEN, &ID),
+        (
+            &ctx.accounts.coin_vault,
+            spl_token::state::Account::LEN,
+            ctx.accounts.token_program.key,
+        ),
+        (
+            &ctx.accounts.pc_vault,
+            spl_token::state::Account::LEN,
+            ctx.accounts.token_program.key,
+        ),
+    ] {
+        let ix = system_instruction::create_account(
+            payer,
+            account.key,
+            rent.minimum_balance(len),
+            len as u64,
+            owner,
+        );
+        crate::invoke_signed(
+            &ix,
+            &account_infos,
+            ctx.signer_seeds,
+            "dex::initialize_market",
+        )?;
+    }
+    for (vault, mint) in [
+        (&ctx.accounts.coin_vault, &ctx.accounts.coin_mint),
+        (&ctx.accounts.pc_vault, &ctx.accounts.pc_mint),
+    ] {
+        let ix = spl_token::instruction::initialize_account(
+            ctx.accounts.token_program.key,
+            vault.key,
+            mint.key,
+            &vault_signer,
+        )?;
+        crate::invoke_signed(
+            &ix,
+            &account_infos,
+            ctx.signer_seeds,
+            "dex::initialize_market",
+        )?;
+    }
+
+    let mut ix = serum_dex::instruction::initialize_market(
+        ctx.accounts.market.key,
+        &ID,
+        ctx.accounts.coin_mint.key,
+        ctx.accounts.pc_mint.key,
+        ctx.accounts.coin_vault.key,
+        ctx.accounts.pc_vault.key,
+        ctx.accounts.bids.key,
+        ctx.accounts.asks.key,
+        ctx.accounts.request_queue.key,
+        ctx.accounts.event_queue.key,
+        coin_lot_size,
+        pc_lot_size,
+        vault_signer_nonce,
+        pc_dust_threshold,
+    )?;
+    ix.accounts.extend(
+        ctx.remaining_accounts
+            .iter()
+            .map(|a| AccountMeta::new_readonly(*a.key, false)),
+    );
+    crate::invoke_signed(
+        &ix,
+        &account_infos,
+        ctx.signer_seeds,
+        "dex::initialize_market",
+    )?;
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn new_order_v3<'info>(
     ctx: CpiContext<'_, '_, '_, 'info, NewOrderV3<'info>>,
@@ -50,10 +181,11 @@ pub fn new_order_v3<'info>(
         limit,
         max_native_pc_qty_including_fees,
     )?;
-    solana_program::program::invoke_signed(
+    crate::invoke_signed(
         &ix,
         &ToAccountInfos::to_account_infos(&ctx),
         ctx.signer_seeds,
+        "dex::new_order_v3",
     )?;
     Ok(())
 }
@@ -74,10 +206,11 @@ pub fn cancel_order_v2<'info>(
         side,
         order_id,
     )?;
-    solana_program::program::invoke_signed(
+    crate::invoke_signed(
         &ix,
         &ToAccountInfos::to_account_infos(&ctx),
         ctx.signer_seeds,
+        "dex::cancel_order_v2",
     )?;
     Ok(())
 }
@@ -99,10 +232,11 @@ pub fn settle_funds<'info>(
         referral.map(|r| r.key),
         ctx.accounts.vault_signer.key,
     )?;
-    solana_program::program::invoke_signed(
+    crate::invoke_signed(
         &ix,
         &ToAccountInfos::to_account_infos(&ctx),
         ctx.signer_seeds,
+        "dex::settle_funds",
     )?;
     Ok(())
 }
@@ -116,10 +250,11 @@ pub fn init_open_orders<'info>(
         ctx.accounts.authority.key,
         ctx.accounts.market.key,
     )?;
-    solana_program::program::invoke_signed(
+    crate::invoke_signed(
         &ix,
         &ToAccountInfos::to_account_infos(&ctx),
         ctx.signer_seeds,
+        "dex::init_open_orders",
     )?;
     Ok(())
 }
@@ -134,10 +269,11 @@ pub fn close_open_orders<'info>(
         ctx.accounts.destination.key,
         ctx.accounts.market.key,
     )?;
-    solana_program::program::invoke_signed(
+    crate::invoke_signed(
         &ix,
         &ToAccountInfos::to_account_infos(&ctx),
         ctx.signer_seeds,
+        "dex::close_open_orders",
     )?;
     Ok(())
 }
@@ -152,10 +288,66 @@ pub fn sweep_fees<'info>(ctx: CpiContext<'_, '_, '_, 'info, SweepFees<'info>>) -
         ctx.accounts.vault_signer.key,
         ctx.accounts.token_program.key,
     )?;
-    solana_program::program::invoke_signed(
+    crate::invoke_signed(
+        &ix,
+        &ToAccountInfos::to_account_infos(&ctx),
+        ctx.signer_seeds,
+        "dex::sweep_fees",
+    )?;
+    Ok(())
+}
+
+/// Processes up to `limit` events on the event queue. The open orders
+/// accounts of the events' owners must be given as `ctx.remaining_accounts`,
+/// sorted by address.
+pub fn consume_events<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, ConsumeEvents<'info>>,
+    limit: u16,
+) -> ProgramResult {
+    let ix = serum_dex::instruction::consume_events(
+        &ID,
+        ctx.remaining_accounts.iter().map(|a| a.key).collect(),
+        ctx.accounts.market.key,
+        ctx.accounts.event_queue.key,
+        ctx.accounts.coin_fee_receivable_account.key,
+        ctx.accounts.pc_fee_receivable_account.key,
+        limit,
+    )?;
+    crate::invoke_signed(
         &ix,
         &ToAccountInfos::to_account_infos(&ctx),
         ctx.signer_seeds,
+        "dex::consume_events",
+    )?;
+    Ok(())
+}
+
+/// Cancels up to `limit` of the open orders account's orders, signed by the
+/// market's prune authority. Only permissioned markets have one.
+pub fn prune<'info>(ctx: CpiContext<'_, '_, '_, 'info, Prune<'info>>, limit: u16) -> ProgramResult {
+    // Prune isn't in the serum_dex version depended on, so it's encoded here
+    // as the DEX does: a version byte, the instruction's tag, then its limit.
+    let mut data = vec![0u8];
+    data.extend_from_slice(&PRUNE.to_le_bytes());
+    data.extend_from_slice(&limit.to_le_bytes());
+    let ix = Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new(*ctx.accounts.market.key, false),
+            AccountMeta::new(*ctx.accounts.bids.key, false),
+            AccountMeta::new(*ctx.accounts.asks.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.prune_authority.key, true),
+            AccountMeta::new(*ctx.accounts.open_orders.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.open_orders_owner.key, false),
+            AccountMeta::new(*ctx.accounts.event_queue.key, false),
+        ],
+        data,
+    };
+    crate::invoke_signed(
+        &ix,
+        &ToAccountInfos::to_account_infos(&ctx),
+        ctx.signer_seeds,
+        "dex::prune",
     )?;
     Ok(())
 }
@@ -230,3 +422,51 @@ pub struct SweepFees<'info> {
     pub vault_signer: AccountInfo<'info>,
     pub token_program: AccountInfo<'info>,
 }
+
+#[derive(Accounts)]
+pub struct InitializeMarket<'info> {
+    pub payer: AccountInfo<'info>,
+    pub market: AccountInfo<'info>,
+    pub request_queue: AccountInfo<'info>,
+    pub event_queue: AccountInfo<'info>,
+    pub bids: AccountInfo<'info>,
+    pub asks: AccountInfo<'info>,
+    pub coin_vault: AccountInfo<'info>,
+    pub pc_vault: AccountInfo<'info>,
+    pub coin_mint: AccountInfo<'info>,
+    pub pc_mint: AccountInfo<'info>,
+    pub vault_signer: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ConsumeEvents<'info> {
+    pub market: AccountInfo<'info>,
+    pub event_queue: AccountInfo<'info>,
+    // Unused by the DEX, but required.
+    pub coin_fee_receivable_account: AccountInfo<'info>,
+    pub pc_fee_receivable_account: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Prune<'info> {
+    pub market: AccountInfo<'info>,
+    pub bids: AccountInfo<'info>,
+    pub asks: AccountInfo<'info>,
+    pub prune_authority: AccountInfo<'info>,
+    pub open_orders: AccountInfo<'info>,
+    pub open_orders_owner: AccountInfo<'info>,
+    pub event_queue: AccountInfo<'info>,
+}
+
+/// The Serum DEX program, for use with `Program<'info, Dex>`.
+#[derive(Clone)]
+pub struct Dex;
+
+impl anchor_lang::Id for Dex {
+    fn id() -> anchor_lang::solana_program::pubkey::Pubkey {
+        ID
+    }
+}
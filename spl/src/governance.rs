@@ -0,0 +1,495 @@
+//! CPI clients and account types for SPL Governance (v3). Its instructions
+//! and accounts are encoded here, rather than depending on the
+//! `spl-governance` crate, whose Solana versions often conflict with a
+//! program's own.
+//!
+//! Governance is deployed separately by many DAOs, so the CPIs target
+//! whichever program is given in the `CpiContext`, and the PDA helpers take
+//! its address. `ID` is the shared mainnet deployment.
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::borsh;
+use anchor_lang::solana_program::account_info::AccountInfo;
+use anchor_lang::solana_program::declare_id;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::{Accounts, AnchorDeserialize, AnchorSerialize, CpiContext, ToAccountInfos};
+use std::ops::Deref;
+
+declare_id!("GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw");
+
+// Indices of the program's instructions.
+const CREATE_PROPOSAL: u8 = 6;
+const CAST_VOTE: u8 = 13;
+const EXECUTE_TRANSACTION: u8 = 16;
+
+/// Returns the address of the proposal created with `proposal_seed`.
+pub fn proposal_address(
+    program_id: &Pubkey,
+    governance: &Pubkey,
+    governing_token_mint: &Pubkey,
+    proposal_seed: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"governance",
+            governance.as_ref(),
+            governing_token_mint.as_ref(),
+            proposal_seed.as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// Returns the address of the token owner's vote record on the proposal.
+pub fn vote_record_address(
+    program_id: &Pubkey,
+    proposal: &Pubkey,
+    token_owner_record: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"governance",
+            proposal.as_ref(),
+            token_owner_record.as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// Returns the address of the realm's config account.
+pub fn realm_config_address(program_id: &Pubkey, realm: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"realm-config", realm.as_ref()], program_id).0
+}
+
+/// Returns the address of the deposit `payer` makes on creating the
+/// proposal.
+pub fn proposal_deposit_address(program_id: &Pubkey, proposal: &Pubkey, payer: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"proposal-deposit", proposal.as_ref(), payer.as_ref()],
+        program_id,
+    )
+    .0
+}
+
+/// Creates a proposal in the `Draft` state. `voter_weight_record` must be
+/// given if the realm has a voter weight plugin.
+#[allow(clippy::too_many_arguments)]
+pub fn create_proposal<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, CreateProposal<'info>>,
+    name: String,
+    description_link: String,
+    vote_type: VoteType,
+    options: Vec<String>,
+    use_deny_option: bool,
+    proposal_seed: Pubkey,
+    voter_weight_record: Option<AccountInfo<'info>>,
+) -> ProgramResult {
+    let mut data = vec![CREATE_PROPOSAL];
+    (
+        name,
+        description_link,
+        vote_type,
+        options,
+        use_deny_option,
+        proposal_seed,
+    )
+        .serialize(&mut data)?;
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*ctx.accounts.realm.key, false),
+        AccountMeta::new(*ctx.accounts.proposal.key, false),
+        AccountMeta::new(*ctx.accounts.governance.key, false),
+        AccountMeta::new(*ctx.accounts.proposal_owner_record.key, false),
+        AccountMeta::new_readonly(*ctx.accounts.governing_token_mint.key, false),
+        AccountMeta::new_readonly(*ctx.accounts.governance_authority.key, true),
+        AccountMeta::new(*ctx.accounts.payer.key, true),
+        AccountMeta::new_readonly(*ctx.accounts.system_program.key, false),
+        AccountMeta::new_readonly(*ctx.accounts.realm_config.key, false),
+    ];
+    if let Some(record) = &voter_weight_record {
+        accounts.push(AccountMeta::new_readonly(*record.key, false));
+    }
+    accounts.push(AccountMeta::new(*ctx.accounts.proposal_deposit.key, false));
+    let ix = Instruction {
+        program_id: *ctx.program.key,
+        accounts,
+        data,
+    };
+    let mut account_infos = ctx.accounts.to_account_infos();
+    account_infos.extend(voter_weight_record);
+    account_infos.push(ctx.program);
+    crate::invoke_signed(
+        &ix,
+        &account_infos,
+        ctx.signer_seeds,
+        "governance::create_proposal",
+    )
+}
+
+/// Casts the token owner's vote on the proposal. `voter_weight_record` and
+/// `max_voter_weight_record` must be given if the realm has the
+/// corresponding plugins.
+pub fn cast_vote<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, CastVote<'info>>,
+    vote: Vote,
+    voter_weight_record: Option<AccountInfo<'info>>,
+    max_voter_weight_record: Option<AccountInfo<'info>>,
+) -> ProgramResult {
+    let mut data = vec![CAST_VOTE];
+    vote.serialize(&mut data)?;
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*ctx.accounts.realm.key, false),
+        AccountMeta::new(*ctx.accounts.governance.key, false),
+        AccountMeta::new(*ctx.accounts.proposal.key, false),
+        AccountMeta::new(*ctx.accounts.proposal_owner_record.key, false),
+        AccountMeta::new(*ctx.accounts.voter_token_owner_record.key, false),
+        AccountMeta::new_readonly(*ctx.accounts.governance_authority.key, true),
+        AccountMeta::new(*ctx.accounts.vote_record.key, false),
+        AccountMeta::new_readonly(*ctx.accounts.vote_governing_token_mint.key, false),
+        AccountMeta::new(*ctx.accounts.payer.key, true),
+        AccountMeta::new_readonly(*ctx.accounts.system_program.key, false),
+        AccountMeta::new_readonly(*ctx.accounts.realm_config.key, false),
+    ];
+    let plugin_records: Vec<AccountInfo<'info>> = voter_weight_record
+        .into_iter()
+        .chain(max_voter_weight_record)
+        .collect();
+    accounts.extend(
+        plugin_records
+            .iter()
+            .map(|record| AccountMeta::new_readonly(*record.key, false)),
+    );
+    let ix = Instruction {
+        program_id: *ctx.program.key,
+        accounts,
+        data,
+    };
+    let mut account_infos = ctx.accounts.to_account_infos();
+    account_infos.extend(plugin_records);
+    account_infos.push(ctx.program);
+    crate::invoke_signed(
+        &ix,
+        &account_infos,
+        ctx.signer_seeds,
+        "governance::cast_vote",
+    )
+}
+
+/// Executes the proposal's transaction, signed by the governance. The
+/// accounts used by the transaction's instructions, including their
+/// programs, must be given as `ctx.remaining_accounts`.
+pub fn execute_transaction<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, ExecuteTransaction<'info>>,
+) -> ProgramResult {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*ctx.accounts.governance.key, false),
+        AccountMeta::new(*ctx.accounts.proposal.key, false),
+        AccountMeta::new(*ctx.accounts.proposal_transaction.key, false),
+    ];
+    accounts.extend(ctx.remaining_accounts.iter().map(|acc| {
+        if acc.is_writable {
+            AccountMeta::new(*acc.key, false)
+        } else {
+            AccountMeta::new_readonly(*acc.key, false)
+        }
+    }));
+    let ix = Instruction {
+        program_id: *ctx.program.key,
+        accounts,
+        data: vec![EXECUTE_TRANSACTION],
+    };
+    let mut account_infos = ctx.accounts.to_account_infos();
+    account_infos.extend(ctx.remaining_accounts);
+    account_infos.push(ctx.program);
+    crate::invoke_signed(
+        &ix,
+        &account_infos,
+        ctx.signer_seeds,
+        "governance::execute_transaction",
+    )
+}
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    pub realm: AccountInfo<'info>,
+    pub proposal: AccountInfo<'info>,
+    pub governance: AccountInfo<'info>,
+    pub proposal_owner_record: AccountInfo<'info>,
+    pub governing_token_mint: AccountInfo<'info>,
+    pub governance_authority: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub realm_config: AccountInfo<'info>,
+    pub proposal_deposit: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    pub realm: AccountInfo<'info>,
+    pub governance: AccountInfo<'info>,
+    pub proposal: AccountInfo<'info>,
+    pub proposal_owner_record: AccountInfo<'info>,
+    pub voter_token_owner_record: AccountInfo<'info>,
+    pub governance_authority: AccountInfo<'info>,
+    pub vote_record: AccountInfo<'info>,
+    pub vote_governing_token_mint: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub realm_config: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteTransaction<'info> {
+    pub governance: AccountInfo<'info>,
+    pub proposal: AccountInfo<'info>,
+    pub proposal_transaction: AccountInfo<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum VoteType {
+    SingleChoice,
+    MultiChoice {
+        choice_type: MultiChoiceType,
+        min_voter_options: u8,
+        max_voter_options: u8,
+        max_winning_options: u8,
+    },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum MultiChoiceType {
+    FullWeight,
+    Weighted,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum Vote {
+    Approve(Vec<VoteChoice>),
+    Deny,
+    Abstain,
+    Veto,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct VoteChoice {
+    pub rank: u8,
+    pub weight_percentage: u8,
+}
+
+// Account types of the program's accounts, as their first byte.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+enum GovernanceAccountType {
+    Uninitialized,
+    RealmV1,
+    TokenOwnerRecordV1,
+    GovernanceV1,
+    ProgramGovernanceV1,
+    ProposalV1,
+    SignatoryRecordV1,
+    VoteRecordV1,
+    ProposalInstructionV1,
+    MintGovernanceV1,
+    TokenGovernanceV1,
+    RealmConfig,
+    VoteRecordV2,
+    ProposalTransactionV2,
+    ProposalV2,
+    ProgramMetadata,
+    RealmV2,
+}
+
+/// A realm account, of either version.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct Realm {
+    pub community_mint: Pubkey,
+    pub config: RealmConfig,
+    pub reserved: [u8; 6],
+    pub legacy1: u16,
+    pub authority: Option<Pubkey>,
+    pub name: String,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct RealmConfig {
+    pub legacy1: u8,
+    pub legacy2: u8,
+    pub reserved: [u8; 6],
+    pub min_community_weight_to_create_governance: u64,
+    pub community_mint_max_voter_weight_source: MintMaxVoterWeightSource,
+    pub council_mint: Option<Pubkey>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum MintMaxVoterWeightSource {
+    SupplyFraction(u64),
+    Absolute(u64),
+}
+
+/// A proposal account, as created by v2 and later of the program.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct Proposal {
+    pub governance: Pubkey,
+    pub governing_token_mint: Pubkey,
+    pub state: ProposalState,
+    pub token_owner_record: Pubkey,
+    pub signatories_count: u8,
+    pub signatories_signed_off_count: u8,
+    pub vote_type: VoteType,
+    pub options: Vec<ProposalOption>,
+    pub deny_vote_weight: Option<u64>,
+    pub reserved1: u8,
+    pub abstain_vote_weight: Option<u64>,
+    pub start_voting_at: Option<i64>,
+    pub draft_at: i64,
+    pub signing_off_at: Option<i64>,
+    pub voting_at: Option<i64>,
+    pub voting_at_slot: Option<u64>,
+    pub voting_completed_at: Option<i64>,
+    pub executing_at: Option<i64>,
+    pub closed_at: Option<i64>,
+    pub execution_flags: InstructionExecutionFlags,
+    pub max_vote_weight: Option<u64>,
+    pub max_voting_time: Option<u32>,
+    pub vote_threshold: Option<VoteThreshold>,
+    pub reserved: [u8; 64],
+    pub name: String,
+    pub description_link: String,
+    pub veto_vote_weight: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ProposalState {
+    Draft,
+    SigningOff,
+    Voting,
+    Succeeded,
+    Executing,
+    Completed,
+    Cancelled,
+    Defeated,
+    ExecutingWithErrors,
+    Vetoed,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ProposalOption {
+    pub label: String,
+    pub vote_weight: u64,
+    pub vote_result: OptionVoteResult,
+    pub transactions_executed_count: u16,
+    pub transactions_count: u16,
+    pub transactions_next_index: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum OptionVoteResult {
+    None,
+    Succeeded,
+    Defeated,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum InstructionExecutionFlags {
+    None,
+    Ordered,
+    UseTransaction,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum VoteThreshold {
+    YesVotePercentage(u8),
+    QuorumPercentage(u8),
+    Disabled,
+}
+
+// Deserializes the account if its type is one of `types`. Accounts may be
+// longer than their fields, e.g., for reserved space, so trailing bytes are
+// ignored.
+fn deserialize_account<T: AnchorDeserialize>(
+    buf: &[u8],
+    types: &[GovernanceAccountType],
+) -> Result<T, ProgramError> {
+    let mut data = buf;
+    let account_type = GovernanceAccountType::deserialize(&mut data)
+        .map_err(|_| ProgramError::from(ErrorCode::AccountDidNotDeserialize))?;
+    if !types.contains(&account_type) {
+        return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+    T::deserialize(&mut data).map_err(|_| ErrorCode::AccountDidNotDeserialize.into())
+}
+
+#[derive(Clone)]
+pub struct RealmAccount(Realm);
+
+impl anchor_lang::AccountDeserialize for RealmAccount {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self, ProgramError> {
+        RealmAccount::try_deserialize_unchecked(buf)
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self, ProgramError> {
+        deserialize_account(
+            buf,
+            &[
+                GovernanceAccountType::RealmV1,
+                GovernanceAccountType::RealmV2,
+            ],
+        )
+        .map(RealmAccount)
+    }
+}
+
+impl anchor_lang::Owners for RealmAccount {
+    fn owners() -> &'static [Pubkey] {
+        &[ID]
+    }
+}
+
+impl Deref for RealmAccount {
+    type Target = Realm;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[derive(Clone)]
+pub struct ProposalAccount(Proposal);
+
+impl anchor_lang::AccountDeserialize for ProposalAccount {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self, ProgramError> {
+        ProposalAccount::try_deserialize_unchecked(buf)
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self, ProgramError> {
+        deserialize_account(buf, &[GovernanceAccountType::ProposalV2]).map(ProposalAccount)
+    }
+}
+
+impl anchor_lang::Owners for ProposalAccount {
+    fn owners() -> &'static [Pubkey] {
+        &[ID]
+    }
+}
+
+impl Deref for ProposalAccount {
+    type Target = Proposal;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// The mainnet SPL Governance program, for use with
+/// `Program<'info, Governance>`.
+#[derive(Clone)]
+pub struct Governance;
+
+impl anchor_lang::Id for Governance {
+    fn id() -> Pubkey {
+        ID
+    }
+}
@@ -1,4 +1,36 @@
+use anchor_lang::error::Error;
+use anchor_lang::solana_program;
+use anchor_lang::solana_program::account_info::AccountInfo;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::Instruction;
+
+pub mod associated_token;
 pub mod dex;
+pub mod governance;
+pub mod memo;
+pub mod metadata;
 pub mod mint;
+pub mod name_service;
 pub mod shmem;
+pub mod stake;
+pub mod stake_pool;
 pub mod token;
+pub mod token_2022;
+pub mod token_interface;
+pub mod token_swap;
+pub mod transfer_hook;
+
+// Invokes a CPI, naming it, e.g., `token::transfer`, in the error's message
+// if it fails.
+pub(crate) fn invoke_signed(
+    ix: &Instruction,
+    account_infos: &[AccountInfo],
+    signer_seeds: &[&[&[u8]]],
+    name: &str,
+) -> ProgramResult {
+    solana_program::program::invoke_signed(ix, account_infos, signer_seeds).map_err(|e| {
+        Error::from(e)
+            .with_message(format!("{} CPI failed", name))
+            .into()
+    })
+}
@@ -0,0 +1,31 @@
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::{Accounts, CpiContext};
+
+pub use spl_memo::ID;
+
+/// Logs the UTF-8 `memo`, e.g., alongside a transfer. Any accounts that must
+/// sign the memo are given as the context's remaining accounts.
+pub fn build_memo<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, BuildMemo>,
+    memo: &[u8],
+) -> ProgramResult {
+    let signers: Vec<&Pubkey> = ctx.remaining_accounts.iter().map(|a| a.key).collect();
+    let ix = spl_memo::build_memo(memo, &signers);
+    let mut account_infos = ctx.remaining_accounts.clone();
+    account_infos.push(ctx.program.clone());
+    crate::invoke_signed(&ix, &account_infos, ctx.signer_seeds, "memo::build_memo")
+}
+
+#[derive(Accounts)]
+pub struct BuildMemo {}
+
+/// The SPL memo program, for use with `Program<'info, Memo>`.
+#[derive(Clone)]
+pub struct Memo;
+
+impl anchor_lang::Id for Memo {
+    fn id() -> Pubkey {
+        ID
+    }
+}
@@ -0,0 +1,350 @@
+//! CPI clients and account types for the Metaplex Token Metadata program.
+//! Its instructions and accounts are encoded here, rather than depending on
+//! the `mpl-token-metadata` crate, whose Solana versions often conflict with
+//! a program's own.
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::borsh;
+use anchor_lang::solana_program::account_info::AccountInfo;
+use anchor_lang::solana_program::declare_id;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::{Accounts, AnchorDeserialize, AnchorSerialize, CpiContext};
+use std::ops::Deref;
+
+declare_id!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+// Indices of the program's instructions.
+const UPDATE_METADATA_ACCOUNT_V2: u8 = 15;
+const CREATE_MASTER_EDITION_V3: u8 = 17;
+const CREATE_METADATA_ACCOUNT_V3: u8 = 33;
+
+// Account key of metadata accounts.
+const KEY_METADATA_V1: u8 = 4;
+
+/// Returns the address of the mint's metadata account.
+pub fn metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"metadata", ID.as_ref(), mint.as_ref()], &ID).0
+}
+
+/// Returns the address of the mint's master edition account.
+pub fn master_edition_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"metadata", ID.as_ref(), mint.as_ref(), b"edition"], &ID).0
+}
+
+/// Creates the mint's metadata account.
+pub fn create_metadata_accounts_v3<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, CreateMetadataAccountsV3<'info>>,
+    data: DataV2,
+    is_mutable: bool,
+    update_authority_is_signer: bool,
+    collection_details: Option<CollectionDetails>,
+) -> ProgramResult {
+    let mut ix_data = vec![CREATE_METADATA_ACCOUNT_V3];
+    (data, is_mutable, collection_details).serialize(&mut ix_data)?;
+    let ix = Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new(*ctx.accounts.metadata.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.mint.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.mint_authority.key, true),
+            AccountMeta::new(*ctx.accounts.payer.key, true),
+            AccountMeta::new_readonly(
+                *ctx.accounts.update_authority.key,
+                update_authority_is_signer,
+            ),
+            AccountMeta::new_readonly(*ctx.accounts.system_program.key, false),
+        ],
+        data: ix_data,
+    };
+    crate::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.metadata,
+            ctx.accounts.mint,
+            ctx.accounts.mint_authority,
+            ctx.accounts.payer,
+            ctx.accounts.update_authority,
+            ctx.accounts.system_program,
+            ctx.program,
+        ],
+        ctx.signer_seeds,
+        "metadata::create_metadata_accounts_v3",
+    )
+}
+
+/// Updates the metadata account, leaving any field given as `None`
+/// unchanged.
+pub fn update_metadata_accounts_v2<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, UpdateMetadataAccountsV2<'info>>,
+    new_update_authority: Option<Pubkey>,
+    data: Option<DataV2>,
+    primary_sale_happened: Option<bool>,
+    is_mutable: Option<bool>,
+) -> ProgramResult {
+    let mut ix_data = vec![UPDATE_METADATA_ACCOUNT_V2];
+    (
+        data,
+        new_update_authority,
+        primary_sale_happened,
+        is_mutable,
+    )
+        .serialize(&mut ix_data)?;
+    let ix = Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new(*ctx.accounts.metadata.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.update_authority.key, true),
+        ],
+        data: ix_data,
+    };
+    crate::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.metadata,
+            ctx.accounts.update_authority,
+            ctx.program,
+        ],
+        ctx.signer_seeds,
+        "metadata::update_metadata_accounts_v2",
+    )
+}
+
+/// Creates the mint's master edition account, transferring the mint and
+/// freeze authorities to it. `max_supply` limits the number of prints, and
+/// is unlimited if `None`.
+pub fn create_master_edition_v3<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, CreateMasterEditionV3<'info>>,
+    max_supply: Option<u64>,
+) -> ProgramResult {
+    let mut ix_data = vec![CREATE_MASTER_EDITION_V3];
+    max_supply.serialize(&mut ix_data)?;
+    let ix = Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new(*ctx.accounts.edition.key, false),
+            AccountMeta::new(*ctx.accounts.mint.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.update_authority.key, true),
+            AccountMeta::new_readonly(*ctx.accounts.mint_authority.key, true),
+            AccountMeta::new(*ctx.accounts.payer.key, true),
+            AccountMeta::new(*ctx.accounts.metadata.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.token_program.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.system_program.key, false),
+        ],
+        data: ix_data,
+    };
+    crate::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.edition,
+            ctx.accounts.mint,
+            ctx.accounts.update_authority,
+            ctx.accounts.mint_authority,
+            ctx.accounts.payer,
+            ctx.accounts.metadata,
+            ctx.accounts.token_program,
+            ctx.accounts.system_program,
+            ctx.program,
+        ],
+        ctx.signer_seeds,
+        "metadata::create_master_edition_v3",
+    )
+}
+
+#[derive(Accounts)]
+pub struct CreateMetadataAccountsV3<'info> {
+    pub metadata: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub mint_authority: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub update_authority: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMetadataAccountsV2<'info> {
+    pub metadata: AccountInfo<'info>,
+    pub update_authority: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreateMasterEditionV3<'info> {
+    pub edition: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub update_authority: AccountInfo<'info>,
+    pub mint_authority: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub metadata: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+/// The data of a metadata account given on creation and update.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct DataV2 {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<Creator>>,
+    pub collection: Option<Collection>,
+    pub uses: Option<Uses>,
+}
+
+/// The data of a metadata account, as stored.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct Data {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<Creator>>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct Creator {
+    pub address: Pubkey,
+    pub verified: bool,
+    /// The creator's share of royalties, in percent.
+    pub share: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct Collection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum CollectionDetails {
+    V1 { size: u64 },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct Uses {
+    pub use_method: UseMethod,
+    pub remaining: u64,
+    pub total: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum UseMethod {
+    Burn,
+    Multiple,
+    Single,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum TokenStandard {
+    NonFungible,
+    FungibleAsset,
+    Fungible,
+    NonFungibleEdition,
+    ProgrammableNonFungible,
+    ProgrammableNonFungibleEdition,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum ProgrammableConfig {
+    V1 { rule_set: Option<Pubkey> },
+}
+
+/// A metadata account.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Metadata {
+    pub update_authority: Pubkey,
+    pub mint: Pubkey,
+    pub data: Data,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<TokenStandard>,
+    pub collection: Option<Collection>,
+    pub uses: Option<Uses>,
+    pub collection_details: Option<CollectionDetails>,
+    pub programmable_config: Option<ProgrammableConfig>,
+}
+
+impl Metadata {
+    // Fields added by later versions of the program may be missing, or
+    // corrupt, in older accounts, in which case they're read as `None`, just
+    // as the program itself does.
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, std::io::Error> {
+        let update_authority = AnchorDeserialize::deserialize(buf)?;
+        let mint = AnchorDeserialize::deserialize(buf)?;
+        let data = AnchorDeserialize::deserialize(buf)?;
+        let primary_sale_happened = AnchorDeserialize::deserialize(buf)?;
+        let is_mutable = AnchorDeserialize::deserialize(buf)?;
+        let edition_nonce = AnchorDeserialize::deserialize(buf)?;
+
+        let token_standard = AnchorDeserialize::deserialize(buf);
+        let collection = AnchorDeserialize::deserialize(buf);
+        let uses = AnchorDeserialize::deserialize(buf);
+        let (token_standard, collection, uses) = match (token_standard, collection, uses) {
+            (Ok(token_standard), Ok(collection), Ok(uses)) => (token_standard, collection, uses),
+            _ => (None, None, None),
+        };
+        let collection_details = AnchorDeserialize::deserialize(buf).unwrap_or(None);
+        let programmable_config = AnchorDeserialize::deserialize(buf).unwrap_or(None);
+
+        Ok(Metadata {
+            update_authority,
+            mint,
+            data,
+            primary_sale_happened,
+            is_mutable,
+            edition_nonce,
+            token_standard,
+            collection,
+            uses,
+            collection_details,
+            programmable_config,
+        })
+    }
+}
+
+/// A metadata account, for use with `CpiAccount` and `InterfaceAccount`.
+#[derive(Clone)]
+pub struct MetadataAccount(Metadata);
+
+impl anchor_lang::AccountDeserialize for MetadataAccount {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self, ProgramError> {
+        if buf.first() != Some(&KEY_METADATA_V1) {
+            return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+        }
+        MetadataAccount::try_deserialize_unchecked(buf)
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self, ProgramError> {
+        let mut data: &[u8] = buf.get(1..).ok_or(ProgramError::InvalidAccountData)?;
+        Metadata::deserialize(&mut data)
+            .map(MetadataAccount)
+            .map_err(|_| ErrorCode::AccountDidNotDeserialize.into())
+    }
+}
+
+impl anchor_lang::Owners for MetadataAccount {
+    fn owners() -> &'static [Pubkey] {
+        &[ID]
+    }
+}
+
+impl Deref for MetadataAccount {
+    type Target = Metadata;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// The Token Metadata program, for use with `Program<'info, TokenMetadata>`.
+#[derive(Clone)]
+pub struct TokenMetadata;
+
+impl anchor_lang::Id for TokenMetadata {
+    fn id() -> Pubkey {
+        ID
+    }
+}
@@ -0,0 +1,219 @@
+//! CPI clients and account types for SPL Name Service. Its instructions and
+//! accounts are encoded here, rather than depending on the
+//! `spl-name-service` crate, whose Solana versions often conflict with a
+//! program's own.
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::borsh;
+use anchor_lang::solana_program::account_info::AccountInfo;
+use anchor_lang::solana_program::declare_id;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::{Accounts, AnchorDeserialize, AnchorSerialize, CpiContext};
+
+declare_id!("namesLPneVptA9Z5rqUDD9tMTWEJwofgaYwp8cawRkX");
+
+/// Prefix of names before hashing.
+pub const HASH_PREFIX: &str = "SPL Name Service";
+
+// Indices of the program's instructions.
+const CREATE: u8 = 0;
+const UPDATE: u8 = 1;
+
+/// Returns the hash of `name`, which, not the name itself, is used to derive
+/// its account.
+pub fn hashed_name(name: &str) -> Vec<u8> {
+    hashv(&[(HASH_PREFIX.to_string() + name).as_bytes()])
+        .to_bytes()
+        .to_vec()
+}
+
+/// Returns the address of the name account with the given hashed name,
+/// class and parent.
+pub fn name_account_address(
+    hashed_name: &[u8],
+    name_class: Option<&Pubkey>,
+    parent_name: Option<&Pubkey>,
+) -> Pubkey {
+    let name_class = name_class.cloned().unwrap_or_default();
+    let parent_name = parent_name.cloned().unwrap_or_default();
+    Pubkey::find_program_address(
+        &[hashed_name, name_class.as_ref(), parent_name.as_ref()],
+        &ID,
+    )
+    .0
+}
+
+/// Returns the address of the reverse lookup account of `address`, i.e.,
+/// the account of class `name_class` named by `address` itself, whose data
+/// is the name `address` resolves from.
+pub fn reverse_lookup_address(
+    address: &Pubkey,
+    name_class: &Pubkey,
+    parent_name: Option<&Pubkey>,
+) -> Pubkey {
+    name_account_address(
+        &hashed_name(&address.to_string()),
+        Some(name_class),
+        parent_name,
+    )
+}
+
+/// Creates the name account, with `space` bytes of data after its header.
+/// `name_class` must be given, as a signer, for a name of a class, and
+/// `parent_name` and its owner, as a signer, for a subname.
+#[allow(clippy::too_many_arguments)]
+pub fn create<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, Create<'info>>,
+    hashed_name: Vec<u8>,
+    lamports: u64,
+    space: u32,
+    name_class: Option<AccountInfo<'info>>,
+    parent_name: Option<AccountInfo<'info>>,
+    parent_name_owner: Option<AccountInfo<'info>>,
+) -> ProgramResult {
+    let mut data = vec![CREATE];
+    (hashed_name, lamports, space).serialize(&mut data)?;
+    // Absent class and parent accounts are given as the default address,
+    // i.e., the system program's.
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*ctx.accounts.system_program.key, false),
+        AccountMeta::new(*ctx.accounts.payer.key, true),
+        AccountMeta::new(*ctx.accounts.name_account.key, false),
+        AccountMeta::new_readonly(*ctx.accounts.name_owner.key, false),
+        match &name_class {
+            Some(name_class) => AccountMeta::new_readonly(*name_class.key, true),
+            None => AccountMeta::new_readonly(Pubkey::default(), false),
+        },
+        match &parent_name {
+            Some(parent_name) => AccountMeta::new_readonly(*parent_name.key, false),
+            None => AccountMeta::new_readonly(Pubkey::default(), false),
+        },
+    ];
+    if let Some(parent_name_owner) = &parent_name_owner {
+        accounts.push(AccountMeta::new_readonly(*parent_name_owner.key, true));
+    }
+    let ix = Instruction {
+        program_id: ID,
+        accounts,
+        data,
+    };
+    let mut account_infos = vec![
+        ctx.accounts.system_program,
+        ctx.accounts.payer,
+        ctx.accounts.name_account,
+        ctx.accounts.name_owner,
+    ];
+    account_infos.extend(name_class);
+    account_infos.extend(parent_name);
+    account_infos.extend(parent_name_owner);
+    account_infos.push(ctx.program);
+    crate::invoke_signed(
+        &ix,
+        &account_infos,
+        ctx.signer_seeds,
+        "name_service::create",
+    )
+}
+
+/// Writes `data` into the name account's data, i.e., after its header, at
+/// `offset`. `update_signer` is the name's owner, or its class if it has
+/// one.
+pub fn update<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, Update<'info>>,
+    offset: u32,
+    data: Vec<u8>,
+) -> ProgramResult {
+    let mut ix_data = vec![UPDATE];
+    (offset, data).serialize(&mut ix_data)?;
+    let ix = Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new(*ctx.accounts.name_account.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.update_signer.key, true),
+        ],
+        data: ix_data,
+    };
+    crate::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.name_account,
+            ctx.accounts.update_signer,
+            ctx.program,
+        ],
+        ctx.signer_seeds,
+        "name_service::update",
+    )
+}
+
+#[derive(Accounts)]
+pub struct Create<'info> {
+    pub payer: AccountInfo<'info>,
+    pub name_account: AccountInfo<'info>,
+    pub name_owner: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Update<'info> {
+    pub name_account: AccountInfo<'info>,
+    pub update_signer: AccountInfo<'info>,
+}
+
+/// The header of a name account, followed by its data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct NameRecordHeader {
+    pub parent_name: Pubkey,
+    pub owner: Pubkey,
+    pub class: Pubkey,
+}
+
+/// A name account.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NameRecord {
+    pub header: NameRecordHeader,
+    pub data: Vec<u8>,
+}
+
+impl NameRecord {
+    /// Returns the name a reverse lookup account resolves to, i.e., its data
+    /// as a borsh string.
+    pub fn reverse_lookup(&self) -> Result<String, ProgramError> {
+        let mut data: &[u8] = &self.data;
+        String::deserialize(&mut data).map_err(|_| ErrorCode::AccountDidNotDeserialize.into())
+    }
+}
+
+impl anchor_lang::AccountDeserialize for NameRecord {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self, ProgramError> {
+        NameRecord::try_deserialize_unchecked(buf)
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self, ProgramError> {
+        let header = NameRecordHeader::deserialize(buf)
+            .map_err(|_| ProgramError::from(ErrorCode::AccountDidNotDeserialize))?;
+        Ok(NameRecord {
+            header,
+            data: buf.to_vec(),
+        })
+    }
+}
+
+impl anchor_lang::Owners for NameRecord {
+    fn owners() -> &'static [Pubkey] {
+        &[ID]
+    }
+}
+
+/// The SPL Name Service program, for use with `Program<'info, NameService>`.
+#[derive(Clone)]
+pub struct NameService;
+
+impl anchor_lang::Id for NameService {
+    fn id() -> Pubkey {
+        ID
+    }
+}
@@ -7,7 +7,6 @@ use solana_program::account_info::AccountInfo;
 use solana_program::declare_id;
 use solana_program::entrypoint::ProgramResult;
 use solana_program::instruction::{AccountMeta, Instruction};
-use solana_program::program;
 
 // TODO: update this once the final shared memory program gets released.
 //       shmem4EWT2sPdVGvTZCzXXRAURL9G5vpPxNwSeKhHUL.
@@ -30,7 +29,7 @@ pub fn ret<'a, 'b, 'c, 'info>(
     };
     let mut accounts = vec![ctx.accounts.buffer];
     accounts.push(ctx.program.clone());
-    program::invoke(&instruction, &accounts)
+    crate::invoke_signed(&instruction, &accounts, &[], "shmem::ret")
 }
 
 #[derive(Accounts)]
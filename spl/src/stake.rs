@@ -0,0 +1,204 @@
+//! CPI clients and account types for the native stake program.
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::solana_program;
+use anchor_lang::solana_program::account_info::AccountInfo;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::solana_program::stake;
+use anchor_lang::solana_program::stake::state::{Authorized, Lockup, StakeState};
+use anchor_lang::{Accounts, CpiContext};
+use std::ops::Deref;
+
+pub use anchor_lang::solana_program::stake::program::ID;
+
+/// Initializes the stake account with its authorities and lockup.
+pub fn initialize<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, Initialize<'info>>,
+    authorized: &Authorized,
+    lockup: &Lockup,
+) -> ProgramResult {
+    let ix = stake::instruction::initialize(ctx.accounts.stake.key, authorized, lockup);
+    crate::invoke_signed(
+        &ix,
+        &[ctx.accounts.stake, ctx.accounts.rent, ctx.program],
+        ctx.signer_seeds,
+        "stake::initialize",
+    )
+}
+
+/// Delegates the stake account to the vote account.
+pub fn delegate_stake<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, DelegateStake<'info>>,
+) -> ProgramResult {
+    let ix = stake::instruction::delegate_stake(
+        ctx.accounts.stake.key,
+        ctx.accounts.staker.key,
+        ctx.accounts.vote.key,
+    );
+    crate::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.stake,
+            ctx.accounts.vote,
+            ctx.accounts.clock,
+            ctx.accounts.stake_history,
+            ctx.accounts.stake_config,
+            ctx.accounts.staker,
+            ctx.program,
+        ],
+        ctx.signer_seeds,
+        "stake::delegate_stake",
+    )
+}
+
+/// Deactivates the stake account's delegation, after which it cools down
+/// over the following epochs.
+pub fn deactivate<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, Deactivate<'info>>,
+) -> ProgramResult {
+    let ix = stake::instruction::deactivate_stake(ctx.accounts.stake.key, ctx.accounts.staker.key);
+    crate::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.stake,
+            ctx.accounts.clock,
+            ctx.accounts.staker,
+            ctx.program,
+        ],
+        ctx.signer_seeds,
+        "stake::deactivate",
+    )
+}
+
+/// Withdraws unstaked lamports from the stake account. `custodian` must be
+/// given, as a signer, to withdraw from a stake account still in lockup.
+pub fn withdraw<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, Withdraw<'info>>,
+    lamports: u64,
+    custodian: Option<AccountInfo<'info>>,
+) -> ProgramResult {
+    let ix = stake::instruction::withdraw(
+        ctx.accounts.stake.key,
+        ctx.accounts.withdrawer.key,
+        ctx.accounts.to.key,
+        lamports,
+        custodian.as_ref().map(|c| c.key),
+    );
+    let mut accounts = vec![
+        ctx.accounts.stake,
+        ctx.accounts.to,
+        ctx.accounts.clock,
+        ctx.accounts.stake_history,
+        ctx.accounts.withdrawer,
+    ];
+    accounts.extend(custodian);
+    accounts.push(ctx.program);
+    crate::invoke_signed(&ix, &accounts, ctx.signer_seeds, "stake::withdraw")
+}
+
+/// Splits `lamports` off the stake account into `split_stake`, which must be
+/// a signer with no data that's owned by the system program, as it's
+/// allocated and assigned to the stake program first.
+pub fn split<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, Split<'info>>,
+    lamports: u64,
+) -> ProgramResult {
+    let ixs = stake::instruction::split(
+        ctx.accounts.stake.key,
+        ctx.accounts.staker.key,
+        lamports,
+        ctx.accounts.split_stake.key,
+    );
+    let accounts = [
+        ctx.accounts.stake,
+        ctx.accounts.split_stake,
+        ctx.accounts.staker,
+        ctx.accounts.system_program,
+        ctx.program,
+    ];
+    for ix in ixs {
+        crate::invoke_signed(&ix, &accounts, ctx.signer_seeds, "stake::split")?;
+    }
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    pub stake: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DelegateStake<'info> {
+    pub stake: AccountInfo<'info>,
+    pub vote: AccountInfo<'info>,
+    pub clock: AccountInfo<'info>,
+    pub stake_history: AccountInfo<'info>,
+    pub stake_config: AccountInfo<'info>,
+    pub staker: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Deactivate<'info> {
+    pub stake: AccountInfo<'info>,
+    pub clock: AccountInfo<'info>,
+    pub staker: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    pub stake: AccountInfo<'info>,
+    pub to: AccountInfo<'info>,
+    pub clock: AccountInfo<'info>,
+    pub stake_history: AccountInfo<'info>,
+    pub withdrawer: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Split<'info> {
+    pub stake: AccountInfo<'info>,
+    pub split_stake: AccountInfo<'info>,
+    pub staker: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Clone)]
+pub struct StakeAccount(StakeState);
+
+impl anchor_lang::AccountDeserialize for StakeAccount {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self, ProgramError> {
+        StakeAccount::try_deserialize_unchecked(buf)
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self, ProgramError> {
+        solana_program::borsh::try_from_slice_unchecked(buf)
+            .map(StakeAccount)
+            .map_err(|_| ErrorCode::AccountDidNotDeserialize.into())
+    }
+}
+
+impl anchor_lang::Owners for StakeAccount {
+    fn owners() -> &'static [Pubkey] {
+        &[ID]
+    }
+}
+
+impl Deref for StakeAccount {
+    type Target = StakeState;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// The native stake program, for use with `Program<'info, Stake>`.
+#[derive(Clone)]
+pub struct Stake;
+
+impl anchor_lang::Id for Stake {
+    fn id() -> Pubkey {
+        ID
+    }
+}
@@ -0,0 +1,434 @@
+//! CPI clients and account types for SPL Stake Pool, for depositing into and
+//! withdrawing from liquid staking pools. Its instructions and accounts are
+//! encoded here, rather than depending on the `spl-stake-pool` crate, whose
+//! Solana versions often conflict with a program's own.
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::borsh;
+use anchor_lang::solana_program::account_info::AccountInfo;
+use anchor_lang::solana_program::declare_id;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::{Accounts, AnchorDeserialize, AnchorSerialize, CpiContext};
+use std::convert::TryFrom;
+
+declare_id!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+
+// Indices of the program's instructions.
+const DEPOSIT_STAKE: u8 = 9;
+const WITHDRAW_STAKE: u8 = 10;
+const DEPOSIT_SOL: u8 = 14;
+const WITHDRAW_SOL: u8 = 16;
+
+// Type of stake pool accounts, as their first byte.
+const ACCOUNT_TYPE_STAKE_POOL: u8 = 1;
+
+/// Returns the address of the stake pool's withdraw authority, which owns
+/// its stake accounts and mints its pool tokens.
+pub fn withdraw_authority_address(program_id: &Pubkey, stake_pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[stake_pool.as_ref(), b"withdraw"], program_id).0
+}
+
+/// Returns the address of the stake pool's default stake deposit authority,
+/// used unless the pool was created with its own.
+pub fn deposit_authority_address(program_id: &Pubkey, stake_pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[stake_pool.as_ref(), b"deposit"], program_id).0
+}
+
+/// Deposits the stake account into the pool, merging it into the validator
+/// stake account for pool tokens. Both of the stake account's authorities
+/// must first be set to the pool's stake deposit authority, which signs only
+/// if it isn't the default one.
+pub fn deposit_stake<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, DepositStake<'info>>,
+) -> ProgramResult {
+    let is_custom_deposit_authority = ctx.accounts.stake_deposit_authority.key
+        != &deposit_authority_address(ctx.program.key, ctx.accounts.stake_pool.key);
+    let ix = Instruction {
+        program_id: *ctx.program.key,
+        accounts: vec![
+            AccountMeta::new(*ctx.accounts.stake_pool.key, false),
+            AccountMeta::new(*ctx.accounts.validator_list.key, false),
+            AccountMeta::new_readonly(
+                *ctx.accounts.stake_deposit_authority.key,
+                is_custom_deposit_authority,
+            ),
+            AccountMeta::new_readonly(*ctx.accounts.withdraw_authority.key, false),
+            AccountMeta::new(*ctx.accounts.stake.key, false),
+            AccountMeta::new(*ctx.accounts.validator_stake.key, false),
+            AccountMeta::new(*ctx.accounts.reserve_stake.key, false),
+            AccountMeta::new(*ctx.accounts.destination.key, false),
+            AccountMeta::new(*ctx.accounts.manager_fee_account.key, false),
+            AccountMeta::new(*ctx.accounts.referrer_fee_account.key, false),
+            AccountMeta::new(*ctx.accounts.pool_mint.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.clock.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.stake_history.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.token_program.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.stake_program.key, false),
+        ],
+        data: vec![DEPOSIT_STAKE],
+    };
+    crate::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.stake_pool,
+            ctx.accounts.validator_list,
+            ctx.accounts.stake_deposit_authority,
+            ctx.accounts.withdraw_authority,
+            ctx.accounts.stake,
+            ctx.accounts.validator_stake,
+            ctx.accounts.reserve_stake,
+            ctx.accounts.destination,
+            ctx.accounts.manager_fee_account,
+            ctx.accounts.referrer_fee_account,
+            ctx.accounts.pool_mint,
+            ctx.accounts.clock,
+            ctx.accounts.stake_history,
+            ctx.accounts.token_program,
+            ctx.accounts.stake_program,
+            ctx.program,
+        ],
+        ctx.signer_seeds,
+        "stake_pool::deposit_stake",
+    )
+}
+
+/// Burns `pool_tokens` pool tokens, splitting their value off the validator
+/// (or reserve) stake account into `stake_destination`, an uninitialized
+/// stake account, whose authorities are set to `user_stake_authority`.
+pub fn withdraw_stake<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, WithdrawStake<'info>>,
+    pool_tokens: u64,
+) -> ProgramResult {
+    let mut data = vec![WITHDRAW_STAKE];
+    pool_tokens.serialize(&mut data)?;
+    let ix = Instruction {
+        program_id: *ctx.program.key,
+        accounts: vec![
+            AccountMeta::new(*ctx.accounts.stake_pool.key, false),
+            AccountMeta::new(*ctx.accounts.validator_list.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.withdraw_authority.key, false),
+            AccountMeta::new(*ctx.accounts.stake_split.key, false),
+            AccountMeta::new(*ctx.accounts.stake_destination.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.user_stake_authority.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.user_transfer_authority.key, true),
+            AccountMeta::new(*ctx.accounts.source.key, false),
+            AccountMeta::new(*ctx.accounts.manager_fee_account.key, false),
+            AccountMeta::new(*ctx.accounts.pool_mint.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.clock.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.token_program.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.stake_program.key, false),
+        ],
+        data,
+    };
+    crate::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.stake_pool,
+            ctx.accounts.validator_list,
+            ctx.accounts.withdraw_authority,
+            ctx.accounts.stake_split,
+            ctx.accounts.stake_destination,
+            ctx.accounts.user_stake_authority,
+            ctx.accounts.user_transfer_authority,
+            ctx.accounts.source,
+            ctx.accounts.manager_fee_account,
+            ctx.accounts.pool_mint,
+            ctx.accounts.clock,
+            ctx.accounts.token_program,
+            ctx.accounts.stake_program,
+            ctx.program,
+        ],
+        ctx.signer_seeds,
+        "stake_pool::withdraw_stake",
+    )
+}
+
+/// Deposits `lamports` from `funding_account` into the pool's reserve for
+/// pool tokens. `sol_deposit_authority` must be given, as a signer, for
+/// pools that restrict SOL deposits.
+pub fn deposit_sol<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, DepositSol<'info>>,
+    lamports: u64,
+    sol_deposit_authority: Option<AccountInfo<'info>>,
+) -> ProgramResult {
+    let mut data = vec![DEPOSIT_SOL];
+    lamports.serialize(&mut data)?;
+    let mut accounts = vec![
+        AccountMeta::new(*ctx.accounts.stake_pool.key, false),
+        AccountMeta::new_readonly(*ctx.accounts.withdraw_authority.key, false),
+        AccountMeta::new(*ctx.accounts.reserve_stake.key, false),
+        AccountMeta::new(*ctx.accounts.funding_account.key, true),
+        AccountMeta::new(*ctx.accounts.destination.key, false),
+        AccountMeta::new(*ctx.accounts.manager_fee_account.key, false),
+        AccountMeta::new(*ctx.accounts.referrer_fee_account.key, false),
+        AccountMeta::new(*ctx.accounts.pool_mint.key, false),
+        AccountMeta::new_readonly(*ctx.accounts.system_program.key, false),
+        AccountMeta::new_readonly(*ctx.accounts.token_program.key, false),
+    ];
+    if let Some(sol_deposit_authority) = &sol_deposit_authority {
+        accounts.push(AccountMeta::new_readonly(*sol_deposit_authority.key, true));
+    }
+    let ix = Instruction {
+        program_id: *ctx.program.key,
+        accounts,
+        data,
+    };
+    let mut account_infos = vec![
+        ctx.accounts.stake_pool,
+        ctx.accounts.withdraw_authority,
+        ctx.accounts.reserve_stake,
+        ctx.accounts.funding_account,
+        ctx.accounts.destination,
+        ctx.accounts.manager_fee_account,
+        ctx.accounts.referrer_fee_account,
+        ctx.accounts.pool_mint,
+        ctx.accounts.system_program,
+        ctx.accounts.token_program,
+    ];
+    account_infos.extend(sol_deposit_authority);
+    account_infos.push(ctx.program);
+    crate::invoke_signed(
+        &ix,
+        &account_infos,
+        ctx.signer_seeds,
+        "stake_pool::deposit_sol",
+    )
+}
+
+/// Burns `pool_tokens` pool tokens, withdrawing their value in lamports from
+/// the pool's reserve into `lamports_destination`. `sol_withdraw_authority`
+/// must be given, as a signer, for pools that restrict SOL withdrawals.
+pub fn withdraw_sol<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, WithdrawSol<'info>>,
+    pool_tokens: u64,
+    sol_withdraw_authority: Option<AccountInfo<'info>>,
+) -> ProgramResult {
+    let mut data = vec![WITHDRAW_SOL];
+    pool_tokens.serialize(&mut data)?;
+    let mut accounts = vec![
+        AccountMeta::new(*ctx.accounts.stake_pool.key, false),
+        AccountMeta::new_readonly(*ctx.accounts.withdraw_authority.key, false),
+        AccountMeta::new_readonly(*ctx.accounts.user_transfer_authority.key, true),
+        AccountMeta::new(*ctx.accounts.source.key, false),
+        AccountMeta::new(*ctx.accounts.reserve_stake.key, false),
+        AccountMeta::new(*ctx.accounts.lamports_destination.key, false),
+        AccountMeta::new(*ctx.accounts.manager_fee_account.key, false),
+        AccountMeta::new(*ctx.accounts.pool_mint.key, false),
+        AccountMeta::new_readonly(*ctx.accounts.clock.key, false),
+        AccountMeta::new_readonly(*ctx.accounts.stake_history.key, false),
+        AccountMeta::new_readonly(*ctx.accounts.stake_program.key, false),
+        AccountMeta::new_readonly(*ctx.accounts.token_program.key, false),
+    ];
+    if let Some(sol_withdraw_authority) = &sol_withdraw_authority {
+        accounts.push(AccountMeta::new_readonly(*sol_withdraw_authority.key, true));
+    }
+    let ix = Instruction {
+        program_id: *ctx.program.key,
+        accounts,
+        data,
+    };
+    let mut account_infos = vec![
+        ctx.accounts.stake_pool,
+        ctx.accounts.withdraw_authority,
+        ctx.accounts.user_transfer_authority,
+        ctx.accounts.source,
+        ctx.accounts.reserve_stake,
+        ctx.accounts.lamports_destination,
+        ctx.accounts.manager_fee_account,
+        ctx.accounts.pool_mint,
+        ctx.accounts.clock,
+        ctx.accounts.stake_history,
+        ctx.accounts.stake_program,
+        ctx.accounts.token_program,
+    ];
+    account_infos.extend(sol_withdraw_authority);
+    account_infos.push(ctx.program);
+    crate::invoke_signed(
+        &ix,
+        &account_infos,
+        ctx.signer_seeds,
+        "stake_pool::withdraw_sol",
+    )
+}
+
+#[derive(Accounts)]
+pub struct DepositStake<'info> {
+    pub stake_pool: AccountInfo<'info>,
+    pub validator_list: AccountInfo<'info>,
+    pub stake_deposit_authority: AccountInfo<'info>,
+    pub withdraw_authority: AccountInfo<'info>,
+    pub stake: AccountInfo<'info>,
+    pub validator_stake: AccountInfo<'info>,
+    pub reserve_stake: AccountInfo<'info>,
+    pub destination: AccountInfo<'info>,
+    pub manager_fee_account: AccountInfo<'info>,
+    pub referrer_fee_account: AccountInfo<'info>,
+    pub pool_mint: AccountInfo<'info>,
+    pub clock: AccountInfo<'info>,
+    pub stake_history: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub stake_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawStake<'info> {
+    pub stake_pool: AccountInfo<'info>,
+    pub validator_list: AccountInfo<'info>,
+    pub withdraw_authority: AccountInfo<'info>,
+    pub stake_split: AccountInfo<'info>,
+    pub stake_destination: AccountInfo<'info>,
+    pub user_stake_authority: AccountInfo<'info>,
+    pub user_transfer_authority: AccountInfo<'info>,
+    pub source: AccountInfo<'info>,
+    pub manager_fee_account: AccountInfo<'info>,
+    pub pool_mint: AccountInfo<'info>,
+    pub clock: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub stake_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DepositSol<'info> {
+    pub stake_pool: AccountInfo<'info>,
+    pub withdraw_authority: AccountInfo<'info>,
+    pub reserve_stake: AccountInfo<'info>,
+    pub funding_account: AccountInfo<'info>,
+    pub destination: AccountInfo<'info>,
+    pub manager_fee_account: AccountInfo<'info>,
+    pub referrer_fee_account: AccountInfo<'info>,
+    pub pool_mint: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSol<'info> {
+    pub stake_pool: AccountInfo<'info>,
+    pub withdraw_authority: AccountInfo<'info>,
+    pub user_transfer_authority: AccountInfo<'info>,
+    pub source: AccountInfo<'info>,
+    pub reserve_stake: AccountInfo<'info>,
+    pub lamports_destination: AccountInfo<'info>,
+    pub manager_fee_account: AccountInfo<'info>,
+    pub pool_mint: AccountInfo<'info>,
+    pub clock: AccountInfo<'info>,
+    pub stake_history: AccountInfo<'info>,
+    pub stake_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+/// A stake pool, i.e., everything after its account type.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct StakePool {
+    pub manager: Pubkey,
+    pub staker: Pubkey,
+    pub stake_deposit_authority: Pubkey,
+    pub stake_withdraw_bump_seed: u8,
+    pub validator_list: Pubkey,
+    pub reserve_stake: Pubkey,
+    pub pool_mint: Pubkey,
+    pub manager_fee_account: Pubkey,
+    pub token_program_id: Pubkey,
+    pub total_lamports: u64,
+    pub pool_token_supply: u64,
+    pub last_update_epoch: u64,
+    pub lockup: Lockup,
+    pub epoch_fee: Fee,
+    pub next_epoch_fee: FutureEpochFee,
+    pub preferred_deposit_validator_vote_address: Option<Pubkey>,
+    pub preferred_withdraw_validator_vote_address: Option<Pubkey>,
+    pub stake_deposit_fee: Fee,
+    pub stake_withdrawal_fee: Fee,
+    pub next_stake_withdrawal_fee: FutureEpochFee,
+    pub stake_referral_fee: u8,
+    pub sol_deposit_authority: Option<Pubkey>,
+    pub sol_deposit_fee: Fee,
+    pub sol_referral_fee: u8,
+    pub sol_withdraw_authority: Option<Pubkey>,
+    pub sol_withdrawal_fee: Fee,
+    pub next_sol_withdrawal_fee: FutureEpochFee,
+    pub last_epoch_pool_token_supply: u64,
+    pub last_epoch_total_lamports: u64,
+}
+
+impl StakePool {
+    /// Returns the lamports `pool_tokens` pool tokens are worth, as of the
+    /// pool's last update, or `None` on overflow.
+    pub fn calc_lamports_withdraw_amount(&self, pool_tokens: u64) -> Option<u64> {
+        if self.pool_token_supply == 0 {
+            return Some(0);
+        }
+        let lamports = (pool_tokens as u128)
+            .checked_mul(self.total_lamports as u128)?
+            .checked_div(self.pool_token_supply as u128)?;
+        u64::try_from(lamports).ok()
+    }
+
+    /// Returns the pool tokens `lamports` lamports are worth, as of the
+    /// pool's last update, before fees, or `None` on overflow.
+    pub fn calc_pool_tokens_for_deposit(&self, lamports: u64) -> Option<u64> {
+        if self.total_lamports == 0 || self.pool_token_supply == 0 {
+            return Some(lamports);
+        }
+        let pool_tokens = (lamports as u128)
+            .checked_mul(self.pool_token_supply as u128)?
+            .checked_div(self.total_lamports as u128)?;
+        u64::try_from(pool_tokens).ok()
+    }
+}
+
+impl anchor_lang::AccountDeserialize for StakePool {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self, ProgramError> {
+        if buf.first() != Some(&ACCOUNT_TYPE_STAKE_POOL) {
+            return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+        }
+        StakePool::try_deserialize_unchecked(buf)
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self, ProgramError> {
+        let mut data: &[u8] = buf.get(1..).ok_or(ProgramError::InvalidAccountData)?;
+        StakePool::deserialize(&mut data).map_err(|_| ErrorCode::AccountDidNotDeserialize.into())
+    }
+}
+
+impl anchor_lang::Owners for StakePool {
+    fn owners() -> &'static [Pubkey] {
+        &[ID]
+    }
+}
+
+/// The lockup of the pool's stake accounts.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Lockup {
+    pub unix_timestamp: i64,
+    pub epoch: u64,
+    pub custodian: Pubkey,
+}
+
+/// A fee of `numerator / denominator`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Fee {
+    pub denominator: u64,
+    pub numerator: u64,
+}
+
+/// A fee change, taking effect after one or two epoch boundaries.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum FutureEpochFee {
+    None,
+    One(Fee),
+    Two(Fee),
+}
+
+/// The SPL Stake Pool program, for use with `Program<'info, StakePoolProgram>`.
+#[derive(Clone)]
+pub struct StakePoolProgram;
+
+impl anchor_lang::Id for StakePoolProgram {
+    fn id() -> Pubkey {
+        ID
+    }
+}
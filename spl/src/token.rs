@@ -1,14 +1,21 @@
-use anchor_lang::solana_program;
+use anchor_lang::prelude::borsh;
 use anchor_lang::solana_program::account_info::AccountInfo;
 use anchor_lang::solana_program::entrypoint::ProgramResult;
 use anchor_lang::solana_program::program_error::ProgramError;
 use anchor_lang::solana_program::program_pack::Pack;
 use anchor_lang::solana_program::pubkey::Pubkey;
-use anchor_lang::{Accounts, CpiContext};
+use anchor_lang::solana_program::rent::Rent;
+use anchor_lang::solana_program::system_instruction;
+use anchor_lang::solana_program::sysvar::Sysvar;
+use anchor_lang::{Accounts, AnchorDeserialize, AnchorSerialize, CpiContext};
 use std::ops::Deref;
 
+pub use spl_token::native_mint;
 pub use spl_token::ID;
 
+/// Transfers tokens with the SPL Token program. Use
+/// `token_interface::transfer_checked` for Token-2022 mints, which may
+/// require transfer hook accounts.
 pub fn transfer<'a, 'b, 'c, 'info>(
     ctx: CpiContext<'a, 'b, 'c, 'info, Transfer<'info>>,
     amount: u64,
@@ -21,7 +28,7 @@ pub fn transfer<'a, 'b, 'c, 'info>(
         &[],
         amount,
     )?;
-    solana_program::program::invoke_signed(
+    crate::invoke_signed(
         &ix,
         &[
             ctx.accounts.from.clone(),
@@ -30,6 +37,7 @@ pub fn transfer<'a, 'b, 'c, 'info>(
             ctx.program.clone(),
         ],
         ctx.signer_seeds,
+        "token::transfer",
     )
 }
 
@@ -45,7 +53,7 @@ pub fn mint_to<'a, 'b, 'c, 'info>(
         &[],
         amount,
     )?;
-    solana_program::program::invoke_signed(
+    crate::invoke_signed(
         &ix,
         &[
             ctx.accounts.to.clone(),
@@ -54,6 +62,7 @@ pub fn mint_to<'a, 'b, 'c, 'info>(
             ctx.program.clone(),
         ],
         ctx.signer_seeds,
+        "token::mint_to",
     )
 }
 
@@ -69,7 +78,7 @@ pub fn burn<'a, 'b, 'c, 'info>(
         &[],
         amount,
     )?;
-    solana_program::program::invoke_signed(
+    crate::invoke_signed(
         &ix,
         &[
             ctx.accounts.to.clone(),
@@ -78,6 +87,93 @@ pub fn burn<'a, 'b, 'c, 'info>(
             ctx.program.clone(),
         ],
         ctx.signer_seeds,
+        "token::burn",
+    )
+}
+
+/// Transfers tokens, checking the mint's decimals.
+pub fn transfer_checked<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, TransferChecked<'info>>,
+    amount: u64,
+    decimals: u8,
+) -> ProgramResult {
+    let ix = spl_token::instruction::transfer_checked(
+        &spl_token::ID,
+        ctx.accounts.from.key,
+        ctx.accounts.mint.key,
+        ctx.accounts.to.key,
+        ctx.accounts.authority.key,
+        &[],
+        amount,
+        decimals,
+    )?;
+    crate::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.from.clone(),
+            ctx.accounts.mint.clone(),
+            ctx.accounts.to.clone(),
+            ctx.accounts.authority.clone(),
+            ctx.program.clone(),
+        ],
+        ctx.signer_seeds,
+        "token::transfer_checked",
+    )
+}
+
+/// Mints tokens, checking the mint's decimals.
+pub fn mint_to_checked<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, MintTo<'info>>,
+    amount: u64,
+    decimals: u8,
+) -> ProgramResult {
+    let ix = spl_token::instruction::mint_to_checked(
+        &spl_token::ID,
+        ctx.accounts.mint.key,
+        ctx.accounts.to.key,
+        ctx.accounts.authority.key,
+        &[],
+        amount,
+        decimals,
+    )?;
+    crate::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.to.clone(),
+            ctx.accounts.mint.clone(),
+            ctx.accounts.authority.clone(),
+            ctx.program.clone(),
+        ],
+        ctx.signer_seeds,
+        "token::mint_to_checked",
+    )
+}
+
+/// Burns tokens, checking the mint's decimals.
+pub fn burn_checked<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, Burn<'info>>,
+    amount: u64,
+    decimals: u8,
+) -> ProgramResult {
+    let ix = spl_token::instruction::burn_checked(
+        &spl_token::ID,
+        ctx.accounts.to.key,
+        ctx.accounts.mint.key,
+        ctx.accounts.authority.key,
+        &[],
+        amount,
+        decimals,
+    )?;
+    crate::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.to.clone(),
+            ctx.accounts.mint.clone(),
+            ctx.accounts.authority.clone(),
+            ctx.program.clone(),
+        ],
+        ctx.signer_seeds,
+        "token::burn_checked",
     )
 }
 
@@ -93,7 +189,7 @@ pub fn approve<'a, 'b, 'c, 'info>(
         &[],
         amount,
     )?;
-    solana_program::program::invoke_signed(
+    crate::invoke_signed(
         &ix,
         &[
             ctx.accounts.to.clone(),
@@ -102,6 +198,107 @@ pub fn approve<'a, 'b, 'c, 'info>(
             ctx.program.clone(),
         ],
         ctx.signer_seeds,
+        "token::approve",
+    )
+}
+
+/// Approves a delegate, checking the mint's decimals.
+pub fn approve_checked<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, ApproveChecked<'info>>,
+    amount: u64,
+    decimals: u8,
+) -> ProgramResult {
+    let ix = spl_token::instruction::approve_checked(
+        &spl_token::ID,
+        ctx.accounts.to.key,
+        ctx.accounts.mint.key,
+        ctx.accounts.delegate.key,
+        ctx.accounts.authority.key,
+        &[],
+        amount,
+        decimals,
+    )?;
+    crate::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.to.clone(),
+            ctx.accounts.mint.clone(),
+            ctx.accounts.delegate.clone(),
+            ctx.accounts.authority.clone(),
+            ctx.program.clone(),
+        ],
+        ctx.signer_seeds,
+        "token::approve_checked",
+    )
+}
+
+/// Revokes the account's delegate.
+pub fn revoke<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, Revoke<'info>>,
+) -> ProgramResult {
+    let ix = spl_token::instruction::revoke(
+        &spl_token::ID,
+        ctx.accounts.source.key,
+        ctx.accounts.authority.key,
+        &[],
+    )?;
+    crate::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.source.clone(),
+            ctx.accounts.authority.clone(),
+            ctx.program.clone(),
+        ],
+        ctx.signer_seeds,
+        "token::revoke",
+    )
+}
+
+/// Freezes the account with the mint's freeze authority.
+pub fn freeze_account<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, FreezeAccount<'info>>,
+) -> ProgramResult {
+    let ix = spl_token::instruction::freeze_account(
+        &spl_token::ID,
+        ctx.accounts.account.key,
+        ctx.accounts.mint.key,
+        ctx.accounts.authority.key,
+        &[],
+    )?;
+    crate::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.account.clone(),
+            ctx.accounts.mint.clone(),
+            ctx.accounts.authority.clone(),
+            ctx.program.clone(),
+        ],
+        ctx.signer_seeds,
+        "token::freeze_account",
+    )
+}
+
+/// Thaws the frozen account with the mint's freeze authority.
+pub fn thaw_account<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, ThawAccount<'info>>,
+) -> ProgramResult {
+    let ix = spl_token::instruction::thaw_account(
+        &spl_token::ID,
+        ctx.accounts.account.key,
+        ctx.accounts.mint.key,
+        ctx.accounts.authority.key,
+        &[],
+    )?;
+    crate::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.account.clone(),
+            ctx.accounts.mint.clone(),
+            ctx.accounts.authority.clone(),
+            ctx.program.clone(),
+        ],
+        ctx.signer_seeds,
+        "token::thaw_account",
     )
 }
 
@@ -114,7 +311,7 @@ pub fn initialize_account<'a, 'b, 'c, 'info>(
         ctx.accounts.mint.key,
         ctx.accounts.authority.key,
     )?;
-    solana_program::program::invoke_signed(
+    crate::invoke_signed(
         &ix,
         &[
             ctx.accounts.account.clone(),
@@ -124,6 +321,7 @@ pub fn initialize_account<'a, 'b, 'c, 'info>(
             ctx.program.clone(),
         ],
         ctx.signer_seeds,
+        "token::initialize_account",
     )
 }
 
@@ -131,16 +329,16 @@ pub fn initialize_mint<'a, 'b, 'c, 'info>(
     ctx: CpiContext<'a, 'b, 'c, 'info, InitializeMint<'info>>,
     decimals: u8,
     authority: &Pubkey,
-    freeze_authority: Option<&Pubkey>
+    freeze_authority: Option<&Pubkey>,
 ) -> ProgramResult {
     let ix = spl_token::instruction::initialize_mint(
         &spl_token::ID,
         ctx.accounts.mint.key,
         &authority,
         freeze_authority,
-        decimals
+        decimals,
     )?;
-    solana_program::program::invoke_signed(
+    crate::invoke_signed(
         &ix,
         &[
             ctx.accounts.mint.clone(),
@@ -148,12 +346,87 @@ pub fn initialize_mint<'a, 'b, 'c, 'info>(
             ctx.program.clone(),
         ],
         ctx.signer_seeds,
+        "token::initialize_mint",
+    )
+}
+
+/// Initializes a token account, like `initialize_account`, but without
+/// requiring the rent sysvar account.
+pub fn initialize_account3<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, InitializeAccount3<'info>>,
+) -> ProgramResult {
+    let ix = spl_token::instruction::initialize_account3(
+        &spl_token::ID,
+        ctx.accounts.account.key,
+        ctx.accounts.mint.key,
+        ctx.accounts.authority.key,
+    )?;
+    crate::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.account.clone(),
+            ctx.accounts.mint.clone(),
+            ctx.program.clone(),
+        ],
+        ctx.signer_seeds,
+        "token::initialize_account3",
+    )
+}
+
+/// Initializes a mint, like `initialize_mint`, but without requiring the
+/// rent sysvar account.
+pub fn initialize_mint2<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, InitializeMint2<'info>>,
+    decimals: u8,
+    authority: &Pubkey,
+    freeze_authority: Option<&Pubkey>,
+) -> ProgramResult {
+    let ix = spl_token::instruction::initialize_mint2(
+        &spl_token::ID,
+        ctx.accounts.mint.key,
+        authority,
+        freeze_authority,
+        decimals,
+    )?;
+    crate::invoke_signed(
+        &ix,
+        &[ctx.accounts.mint.clone(), ctx.program.clone()],
+        ctx.signer_seeds,
+        "token::initialize_mint2",
+    )
+}
+
+/// Closes a token account with a zero balance, or any native SOL account,
+/// sending its lamports to the destination.
+pub fn close_account<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, CloseAccount<'info>>,
+) -> ProgramResult {
+    let ix = spl_token::instruction::close_account(
+        &spl_token::ID,
+        ctx.accounts.account.key,
+        ctx.accounts.destination.key,
+        ctx.accounts.authority.key,
+        &[],
+    )?;
+    crate::invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.account.clone(),
+            ctx.accounts.destination.clone(),
+            ctx.accounts.authority.clone(),
+            ctx.program.clone(),
+        ],
+        ctx.signer_seeds,
+        "token::close_account",
     )
 }
 
+/// Sets, or clears with `None`, one of the mint's or account's authorities,
+/// e.g., transferring the mint authority to a PDA with
+/// `AuthorityType::MintTokens`.
 pub fn set_authority<'a, 'b, 'c, 'info>(
     ctx: CpiContext<'a, 'b, 'c, 'info, SetAuthority<'info>>,
-    authority_type: spl_token::instruction::AuthorityType,
+    authority_type: impl Into<spl_token::instruction::AuthorityType>,
     new_authority: Option<Pubkey>,
 ) -> ProgramResult {
     let mut spl_new_authority: Option<&Pubkey> = None;
@@ -165,11 +438,11 @@ pub fn set_authority<'a, 'b, 'c, 'info>(
         &spl_token::ID,
         ctx.accounts.account_or_mint.key,
         spl_new_authority,
-        authority_type,
+        authority_type.into(),
         ctx.accounts.current_authority.key,
         &[], // TODO: Support multisig signers.
     )?;
-    solana_program::program::invoke_signed(
+    crate::invoke_signed(
         &ix,
         &[
             ctx.accounts.account_or_mint.clone(),
@@ -177,9 +450,93 @@ pub fn set_authority<'a, 'b, 'c, 'info>(
             ctx.program.clone(),
         ],
         ctx.signer_seeds,
+        "token::set_authority",
+    )
+}
+
+/// Updates the token balance of a native SOL account, i.e., of the
+/// `native_mint`, to match its lamports, e.g., after transferring SOL to it.
+pub fn sync_native<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, SyncNative<'info>>,
+) -> ProgramResult {
+    let ix = spl_token::instruction::sync_native(&spl_token::ID, ctx.accounts.account.key)?;
+    crate::invoke_signed(
+        &ix,
+        &[ctx.accounts.account.clone(), ctx.program.clone()],
+        ctx.signer_seeds,
+        "token::sync_native",
     )
 }
 
+/// Wraps `lamports` SOL in a new token account of the `native_mint`, owned
+/// by `authority`, for the duration of `f`, e.g., for swapping SOL on a DEX.
+/// The account is created and funded by the payer, and closed after `f`,
+/// returning its remaining SOL, along with its rent, to the payer.
+///
+/// The account must sign its creation, e.g., as a PDA via the context's
+/// signer seeds.
+pub fn with_wrapped_sol<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, WrapSol<'info>>,
+    lamports: u64,
+    f: impl FnOnce(&AccountInfo<'info>) -> ProgramResult,
+) -> ProgramResult {
+    let WrapSol {
+        payer,
+        account,
+        mint,
+        authority,
+        system_program,
+    } = &ctx.accounts;
+
+    let rent = Rent::get()?.minimum_balance(spl_token::state::Account::LEN);
+    crate::invoke_signed(
+        &system_instruction::create_account(
+            payer.key,
+            account.key,
+            rent,
+            spl_token::state::Account::LEN as u64,
+            &spl_token::ID,
+        ),
+        &[payer.clone(), account.clone(), system_program.clone()],
+        ctx.signer_seeds,
+        "token::with_wrapped_sol",
+    )?;
+    initialize_account3(CpiContext::new_with_signer(
+        ctx.program.clone(),
+        InitializeAccount3 {
+            account: account.clone(),
+            mint: mint.clone(),
+            authority: authority.clone(),
+        },
+        ctx.signer_seeds,
+    ))?;
+    crate::invoke_signed(
+        &system_instruction::transfer(payer.key, account.key, lamports),
+        &[payer.clone(), account.clone(), system_program.clone()],
+        ctx.signer_seeds,
+        "token::with_wrapped_sol",
+    )?;
+    sync_native(CpiContext::new_with_signer(
+        ctx.program.clone(),
+        SyncNative {
+            account: account.clone(),
+        },
+        ctx.signer_seeds,
+    ))?;
+
+    f(account)?;
+
+    close_account(CpiContext::new_with_signer(
+        ctx.program.clone(),
+        CloseAccount {
+            account: account.clone(),
+            destination: payer.clone(),
+            authority: authority.clone(),
+        },
+        ctx.signer_seeds,
+    ))
+}
+
 #[derive(Accounts)]
 pub struct Transfer<'info> {
     pub from: AccountInfo<'info>,
@@ -187,6 +544,14 @@ pub struct Transfer<'info> {
     pub authority: AccountInfo<'info>,
 }
 
+#[derive(Accounts)]
+pub struct TransferChecked<'info> {
+    pub from: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub to: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+}
+
 #[derive(Accounts)]
 pub struct MintTo<'info> {
     pub mint: AccountInfo<'info>,
@@ -208,6 +573,34 @@ pub struct Approve<'info> {
     pub authority: AccountInfo<'info>,
 }
 
+#[derive(Accounts)]
+pub struct ApproveChecked<'info> {
+    pub to: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub delegate: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Revoke<'info> {
+    pub source: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FreezeAccount<'info> {
+    pub account: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ThawAccount<'info> {
+    pub account: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+}
+
 #[derive(Accounts)]
 pub struct InitializeAccount<'info> {
     pub account: AccountInfo<'info>,
@@ -222,12 +615,70 @@ pub struct InitializeMint<'info> {
     pub rent: AccountInfo<'info>,
 }
 
+#[derive(Accounts)]
+pub struct InitializeAccount3<'info> {
+    pub account: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeMint2<'info> {
+    pub mint: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseAccount<'info> {
+    pub account: AccountInfo<'info>,
+    pub destination: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+}
+
 #[derive(Accounts)]
 pub struct SetAuthority<'info> {
     pub current_authority: AccountInfo<'info>,
     pub account_or_mint: AccountInfo<'info>,
 }
 
+#[derive(Accounts)]
+pub struct SyncNative<'info> {
+    pub account: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WrapSol<'info> {
+    pub payer: AccountInfo<'info>,
+    pub account: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+/// The authorities of a mint or token account, which, unlike
+/// `spl_token::instruction::AuthorityType`, can be an instruction argument.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum AuthorityType {
+    /// Authority to mint new tokens.
+    MintTokens,
+    /// Authority to freeze any account of the mint.
+    FreezeAccount,
+    /// Owner of a token account.
+    AccountOwner,
+    /// Authority to close a token account.
+    CloseAccount,
+}
+
+impl From<AuthorityType> for spl_token::instruction::AuthorityType {
+    fn from(authority_type: AuthorityType) -> Self {
+        match authority_type {
+            AuthorityType::MintTokens => Self::MintTokens,
+            AuthorityType::FreezeAccount => Self::FreezeAccount,
+            AuthorityType::AccountOwner => Self::AccountOwner,
+            AuthorityType::CloseAccount => Self::CloseAccount,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct TokenAccount(spl_token::state::Account);
 
@@ -256,6 +707,10 @@ impl Deref for TokenAccount {
 #[derive(Clone)]
 pub struct Mint(spl_token::state::Mint);
 
+impl Mint {
+    pub const LEN: usize = spl_token::state::Mint::LEN;
+}
+
 impl anchor_lang::AccountDeserialize for Mint {
     fn try_deserialize(buf: &mut &[u8]) -> Result<Self, ProgramError> {
         Mint::try_deserialize_unchecked(buf)
@@ -274,6 +729,16 @@ impl Deref for Mint {
     }
 }
 
+/// The SPL token program, for use with `Program<'info, Token>`.
+#[derive(Clone)]
+pub struct Token;
+
+impl anchor_lang::Id for Token {
+    fn id() -> Pubkey {
+        ID
+    }
+}
+
 // Field parsers to save compute. All account validation is assumed to be done
 // outside of these methods.
 pub mod accessor {
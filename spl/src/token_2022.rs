@@ -0,0 +1,637 @@
+//! CPI clients for the Token-2022 extension instructions. The base
+//! instructions shared with SPL Token are in `token_interface`.
+
+use anchor_lang::prelude::borsh;
+use anchor_lang::solana_program::account_info::AccountInfo;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::{Accounts, AnchorDeserialize, AnchorSerialize, CpiContext};
+use spl_token_2022::extension::{
+    default_account_state, interest_bearing_mint, memo_transfer, transfer_fee,
+};
+
+pub use spl_token_2022::ID;
+
+/// The Token-2022 program, for use with `Program<'info, Token2022>`.
+#[derive(Clone)]
+pub struct Token2022;
+
+impl anchor_lang::Id for Token2022 {
+    fn id() -> Pubkey {
+        ID
+    }
+}
+
+/// The state of a token account, which, unlike
+/// `spl_token_2022::state::AccountState`, can be an instruction argument.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum AccountState {
+    Uninitialized,
+    Initialized,
+    Frozen,
+}
+
+impl From<AccountState> for spl_token_2022::state::AccountState {
+    fn from(state: AccountState) -> Self {
+        match state {
+            AccountState::Uninitialized => Self::Uninitialized,
+            AccountState::Initialized => Self::Initialized,
+            AccountState::Frozen => Self::Frozen,
+        }
+    }
+}
+
+// Invokes an instruction with the given accounts, followed by the program.
+fn invoke<'info>(
+    ix: Instruction,
+    mut accounts: Vec<AccountInfo<'info>>,
+    program: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    name: &str,
+) -> ProgramResult {
+    accounts.push(program);
+    crate::invoke_signed(
+        &ix,
+        &accounts,
+        signer_seeds,
+        &format!("token_2022::{}", name),
+    )
+}
+
+/// Initializes the mint's transfer fee, before the mint itself.
+pub fn transfer_fee_initialize<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, TransferFeeInitialize<'info>>,
+    transfer_fee_config_authority: Option<&Pubkey>,
+    withdraw_withheld_authority: Option<&Pubkey>,
+    transfer_fee_basis_points: u16,
+    maximum_fee: u64,
+) -> ProgramResult {
+    let ix = transfer_fee::instruction::initialize_transfer_fee_config(
+        &ID,
+        ctx.accounts.mint.key,
+        transfer_fee_config_authority,
+        withdraw_withheld_authority,
+        transfer_fee_basis_points,
+        maximum_fee,
+    )?;
+    invoke(
+        ix,
+        vec![ctx.accounts.mint.clone()],
+        ctx.program.clone(),
+        ctx.signer_seeds,
+        "transfer_fee_initialize",
+    )
+}
+
+/// Sets the mint's transfer fee, taking effect two epochs later.
+pub fn transfer_fee_set<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, TransferFeeSet<'info>>,
+    transfer_fee_basis_points: u16,
+    maximum_fee: u64,
+) -> ProgramResult {
+    let ix = transfer_fee::instruction::set_transfer_fee(
+        &ID,
+        ctx.accounts.mint.key,
+        ctx.accounts.authority.key,
+        &[],
+        transfer_fee_basis_points,
+        maximum_fee,
+    )?;
+    invoke(
+        ix,
+        vec![ctx.accounts.mint.clone(), ctx.accounts.authority.clone()],
+        ctx.program.clone(),
+        ctx.signer_seeds,
+        "transfer_fee_set",
+    )
+}
+
+/// Transfers tokens, checking the mint's decimals and the expected fee,
+/// which is withheld in the destination account.
+pub fn transfer_checked_with_fee<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, TransferCheckedWithFee<'info>>,
+    amount: u64,
+    decimals: u8,
+    fee: u64,
+) -> ProgramResult {
+    let ix = transfer_fee::instruction::transfer_checked_with_fee(
+        &ID,
+        ctx.accounts.from.key,
+        ctx.accounts.mint.key,
+        ctx.accounts.to.key,
+        ctx.accounts.authority.key,
+        &[],
+        amount,
+        decimals,
+        fee,
+    )?;
+    invoke(
+        ix,
+        vec![
+            ctx.accounts.from.clone(),
+            ctx.accounts.mint.clone(),
+            ctx.accounts.to.clone(),
+            ctx.accounts.authority.clone(),
+        ],
+        ctx.program.clone(),
+        ctx.signer_seeds,
+        "transfer_checked_with_fee",
+    )
+}
+
+/// Withdraws the fees harvested to the mint with the mint's withdraw
+/// withheld authority.
+pub fn withdraw_withheld_tokens_from_mint<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, WithdrawWithheldTokensFromMint<'info>>,
+) -> ProgramResult {
+    let ix = transfer_fee::instruction::withdraw_withheld_tokens_from_mint(
+        &ID,
+        ctx.accounts.mint.key,
+        ctx.accounts.destination.key,
+        ctx.accounts.authority.key,
+        &[],
+    )?;
+    invoke(
+        ix,
+        vec![
+            ctx.accounts.mint.clone(),
+            ctx.accounts.destination.clone(),
+            ctx.accounts.authority.clone(),
+        ],
+        ctx.program.clone(),
+        ctx.signer_seeds,
+        "withdraw_withheld_tokens_from_mint",
+    )
+}
+
+/// Withdraws the fees withheld in the `sources` token accounts with the
+/// mint's withdraw withheld authority.
+pub fn withdraw_withheld_tokens_from_accounts<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, WithdrawWithheldTokensFromAccounts<'info>>,
+    sources: Vec<AccountInfo<'info>>,
+) -> ProgramResult {
+    let ix = transfer_fee::instruction::withdraw_withheld_tokens_from_accounts(
+        &ID,
+        ctx.accounts.mint.key,
+        ctx.accounts.destination.key,
+        ctx.accounts.authority.key,
+        &[],
+        &sources.iter().map(|source| source.key).collect::<Vec<_>>(),
+    )?;
+    let mut accounts = vec![
+        ctx.accounts.mint.clone(),
+        ctx.accounts.destination.clone(),
+        ctx.accounts.authority.clone(),
+    ];
+    accounts.extend(sources);
+    invoke(
+        ix,
+        accounts,
+        ctx.program.clone(),
+        ctx.signer_seeds,
+        "withdraw_withheld_tokens_from_accounts",
+    )
+}
+
+/// Moves the fees withheld in the `sources` token accounts to the mint,
+/// which anyone may do.
+pub fn harvest_withheld_tokens_to_mint<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, HarvestWithheldTokensToMint<'info>>,
+    sources: Vec<AccountInfo<'info>>,
+) -> ProgramResult {
+    let ix = transfer_fee::instruction::harvest_withheld_tokens_to_mint(
+        &ID,
+        ctx.accounts.mint.key,
+        &sources.iter().map(|source| source.key).collect::<Vec<_>>(),
+    )?;
+    let mut accounts = vec![ctx.accounts.mint.clone()];
+    accounts.extend(sources);
+    invoke(
+        ix,
+        accounts,
+        ctx.program.clone(),
+        ctx.signer_seeds,
+        "harvest_withheld_tokens_to_mint",
+    )
+}
+
+/// Requires memos on transfers into the account.
+pub fn memo_transfer_enable<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, MemoTransfer<'info>>,
+) -> ProgramResult {
+    let ix = memo_transfer::instruction::enable_required_transfer_memos(
+        &ID,
+        ctx.accounts.account.key,
+        ctx.accounts.owner.key,
+        &[],
+    )?;
+    invoke(
+        ix,
+        vec![ctx.accounts.account.clone(), ctx.accounts.owner.clone()],
+        ctx.program.clone(),
+        ctx.signer_seeds,
+        "memo_transfer_enable",
+    )
+}
+
+/// Stops requiring memos on transfers into the account.
+pub fn memo_transfer_disable<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, MemoTransfer<'info>>,
+) -> ProgramResult {
+    let ix = memo_transfer::instruction::disable_required_transfer_memos(
+        &ID,
+        ctx.accounts.account.key,
+        ctx.accounts.owner.key,
+        &[],
+    )?;
+    invoke(
+        ix,
+        vec![ctx.accounts.account.clone(), ctx.accounts.owner.clone()],
+        ctx.program.clone(),
+        ctx.signer_seeds,
+        "memo_transfer_disable",
+    )
+}
+
+/// Initializes the state of the mint's new token accounts, before the mint
+/// itself.
+pub fn default_account_state_initialize<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, DefaultAccountStateInitialize<'info>>,
+    state: AccountState,
+) -> ProgramResult {
+    let ix = default_account_state::instruction::initialize_default_account_state(
+        &ID,
+        ctx.accounts.mint.key,
+        &state.into(),
+    )?;
+    invoke(
+        ix,
+        vec![ctx.accounts.mint.clone()],
+        ctx.program.clone(),
+        ctx.signer_seeds,
+        "default_account_state_initialize",
+    )
+}
+
+/// Updates the state of the mint's new token accounts with the mint's
+/// freeze authority.
+pub fn default_account_state_update<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, DefaultAccountStateUpdate<'info>>,
+    state: AccountState,
+) -> ProgramResult {
+    let ix = default_account_state::instruction::update_default_account_state(
+        &ID,
+        ctx.accounts.mint.key,
+        ctx.accounts.freeze_authority.key,
+        &[],
+        &state.into(),
+    )?;
+    invoke(
+        ix,
+        vec![
+            ctx.accounts.mint.clone(),
+            ctx.accounts.freeze_authority.clone(),
+        ],
+        ctx.program.clone(),
+        ctx.signer_seeds,
+        "default_account_state_update",
+    )
+}
+
+/// Initializes the mint's interest rate, in basis points, before the mint
+/// itself.
+pub fn interest_bearing_mint_initialize<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, InterestBearingMintInitialize<'info>>,
+    rate_authority: Option<Pubkey>,
+    rate: i16,
+) -> ProgramResult {
+    let ix = interest_bearing_mint::instruction::initialize(
+        &ID,
+        ctx.accounts.mint.key,
+        rate_authority,
+        rate,
+    )?;
+    invoke(
+        ix,
+        vec![ctx.accounts.mint.clone()],
+        ctx.program.clone(),
+        ctx.signer_seeds,
+        "interest_bearing_mint_initialize",
+    )
+}
+
+/// Updates the mint's interest rate, in basis points, with its rate
+/// authority.
+pub fn interest_bearing_mint_update_rate<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, InterestBearingMintUpdateRate<'info>>,
+    rate: i16,
+) -> ProgramResult {
+    let ix = interest_bearing_mint::instruction::update_rate(
+        &ID,
+        ctx.accounts.mint.key,
+        ctx.accounts.rate_authority.key,
+        &[],
+        rate,
+    )?;
+    invoke(
+        ix,
+        vec![
+            ctx.accounts.mint.clone(),
+            ctx.accounts.rate_authority.clone(),
+        ],
+        ctx.program.clone(),
+        ctx.signer_seeds,
+        "interest_bearing_mint_update_rate",
+    )
+}
+
+/// Initializes the mint's permanent delegate, who may transfer or burn any
+/// of its tokens, before the mint itself.
+pub fn permanent_delegate_initialize<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, PermanentDelegateInitialize<'info>>,
+    delegate: &Pubkey,
+) -> ProgramResult {
+    let ix = spl_token_2022::instruction::initialize_permanent_delegate(
+        &ID,
+        ctx.accounts.mint.key,
+        delegate,
+    )?;
+    invoke(
+        ix,
+        vec![ctx.accounts.mint.clone()],
+        ctx.program.clone(),
+        ctx.signer_seeds,
+        "permanent_delegate_initialize",
+    )
+}
+
+// The group pointer, group member pointer and token group instructions are
+// newer than the `spl_token_2022` version depended on, so they're encoded
+// here.
+
+// Indices of the pointer extensions' instructions, followed by their
+// `Initialize` sub-instruction.
+const GROUP_POINTER_EXTENSION: u8 = 40;
+const GROUP_MEMBER_POINTER_EXTENSION: u8 = 41;
+const POINTER_INITIALIZE: u8 = 0;
+
+// Encodes an optional address as the extensions do, zeroed if unset.
+fn optional_address(address: Option<&Pubkey>) -> [u8; 32] {
+    address.cloned().unwrap_or_default().to_bytes()
+}
+
+// Returns the discriminator of the token group interface instruction.
+fn token_group_discriminator(name: &str) -> [u8; 8] {
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(
+        &hash(format!("spl_token_group_interface:{}", name).as_bytes()).to_bytes()[..8],
+    );
+    discriminator
+}
+
+/// Initializes the mint's group pointer, i.e., the account holding the
+/// group it's the mint of, usually the mint itself, before the mint itself.
+pub fn group_pointer_initialize<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, GroupPointerInitialize<'info>>,
+    authority: Option<&Pubkey>,
+    group_address: Option<&Pubkey>,
+) -> ProgramResult {
+    let mut data = vec![GROUP_POINTER_EXTENSION, POINTER_INITIALIZE];
+    data.extend_from_slice(&optional_address(authority));
+    data.extend_from_slice(&optional_address(group_address));
+    let ix = Instruction {
+        program_id: *ctx.program.key,
+        accounts: vec![AccountMeta::new(*ctx.accounts.mint.key, false)],
+        data,
+    };
+    invoke(
+        ix,
+        vec![ctx.accounts.mint.clone()],
+        ctx.program.clone(),
+        ctx.signer_seeds,
+        "group_pointer_initialize",
+    )
+}
+
+/// Initializes the mint's group member pointer, i.e., the account holding
+/// its membership of a group, usually the mint itself, before the mint
+/// itself.
+pub fn group_member_pointer_initialize<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, GroupMemberPointerInitialize<'info>>,
+    authority: Option<&Pubkey>,
+    member_address: Option<&Pubkey>,
+) -> ProgramResult {
+    let mut data = vec![GROUP_MEMBER_POINTER_EXTENSION, POINTER_INITIALIZE];
+    data.extend_from_slice(&optional_address(authority));
+    data.extend_from_slice(&optional_address(member_address));
+    let ix = Instruction {
+        program_id: *ctx.program.key,
+        accounts: vec![AccountMeta::new(*ctx.accounts.mint.key, false)],
+        data,
+    };
+    invoke(
+        ix,
+        vec![ctx.accounts.mint.clone()],
+        ctx.program.clone(),
+        ctx.signer_seeds,
+        "group_member_pointer_initialize",
+    )
+}
+
+/// Initializes the group of the mint, with at most `max_size` members,
+/// after the mint itself. The group account, i.e., the mint, must already
+/// hold enough lamports for the group's extension.
+pub fn token_group_initialize<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, TokenGroupInitialize<'info>>,
+    update_authority: Option<&Pubkey>,
+    max_size: u32,
+) -> ProgramResult {
+    let mut data = token_group_discriminator("initialize_token_group").to_vec();
+    data.extend_from_slice(&optional_address(update_authority));
+    data.extend_from_slice(&max_size.to_le_bytes());
+    let ix = Instruction {
+        program_id: *ctx.program.key,
+        accounts: vec![
+            AccountMeta::new(*ctx.accounts.group.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.mint.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.mint_authority.key, true),
+        ],
+        data,
+    };
+    invoke(
+        ix,
+        vec![
+            ctx.accounts.group.clone(),
+            ctx.accounts.mint.clone(),
+            ctx.accounts.mint_authority.clone(),
+        ],
+        ctx.program.clone(),
+        ctx.signer_seeds,
+        "token_group_initialize",
+    )
+}
+
+/// Sets the maximum number of members of the group, which can't be less
+/// than its current size.
+pub fn token_group_update_max_size<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, TokenGroupUpdateMaxSize<'info>>,
+    max_size: u32,
+) -> ProgramResult {
+    let mut data = token_group_discriminator("update_group_max_size").to_vec();
+    data.extend_from_slice(&max_size.to_le_bytes());
+    let ix = Instruction {
+        program_id: *ctx.program.key,
+        accounts: vec![
+            AccountMeta::new(*ctx.accounts.group.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.update_authority.key, true),
+        ],
+        data,
+    };
+    invoke(
+        ix,
+        vec![
+            ctx.accounts.group.clone(),
+            ctx.accounts.update_authority.clone(),
+        ],
+        ctx.program.clone(),
+        ctx.signer_seeds,
+        "token_group_update_max_size",
+    )
+}
+
+/// Adds the member mint to the group, after the mint itself. The member
+/// account, i.e., the member mint, must already hold enough lamports for the
+/// member's extension.
+pub fn token_member_initialize<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, TokenMemberInitialize<'info>>,
+) -> ProgramResult {
+    let ix = Instruction {
+        program_id: *ctx.program.key,
+        accounts: vec![
+            AccountMeta::new(*ctx.accounts.member.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.member_mint.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.member_mint_authority.key, true),
+            AccountMeta::new(*ctx.accounts.group.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.group_update_authority.key, true),
+        ],
+        data: token_group_discriminator("initialize_member").to_vec(),
+    };
+    invoke(
+        ix,
+        vec![
+            ctx.accounts.member.clone(),
+            ctx.accounts.member_mint.clone(),
+            ctx.accounts.member_mint_authority.clone(),
+            ctx.accounts.group.clone(),
+            ctx.accounts.group_update_authority.clone(),
+        ],
+        ctx.program.clone(),
+        ctx.signer_seeds,
+        "token_member_initialize",
+    )
+}
+
+#[derive(Accounts)]
+pub struct TransferFeeInitialize<'info> {
+    pub mint: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TransferFeeSet<'info> {
+    pub mint: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TransferCheckedWithFee<'info> {
+    pub from: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub to: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawWithheldTokensFromMint<'info> {
+    pub mint: AccountInfo<'info>,
+    pub destination: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawWithheldTokensFromAccounts<'info> {
+    pub mint: AccountInfo<'info>,
+    pub destination: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct HarvestWithheldTokensToMint<'info> {
+    pub mint: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MemoTransfer<'info> {
+    pub account: AccountInfo<'info>,
+    pub owner: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DefaultAccountStateInitialize<'info> {
+    pub mint: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DefaultAccountStateUpdate<'info> {
+    pub mint: AccountInfo<'info>,
+    pub freeze_authority: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InterestBearingMintInitialize<'info> {
+    pub mint: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InterestBearingMintUpdateRate<'info> {
+    pub mint: AccountInfo<'info>,
+    pub rate_authority: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PermanentDelegateInitialize<'info> {
+    pub mint: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GroupPointerInitialize<'info> {
+    pub mint: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GroupMemberPointerInitialize<'info> {
+    pub mint: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TokenGroupInitialize<'info> {
+    pub group: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub mint_authority: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TokenGroupUpdateMaxSize<'info> {
+    pub group: AccountInfo<'info>,
+    pub update_authority: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TokenMemberInitialize<'info> {
+    pub member: AccountInfo<'info>,
+    pub member_mint: AccountInfo<'info>,
+    pub member_mint_authority: AccountInfo<'info>,
+    pub group: AccountInfo<'info>,
+    pub group_update_authority: AccountInfo<'info>,
+}
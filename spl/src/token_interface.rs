@@ -0,0 +1,182 @@
+//! Account and program types shared by SPL Token and Token-2022, for use with
+//! `InterfaceAccount` and `Interface`. Only the base account state is
+//! deserialized, so Token-2022 extensions are ignored.
+
+use crate::transfer_hook;
+use anchor_lang::solana_program::account_info::AccountInfo;
+use anchor_lang::solana_program::declare_id;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::{Accounts, CpiContext};
+use std::ops::Deref;
+
+pub use token_2022::ID as TOKEN_2022_ID;
+mod token_2022 {
+    use super::*;
+    declare_id!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+}
+
+lazy_static::lazy_static! {
+    static ref IDS: [Pubkey; 2] = [spl_token::ID, TOKEN_2022_ID];
+}
+
+/// Both token programs, for use with `Interface<'info, TokenInterface>`.
+#[derive(Clone)]
+pub struct TokenInterface;
+
+impl anchor_lang::Ids for TokenInterface {
+    fn ids() -> &'static [Pubkey] {
+        &*IDS
+    }
+}
+
+/// Transfers tokens with either token program, given by the context, checking
+/// the mint's decimals.
+///
+/// Transfers of Token-2022 mints with a transfer hook invoke the hook, which
+/// requires the hook program, its `ExtraAccountMetaList` account and the
+/// accounts it lists. These must be given as the context's remaining
+/// accounts, e.g., `ctx.remaining_accounts.to_vec()`, and are appended to the
+/// instruction in the order the token program expects.
+pub fn transfer_checked<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, TransferChecked<'info>>,
+    amount: u64,
+    decimals: u8,
+) -> ProgramResult {
+    // Built by hand, since the SPL Token builders reject other token programs.
+    let mut ix = Instruction {
+        program_id: *ctx.program.key,
+        accounts: vec![
+            AccountMeta::new(*ctx.accounts.from.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.mint.key, false),
+            AccountMeta::new(*ctx.accounts.to.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.authority.key, true),
+        ],
+        data: spl_token::instruction::TokenInstruction::TransferChecked { amount, decimals }.pack(),
+    };
+    let mut account_infos = vec![
+        ctx.accounts.from.clone(),
+        ctx.accounts.mint.clone(),
+        ctx.accounts.to.clone(),
+        ctx.accounts.authority.clone(),
+    ];
+    if let Some(hook_program_id) = transfer_hook::program_id(&ctx.accounts.mint)? {
+        transfer_hook::add_extra_accounts(
+            &mut ix,
+            &mut account_infos,
+            &hook_program_id,
+            amount,
+            &ctx.remaining_accounts,
+        )?;
+    }
+    account_infos.push(ctx.program.clone());
+    crate::invoke_signed(
+        &ix,
+        &account_infos,
+        ctx.signer_seeds,
+        "token_interface::transfer_checked",
+    )
+}
+
+/// Initializes the mint with either token program, given by the context.
+pub fn initialize_mint2<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, InitializeMint2<'info>>,
+    decimals: u8,
+    authority: &Pubkey,
+    freeze_authority: Option<&Pubkey>,
+) -> ProgramResult {
+    let ix = Instruction {
+        program_id: *ctx.program.key,
+        accounts: vec![AccountMeta::new(*ctx.accounts.mint.key, false)],
+        data: spl_token::instruction::TokenInstruction::InitializeMint2 {
+            decimals,
+            mint_authority: *authority,
+            freeze_authority: freeze_authority.cloned().into(),
+        }
+        .pack(),
+    };
+    crate::invoke_signed(
+        &ix,
+        &[ctx.accounts.mint.clone(), ctx.program.clone()],
+        ctx.signer_seeds,
+        "token_interface::initialize_mint2",
+    )
+}
+
+#[derive(Accounts)]
+pub struct InitializeMint2<'info> {
+    pub mint: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TransferChecked<'info> {
+    pub from: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub to: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+}
+
+#[derive(Clone)]
+pub struct TokenAccount(spl_token::state::Account);
+
+impl anchor_lang::AccountDeserialize for TokenAccount {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self, ProgramError> {
+        TokenAccount::try_deserialize_unchecked(buf)
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self, ProgramError> {
+        let len = spl_token::state::Account::LEN;
+        if buf.len() < len {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        spl_token::state::Account::unpack(&buf[..len]).map(TokenAccount)
+    }
+}
+
+impl anchor_lang::Owners for TokenAccount {
+    fn owners() -> &'static [Pubkey] {
+        &*IDS
+    }
+}
+
+impl Deref for TokenAccount {
+    type Target = spl_token::state::Account;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[derive(Clone)]
+pub struct Mint(spl_token::state::Mint);
+
+impl anchor_lang::AccountDeserialize for Mint {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self, ProgramError> {
+        Mint::try_deserialize_unchecked(buf)
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self, ProgramError> {
+        let len = spl_token::state::Mint::LEN;
+        if buf.len() < len {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        spl_token::state::Mint::unpack(&buf[..len]).map(Mint)
+    }
+}
+
+impl anchor_lang::Owners for Mint {
+    fn owners() -> &'static [Pubkey] {
+        &*IDS
+    }
+}
+
+impl Deref for Mint {
+    type Target = spl_token::state::Mint;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
@@ -0,0 +1,288 @@
+//! CPI clients and account types for SPL Token Swap (v3), which supports
+//! pools of both SPL Token and Token-2022 tokens. Its instructions and
+//! accounts are encoded here, rather than depending on the `spl-token-swap`
+//! crate, whose Solana versions often conflict with a program's own.
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::borsh;
+use anchor_lang::solana_program::account_info::AccountInfo;
+use anchor_lang::solana_program::declare_id;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::{Accounts, AnchorDeserialize, AnchorSerialize, CpiContext, ToAccountInfos};
+use std::ops::Deref;
+
+declare_id!("SwapsVeCiPHMUAtzQWZw7RjsKjgCjhwU55QGu4U1Szw");
+
+// Indices of the program's instructions.
+const SWAP: u8 = 1;
+const DEPOSIT_ALL_TOKEN_TYPES: u8 = 2;
+const WITHDRAW_ALL_TOKEN_TYPES: u8 = 3;
+
+// Version of pool accounts, as their first byte.
+const SWAP_V1: u8 = 1;
+
+/// Swaps `amount_in` of the source token for at least `minimum_amount_out`
+/// of the destination token. A host fee account may be given as the first of
+/// `ctx.remaining_accounts`.
+pub fn swap<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, Swap<'info>>,
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> ProgramResult {
+    let mut data = vec![SWAP];
+    (amount_in, minimum_amount_out).serialize(&mut data)?;
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*ctx.accounts.swap.key, false),
+        AccountMeta::new_readonly(*ctx.accounts.swap_authority.key, false),
+        AccountMeta::new_readonly(*ctx.accounts.user_transfer_authority.key, true),
+        AccountMeta::new(*ctx.accounts.source.key, false),
+        AccountMeta::new(*ctx.accounts.swap_source.key, false),
+        AccountMeta::new(*ctx.accounts.swap_destination.key, false),
+        AccountMeta::new(*ctx.accounts.destination.key, false),
+        AccountMeta::new(*ctx.accounts.pool_mint.key, false),
+        AccountMeta::new(*ctx.accounts.pool_fee_account.key, false),
+        AccountMeta::new_readonly(*ctx.accounts.source_mint.key, false),
+        AccountMeta::new_readonly(*ctx.accounts.destination_mint.key, false),
+        AccountMeta::new_readonly(*ctx.accounts.source_token_program.key, false),
+        AccountMeta::new_readonly(*ctx.accounts.destination_token_program.key, false),
+        AccountMeta::new_readonly(*ctx.accounts.pool_token_program.key, false),
+    ];
+    if let Some(host_fee_account) = ctx.remaining_accounts.first() {
+        accounts.push(AccountMeta::new(*host_fee_account.key, false));
+    }
+    let ix = Instruction {
+        program_id: *ctx.program.key,
+        accounts,
+        data,
+    };
+    crate::invoke_signed(
+        &ix,
+        &ToAccountInfos::to_account_infos(&ctx),
+        ctx.signer_seeds,
+        "token_swap::swap",
+    )
+}
+
+/// Deposits both tokens into the pool for `pool_token_amount` pool tokens,
+/// depositing at most the given amount of each.
+pub fn deposit_all_token_types<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, DepositAllTokenTypes<'info>>,
+    pool_token_amount: u64,
+    maximum_token_a_amount: u64,
+    maximum_token_b_amount: u64,
+) -> ProgramResult {
+    let mut data = vec![DEPOSIT_ALL_TOKEN_TYPES];
+    (
+        pool_token_amount,
+        maximum_token_a_amount,
+        maximum_token_b_amount,
+    )
+        .serialize(&mut data)?;
+    let ix = Instruction {
+        program_id: *ctx.program.key,
+        accounts: vec![
+            AccountMeta::new_readonly(*ctx.accounts.swap.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.swap_authority.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.user_transfer_authority.key, true),
+            AccountMeta::new(*ctx.accounts.source_a.key, false),
+            AccountMeta::new(*ctx.accounts.source_b.key, false),
+            AccountMeta::new(*ctx.accounts.token_a.key, false),
+            AccountMeta::new(*ctx.accounts.token_b.key, false),
+            AccountMeta::new(*ctx.accounts.pool_mint.key, false),
+            AccountMeta::new(*ctx.accounts.destination.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.token_a_mint.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.token_b_mint.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.token_a_program.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.token_b_program.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.pool_token_program.key, false),
+        ],
+        data,
+    };
+    crate::invoke_signed(
+        &ix,
+        &ToAccountInfos::to_account_infos(&ctx),
+        ctx.signer_seeds,
+        "token_swap::deposit_all_token_types",
+    )
+}
+
+/// Burns `pool_token_amount` pool tokens for both of the pool's tokens,
+/// withdrawing at least the given amount of each.
+pub fn withdraw_all_token_types<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, WithdrawAllTokenTypes<'info>>,
+    pool_token_amount: u64,
+    minimum_token_a_amount: u64,
+    minimum_token_b_amount: u64,
+) -> ProgramResult {
+    let mut data = vec![WITHDRAW_ALL_TOKEN_TYPES];
+    (
+        pool_token_amount,
+        minimum_token_a_amount,
+        minimum_token_b_amount,
+    )
+        .serialize(&mut data)?;
+    let ix = Instruction {
+        program_id: *ctx.program.key,
+        accounts: vec![
+            AccountMeta::new_readonly(*ctx.accounts.swap.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.swap_authority.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.user_transfer_authority.key, true),
+            AccountMeta::new(*ctx.accounts.pool_mint.key, false),
+            AccountMeta::new(*ctx.accounts.source.key, false),
+            AccountMeta::new(*ctx.accounts.token_a.key, false),
+            AccountMeta::new(*ctx.accounts.token_b.key, false),
+            AccountMeta::new(*ctx.accounts.destination_a.key, false),
+            AccountMeta::new(*ctx.accounts.destination_b.key, false),
+            AccountMeta::new(*ctx.accounts.pool_fee_account.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.token_a_mint.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.token_b_mint.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.pool_token_program.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.token_a_program.key, false),
+            AccountMeta::new_readonly(*ctx.accounts.token_b_program.key, false),
+        ],
+        data,
+    };
+    crate::invoke_signed(
+        &ix,
+        &ToAccountInfos::to_account_infos(&ctx),
+        ctx.signer_seeds,
+        "token_swap::withdraw_all_token_types",
+    )
+}
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    pub swap: AccountInfo<'info>,
+    pub swap_authority: AccountInfo<'info>,
+    pub user_transfer_authority: AccountInfo<'info>,
+    pub source: AccountInfo<'info>,
+    pub swap_source: AccountInfo<'info>,
+    pub swap_destination: AccountInfo<'info>,
+    pub destination: AccountInfo<'info>,
+    pub pool_mint: AccountInfo<'info>,
+    pub pool_fee_account: AccountInfo<'info>,
+    pub source_mint: AccountInfo<'info>,
+    pub destination_mint: AccountInfo<'info>,
+    pub source_token_program: AccountInfo<'info>,
+    pub destination_token_program: AccountInfo<'info>,
+    pub pool_token_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DepositAllTokenTypes<'info> {
+    pub swap: AccountInfo<'info>,
+    pub swap_authority: AccountInfo<'info>,
+    pub user_transfer_authority: AccountInfo<'info>,
+    pub source_a: AccountInfo<'info>,
+    pub source_b: AccountInfo<'info>,
+    pub token_a: AccountInfo<'info>,
+    pub token_b: AccountInfo<'info>,
+    pub pool_mint: AccountInfo<'info>,
+    pub destination: AccountInfo<'info>,
+    pub token_a_mint: AccountInfo<'info>,
+    pub token_b_mint: AccountInfo<'info>,
+    pub token_a_program: AccountInfo<'info>,
+    pub token_b_program: AccountInfo<'info>,
+    pub pool_token_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawAllTokenTypes<'info> {
+    pub swap: AccountInfo<'info>,
+    pub swap_authority: AccountInfo<'info>,
+    pub user_transfer_authority: AccountInfo<'info>,
+    pub pool_mint: AccountInfo<'info>,
+    pub source: AccountInfo<'info>,
+    pub token_a: AccountInfo<'info>,
+    pub token_b: AccountInfo<'info>,
+    pub destination_a: AccountInfo<'info>,
+    pub destination_b: AccountInfo<'info>,
+    pub pool_fee_account: AccountInfo<'info>,
+    pub token_a_mint: AccountInfo<'info>,
+    pub token_b_mint: AccountInfo<'info>,
+    pub pool_token_program: AccountInfo<'info>,
+    pub token_a_program: AccountInfo<'info>,
+    pub token_b_program: AccountInfo<'info>,
+}
+
+/// The state of a swap pool.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct SwapV1 {
+    pub is_initialized: bool,
+    pub bump_seed: u8,
+    pub token_program_id: Pubkey,
+    pub token_a: Pubkey,
+    pub token_b: Pubkey,
+    pub pool_mint: Pubkey,
+    pub token_a_mint: Pubkey,
+    pub token_b_mint: Pubkey,
+    pub pool_fee_account: Pubkey,
+    pub fees: Fees,
+    pub swap_curve: SwapCurve,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Fees {
+    pub trade_fee_numerator: u64,
+    pub trade_fee_denominator: u64,
+    pub owner_trade_fee_numerator: u64,
+    pub owner_trade_fee_denominator: u64,
+    pub owner_withdraw_fee_numerator: u64,
+    pub owner_withdraw_fee_denominator: u64,
+    pub host_fee_numerator: u64,
+    pub host_fee_denominator: u64,
+}
+
+/// The pool's curve. `calculator` holds the curve's parameters, whose
+/// layout depends on `curve_type`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct SwapCurve {
+    pub curve_type: u8,
+    pub calculator: [u8; 32],
+}
+
+#[derive(Clone)]
+pub struct SwapPool(SwapV1);
+
+impl anchor_lang::AccountDeserialize for SwapPool {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self, ProgramError> {
+        if buf.first() != Some(&SWAP_V1) {
+            return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+        }
+        SwapPool::try_deserialize_unchecked(buf)
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self, ProgramError> {
+        let mut data: &[u8] = buf.get(1..).ok_or(ProgramError::InvalidAccountData)?;
+        SwapV1::deserialize(&mut data)
+            .map(SwapPool)
+            .map_err(|_| ErrorCode::AccountDidNotDeserialize.into())
+    }
+}
+
+impl anchor_lang::Owners for SwapPool {
+    fn owners() -> &'static [Pubkey] {
+        &[ID]
+    }
+}
+
+impl Deref for SwapPool {
+    type Target = SwapV1;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// The SPL Token Swap program, for use with `Program<'info, TokenSwap>`.
+#[derive(Clone)]
+pub struct TokenSwap;
+
+impl anchor_lang::Id for TokenSwap {
+    fn id() -> Pubkey {
+        ID
+    }
+}
@@ -0,0 +1,246 @@
+//! Resolution of the accounts required by Token-2022 mints with the transfer
+//! hook extension, whose transfers invoke the hook program with the accounts
+//! listed in its `ExtraAccountMetaList` account for the mint.
+
+use anchor_lang::solana_program::account_info::AccountInfo;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use std::convert::TryFrom;
+
+// Token-2022 extensions follow the account type, which is stored after the
+// length of a token account, regardless of the account's base type.
+const EXTENSIONS_OFFSET: usize = spl_token::state::Account::LEN + 1;
+const EXTENSION_TYPE_TRANSFER_HOOK: u16 = 14;
+
+const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+const EXTRA_ACCOUNT_META_LEN: usize = 35;
+
+/// Returns the transfer hook program of a Token-2022 mint, if any.
+pub fn program_id(mint: &AccountInfo) -> Result<Option<Pubkey>, ProgramError> {
+    let data = mint.try_borrow_data()?;
+    if data.len() < EXTENSIONS_OFFSET || data.len() == spl_token::state::Multisig::LEN {
+        return Ok(None);
+    }
+    let mut extensions = &data[EXTENSIONS_OFFSET..];
+    while extensions.len() >= 4 {
+        let extension_type = u16::from_le_bytes([extensions[0], extensions[1]]);
+        let len = u16::from_le_bytes([extensions[2], extensions[3]]) as usize;
+        let value = extensions
+            .get(4..4 + len)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        if extension_type == EXTENSION_TYPE_TRANSFER_HOOK {
+            // The extension is the hook's authority followed by its program,
+            // either of which is zeroed if unset.
+            let program_id = value
+                .get(32..64)
+                .map(|key| Pubkey::new_from_array(<[u8; 32]>::try_from(key).unwrap()))
+                .ok_or(ProgramError::InvalidAccountData)?;
+            return Ok(Some(program_id).filter(|key| *key != Pubkey::default()));
+        }
+        extensions = &extensions[4 + len..];
+    }
+    Ok(None)
+}
+
+/// Returns the address of the hook program's `ExtraAccountMetaList` account
+/// for the mint.
+pub fn extra_account_metas_address(mint: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[EXTRA_ACCOUNT_METAS_SEED, mint.as_ref()], program_id).0
+}
+
+/// Appends the accounts required by the transfer hook `program_id` to a
+/// `transfer_checked` instruction, whose source, mint, destination and
+/// authority are the first accounts of `ix` and `account_infos`.
+///
+/// The extra accounts listed by the hook's `ExtraAccountMetaList` are
+/// resolved against the hook's execute instruction and looked up in
+/// `remaining_accounts`, followed by the list itself and the hook program,
+/// so all of them must be given.
+pub fn add_extra_accounts<'info>(
+    ix: &mut Instruction,
+    account_infos: &mut Vec<AccountInfo<'info>>,
+    program_id: &Pubkey,
+    amount: u64,
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<(), ProgramError> {
+    let program_info = find_account(remaining_accounts, program_id)?;
+    let extra_account_metas = extra_account_metas_address(&ix.accounts[1].pubkey, program_id);
+
+    // Without the list, the hook doesn't require any extra accounts.
+    if let Ok(extra_account_metas_info) = find_account(remaining_accounts, &extra_account_metas) {
+        // The hook's execute instruction, which gives the transfer's accounts
+        // and the list as read only.
+        let mut execute_data = execute_discriminator().to_vec();
+        execute_data.extend_from_slice(&amount.to_le_bytes());
+        let mut execute_metas: Vec<AccountMeta> = ix.accounts[..4]
+            .iter()
+            .map(|meta| AccountMeta::new_readonly(meta.pubkey, false))
+            .collect();
+        execute_metas.push(AccountMeta::new_readonly(extra_account_metas, false));
+        let mut execute_infos = account_infos[..4].to_vec();
+        execute_infos.push(extra_account_metas_info.clone());
+
+        let data = extra_account_metas_info.try_borrow_data()?;
+        for extra in unpack_extra_account_metas(&data)?.chunks(EXTRA_ACCOUNT_META_LEN) {
+            let mut meta = resolve(extra, &execute_data, program_id, &execute_infos)?;
+            // Extra accounts are never given more privileges than they have
+            // elsewhere in the execute instruction.
+            let existing: Vec<&AccountMeta> = execute_metas
+                .iter()
+                .filter(|existing| existing.pubkey == meta.pubkey)
+                .collect();
+            if !existing.is_empty() {
+                meta.is_signer &= existing.iter().any(|existing| existing.is_signer);
+                meta.is_writable &= existing.iter().any(|existing| existing.is_writable);
+            }
+            execute_infos.push(find_account(remaining_accounts, &meta.pubkey)?.clone());
+            execute_metas.push(meta);
+        }
+
+        ix.accounts.extend_from_slice(&execute_metas[5..]);
+        account_infos.extend_from_slice(&execute_infos[5..]);
+        ix.accounts
+            .push(AccountMeta::new_readonly(extra_account_metas, false));
+        account_infos.push(extra_account_metas_info.clone());
+    }
+
+    ix.accounts
+        .push(AccountMeta::new_readonly(*program_id, false));
+    account_infos.push(program_info.clone());
+    Ok(())
+}
+
+fn execute_discriminator() -> [u8; 8] {
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash(b"spl-transfer-hook-interface:execute").to_bytes()[..8]);
+    discriminator
+}
+
+fn find_account<'a, 'info>(
+    accounts: &'a [AccountInfo<'info>],
+    key: &Pubkey,
+) -> Result<&'a AccountInfo<'info>, ProgramError> {
+    accounts
+        .iter()
+        .find(|account| account.key == key)
+        .ok_or(ProgramError::NotEnoughAccountKeys)
+}
+
+// Returns the packed extra account metas for the execute instruction from
+// the list's type-length-value entries.
+fn unpack_extra_account_metas(mut data: &[u8]) -> Result<&[u8], ProgramError> {
+    let discriminator = execute_discriminator();
+    while data.len() >= 12 {
+        let len = u32::from_le_bytes(<[u8; 4]>::try_from(&data[8..12]).unwrap()) as usize;
+        let value = data
+            .get(12..12 + len)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        if data[..8] == discriminator {
+            let count = value
+                .get(..4)
+                .map(|count| u32::from_le_bytes(<[u8; 4]>::try_from(count).unwrap()) as usize)
+                .ok_or(ProgramError::InvalidAccountData)?;
+            return value
+                .get(4..4 + count * EXTRA_ACCOUNT_META_LEN)
+                .ok_or(ProgramError::InvalidAccountData);
+        }
+        data = &data[12 + len..];
+    }
+    Err(ProgramError::InvalidAccountData)
+}
+
+// Resolves a packed extra account meta, which is either a literal address or
+// a PDA of the hook program, or of another account of the instruction, whose
+// seeds are taken from the instruction.
+fn resolve(
+    extra: &[u8],
+    ix_data: &[u8],
+    program_id: &Pubkey,
+    account_infos: &[AccountInfo],
+) -> Result<AccountMeta, ProgramError> {
+    let address_config = <[u8; 32]>::try_from(&extra[1..33]).unwrap();
+    let pubkey = match extra[0] {
+        0 => Pubkey::new_from_array(address_config),
+        1 => resolve_pda(&address_config, ix_data, program_id, account_infos)?,
+        index if index >= 1 << 7 => {
+            let program = account(account_infos, (index - (1 << 7)) as usize)?;
+            resolve_pda(&address_config, ix_data, program.key, account_infos)?
+        }
+        _ => return Err(ProgramError::InvalidAccountData),
+    };
+    Ok(AccountMeta {
+        pubkey,
+        is_signer: extra[33] != 0,
+        is_writable: extra[34] != 0,
+    })
+}
+
+fn resolve_pda(
+    address_config: &[u8; 32],
+    ix_data: &[u8],
+    program_id: &Pubkey,
+    account_infos: &[AccountInfo],
+) -> Result<Pubkey, ProgramError> {
+    let config = |i: usize| {
+        address_config
+            .get(i)
+            .map(|b| *b as usize)
+            .ok_or(ProgramError::InvalidAccountData)
+    };
+    let slice = |data: &[u8], start: usize, len: usize| {
+        data.get(start..start + len)
+            .map(|s| s.to_vec())
+            .ok_or(ProgramError::InvalidAccountData)
+    };
+
+    let mut seeds: Vec<Vec<u8>> = vec![];
+    let mut i = 0;
+    while i < address_config.len() {
+        match address_config[i] {
+            // Uninitialized, i.e., the end of the seeds.
+            0 => break,
+            // Literal.
+            1 => {
+                let len = config(i + 1)?;
+                seeds.push(slice(address_config, i + 2, len)?);
+                i += 2 + len;
+            }
+            // Instruction data.
+            2 => {
+                seeds.push(slice(ix_data, config(i + 1)?, config(i + 2)?)?);
+                i += 3;
+            }
+            // Account key.
+            3 => {
+                seeds.push(
+                    account(account_infos, config(i + 1)?)?
+                        .key
+                        .to_bytes()
+                        .to_vec(),
+                );
+                i += 2;
+            }
+            // Account data.
+            4 => {
+                let data = account(account_infos, config(i + 1)?)?.try_borrow_data()?;
+                seeds.push(slice(&data, config(i + 2)?, config(i + 3)?)?);
+                i += 4;
+            }
+            _ => return Err(ProgramError::InvalidAccountData),
+        }
+    }
+    let seeds: Vec<&[u8]> = seeds.iter().map(|seed| seed.as_slice()).collect();
+    Ok(Pubkey::find_program_address(&seeds, program_id).0)
+}
+
+fn account<'a, 'info>(
+    account_infos: &'a [AccountInfo<'info>],
+    index: usize,
+) -> Result<&'a AccountInfo<'info>, ProgramError> {
+    account_infos
+        .get(index)
+        .ok_or(ProgramError::NotEnoughAccountKeys)
+}
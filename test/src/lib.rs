@@ -0,0 +1,249 @@
+//! `anchor_test` runs Anchor programs, along with the cross program
+//! invocations between them, on a `solana-program-test` bank, so that they
+//! can be tested in Rust without starting a validator, e.g.,
+//!
+//! ```ignore
+//! let mut ctx = anchor_test::workspace![puppet, puppet_master].start().await;
+//! ctx.request(puppet_master::ID)
+//!     .accounts(puppet_master::accounts::PullStrings { .. })
+//!     .args(puppet_master::instruction::PullStrings { data: 5 })
+//!     .send()
+//!     .await?;
+//! ```
+//!
+//! Programs run natively, via their generated `entry` function, unless the
+//! `BPF_OUT_DIR` environment variable is set, in which case their compiled
+//! `<lib_name>.so` in that directory is loaded instead, e.g.,
+//! `BPF_OUT_DIR=target/deploy cargo test` after `anchor build`. Program crates
+//! should be dependencies with the `cpi` feature, which leaves out their
+//! entrypoints but not their `entry` functions, and must `declare_id!` the
+//! address they're registered at.
+
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use solana_program_runtime::invoke_context::BuiltinFunctionWithContext;
+use solana_program_test::{BanksClient, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::account::Account;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+pub use anchor_lang;
+pub use solana_program_test;
+pub use solana_sdk;
+
+/// Returns a [`Workspace`] with the given programs, each named by its
+/// crate's lib name, registered at its declared `ID`, and running natively
+/// via its `entry` function, e.g., `workspace![puppet, puppet_master]`.
+#[macro_export]
+macro_rules! workspace {
+    ($($program:ident),* $(,)?) => {
+        $crate::Workspace::new()
+            $(.program(
+                stringify!($program),
+                $program::ID,
+                $crate::solana_program_test::processor!($program::entry),
+            ))*
+    };
+}
+
+/// Workspace registers a set of programs, and any accounts they expect to
+/// exist, with a `ProgramTest`. Its primary use is to start a
+/// [`TestContext`] via the `start` method.
+pub struct Workspace {
+    program_test: ProgramTest,
+    programs: BTreeMap<String, Pubkey>,
+}
+
+impl Default for Workspace {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Workspace {
+    pub fn new() -> Self {
+        Self {
+            program_test: ProgramTest::default(),
+            programs: BTreeMap::new(),
+        }
+    }
+
+    /// Adds the program with the given lib name at `program_id`, which should
+    /// be the address it declares via `declare_id!`. Without a `processor`,
+    /// its `<name>.so` is always loaded.
+    pub fn program(
+        mut self,
+        name: &str,
+        program_id: Pubkey,
+        processor: Option<BuiltinFunctionWithContext>,
+    ) -> Self {
+        self.program_test.add_program(name, program_id, processor);
+        self.programs.insert(name.to_string(), program_id);
+        self
+    }
+
+    /// Adds an account existing at genesis.
+    pub fn account(mut self, address: Pubkey, account: Account) -> Self {
+        self.program_test.add_account(address, account);
+        self
+    }
+
+    /// Returns the underlying `ProgramTest`, for configuring anything else.
+    pub fn program_test(&mut self) -> &mut ProgramTest {
+        &mut self.program_test
+    }
+
+    pub async fn start(self) -> TestContext {
+        TestContext {
+            context: self.program_test.start_with_context().await,
+            programs: self.programs,
+        }
+    }
+}
+
+/// TestContext is the handle to a running bank with the workspace's
+/// programs, used to build and send requests and fetch deserialized
+/// accounts.
+pub struct TestContext {
+    context: ProgramTestContext,
+    programs: BTreeMap<String, Pubkey>,
+}
+
+impl TestContext {
+    /// Returns the address of the program with the given lib name.
+    pub fn id(&self, name: &str) -> Pubkey {
+        *self
+            .programs
+            .get(name)
+            .unwrap_or_else(|| panic!("Program not in workspace: {}", name))
+    }
+
+    /// Returns the payer, funded at genesis, of all requests.
+    pub fn payer(&self) -> &Keypair {
+        &self.context.payer
+    }
+
+    pub fn banks_client(&mut self) -> &mut BanksClient {
+        &mut self.context.banks_client
+    }
+
+    /// Returns the underlying `ProgramTestContext`, e.g., for warping to a
+    /// slot.
+    pub fn context(&mut self) -> &mut ProgramTestContext {
+        &mut self.context
+    }
+
+    /// Returns a request builder for the given program.
+    pub fn request(&mut self, program_id: Pubkey) -> RequestBuilder<'_> {
+        RequestBuilder {
+            context: &mut self.context,
+            program_id,
+            accounts: Vec::new(),
+            instructions: Vec::new(),
+            instruction_data: None,
+            signers: Vec::new(),
+        }
+    }
+
+    /// Returns the account at the given address.
+    pub async fn account<T: AccountDeserialize>(
+        &mut self,
+        address: Pubkey,
+    ) -> Result<T, TestError> {
+        let account = self
+            .context
+            .banks_client
+            .get_account(address)
+            .await?
+            .ok_or(TestError::AccountNotFound)?;
+        let mut data: &[u8] = &account.data;
+        T::try_deserialize(&mut data).map_err(Into::into)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum TestError {
+    #[error("Account not found")]
+    AccountNotFound,
+    #[error("{0}")]
+    ProgramError(#[from] ProgramError),
+    #[error("{0}")]
+    BanksClientError(#[from] BanksClientError),
+}
+
+/// `RequestBuilder` provides a builder interface to create and send
+/// transactions to the bank, mirroring `anchor_client::RequestBuilder`.
+pub struct RequestBuilder<'a> {
+    context: &'a mut ProgramTestContext,
+    program_id: Pubkey,
+    accounts: Vec<AccountMeta>,
+    instructions: Vec<Instruction>,
+    // Serialized instruction data for the target program.
+    instruction_data: Option<Vec<u8>>,
+    signers: Vec<&'a dyn Signer>,
+}
+
+impl<'a> RequestBuilder<'a> {
+    pub fn instruction(mut self, ix: Instruction) -> Self {
+        self.instructions.push(ix);
+        self
+    }
+
+    pub fn program(mut self, program_id: Pubkey) -> Self {
+        self.program_id = program_id;
+        self
+    }
+
+    pub fn accounts(mut self, accounts: impl ToAccountMetas) -> Self {
+        let mut metas = accounts.to_account_metas(None);
+        self.accounts.append(&mut metas);
+        self
+    }
+
+    pub fn args(mut self, args: impl InstructionData) -> Self {
+        self.instruction_data = Some(args.data());
+        self
+    }
+
+    pub fn signer(mut self, signer: &'a dyn Signer) -> Self {
+        self.signers.push(signer);
+        self
+    }
+
+    /// Returns the request's instructions, ending with the one built from
+    /// `accounts` and `args`, if given.
+    pub fn instructions(&self) -> Vec<Instruction> {
+        let mut instructions = self.instructions.clone();
+        if let Some(data) = &self.instruction_data {
+            instructions.push(Instruction {
+                program_id: self.program_id,
+                data: data.clone(),
+                accounts: self.accounts.clone(),
+            });
+        }
+        instructions
+    }
+
+    /// Signs the request with the payer and all signers, and processes it
+    /// in a new block.
+    pub async fn send(self) -> Result<(), TestError> {
+        let instructions = self.instructions();
+        let context = self.context;
+        let mut signers = self.signers;
+        signers.push(&context.payer);
+
+        let blockhash = context.banks_client.get_latest_blockhash().await?;
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&context.payer.pubkey()),
+            &signers,
+            blockhash,
+        );
+        context.banks_client.process_transaction(tx).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use anchor_test::Workspace;
+use solana_program_test::processor;
+use solana_sdk::account::Account;
+
+solana_sdk::declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[program]
+pub mod counter {
+    use super::*;
+
+    pub fn set(ctx: Context<Set>, count: u64) -> ProgramResult {
+        ctx.accounts.counter.count = count;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Set<'info> {
+    #[account(mut)]
+    pub counter: ProgramAccount<'info, Counter>,
+}
+
+#[account]
+pub struct Counter {
+    pub count: u64,
+}
+
+#[tokio::test]
+async fn test_workspace_program() {
+    let counter = Pubkey::new_unique();
+    let mut data = Vec::new();
+    Counter { count: 0 }.try_serialize(&mut data).unwrap();
+
+    let mut ctx = Workspace::new()
+        .program("counter", ID, processor!(entry))
+        .account(
+            counter,
+            Account {
+                lamports: 1_000_000_000,
+                data,
+                owner: ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .start()
+        .await;
+    assert_eq!(ctx.id("counter"), ID);
+
+    ctx.request(ID)
+        .accounts(accounts::Set { counter })
+        .args(instruction::Set { count: 5 })
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(ctx.account::<Counter>(counter).await.unwrap().count, 5);
+}
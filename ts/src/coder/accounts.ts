@@ -2,7 +2,6 @@ import { Layout } from "buffer-layout";
 import { Idl } from "../idl";
 import { IdlCoder } from "./idl";
 import { sha256 } from "js-sha256";
-import * as base64 from "base64-js";
 
 /**
  * Number of bytes of the account discriminator.
@@ -18,13 +17,19 @@ export class AccountsCoder {
    */
   private accountLayouts: Map<string, Layout>;
   /**
-   * Maps account discriminator to type identifier.
+   * Maps account type identifier to its discriminator.
    */
-  private accountNames: Map<string, string>;
+  private accountDiscriminators: Map<string, Buffer>;
+  /**
+   * Maps account type identifier to its schema version, if versioned.
+   */
+  private accountVersions: Map<string, number>;
 
   public constructor(idl: Idl) {
     if (idl.accounts === undefined) {
       this.accountLayouts = new Map();
+      this.accountDiscriminators = new Map();
+      this.accountVersions = new Map();
       return;
     }
     const layouts: [string, Layout][] = idl.accounts.map((acc) => {
@@ -33,16 +38,41 @@ export class AccountsCoder {
 
     this.accountLayouts = new Map(layouts);
 
-    const accountNamesArray: [string, string][] = idl.accounts.map((accountType) => {
-      return [base64.fromByteArray(accountDiscriminatorSync(accountType.name)), accountType.name] as [string, string]
-    })
-    this.accountNames = new Map(accountNamesArray)
+    this.accountDiscriminators = new Map(
+      idl.accounts.map(
+        (acc) =>
+          [
+            acc.name,
+            acc.discriminator !== undefined
+              ? Buffer.from(acc.discriminator)
+              : accountDiscriminatorSync(acc.name),
+          ] as [string, Buffer]
+      )
+    );
+
+    this.accountVersions = new Map(
+      idl.accounts
+        .filter((acc) => acc.version !== undefined)
+        .map((acc) => [acc.name, acc.version] as [string, number])
+    );
+  }
+  /**
+   * Get account type identifier from the account data's discriminator
+   */
+  public getAccountName(data: Buffer): string {
+    for (const [name, discriminator] of this.accountDiscriminators) {
+      if (discriminator.equals(data.slice(0, discriminator.length))) {
+        return name;
+      }
+    }
+    return undefined;
   }
+
   /**
-   * Get account type identifier from discriminator
+   * Returns the discriminator of the given account type.
    */
-  public getAccountName(discriminator: Buffer): string {
-    return this.accountNames.get(base64.fromByteArray(discriminator))
+  public discriminator(accountName: string): Buffer {
+    return this.accountDiscriminators.get(accountName);
   }
 
   public async encode<T = any>(
@@ -53,13 +83,31 @@ export class AccountsCoder {
     const layout = this.accountLayouts.get(accountName);
     const len = layout.encode(account, buffer);
     let accountData = buffer.slice(0, len);
-    let discriminator = await accountDiscriminator(accountName);
+    const discriminator = this.discriminator(accountName);
+    const version = this.accountVersions.get(accountName);
+    if (version !== undefined) {
+      return Buffer.concat([discriminator, Buffer.from([version]), accountData]);
+    }
     return Buffer.concat([discriminator, accountData]);
   }
 
   public decode<T = any>(accountName: string, ix: Buffer): T {
-    // Chop off the discriminator before decoding.
-    const data = ix.slice(8);
+    // Chop off the discriminator (and version, if any) before decoding.
+    const discriminatorSize = this.discriminator(accountName).length;
+    const version = this.accountVersions.get(accountName);
+    if (version !== undefined) {
+      // Older layouts are only known to the program, which migrates them.
+      const given = ix[discriminatorSize];
+      if (given !== 0 && given !== version) {
+        throw new Error(
+          `Unsupported version ${given} for account ${accountName}`
+        );
+      }
+      return this.accountLayouts
+        .get(accountName)
+        .decode(ix.slice(discriminatorSize + 1));
+    }
+    const data = ix.slice(discriminatorSize);
     const layout = this.accountLayouts.get(accountName);
     return layout.decode(data);
   }
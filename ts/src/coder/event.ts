@@ -5,44 +5,44 @@ import { Idl, IdlTypeDef } from "../idl";
 import { Event } from "../program/event";
 import { IdlCoder } from "./idl";
 
-export class EventCoder {
-  /**
-   * Maps account type identifier to a layout.
-   */
-  private layouts: Map<string, Layout>;
+type EventLayout = { name: string; version?: number; layout: Layout };
 
+export class EventCoder {
   /**
-   * Maps base64 encoded event discriminator to event name.
+   * Maps base64 encoded event discriminator to the event's name, version,
+   * and layout.
    */
-  private discriminators: Map<string, string>;
+  private events: Map<string, EventLayout>;
 
   public constructor(idl: Idl) {
     if (idl.events === undefined) {
-      this.layouts = new Map();
+      this.events = new Map();
       return;
     }
-    const layouts = idl.events.map((event) => {
-      let eventTypeDef: IdlTypeDef = {
-        name: event.name,
-        type: {
-          kind: "struct",
-          fields: event.fields.map((f) => {
-            return { name: f.name, type: f.type };
-          }),
-        },
-      };
-      return [event.name, IdlCoder.typeDefLayout(eventTypeDef, idl.types)];
-    });
-    // @ts-ignore
-    this.layouts = new Map(layouts);
-
-    this.discriminators = new Map<string, string>(
-      idl.events === undefined
-        ? []
-        : idl.events.map((e) => [
-            base64.fromByteArray(eventDiscriminator(e.name)),
-            e.name,
-          ])
+    this.events = new Map(
+      idl.events.map((event): [string, EventLayout] => {
+        let eventTypeDef: IdlTypeDef = {
+          name: event.name,
+          type: {
+            kind: "struct",
+            fields: event.fields.map((f) => {
+              return { name: f.name, type: f.type };
+            }),
+          },
+        };
+        const disc =
+          event.discriminator !== undefined
+            ? Buffer.from(event.discriminator)
+            : eventDiscriminator(event.name);
+        return [
+          base64.fromByteArray(disc),
+          {
+            name: event.name,
+            version: event.version,
+            layout: IdlCoder.typeDefLayout(eventTypeDef, idl.types),
+          },
+        ];
+      })
     );
   }
 
@@ -54,20 +54,45 @@ export class EventCoder {
     } catch (e){
       return null;
     }
+    return this.decodeBytes(logArr);
+  }
+
+  /**
+   * Decodes an event emitted via `emit_cpi!`, given the data of the self-CPI
+   * instruction found in the transaction's inner instructions.
+   */
+  public decodeCpi(ixData: Buffer): Event | null {
+    if (!ixData.slice(0, 8).equals(EVENT_IX_TAG_LE)) {
+      return null;
+    }
+    return this.decodeBytes(ixData.slice(8));
+  }
+
+  private decodeBytes(logArr: Buffer): Event | null {
     const disc = base64.fromByteArray(logArr.slice(0, 8));
 
     // Only deserialize if the discriminator implies a proper event.
-    const eventName = this.discriminators.get(disc);
-    if (eventName === undefined) {
+    const event = this.events.get(disc);
+    if (event === undefined) {
       return null;
     }
 
-    const layout = this.layouts.get(eventName);
-    const data = layout.decode(logArr.slice(8));
-    return { data, name: eventName };
+    const data = event.layout.decode(logArr.slice(8));
+    if (event.version === undefined) {
+      return { data, name: event.name };
+    }
+    return { data, name: event.name, version: event.version };
   }
 }
 
+// Prefix of the instruction data of event self-CPIs, i.e., the little endian
+// bytes of the `anchor_lang::event::EVENT_IX_TAG` u64.
+export const EVENT_IX_TAG_LE = Buffer.from(
+  sha256.digest("anchor:event")
+)
+  .slice(0, 8)
+  .reverse();
+
 export function eventDiscriminator(name: string): Buffer {
   return Buffer.from(sha256.digest(`event:${name}`)).slice(0, 8);
 }
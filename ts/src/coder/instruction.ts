@@ -40,7 +40,7 @@ export class InstructionCoder {
 
     const sighashLayouts = new Map();
     idl.instructions.forEach((ix) => {
-      const sh = sighash(SIGHASH_GLOBAL_NAMESPACE, ix.name);
+      const sh = sighash(ix.interface ?? SIGHASH_GLOBAL_NAMESPACE, ix.name);
       sighashLayouts.set(bs58.encode(sh), {
         layout: this.ixLayout.get(ix.name),
         name: ix.name,
@@ -64,7 +64,12 @@ export class InstructionCoder {
    * Encodes a program instruction.
    */
   public encode(ixName: string, ix: any) {
-    return this._encode(SIGHASH_GLOBAL_NAMESPACE, ixName, ix);
+    const idlIx = this.idl.instructions.find((i) => i.name === ixName);
+    return this._encode(
+      idlIx?.interface ?? SIGHASH_GLOBAL_NAMESPACE,
+      ixName,
+      ix
+    );
   }
 
   /**
@@ -51,6 +51,9 @@ const LangErrorCode = {
   InstructionFallbackNotFound: 101,
   InstructionDidNotDeserialize: 102,
   InstructionDidNotSerialize: 103,
+  ReturnDataNotSet: 104,
+  ReturnDataDidNotDeserialize: 105,
+  InstructionReentrant: 106,
 
   // IDL instructions.
   IdlInstructionStub: 120,
@@ -70,6 +73,13 @@ const LangErrorCode = {
   ConstraintAssociatedInit: 150,
   ConstraintClose: 151,
   ConstraintAddress: 152,
+  ConstraintSpace: 153,
+  ConstraintTokenMint: 154,
+  ConstraintTokenOwner: 155,
+  ConstraintRealloc: 156,
+  ConstraintAssociatedToken: 157,
+  ConstraintVariant: 158,
+  ConstraintInstructionSysvar: 159,
 
   // Accounts.
   AccountDiscriminatorAlreadySet: 160,
@@ -80,10 +90,36 @@ const LangErrorCode = {
   AccountNotEnoughKeys: 165,
   AccountNotMutable: 166,
   AccountNotProgramOwned: 167,
+  AccountNotSigner: 168,
+  InvalidProgramId: 169,
+  InvalidProgramExecutable: 170,
+  AccountNotSystemOwned: 171,
+  AccountOwnedByWrongProgram: 172,
+  AccountVersionNotSupported: 173,
 
   // State.
   StateInvalidAddress: 180,
 
+  // Require.
+  RequireEqViolated: 190,
+  RequireNeqViolated: 191,
+  RequireKeysEqViolated: 192,
+  RequireKeysNeqViolated: 193,
+  RequireGtViolated: 194,
+  RequireGteViolated: 195,
+
+  // Events.
+  EventDiscriminatorMismatch: 200,
+  EventDidNotDeserialize: 201,
+
+  // Mint and token account constraints.
+  ConstraintMintMintAuthority: 210,
+  ConstraintMintFreezeAuthority: 211,
+  ConstraintMintDecimals: 212,
+  ConstraintMintTokenProgram: 213,
+  ConstraintTokenDelegate: 214,
+  ConstraintTokenCloseAuthority: 215,
+
   // Used for APIs that shouldn't be used anymore.
   Deprecated: 299,
 };
@@ -106,6 +142,18 @@ const LangErrorMessage = new Map([
     LangErrorCode.InstructionDidNotSerialize,
     "The program could not serialize the given instruction",
   ],
+  [
+    LangErrorCode.ReturnDataNotSet,
+    "The invoked program did not set any return data",
+  ],
+  [
+    LangErrorCode.ReturnDataDidNotDeserialize,
+    "The return data could not be deserialized",
+  ],
+  [
+    LangErrorCode.InstructionReentrant,
+    "The instruction was invoked reentrantly",
+  ],
 
   // Idl instructions.
   [
@@ -134,6 +182,19 @@ const LangErrorMessage = new Map([
   ],
   [LangErrorCode.ConstraintClose, "A close constraint was violated"],
   [LangErrorCode.ConstraintAddress, "An address constraint was violated"],
+  [LangErrorCode.ConstraintSpace, "A space constraint was violated"],
+  [LangErrorCode.ConstraintTokenMint, "A token mint constraint was violated"],
+  [LangErrorCode.ConstraintTokenOwner, "A token owner constraint was violated"],
+  [LangErrorCode.ConstraintRealloc, "A realloc constraint was violated"],
+  [
+    LangErrorCode.ConstraintAssociatedToken,
+    "An associated token constraint was violated",
+  ],
+  [LangErrorCode.ConstraintVariant, "A variant constraint was violated"],
+  [
+    LangErrorCode.ConstraintInstructionSysvar,
+    "An instruction sysvar constraint was violated",
+  ],
 
   // Accounts.
   [
@@ -159,6 +220,24 @@ const LangErrorMessage = new Map([
     LangErrorCode.AccountNotProgramOwned,
     "The given account is not owned by the executing program",
   ],
+  [LangErrorCode.AccountNotSigner, "The given account did not sign"],
+  [LangErrorCode.InvalidProgramId, "Program ID was not as expected"],
+  [
+    LangErrorCode.InvalidProgramExecutable,
+    "Program account is not executable",
+  ],
+  [
+    LangErrorCode.AccountNotSystemOwned,
+    "The given account is not owned by the system program",
+  ],
+  [
+    LangErrorCode.AccountOwnedByWrongProgram,
+    "The given account is owned by a different program than expected",
+  ],
+  [
+    LangErrorCode.AccountVersionNotSupported,
+    "The account's version is newer than the program supports",
+  ],
 
   // State.
   [
@@ -166,6 +245,53 @@ const LangErrorMessage = new Map([
     "The given state account does not have the correct address",
   ],
 
+  // Require.
+  [LangErrorCode.RequireEqViolated, "A require_eq expression was violated"],
+  [LangErrorCode.RequireNeqViolated, "A require_neq expression was violated"],
+  [
+    LangErrorCode.RequireKeysEqViolated,
+    "A require_keys_eq expression was violated",
+  ],
+  [
+    LangErrorCode.RequireKeysNeqViolated,
+    "A require_keys_neq expression was violated",
+  ],
+  [LangErrorCode.RequireGtViolated, "A require_gt expression was violated"],
+  [LangErrorCode.RequireGteViolated, "A require_gte expression was violated"],
+
+  // Events.
+  [
+    LangErrorCode.EventDiscriminatorMismatch,
+    "The event discriminator did not match what was expected",
+  ],
+  [LangErrorCode.EventDidNotDeserialize, "Failed to deserialize the event"],
+
+  // Mint and token account constraints.
+  [
+    LangErrorCode.ConstraintMintMintAuthority,
+    "A mint mint authority constraint was violated",
+  ],
+  [
+    LangErrorCode.ConstraintMintFreezeAuthority,
+    "A mint freeze authority constraint was violated",
+  ],
+  [
+    LangErrorCode.ConstraintMintDecimals,
+    "A mint decimals constraint was violated",
+  ],
+  [
+    LangErrorCode.ConstraintMintTokenProgram,
+    "A mint token program constraint was violated",
+  ],
+  [
+    LangErrorCode.ConstraintTokenDelegate,
+    "A token delegate constraint was violated",
+  ],
+  [
+    LangErrorCode.ConstraintTokenCloseAuthority,
+    "A token close authority constraint was violated",
+  ],
+
   // Misc.
   [
     LangErrorCode.Deprecated,
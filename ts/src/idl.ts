@@ -10,11 +10,15 @@ export type Idl = {
   types?: IdlTypeDef[];
   events?: IdlEvent[];
   errors?: IdlErrorCode[];
+  errorOffset?: number;
 };
 
 export type IdlEvent = {
   name: string;
   fields: IdlEventField[];
+  namespace?: string;
+  version?: number;
+  discriminator?: number[];
 };
 
 export type IdlEventField = {
@@ -27,6 +31,8 @@ export type IdlInstruction = {
   name: string;
   accounts: IdlAccountItem[];
   args: IdlField[];
+  returns?: IdlType;
+  interface?: string;
 };
 
 export type IdlState = {
@@ -42,6 +48,7 @@ export type IdlAccount = {
   name: string;
   isMut: boolean;
   isSigner: boolean;
+  isOptional?: boolean;
 };
 
 // A nested/recursive version of IdlAccount.
@@ -58,6 +65,10 @@ export type IdlField = {
 export type IdlTypeDef = {
   name: string;
   type: IdlTypeDefTy;
+  // Only set for accounts declared with `#[account(version = <n>)]`.
+  version?: number;
+  // Only set for accounts whose discriminator isn't derived from the name.
+  discriminator?: number[];
 };
 
 type IdlTypeDefTy = {
@@ -48,7 +48,8 @@ export function validateAccounts(
       // @ts-ignore
       validateAccounts(acc.accounts, accounts[acc.name]);
     } else {
-      if (accounts[acc.name] === undefined) {
+      // @ts-ignore
+      if (accounts[acc.name] === undefined && !acc.isOptional) {
         throw new Error(`Invalid arguments: ${acc.name} not provided.`);
       }
     }
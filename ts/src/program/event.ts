@@ -8,6 +8,8 @@ const LOG_START_INDEX = "Program log: ".length;
 export type Event = {
   name: string;
   data: Object;
+  // Set if the event was declared with `#[event(version = <n>)]`.
+  version?: number;
 };
 
 export class EventParser {
@@ -343,9 +343,7 @@ export class Program {
     const subscriptionId = this._provider.connection.onProgramAccountChange(this.programId, 
       async (keyedAccountInfo: KeyedAccountInfo, context: Context) => {
         const acc = keyedAccountInfo.accountInfo;
-        const disc = acc.data.slice(0, 8);
-        
-        const accountName = this._coder.accounts.getAccountName(disc);
+        const accountName = this._coder.accounts.getAccountName(acc.data);
         let accountData: any;
         if (accountName) {
           accountData = this._coder.accounts.decode(
@@ -10,11 +10,7 @@ import {
 } from "@solana/web3.js";
 import Provider from "../../provider";
 import { Idl, IdlTypeDef } from "../../idl";
-import Coder, {
-  ACCOUNT_DISCRIMINATOR_SIZE,
-  accountDiscriminator,
-  accountSize,
-} from "../../coder";
+import Coder, { accountSize } from "../../coder";
 import { Subscription, Address, translateAddress } from "../common";
 import { getProvider } from "../../";
 import * as pubkeyUtil from "../../utils/pubkey";
@@ -113,7 +109,10 @@ export class AccountClient {
     this._programId = programId;
     this._provider = provider ?? getProvider();
     this._coder = coder ?? new Coder(idl);
-    this._size = ACCOUNT_DISCRIMINATOR_SIZE + accountSize(idl, idlAccount);
+    this._size =
+      this._coder.accounts.discriminator(idlAccount.name).length +
+      (idlAccount.version !== undefined ? 1 : 0) +
+      accountSize(idl, idlAccount);
   }
 
   /**
@@ -130,8 +129,10 @@ export class AccountClient {
     }
 
     // Assert the account discriminator is correct.
-    const discriminator = await accountDiscriminator(this._idlAccount.name);
-    if (discriminator.compare(accountInfo.data.slice(0, 8))) {
+    const discriminator = this._coder.accounts.discriminator(
+      this._idlAccount.name
+    );
+    if (discriminator.compare(accountInfo.data.slice(0, discriminator.length))) {
       throw new Error("Invalid account discriminator");
     }
 
@@ -142,7 +143,7 @@ export class AccountClient {
    * Returns all instances of this account type for the program.
    */
   async all(filter?: Buffer): Promise<ProgramAccount<any>[]> {
-    let bytes = await accountDiscriminator(this._idlAccount.name);
+    let bytes = this._coder.accounts.discriminator(this._idlAccount.name);
     if (filter !== undefined) {
       bytes = Buffer.concat([bytes, filter]);
     }
@@ -42,13 +42,21 @@ export default class InstructionNamespaceFactory {
 
     // Utility fn for ordering the accounts for this instruction.
     ix["accounts"] = (accs: Accounts) => {
-      return InstructionNamespaceFactory.accountsArray(accs, idlIx.accounts);
+      return InstructionNamespaceFactory.accountsArray(
+        accs,
+        idlIx.accounts,
+        programId
+      );
     };
 
     return ix;
   }
 
-  public static accountsArray(ctx: Accounts, accounts: IdlAccountItem[]): any {
+  public static accountsArray(
+    ctx: Accounts,
+    accounts: IdlAccountItem[],
+    programId?: PublicKey
+  ): any {
     return accounts
       .map((acc: IdlAccountItem) => {
         // Nested accounts.
@@ -58,10 +66,22 @@ export default class InstructionNamespaceFactory {
           const rpcAccs = ctx[acc.name] as Accounts;
           return InstructionNamespaceFactory.accountsArray(
             rpcAccs,
-            nestedAccounts
+            nestedAccounts,
+            programId
           ).flat();
         } else {
           const account: IdlAccount = acc as IdlAccount;
+          // Absent optional accounts are signaled with the program id.
+          if (
+            account.isOptional &&
+            (ctx[acc.name] === undefined || ctx[acc.name] === null)
+          ) {
+            return {
+              pubkey: programId,
+              isWritable: false,
+              isSigner: false,
+            };
+          }
           return {
             pubkey: translateAddress(ctx[acc.name] as Address),
             isWritable: account.isMut,
@@ -115,7 +115,11 @@ export class StateClient {
         ixItem["accounts"] = (accounts: Accounts) => {
           const keys = stateInstructionKeys(programId, provider, m, accounts);
           return keys.concat(
-            InstructionNamespaceFactory.accountsArray(accounts, m.accounts)
+            InstructionNamespaceFactory.accountsArray(
+              accounts,
+              m.accounts,
+              programId
+            )
           );
         };
         // Build transaction method.